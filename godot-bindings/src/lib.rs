@@ -127,6 +127,11 @@ pub fn clear_dir(dir: &Path, watch: &mut StopWatch) {
     std::fs::create_dir_all(dir).unwrap_or_else(|e| panic!("failed to create dir: {e}"));
 }
 
+/// Full version string of the Godot API, against which bindings are generated.
+pub fn godot_version_string() -> String {
+    get_godot_version().full_string
+}
+
 pub fn emit_godot_version_cfg() {
     let GodotVersion {
         major,
@@ -136,7 +141,7 @@ pub fn emit_godot_version_cfg() {
     } = get_godot_version();
 
     // Start at 1; checking for "since/before 4.0" makes no sense
-    let max = 2;
+    let max = 3;
     for m in 1..=minor {
         println!(r#"cargo:rustc-cfg=since_api="{major}.{m}""#);
     }
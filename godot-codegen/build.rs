@@ -4,6 +4,60 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use std::path::Path;
+
+#[path = "src/fingerprint.rs"]
+mod fingerprint;
+
 fn main() {
     godot_bindings::emit_godot_version_cfg();
+    emit_source_hash();
+}
+
+/// Hashes the generator's own sources, so that codegen cache entries are invalidated when the generator changes.
+fn emit_source_hash() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let src_dir = manifest_dir.join("src");
+
+    let mut files = Vec::new();
+    collect_files(&src_dir, &mut files);
+    files.push(manifest_dir.join("Cargo.toml"));
+
+    // Directory iteration order is platform-dependent.
+    files.sort();
+
+    let mut fingerprint = fingerprint::Fingerprint::new();
+    for file in &files {
+        let relative = file.strip_prefix(manifest_dir).unwrap();
+        let contents = std::fs::read(file)
+            .unwrap_or_else(|e| panic!("failed to read {};\n\t{}", file.display(), e));
+
+        fingerprint.update(relative.to_string_lossy().as_bytes());
+        fingerprint.update(&contents);
+    }
+
+    println!(
+        "cargo:rustc-env=GODOT_CODEGEN_SOURCE_HASH={}",
+        fingerprint.to_hex()
+    );
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    println!("cargo:rerun-if-changed=build.rs");
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) {
+    let entries = std::fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("failed to read dir {};\n\t{}", dir.display(), e));
+
+    for entry in entries {
+        let path = entry
+            .unwrap_or_else(|e| panic!("failed to read dir entry;\n\t{e}"))
+            .path();
+
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
 }
@@ -8,6 +8,7 @@
 #![allow(dead_code)]
 #![allow(clippy::question_mark)] // in #[derive(DeJson)]
 
+use crate::diagnostics::codegen_log;
 use nanoserde::DeJson;
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
@@ -71,6 +72,8 @@ pub struct Class {
     pub constants: Option<Vec<ClassConstant>>,
     pub enums: Option<Vec<Enum>>,
     pub methods: Option<Vec<ClassMethod>>,
+    /// Deprecation message, present if the class is deprecated (may be empty).
+    pub deprecated: Option<String>,
     // pub properties: Option<Vec<Property>>,
     // pub signals: Option<Vec<Signal>>,
 }
@@ -198,6 +201,8 @@ pub struct ClassMethod {
     pub hash: Option<i64>,
     pub return_value: Option<MethodReturn>,
     pub arguments: Option<Vec<MethodArg>>,
+    /// Deprecation message, present if the method is deprecated (may be empty).
+    pub deprecated: Option<String>,
 }
 
 impl ClassMethod {
@@ -268,7 +273,7 @@ pub fn load_extension_api(
         DeJson::deserialize_json(json_str).expect("failed to deserialize JSON");
     watch.record("deserialize_json");
 
-    println!("Parsed extension_api.json for version {:?}", model.header);
+    codegen_log!("Parsed extension_api.json for version {:?}", model.header);
 
     (model, build_config)
 }
@@ -0,0 +1,137 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Cache of generated files, reused across clean builds.
+//!
+//! If the environment variable `GODOT_CODEGEN_CACHE` points to a directory, the build scripts store their generated files there,
+//! keyed by Godot version, gdext version, codegen features and content hashes of the API JSON and the generator's sources.
+//! Subsequent builds with the same key copy the files instead of parsing the API and running the generator. The directory may
+//! be shared between machines, e.g. as a CI cache artifact.
+//!
+//! Entries are never invalidated except through their key. Since the key covers the generator's sources and the API contents,
+//! modifying either (e.g. a patched generator or a custom Godot build with the same version string) yields a new entry.
+
+use crate::diagnostics::codegen_log;
+use crate::fingerprint::Fingerprint;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CACHE_ENV_VAR: &str = "GODOT_CODEGEN_CACHE";
+
+/// Copies cached files for `crate_key` (e.g. `"core"`) into `gen_path`. Returns `false` if there is no cache entry.
+pub fn restore_from_cache(crate_key: &str, gen_path: &Path) -> bool {
+    let Some(entry_dir) = cache_entry_dir(crate_key) else {
+        return false;
+    };
+
+    if !entry_dir.is_dir() {
+        codegen_log!("Codegen cache miss: {}", entry_dir.display());
+        return false;
+    }
+
+    copy_dir(&entry_dir, gen_path);
+    codegen_log!("Restored generated files from {}", entry_dir.display());
+    true
+}
+
+/// Stores the files in `gen_path` as cache entry for `crate_key`, if caching is enabled.
+pub fn store_in_cache(crate_key: &str, gen_path: &Path) {
+    let Some(entry_dir) = cache_entry_dir(crate_key) else {
+        return;
+    };
+
+    // Write to a temporary directory first, so that concurrent builds never observe a partially written entry.
+    let tmp_dir = entry_dir.with_extension(format!("tmp{}", std::process::id()));
+    copy_dir(gen_path, &tmp_dir);
+
+    if fs::rename(&tmp_dir, &entry_dir).is_err() {
+        // Another build stored the same entry in the meantime.
+        let _ = fs::remove_dir_all(&tmp_dir);
+    }
+}
+
+fn cache_entry_dir(crate_key: &str) -> Option<PathBuf> {
+    println!("cargo:rerun-if-env-changed={CACHE_ENV_VAR}");
+    let root = std::env::var_os(CACHE_ENV_VAR)?;
+
+    let key = format!(
+        "godot-{}_gdext-{}_{}_{}",
+        godot_bindings::godot_version_string(),
+        env!("CARGO_PKG_VERSION"),
+        feature_key(),
+        content_key()
+    );
+    let key: String = key
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => c,
+            _ => '_',
+        })
+        .collect();
+
+    Some(PathBuf::from(root).join(key).join(crate_key))
+}
+
+/// Hash over the generator's sources (computed by the build script) and the API JSON.
+///
+/// With `custom-godot`, this loads the JSON from the Godot binary even on a cache hit; parsing it and generating code is what
+/// the cache saves.
+fn content_key() -> String {
+    let json = godot_bindings::load_gdextension_json(&mut godot_bindings::StopWatch::start());
+
+    let mut fingerprint = Fingerprint::new();
+    fingerprint.update(env!("GODOT_CODEGEN_SOURCE_HASH").as_bytes());
+    fingerprint.update(json.as_bytes());
+    fingerprint.to_hex()
+}
+
+/// Features that affect the generated code.
+fn feature_key() -> String {
+    let features = [
+        ("full", cfg!(feature = "codegen-full")),
+        ("double", cfg!(feature = "double-precision")),
+        ("fmt", cfg!(feature = "codegen-fmt")),
+    ];
+
+    let enabled: Vec<&str> = features
+        .iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| *name)
+        .collect();
+
+    if enabled.is_empty() {
+        "minimal".to_string()
+    } else {
+        enabled.join("-")
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to)
+        .unwrap_or_else(|e| panic!("failed to create dir {};\n\t{}", to.display(), e));
+
+    let entries = fs::read_dir(from)
+        .unwrap_or_else(|e| panic!("failed to read dir {};\n\t{}", from.display(), e));
+
+    for entry in entries {
+        let entry = entry.unwrap_or_else(|e| panic!("failed to read dir entry;\n\t{e}"));
+        let src = entry.path();
+        let dst = to.join(entry.file_name());
+
+        if src.is_dir() {
+            copy_dir(&src, &dst);
+        } else {
+            fs::copy(&src, &dst).unwrap_or_else(|e| {
+                panic!(
+                    "failed to copy {} to {};\n\t{}",
+                    src.display(),
+                    dst.display(),
+                    e
+                )
+            });
+        }
+    }
+}
@@ -33,10 +33,45 @@ struct MethodTableInfo {
     pre_init_code: TokenStream,
     method_decls: Vec<TokenStream>,
     method_inits: Vec<TokenStream>,
+    method_getters: Vec<TokenStream>,
     class_count: usize,
     method_count: usize,
 }
 
+impl MethodTableInfo {
+    /// Creates a table whose entries are all lazy, i.e. `load()` does not need any parameters.
+    fn new_lazy(table_name: Ident) -> Self {
+        Self {
+            table_name,
+            imports: TokenStream::new(),
+            ctor_parameters: TokenStream::new(),
+            pre_init_code: TokenStream::new(),
+            method_decls: vec![],
+            method_inits: vec![],
+            method_getters: vec![],
+            class_count: 0,
+            method_count: 0,
+        }
+    }
+
+    /// Adds a function pointer, which is fetched from Godot on first access through `load_expr`.
+    fn push_lazy_entry(&mut self, field: &Ident, bind_ty: TokenStream, load_expr: TokenStream) {
+        self.method_decls.push(quote! {
+            #field: std::sync::OnceLock<#bind_ty>,
+        });
+        self.method_inits.push(quote! {
+            #field: std::sync::OnceLock::new(),
+        });
+        self.method_getters.push(quote! {
+            #[inline]
+            pub fn #field(&self) -> #bind_ty {
+                *self.#field.get_or_init(|| unsafe { #load_expr })
+            }
+        });
+        self.method_count += 1;
+    }
+}
+
 pub struct TypeNames {
     /// Name in JSON: "int" or "PackedVector2Array"
     pub json_builtin_name: String,
@@ -122,22 +157,7 @@ pub(crate) fn generate_sys_utilities_file(
     sys_gen_path: &Path,
     submit_fn: &mut SubmitFn,
 ) {
-    let mut table = MethodTableInfo {
-        table_name: ident("UtilityFunctionTable"),
-        imports: quote! {},
-        ctor_parameters: quote! {
-            interface: &crate::GDExtensionInterface,
-            string_names: &mut crate::StringCache,
-        },
-        pre_init_code: quote! {
-            let get_utility_fn = interface.variant_get_ptr_utility_function
-                .expect("variant_get_ptr_utility_function absent");
-        },
-        method_decls: vec![],
-        method_inits: vec![],
-        class_count: 0,
-        method_count: 0,
-    };
+    let mut table = MethodTableInfo::new_lazy(ident("UtilityFunctionTable"));
 
     for function in api.utility_functions.iter() {
         if codegen_special_cases::is_function_excluded(function, ctx) {
@@ -148,20 +168,11 @@ pub(crate) fn generate_sys_utilities_file(
         let field = util::make_utility_function_ptr_name(function);
         let hash = function.hash;
 
-        table.method_decls.push(quote! {
-            pub #field: crate::UtilityFunctionBind,
-        });
-
-        table.method_inits.push(quote! {
-            #field: {
-                let utility_fn = unsafe {
-                    get_utility_fn(string_names.fetch(#fn_name_str), #hash)
-                };
-                crate::validate_utility_function(utility_fn, #fn_name_str, #hash)
-            },
-        });
-
-        table.method_count += 1;
+        table.push_lazy_entry(
+            &field,
+            quote! { crate::UtilityFunctionBind },
+            quote! { crate::load_utility_function(#fn_name_str, #hash) },
+        );
     }
 
     let code = make_method_table(table);
@@ -170,6 +181,9 @@ pub(crate) fn generate_sys_utilities_file(
 }
 
 /// Generate code for a method table based on shared layout.
+///
+/// Entries added with [`MethodTableInfo::push_lazy_entry`] are not fetched in `load()`, but on first access. This keeps
+/// extension startup cheap, as most projects only use a fraction of the API.
 fn make_method_table(info: MethodTableInfo) -> TokenStream {
     let MethodTableInfo {
         table_name,
@@ -178,6 +192,7 @@ fn make_method_table(info: MethodTableInfo) -> TokenStream {
         pre_init_code,
         method_decls,
         method_inits,
+        method_getters,
         class_count,
         method_count,
     } = info;
@@ -197,6 +212,7 @@ fn make_method_table(info: MethodTableInfo) -> TokenStream {
             #( #method_decls )*
         }
 
+        #[allow(non_snake_case)]
         impl #table_name {
             pub const CLASS_COUNT: usize = #class_count;
             pub const METHOD_COUNT: usize = #method_count;
@@ -211,6 +227,8 @@ fn make_method_table(info: MethodTableInfo) -> TokenStream {
                     #( #method_inits )*
                 }
             }
+
+            #( #method_getters )*
         }
     }
 }
@@ -239,6 +257,7 @@ pub(crate) fn generate_core_mod_file(gen_path: &Path, submit_fn: &mut SubmitFn)
     // When invoked by another crate during unit-test (not integration test), don't run generator
     let code = quote! {
         pub mod central;
+        pub mod global;
         pub mod classes;
         pub mod builtin_classes;
         pub mod utilities;
@@ -258,8 +277,11 @@ pub(crate) fn generate_core_central_file(
     let builtin_types = BuiltinTypeMap::load(api);
     let central_items = make_central_items(api, build_config, builtin_types, ctx);
     let core_code = make_core_code(&central_items);
+    let global_code = make_global_code(&central_items);
 
+    // Global enums live in their own file, so that changes to them do not touch the (otherwise unrelated) central code.
     submit_fn(gen_path.join("central.rs"), core_code);
+    submit_fn(gen_path.join("global.rs"), global_code);
 }
 
 fn make_sys_code(central_items: &CentralItems) -> TokenStream {
@@ -416,7 +438,6 @@ fn make_core_code(central_items: &CentralItems) -> TokenStream {
     let CentralItems {
         variant_ty_enumerators_pascal,
         variant_ty_enumerators_rust,
-        global_enum_defs,
         ..
     } = central_items;
 
@@ -450,17 +471,23 @@ fn make_core_code(central_items: &CentralItems) -> TokenStream {
                 Ok(dispatch)
             }
         }
+    }
+}
 
-        /// Global enums and constants.
-        ///
-        /// A list of global-scope enumerated constants.
-        /// For global built-in functions, check out the [`utilities` module][crate::engine::utilities].
-        ///
-        /// See also [Godot docs for `@GlobalScope`](https://docs.godotengine.org/en/stable/classes/class_@globalscope.html#enumerations).
-        pub mod global {
-            use crate::sys;
-            #( #global_enum_defs )*
-        }
+fn make_global_code(central_items: &CentralItems) -> TokenStream {
+    let global_enum_defs = &central_items.global_enum_defs;
+
+    quote! {
+        //! Global enums and constants.
+        //!
+        //! A list of global-scope enumerated constants.
+        //! For global built-in functions, check out the [`utilities` module][crate::engine::utilities].
+        //!
+        //! See also [Godot docs for `@GlobalScope`](https://docs.godotengine.org/en/stable/classes/class_@globalscope.html#enumerations).
+
+        use crate::sys;
+
+        #( #global_enum_defs )*
     }
 }
 
@@ -569,6 +596,7 @@ fn make_builtin_lifecycle_table(builtin_types: &BuiltinTypeMap) -> TokenStream {
         },
         method_decls: Vec::with_capacity(len),
         method_inits: Vec::with_capacity(len),
+        method_getters: vec![],
         class_count: 0,
         method_count: len,
     };
@@ -596,21 +624,8 @@ fn make_class_method_table(
     api_level: ClassCodegenLevel,
     ctx: &mut Context,
 ) -> TokenStream {
-    let mut table = MethodTableInfo {
-        table_name: api_level.table_struct(),
-        imports: TokenStream::new(),
-        ctor_parameters: quote! {
-            interface: &crate::GDExtensionInterface,
-            string_names: &mut crate::StringCache,
-        },
-        pre_init_code: TokenStream::new(), // late-init, depends on class string names
-        method_decls: vec![],
-        method_inits: vec![],
-        class_count: 0,
-        method_count: 0,
-    };
+    let mut table = MethodTableInfo::new_lazy(api_level.table_struct());
 
-    let mut class_sname_decls = Vec::new();
     for class in api.classes.iter() {
         if special_cases::is_class_deleted(&TyName::from_godot(&class.name))
             || codegen_special_cases::is_class_excluded(&class.name)
@@ -619,47 +634,15 @@ fn make_class_method_table(
             continue;
         }
 
-        let class_var = format_ident!("sname_{}", &class.name);
-        let initializer_expr = util::make_sname_ptr(&class.name);
-
-        let prev_method_count = table.method_count;
-        populate_class_methods(&mut table, class, &class_var, ctx);
-        if table.method_count > prev_method_count {
-            // Only create class variable if any methods have been added.
-            class_sname_decls.push(quote! {
-                let #class_var = #initializer_expr;
-            });
-        }
-
+        populate_class_methods(&mut table, class, ctx);
         table.class_count += 1;
     }
 
-    table.pre_init_code = quote! {
-        let get_method_bind = interface.classdb_get_method_bind.expect("classdb_get_method_bind absent");
-
-        #( #class_sname_decls )*
-    };
-
     make_method_table(table)
 }
 
 fn make_builtin_method_table(api: &ExtensionApi, builtin_types: &BuiltinTypeMap) -> TokenStream {
-    let mut table = MethodTableInfo {
-        table_name: ident("BuiltinMethodTable"),
-        imports: TokenStream::new(),
-        ctor_parameters: quote! {
-            interface: &crate::GDExtensionInterface,
-            string_names: &mut crate::StringCache,
-        },
-        pre_init_code: quote! {
-            use crate as sys;
-            let get_builtin_method = interface.variant_get_ptr_builtin_method.expect("variant_get_ptr_builtin_method absent");
-        },
-        method_decls: vec![],
-        method_inits: vec![],
-        class_count: 0,
-        method_count: 0,
-    };
+    let mut table = MethodTableInfo::new_lazy(ident("BuiltinMethodTable"));
 
     // TODO reuse builtin_types without api
     for builtin in api.builtin_classes.iter() {
@@ -674,13 +657,7 @@ fn make_builtin_method_table(api: &ExtensionApi, builtin_types: &BuiltinTypeMap)
     make_method_table(table)
 }
 
-/// Returns whether at least 1 method was added.
-fn populate_class_methods(
-    table: &mut MethodTableInfo,
-    class: &Class,
-    class_var: &Ident,
-    ctx: &mut Context,
-) {
+fn populate_class_methods(table: &mut MethodTableInfo, class: &Class, ctx: &mut Context) {
     let class_name_str = class.name.as_str();
 
     for method in option_as_slice(&class.methods) {
@@ -689,14 +666,17 @@ fn populate_class_methods(
         }
 
         let field = util::make_class_method_ptr_name(&class.name, method);
+        let method_name_str = method.name.as_str();
+        let hash = method.hash.unwrap_or_else(|| {
+            panic!("class method has no hash: {class_name_str}::{method_name_str}")
+        });
 
         // Note: varcall/ptrcall is only decided at call time; the method bind is the same for both.
-        let method_decl = quote! { pub #field: crate::ClassMethodBind, };
-        let method_init = make_class_method_init(method, &field, class_var, class_name_str);
-
-        table.method_decls.push(method_decl);
-        table.method_inits.push(method_init);
-        table.method_count += 1;
+        table.push_lazy_entry(
+            &field,
+            quote! { crate::ClassMethodBind },
+            quote! { crate::load_class_method(#class_name_str, #method_name_str, #hash) },
+        );
     }
 }
 
@@ -705,73 +685,27 @@ fn populate_builtin_methods(
     builtin_class: &BuiltinClass,
     type_name: &TypeNames,
 ) {
+    let variant_type = &type_name.sys_variant_type;
+    let variant_type_str = &type_name.json_builtin_name;
+
     for method in option_as_slice(&builtin_class.methods) {
         if codegen_special_cases::is_builtin_method_excluded(method) {
             continue;
         }
 
         let field = util::make_builtin_method_ptr_name(type_name, method);
+        let method_name_str = method.name.as_str();
+        let hash = method.hash.unwrap_or_else(|| {
+            panic!("builtin method has no hash: {variant_type_str}::{method_name_str}")
+        });
 
-        let method_decl = quote! { pub #field: crate::BuiltinMethodBind, };
-        let method_init = make_builtin_method_init(method, &field, type_name);
-
-        table.method_decls.push(method_decl);
-        table.method_inits.push(method_init);
-        table.method_count += 1;
-    }
-}
-
-fn make_class_method_init(
-    method: &ClassMethod,
-    field: &Ident,
-    class_var: &Ident,
-    class_name_str: &str,
-) -> TokenStream {
-    let method_name_str = method.name.as_str();
-    let method_sname = util::make_sname_ptr(method_name_str);
-
-    let hash = method.hash.unwrap_or_else(|| {
-        panic!(
-            "class method has no hash: {}::{}",
-            class_name_str, method_name_str
-        )
-    });
-
-    quote! {
-        #field: {
-            let method_bind = unsafe {
-                get_method_bind(#class_var, #method_sname, #hash)
-            };
-            crate::validate_class_method(method_bind, #class_name_str, #method_name_str, #hash)
-        },
-    }
-}
-
-fn make_builtin_method_init(
-    method: &BuiltinClassMethod,
-    field: &Ident,
-    type_name: &TypeNames,
-) -> TokenStream {
-    let method_name_str = method.name.as_str();
-    let method_sname = util::make_sname_ptr(method_name_str);
-
-    let variant_type = &type_name.sys_variant_type;
-    let variant_type_str = &type_name.json_builtin_name;
-
-    let hash = method.hash.unwrap_or_else(|| {
-        panic!(
-            "builtin method has no hash: {}::{}",
-            variant_type_str, method_name_str
-        )
-    });
-
-    quote! {
-        #field: {
-            let method_bind = unsafe {
-                get_builtin_method(sys::#variant_type, #method_sname, #hash)
-            };
-            crate::validate_builtin_method(method_bind, #variant_type_str, #method_name_str, #hash)
-        },
+        table.push_lazy_entry(
+            &field,
+            quote! { crate::BuiltinMethodBind },
+            quote! {
+                crate::load_builtin_method(crate::#variant_type, #variant_type_str, #method_name_str, #hash)
+            },
+        );
     }
 }
 
@@ -13,6 +13,7 @@ use std::path::Path;
 use crate::api_parser::*;
 use crate::central_generator::{collect_builtin_types, BuiltinTypeInfo};
 use crate::context::NotificationEnum;
+use crate::diagnostics::CodegenReport;
 use crate::util::{
     ident, make_string_name, option_as_slice, parse_native_structures_format, safe_ident,
     to_pascal_case, to_rust_expr, to_rust_type, to_rust_type_abi, to_snake_case,
@@ -80,6 +81,7 @@ impl FnParam {
         let default_value = method_arg
             .default_value
             .as_ref()
+            .filter(|v| is_default_representable(v, &type_))
             .map(|v| to_rust_expr(v, &type_));
 
         FnParam {
@@ -161,6 +163,7 @@ struct FnSignature<'a> {
     qualifier: FnQualifier,
     params: Vec<FnParam>,
     return_value: FnReturn,
+    deprecated: Option<&'a str>,
 }
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
@@ -171,6 +174,8 @@ struct FnCode {
     init_code: TokenStream,
     varcall_invocation: TokenStream,
     ptrcall_invocation: TokenStream,
+    /// Leading arguments to the shared `sys::class_method_ptrcall*` thunks. If present, these replace `ptrcall_invocation`.
+    ptrcall_thunk_args: Option<TokenStream>,
 }
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
@@ -227,6 +232,7 @@ pub(crate) fn generate_class_files(
     ctx: &mut Context,
     _build_config: [&str; 2],
     gen_path: &Path,
+    report: &mut CodegenReport,
     submit_fn: &mut SubmitFn,
 ) {
     let _ = std::fs::remove_dir_all(gen_path);
@@ -246,6 +252,12 @@ pub(crate) fn generate_class_files(
         let generated_class = make_class(class, &class_name, ctx);
         let file_contents = generated_class.code;
 
+        report.add_class(
+            class_name.rust_ty.to_string(),
+            class_name.godot_ty.clone(),
+            make_method_names(class, &class_name, ctx),
+        );
+
         let out_path = gen_path.join(format!("{}.rs", module_name.rust_mod));
 
         submit_fn(out_path, file_contents);
@@ -494,7 +506,10 @@ fn make_class(class: &Class, class_name: &TyName, ctx: &mut Context) -> Generate
     };
 
     let constructor = make_constructor(class, ctx);
-    let get_method_table = util::get_api_level(class).table_global_getter();
+    let api_level = util::get_api_level(class);
+    let get_method_table = api_level.table_global_getter();
+    let init_level = api_level.to_init_level();
+    let deprecated_attr = make_deprecated_attr(class.deprecated.as_deref());
 
     let FnDefinitions {
         functions: methods,
@@ -598,6 +613,7 @@ fn make_class(class: &Class, class_name: &TyName, ctx: &mut Context) -> Generate
             use super::*;
 
             #[doc = #class_doc]
+            #deprecated_attr
             #[derive(Debug)]
             #[repr(transparent)]
             pub struct #class_name {
@@ -615,6 +631,7 @@ fn make_class(class: &Class, class_name: &TyName, ctx: &mut Context) -> Generate
                 type Base = #base_ty;
                 type Declarer = crate::obj::dom::EngineDomain;
                 type Mem = crate::obj::mem::#memory;
+                const INIT_LEVEL: Option<crate::init::InitLevel> = Some(#init_level);
 
                 fn class_name() -> ClassName {
                     ClassName::from_ascii_cstr(#class_name_cstr)
@@ -879,20 +896,35 @@ fn make_native_structure(
 ) -> GeneratedBuiltin {
     let class_name = &class_name.rust_ty;
 
-    let fields = make_native_structure_fields(&structure.format, ctx);
+    let fields = parse_native_structures_format(&structure.format)
+        .expect("Could not parse native_structures format field");
+
+    let mut field_definitions = vec![];
+    let mut accessors = vec![];
+    for field in fields {
+        let (field_definition, accessor) = make_native_structure_field_and_accessor(field, ctx);
+
+        field_definitions.push(field_definition);
+        accessors.extend(accessor);
+    }
 
     // mod re_export needed, because class should not appear inside the file module, and we can't re-export private struct as pub
     let tokens = quote! {
         use godot_ffi as sys;
         use crate::builtin::*;
         use crate::engine::native::*;
-        use crate::obj::Gd;
+        use crate::obj::{Gd, GodotClass, InstanceId};
         use crate::sys::GodotFfi as _;
         use crate::engine::Object;
 
         #[repr(C)]
+        #[derive(Clone, PartialEq, Debug)]
         pub struct #class_name {
-            #fields
+            #( #field_definitions )*
+        }
+
+        impl #class_name {
+            #( #accessors )*
         }
     };
     // note: TypePtr -> ObjectPtr conversion OK?
@@ -900,26 +932,112 @@ fn make_native_structure(
     GeneratedBuiltin { code: tokens }
 }
 
-fn make_native_structure_fields(format_str: &str, ctx: &mut Context) -> TokenStream {
-    let fields = parse_native_structures_format(format_str)
-        .expect("Could not parse native_structures format field");
-    let field_definitions = fields
-        .into_iter()
-        .map(|field| make_native_structure_field_definition(field, ctx));
-    quote! {
-        #( #field_definitions )*
-    }
-}
-
-fn make_native_structure_field_definition(
+/// Returns the field definition, plus accessors converting fields that are awkward to use directly.
+fn make_native_structure_field_and_accessor(
     field: NativeStructuresField,
     ctx: &mut Context,
-) -> TokenStream {
+) -> (TokenStream, Option<TokenStream>) {
+    let field_name = ident(&to_snake_case(&field.field_name));
+
+    // Fixed-size arrays, e.g. `collisions[32]`.
+    if let Some((elem_type, size)) = field.field_type.split_once('[') {
+        let elem_type = normalize_native_structure_field_type(elem_type);
+        let elem_type = to_rust_type_abi(&elem_type, ctx);
+        let size = size
+            .trim_end_matches(']')
+            .parse::<usize>()
+            .expect("native structure array size");
+        let size = Literal::usize_unsuffixed(size);
+
+        let definition = quote! {
+            pub #field_name: [#elem_type; #size],
+        };
+        return (definition, None);
+    }
+
     let field_type = normalize_native_structure_field_type(&field.field_type);
     let field_type = to_rust_type_abi(&field_type, ctx);
-    let field_name = ident(&to_snake_case(&field.field_name));
-    quote! {
-        pub #field_name: #field_type,
+
+    match &field_type {
+        // `Object*` has the ABI of an object pointer, not of `*mut Gd<Object>`.
+        RustTy::RawPointer { inner, .. } if matches!(**inner, RustTy::EngineClass { .. }) => {
+            let getter = format_ident!("{field_name}_as_object");
+            let setter = format_ident!("set_{field_name}_as_object");
+            let definition = quote! {
+                pub #field_name: sys::GDExtensionObjectPtr,
+            };
+            let accessor = quote! {
+                /// Returns the object pointed to by this field, or `None` if the pointer is null.
+                ///
+                /// # Safety
+                /// The pointer must be null or point to a live Godot object.
+                pub unsafe fn #getter(&self) -> Option<Gd<Object>> {
+                    if self.#field_name.is_null() {
+                        None
+                    } else {
+                        Some(Gd::from_obj_sys(self.#field_name))
+                    }
+                }
+
+                /// Points this field to `object`, without affecting its reference count.
+                pub fn #setter<T: GodotClass>(&mut self, object: &Gd<T>) {
+                    self.#field_name = object.obj_sys();
+                }
+            };
+
+            (definition, Some(accessor))
+        }
+
+        // Enums have the C++ layout of an `int`, unlike their Rust counterparts; reading arbitrary ints as Rust enums would be UB.
+        RustTy::EngineEnum { .. } => {
+            let getter = format_ident!("{field_name}_as_enum");
+            let setter = format_ident!("set_{field_name}_as_enum");
+            let definition = quote! {
+                pub #field_name: i32,
+            };
+            let accessor = quote! {
+                /// Returns the enumerator stored in this field; unknown ordinals are kept as `Unknown`.
+                pub fn #getter(&self) -> #field_type {
+                    crate::obj::EngineEnum::from_ord(self.#field_name)
+                }
+
+                /// Stores the ordinal of `value` in this field.
+                pub fn #setter(&mut self, value: #field_type) {
+                    self.#field_name = crate::obj::EngineEnum::ord(value);
+                }
+            };
+
+            (definition, Some(accessor))
+        }
+
+        // `ObjectID` is a native structure wrapping a `u64`; expose it as `InstanceId`.
+        _ if field.field_type == "ObjectID" => {
+            let getter = format_ident!("{field_name}_as_instance_id");
+            let setter = format_ident!("set_{field_name}_as_instance_id");
+            let definition = quote! {
+                pub #field_name: #field_type,
+            };
+            let accessor = quote! {
+                /// Returns the instance ID stored in this field, or `None` if it is zero.
+                pub fn #getter(&self) -> Option<InstanceId> {
+                    InstanceId::try_from_u64(self.#field_name.id)
+                }
+
+                /// Stores `instance_id` in this field; `None` is stored as zero.
+                pub fn #setter(&mut self, instance_id: Option<InstanceId>) {
+                    self.#field_name.id = instance_id.map_or(0, InstanceId::to_u64);
+                }
+            };
+
+            (definition, Some(accessor))
+        }
+
+        _ => {
+            let definition = quote! {
+                pub #field_name: #field_type,
+            };
+            (definition, None)
+        }
     }
 }
 
@@ -1079,15 +1197,27 @@ fn make_special_builtin_methods(class_name: &TyName, _ctx: &Context) -> TokenStr
     }
 }
 
+fn is_method_skipped(method: &ClassMethod, class_name: &TyName, ctx: &mut Context) -> bool {
+    codegen_special_cases::is_method_excluded(method, false, ctx)
+        || special_cases::is_deleted(class_name, &method.name)
+}
+
+/// Rust names of all non-virtual methods generated for `class`.
+fn make_method_names(class: &Class, class_name: &TyName, ctx: &mut Context) -> Vec<String> {
+    option_as_slice(&class.methods)
+        .iter()
+        .filter(|method| !is_method_skipped(method, class_name, ctx))
+        .map(|method| special_cases::maybe_renamed(class_name, &method.name).to_string())
+        .collect()
+}
+
 fn make_method_definition(
     method: &ClassMethod,
     class_name: &TyName,
     get_method_table: &Ident,
     ctx: &mut Context,
 ) -> FnDefinition {
-    if codegen_special_cases::is_method_excluded(method, false, ctx)
-        || special_cases::is_deleted(class_name, &method.name)
-    {
+    if is_method_skipped(method, class_name, ctx) {
         return FnDefinition::none();
     }
     /*if method.map_args(|args| args.is_empty()) {
@@ -1108,31 +1238,31 @@ fn make_method_definition(
         quote! { self.object_ptr },
     );
 
+    // Only vararg methods need varcall, since their arity is not known up front. All other methods use ptrcall, which passes
+    // arguments and return values in their native representation, without boxing them into Variant.
     let is_varcall = method.is_vararg;
     let variant_ffi = is_varcall.then(VariantFfi::variant_ptr);
 
-    let function_provider = if method.is_vararg {
-        // varcall
-        quote! { sys::interface_fn!(object_method_bind_call) }
-    } else {
-        // ptrcall
-        quote! { sys::interface_fn!(object_method_bind_ptrcall) }
-    };
-
     let fn_ptr = util::make_class_method_ptr_name(&class_name.godot_ty, method);
+    let receiver_ffi_arg = &receiver.ffi_arg;
 
-    let init_code = quote! {
-        let __method_bind = sys::#get_method_table().#fn_ptr;
-        let __call_fn = #function_provider;
+    // Ptrcalls go through shared thunks instead of inlining the call sequence, see sys::class_method_ptrcall().
+    let (init_code, ptrcall_thunk_args) = if is_varcall {
+        let init_code = quote! {
+            let __method_bind = sys::#get_method_table().#fn_ptr();
+            let __call_fn = sys::interface_fn!(object_method_bind_call);
+        };
+        (init_code, None)
+    } else {
+        let init_code = quote! {
+            let __method_bind = sys::#get_method_table().#fn_ptr();
+        };
+        (init_code, Some(quote! { __method_bind, #receiver_ffi_arg }))
     };
 
-    let receiver_ffi_arg = &receiver.ffi_arg;
     let varcall_invocation = quote! {
         __call_fn(__method_bind, #receiver_ffi_arg, __args_ptr, __args.len() as i64, return_ptr, std::ptr::addr_of_mut!(__err));
     };
-    let ptrcall_invocation = quote! {
-        __call_fn(__method_bind, #receiver_ffi_arg, __args_ptr, return_ptr);
-    };
 
     make_function_definition(
         &FnSignature {
@@ -1143,13 +1273,15 @@ fn make_method_definition(
             qualifier: FnQualifier::for_method(method.is_const, method.is_static),
             params: FnParam::new_range(&method.arguments, ctx),
             return_value: FnReturn::new(&method.return_value, ctx),
+            deprecated: method.deprecated.as_deref(),
         },
         &FnCode {
             receiver,
             variant_ffi,
             init_code,
             varcall_invocation,
-            ptrcall_invocation,
+            ptrcall_invocation: TokenStream::new(),
+            ptrcall_thunk_args,
         },
     )
 }
@@ -1178,7 +1310,7 @@ fn make_builtin_method_definition(
     let fn_ptr = util::make_builtin_method_ptr_name(&type_info.type_names, method);
 
     let init_code = quote! {
-        let __call_fn = sys::builtin_method_table().#fn_ptr;
+        let __call_fn = sys::builtin_method_table().#fn_ptr();
     };
 
     let receiver = make_receiver(method.is_static, method.is_const, quote! { self.sys_ptr });
@@ -1199,6 +1331,7 @@ fn make_builtin_method_definition(
             // They are not public-facing and need more involved implementation (lifetimes etc). Also reduces number of symbols in API.
             params: FnParam::new_range_no_defaults(&method.arguments, ctx),
             return_value: FnReturn::new(&return_value, ctx),
+            deprecated: None,
         },
         &FnCode {
             receiver,
@@ -1206,6 +1339,7 @@ fn make_builtin_method_definition(
             init_code,
             varcall_invocation: ptrcall_invocation.clone(),
             ptrcall_invocation,
+            ptrcall_thunk_args: None,
         },
     )
 }
@@ -1227,7 +1361,7 @@ pub(crate) fn make_utility_function_definition(
         .map(MethodReturn::from_type_no_meta);
     let variant_ffi = function.is_vararg.then_some(VariantFfi::type_ptr());
     let init_code = quote! {
-        let __call_fn = sys::utility_function_table().#fn_ptr;
+        let __call_fn = sys::utility_function_table().#fn_ptr();
     };
     let invocation = quote! {
         __call_fn(return_ptr, __args_ptr, __args.len() as i32);
@@ -1242,6 +1376,7 @@ pub(crate) fn make_utility_function_definition(
             qualifier: FnQualifier::Global,
             params: FnParam::new_range(&function.arguments, ctx),
             return_value: FnReturn::new(&return_value, ctx),
+            deprecated: None,
         },
         &FnCode {
             receiver: FnReceiver::global_function(),
@@ -1249,6 +1384,7 @@ pub(crate) fn make_utility_function_definition(
             init_code,
             varcall_invocation: invocation.clone(),
             ptrcall_invocation: invocation,
+            ptrcall_thunk_args: None,
         },
     );
 
@@ -1284,8 +1420,17 @@ fn make_vis(is_private: bool) -> TokenStream {
     }
 }
 
+fn make_deprecated_attr(deprecated: Option<&str>) -> TokenStream {
+    match deprecated {
+        Some("") => quote! { #[deprecated] },
+        Some(note) => quote! { #[deprecated(note = #note)] },
+        None => TokenStream::new(),
+    }
+}
+
 fn make_function_definition(sig: &FnSignature, code: &FnCode) -> FnDefinition {
     let has_default_params = function_uses_default_params(sig);
+    let deprecated_attr = make_deprecated_attr(sig.deprecated);
     let vis = if has_default_params {
         // Public API mapped by separate function.
         // Needs to be crate-public because default-arg builder lives outside of the module.
@@ -1308,8 +1453,8 @@ fn make_function_definition(sig: &FnSignature, code: &FnCode) -> FnDefinition {
     };
 
     let is_varcall = code.variant_ffi.is_some();
-    let [params, variant_types, arg_exprs, arg_names] =
-        make_params_and_impl(&sig.params, is_varcall, false);
+    let [params, variant_types, arg_exprs, arg_names, arg_conversions] =
+        make_params_and_impl(&sig.params, is_varcall, sig.is_virtual, false);
 
     let primary_fn_name = if has_default_params {
         format_ident!("{}_full", safe_ident(sig.function_name))
@@ -1366,6 +1511,7 @@ fn make_function_definition(sig: &FnSignature, code: &FnCode) -> FnDefinition {
     let primary_function = if sig.is_virtual {
         quote! {
             #safety_doc
+            #deprecated_attr
             #maybe_unsafe fn #primary_fn_name(
                 #receiver_param
                 #( #params, )*
@@ -1379,11 +1525,14 @@ fn make_function_definition(sig: &FnSignature, code: &FnCode) -> FnDefinition {
         let init_code = &code.init_code;
         quote! {
             #safety_doc
+            #deprecated_attr
             #vis #maybe_unsafe fn #primary_fn_name(
                 #receiver_param
                 #( #params, )*
                 varargs: &[Variant]
             ) #return_decl {
+                #( #arg_conversions )*
+
                 unsafe {
                     #init_code
 
@@ -1391,9 +1540,11 @@ fn make_function_definition(sig: &FnSignature, code: &FnCode) -> FnDefinition {
                         #( #arg_exprs ),*
                     ];
 
-                    let mut __args = Vec::with_capacity(__explicit_args.len() + varargs.len());
-                    __args.extend(__explicit_args.iter().map(Variant::#sys_method));
-                    __args.extend(varargs.iter().map(Variant::#sys_method));
+                    // Stack-allocated for the common case of few arguments.
+                    let __args = sys::ArgPtrBuffer::new(
+                        __explicit_args.len() + varargs.len(),
+                        __explicit_args.iter().chain(varargs).map(Variant::#sys_method),
+                    );
 
                     let __args_ptr = __args.as_ptr();
 
@@ -1404,15 +1555,26 @@ fn make_function_definition(sig: &FnSignature, code: &FnCode) -> FnDefinition {
     } else {
         // ptrcall
         let init_code = &code.init_code;
+        let args_ptr_decl = if code.ptrcall_thunk_args.is_none() {
+            quote! { let __args_ptr = __args.as_ptr(); }
+        } else {
+            TokenStream::new() // thunks take the slice directly
+        };
+
         quote! {
             #safety_doc
+            #deprecated_attr
             #vis #maybe_unsafe fn #primary_fn_name(
                 #receiver_param
                 #( #params, )*
             ) #return_decl {
+                #( #arg_conversions )*
+
                 unsafe {
                     #init_code
 
+                    // Arguments are moved into the tuple and passed by pointer to their storage. Ref-counted builtins
+                    // (strings, packed arrays) thus hand their existing buffer to Godot, without any copy.
                     #[allow(clippy::let_unit_value)]
                     let __args = (
                         #( #arg_exprs, )*
@@ -1422,8 +1584,7 @@ fn make_function_definition(sig: &FnSignature, code: &FnCode) -> FnDefinition {
                         #( sys::GodotFfi::as_arg_ptr(&__args.#args_indices) ),*
                     ];
 
-                    let __args_ptr = __args.as_ptr();
-
+                    #args_ptr_decl
                     #call_code
                 }
             }
@@ -1452,6 +1613,7 @@ fn make_function_definition_with_defaults(
     let simple_fn_name = safe_ident(sig.function_name);
     let extended_fn_name = format_ident!("{}_ex", simple_fn_name);
     let vis = make_vis(sig.is_private);
+    let deprecated_attr = make_deprecated_attr(sig.deprecated);
 
     let (builder_doc, surround_class_prefix) = make_extender_doc(sig, &extended_fn_name);
 
@@ -1474,6 +1636,8 @@ fn make_function_definition_with_defaults(
     let receiver_param = &code.receiver.param;
     let receiver_self = &code.receiver.self_prefix;
     let (required_params, required_args) = make_params_and_args(&required_fn_params);
+    let (required_generic_params, required_conversions) =
+        make_generic_params(&required_fn_params, sig.is_virtual);
     let return_decl = &sig.return_value.decl;
 
     // Technically, the builder would not need a lifetime -- it could just maintain an `object_ptr` copy.
@@ -1516,9 +1680,10 @@ fn make_function_definition_with_defaults(
 
     let functions = quote! {
         #[inline]
+        #deprecated_attr
         #vis fn #simple_fn_name(
             #receiver_param
-            #( #required_params, )*
+            #( #required_generic_params, )*
         ) #return_decl {
             #receiver_self #extended_fn_name(
                 #( #required_args, )*
@@ -1526,10 +1691,13 @@ fn make_function_definition_with_defaults(
         }
 
         #[inline]
+        #deprecated_attr
         #vis fn #extended_fn_name(
             #receiver_param
-            #( #required_params, )*
+            #( #required_generic_params, )*
         ) -> #builder_ty #builder_anon_lifetime {
+            #( #required_conversions )*
+
             #builder_ty::new(
                 #object_arg
                 #( #required_args, )*
@@ -1719,12 +1887,14 @@ fn make_receiver(is_static: bool, is_const: bool, ffi_arg: TokenStream) -> FnRec
 fn make_params_and_impl(
     method_args: &[FnParam],
     is_varcall: bool,
+    is_virtual: bool,
     skip_defaults: bool,
-) -> [Vec<TokenStream>; 4] {
+) -> [Vec<TokenStream>; 5] {
     let mut params = vec![];
     let mut variant_types = vec![];
     let mut arg_exprs = vec![];
     let mut arg_names = vec![];
+    let mut arg_conversions = vec![];
 
     for param in method_args.iter() {
         if skip_defaults && param.default_value.is_some() {
@@ -1742,13 +1912,16 @@ fn make_params_and_impl(
             quote! { <#param_ty as sys::GodotFuncMarshal>::try_into_via(#param_name).unwrap() }
         };
 
-        params.push(quote! { #param_name: #param_ty });
+        let (param, conversion) = make_generic_param(param, is_virtual);
+
+        params.push(param);
         variant_types.push(quote! { <#param_ty as VariantMetadata>::variant_type() });
         arg_exprs.push(arg_expr);
         arg_names.push(quote! { #param_name });
+        arg_conversions.extend(conversion);
     }
 
-    [params, variant_types, arg_exprs, arg_names]
+    [params, variant_types, arg_exprs, arg_names, arg_conversions]
 }
 
 fn make_params_and_args(method_args: &[&FnParam]) -> (Vec<TokenStream>, Vec<TokenStream>) {
@@ -1763,6 +1936,43 @@ fn make_params_and_args(method_args: &[&FnParam]) -> (Vec<TokenStream>, Vec<Toke
         .unzip()
 }
 
+fn make_generic_params(
+    method_args: &[&FnParam],
+    is_virtual: bool,
+) -> (Vec<TokenStream>, Vec<TokenStream>) {
+    let mut params = vec![];
+    let mut conversions = vec![];
+
+    for param in method_args {
+        let (param, conversion) = make_generic_param(param, is_virtual);
+
+        params.push(param);
+        conversions.extend(conversion);
+    }
+
+    (params, conversions)
+}
+
+/// Object parameters accept any subclass via `impl AsObjectArg<T>`, which is converted to `Gd<T>` at the start of the body.
+///
+/// Virtual methods keep `Gd<T>`, since they are implemented by the user and called by Godot.
+fn make_generic_param(param: &FnParam, is_virtual: bool) -> (TokenStream, Option<TokenStream>) {
+    let param_name = &param.name;
+    let param_ty = &param.type_;
+
+    match param_ty {
+        RustTy::EngineClass { class, .. } if !is_virtual => {
+            let class = ident(class);
+            let param = quote! { #param_name: impl crate::obj::AsObjectArg<crate::engine::#class> };
+            let conversion =
+                quote! { let #param_name = crate::obj::AsObjectArg::into_object_arg(#param_name); };
+
+            (param, Some(conversion))
+        }
+        _ => (quote! { #param_name: #param_ty }, None),
+    }
+}
+
 fn make_return_and_impl(
     return_value: &FnReturn,
     code: &FnCode,
@@ -1804,6 +2014,30 @@ fn make_return_and_impl(
         };
     }
 
+    // Ptrcall through shared thunk
+    if let Some(thunk_args) = &code.ptrcall_thunk_args {
+        return match return_ty {
+            Some(RustTy::EngineClass { tokens, .. }) => {
+                let return_ty = tokens;
+                quote! {
+                    <#return_ty>::from_sys_init_opt(|return_ptr| {
+                        sys::class_method_ptrcall(#thunk_args, &__args, return_ptr)
+                    })
+                }
+            }
+            Some(return_ty) => {
+                quote! {
+                    sys::class_method_ptrcall_ret::<#return_ty>(#thunk_args, &__args)
+                }
+            }
+            None => {
+                quote! {
+                    sys::class_method_ptrcall(#thunk_args, &__args, std::ptr::null_mut())
+                }
+            }
+        };
+    }
+
     // Ptrcall
     let ptrcall_invocation = &code.ptrcall_invocation;
     match return_ty {
@@ -1916,6 +2150,7 @@ fn make_virtual_method(method: &ClassMethod, ctx: &mut Context) -> TokenStream {
             qualifier: FnQualifier::for_method(method.is_const, method.is_static),
             params: FnParam::new_range(&method.arguments, ctx),
             return_value: FnReturn::new(&method.return_value, ctx),
+            deprecated: None, // would warn in #[godot_api] code that forwards to user overrides
         },
         &FnCode {
             receiver: make_receiver(false, method.is_const, TokenStream::new()),
@@ -1924,6 +2159,7 @@ fn make_virtual_method(method: &ClassMethod, ctx: &mut Context) -> TokenStream {
             init_code: TokenStream::new(),
             varcall_invocation: TokenStream::new(),
             ptrcall_invocation: TokenStream::new(),
+            ptrcall_thunk_args: None,
         },
     );
 
@@ -2004,6 +2240,14 @@ fn function_uses_pointers(sig: &FnSignature) -> bool {
     has_pointer_params || has_pointer_return
 }
 
+/// Whether a GDScript default value can be expressed in Rust for the given parameter type.
+///
+/// Object parameters with `null` default cannot be represented as long as they are mapped to `Gd<T>` and not `Option<Gd<T>>` (#156).
+/// Such parameters are treated as required, so that the `_ex()` builder never has to come up with a value.
+fn is_default_representable(gdscript_expr: &str, ty: &RustTy) -> bool {
+    !(gdscript_expr == "null" && matches!(ty, RustTy::EngineClass { .. }))
+}
+
 fn function_uses_default_params(sig: &FnSignature) -> bool {
     sig.params.iter().any(|arg| arg.default_value.is_some())
         && !special_cases::is_excluded_from_default_params(sig.surrounding_class, sig.function_name)
@@ -115,9 +115,16 @@ const SELECTED_CLASSES: &[&str] = &[
     "AnimatedSprite2D",
     "ArrayMesh",
     "Area2D",
+    "AudioServer",
+    "AudioStream",
+    "AudioStreamPlayback",
+    "AudioStreamPlaybackResampled",
     "AudioStreamPlayer",
+    "AudioStreamWAV",
     "BaseButton",
+    "BaseMaterial3D",
     "Button",
+    "BoxContainer",
     "BoxMesh",
     "Camera2D",
     "Camera3D",
@@ -126,19 +133,55 @@ const SELECTED_CLASSES: &[&str] = &[
     "ClassDB",
     "CollisionObject2D",
     "CollisionShape2D",
+    "Container",
     "Control",
+    "EditorCommandPalette",
+    "EditorDebuggerPlugin",
+    "EditorDebuggerSession",
+    "EditorExportPlatform",
+    "EditorExportPlugin",
+    "EditorInterface",
+    "EditorNode3DGizmo",
+    "EditorNode3DGizmoPlugin",
+    "EditorFileSystem",
+    "EditorImportPlugin",
+    "EditorInspectorPlugin",
+    "EditorPlugin",
+    "EditorProperty",
+    "EditorResourcePreview",
+    "EditorResourcePreviewGenerator",
+    "EditorScenePostImport",
+    "EditorScenePostImportPlugin",
+    "EditorSelection",
+    "EditorSyntaxHighlighter",
+    "EditorTranslationParserPlugin",
+    "EditorUndoRedoManager",
     "Engine",
+    "EngineDebugger",
     "FileAccess",
+    "HTTPClient",
     "HTTPRequest",
     "Image",
+    "ImageTexture",
     "ImageTextureLayered",
+    "FileSystemDock",
+    "Font",
+    "FontFile",
     "Input",
     "InputEvent",
     "InputEventAction",
+    "JSON",
     "Label",
     "MainLoop",
     "Marker2D",
+    "Material",
     "Mesh",
+    "MovieWriter",
+    "MultiplayerPeer",
+    "MultiplayerPeerExtension",
+    "NavigationMesh",
+    "NavigationServer2D",
+    "NavigationServer3D",
     "Node",
     "Node2D",
     "Node3D",
@@ -146,25 +189,75 @@ const SELECTED_CLASSES: &[&str] = &[
     "Object",
     "OS",
     "PackedScene",
+    "PacketPeer",
+    "PacketPeerExtension",
     "PathFollow2D",
+    "Performance",
     "PhysicsBody2D",
+    "PhysicsDirectBodyState2D",
+    "PhysicsDirectBodyState2DExtension",
+    "PhysicsDirectBodyState3D",
+    "PhysicsDirectBodyState3DExtension",
+    "PhysicsDirectSpaceState2D",
+    "PhysicsDirectSpaceState2DExtension",
+    "PhysicsDirectSpaceState3D",
+    "PhysicsDirectSpaceState3DExtension",
+    "PhysicsServer2D",
+    "PhysicsServer2DExtension",
+    "PhysicsServer2DManager",
+    "PhysicsServer3D",
+    "PhysicsServer3DExtension",
+    "PhysicsServer3DManager",
+    "Popup",
+    "PopupMenu",
     "PrimitiveMesh",
+    "ProjectSettings",
+    "PropertyTweener",
     "RefCounted",
     "RenderingServer",
     "Resource",
     "ResourceFormatLoader",
+    "ResourceFormatSaver",
+    "ResourceImporter",
     "ResourceLoader",
+    "ResourceSaver",
     "RigidBody2D",
     "SceneTree",
+    "SceneTreeTimer",
+    "Script",
+    "ScriptExtension",
+    "ScriptLanguage",
+    "ScriptLanguageExtension",
     "Sprite2D",
     "SpriteFrames",
+    "StandardMaterial3D",
+    "StreamPeer",
+    "StreamPeerExtension",
+    "SyntaxHighlighter",
+    "TextEdit",
     "TextServer",
     "TextServerExtension",
+    "TextServerManager",
     "Texture",
+    "Texture2D",
     "Texture2DArray",
     "TextureLayered",
     "Time",
     "Timer",
+    "Translation",
+    "TranslationServer",
+    "Tween",
+    "Tweener",
+    "UndoRedo",
+    "VBoxContainer",
+    "VideoStream",
+    "VideoStreamPlayback",
+    "VideoStreamPlayer",
+    "WebRTCDataChannel",
+    "WebRTCDataChannelExtension",
+    "WebRTCPeerConnection",
+    "WebRTCPeerConnectionExtension",
     "Window",
+    "WorkerThreadPool",
     "Viewport",
 ];
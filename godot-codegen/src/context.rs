@@ -5,6 +5,7 @@
  */
 
 use crate::api_parser::Class;
+use crate::diagnostics::codegen_log;
 use crate::{codegen_special_cases, util, ExtensionApi, GodotTy, RustTy, TyName};
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, ToTokens};
@@ -49,13 +50,13 @@ impl<'a> Context<'a> {
             }
 
             // Populate class lookup by name
-            println!("-- add engine class {}", class_name.description());
+            codegen_log!("-- add engine class {}", class_name.description());
             ctx.engine_classes.insert(class_name.clone(), class);
 
             // Populate derived-to-base relations
             if let Some(base) = class.inherits.as_ref() {
                 let base_name = TyName::from_godot(base);
-                println!("  -- inherits {}", base_name.description());
+                codegen_log!("  -- inherits {}", base_name.description());
                 ctx.inheritance_tree.insert(class_name.clone(), base_name);
             }
 
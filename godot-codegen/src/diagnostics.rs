@@ -0,0 +1,114 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Build output of the code generator.
+//!
+//! Codegen is silent by default. The environment variable `GODOT_CODEGEN_LOG` enables more output:
+//! * `verbose`: print progress (parsed API version, engine classes and their bases) to the build script output.
+//! * `report`: like `verbose`, and additionally write `codegen-report.json` next to the generated code.
+//!   It lists every generated engine class together with its methods, and is meant to be consumed by tools.
+
+use nanoserde::SerJson;
+use std::path::Path;
+use std::sync::OnceLock;
+
+const LOG_ENV_VAR: &str = "GODOT_CODEGEN_LOG";
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub(crate) enum LogLevel {
+    Quiet,
+    Verbose,
+    Report,
+}
+
+impl LogLevel {
+    fn from_env_value(value: &str) -> Option<Self> {
+        let level = match value {
+            "" | "quiet" => Self::Quiet,
+            "verbose" => Self::Verbose,
+            "report" => Self::Report,
+            _ => return None,
+        };
+        Some(level)
+    }
+}
+
+/// Log level, read once from `GODOT_CODEGEN_LOG`.
+pub(crate) fn log_level() -> LogLevel {
+    static LEVEL: OnceLock<LogLevel> = OnceLock::new();
+
+    *LEVEL.get_or_init(|| {
+        println!("cargo:rerun-if-env-changed={LOG_ENV_VAR}");
+
+        match std::env::var(LOG_ENV_VAR) {
+            Ok(value) => LogLevel::from_env_value(&value).unwrap_or_else(|| {
+                panic!(
+                    "{LOG_ENV_VAR}={value:?} is invalid; expected one of: quiet, verbose, report"
+                )
+            }),
+            Err(_) => LogLevel::Quiet,
+        }
+    })
+}
+
+/// Prints to the build script output, if `GODOT_CODEGEN_LOG` is at least `verbose`.
+macro_rules! codegen_log {
+    ($($args:tt)*) => {
+        if $crate::diagnostics::log_level() >= $crate::diagnostics::LogLevel::Verbose {
+            println!($($args)*);
+        }
+    };
+}
+
+pub(crate) use codegen_log;
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Machine-readable report
+
+#[derive(SerJson)]
+pub(crate) struct CodegenReport {
+    godot_version: String,
+    classes: Vec<ReportClass>,
+}
+
+#[derive(SerJson)]
+struct ReportClass {
+    name: String,
+    godot_name: String,
+    methods: Vec<String>,
+}
+
+impl CodegenReport {
+    pub fn new(godot_version: String) -> Self {
+        Self {
+            godot_version,
+            classes: vec![],
+        }
+    }
+
+    pub fn add_class(&mut self, name: String, godot_name: String, methods: Vec<String>) {
+        self.classes.push(ReportClass {
+            name,
+            godot_name,
+            methods,
+        });
+    }
+
+    /// Writes the report to `to_file`, if `GODOT_CODEGEN_LOG=report`.
+    pub fn write_if_enabled(&self, to_file: &Path) {
+        if log_level() < LogLevel::Report {
+            return;
+        }
+
+        std::fs::write(to_file, self.serialize_json()).unwrap_or_else(|e| {
+            panic!(
+                "failed to write codegen report to {};\n\t{}",
+                to_file.display(),
+                e
+            )
+        });
+    }
+}
@@ -0,0 +1,35 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Content hash for codegen cache keys.
+//!
+//! Also included by this crate's build script, so it must not depend on anything else in the crate.
+//! Uses FNV-1a rather than `std`'s `DefaultHasher`, whose output may differ between Rust versions and thus between machines
+//! sharing a cache.
+
+const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const PRIME: u64 = 0x0100_0000_01b3;
+
+/// Incremental 64-bit FNV-1a hash.
+pub(crate) struct Fingerprint(u64);
+
+impl Fingerprint {
+    pub fn new() -> Self {
+        Self(OFFSET_BASIS)
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(PRIME);
+        }
+    }
+
+    /// Hash as 16 hex digits.
+    pub fn to_hex(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
@@ -47,6 +47,8 @@ fn generate_proc_address_funcs(h_path: &Path) -> TokenStream {
 
     let mut fptr_decls = vec![];
     let mut fptr_inits = vec![];
+    let mut fptr_names = vec![];
+    let mut fptr_name_strs = vec![];
     for fptr in func_ptrs {
         let GodotFuncPtr {
             name,
@@ -72,6 +74,8 @@ fn generate_proc_address_funcs(h_path: &Path) -> TokenStream {
 
         fptr_decls.push(decl);
         fptr_inits.push(init);
+        fptr_name_strs.push(name.to_string());
+        fptr_names.push(name);
     }
 
     // Do not derive Copy -- even though the struct is bitwise-copyable, this is rarely needed and may point to an error.
@@ -95,6 +99,17 @@ fn generate_proc_address_funcs(h_path: &Path) -> TokenStream {
                     #( #fptr_inits )*
                 }
             }
+
+            /// Names of functions declared in the header, which the running Godot binary did not provide.
+            pub(crate) fn missing_functions(&self) -> Vec<&'static str> {
+                let mut missing = vec![];
+                #(
+                    if self.#fptr_names.is_none() {
+                        missing.push(#fptr_name_strs);
+                    }
+                )*
+                missing
+            }
         }
     };
     code
@@ -5,10 +5,13 @@
  */
 
 mod api_parser;
+mod cache;
 mod central_generator;
 mod class_generator;
 mod codegen_special_cases;
 mod context;
+mod diagnostics;
+mod fingerprint;
 mod interface_generator;
 mod special_cases;
 mod util;
@@ -26,6 +29,7 @@ use class_generator::{
     generate_builtin_class_files, generate_class_files, generate_native_structures_files,
 };
 use context::Context;
+use diagnostics::CodegenReport;
 use interface_generator::generate_sys_interface_file;
 use util::{ident, to_pascal_case, to_snake_case};
 use utilities_generator::generate_utilities_file;
@@ -39,6 +43,8 @@ use proc_macro2::{Ident, TokenStream};
 use quote::{quote, ToTokens};
 use std::path::{Path, PathBuf};
 
+pub use cache::{restore_from_cache, store_in_cache};
+
 pub type SubmitFn = dyn FnMut(PathBuf, TokenStream);
 
 fn write_file(path: &Path, contents: String) {
@@ -96,6 +102,7 @@ pub fn generate_core_files(core_gen_path: &Path) {
 
     let (api, build_config) = load_extension_api(&mut watch);
     let mut ctx = Context::build_from_api(&api);
+    let mut report = CodegenReport::new(api.header.version_full_name.clone());
     watch.record("build_context");
 
     generate_core_central_file(&api, &mut ctx, build_config, core_gen_path, &mut submit_fn);
@@ -111,6 +118,7 @@ pub fn generate_core_files(core_gen_path: &Path) {
         &mut ctx,
         build_config,
         &core_gen_path.join("classes"),
+        &mut report,
         &mut submit_fn,
     );
     watch.record("generate_class_files");
@@ -133,6 +141,7 @@ pub fn generate_core_files(core_gen_path: &Path) {
     );
     watch.record("generate_native_structures_files");
 
+    report.write_if_enabled(&core_gen_path.join("codegen-report.json"));
     watch.write_stats_to(&core_gen_path.join("codegen-stats.txt"));
 }
 
@@ -187,7 +196,7 @@ enum RustTy {
     /// `Gd<Node>`
     EngineClass {
         tokens: TokenStream,
-        #[allow(dead_code)] // currently not read
+        /// Rust name of the class, e.g. `Node`.
         class: String,
     },
 }
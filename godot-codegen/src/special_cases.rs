@@ -26,12 +26,6 @@ pub(crate) fn is_deleted(class_name: &TyName, godot_method_name: &str) -> bool {
         //| ("Object", "to_string")
         | ("Object", "get_instance_id")
 
-        // Thread APIs
-        | ("ResourceLoader", "load_threaded_get")
-        | ("ResourceLoader", "load_threaded_get_status")
-        | ("ResourceLoader", "load_threaded_request")
-        // also: enum ThreadLoadStatus
-
         => true, _ => false
     }
 }
@@ -92,6 +86,33 @@ pub(crate) fn is_class_deleted(class_name: &TyName) -> bool {
     }
 }
 
+/// Classes which Godot registers together with the servers, although their name does not end in `Server`.
+///
+/// Subclasses of these (e.g. a custom physics or text server) must be registered at `Servers` level, before Godot instantiates its servers.
+#[rustfmt::skip]
+pub(crate) fn is_class_level_server(class_name: &str) -> bool {
+    match class_name {
+        | "PhysicsDirectBodyState2D"
+        | "PhysicsDirectBodyState2DExtension"
+        | "PhysicsDirectBodyState3D"
+        | "PhysicsDirectBodyState3DExtension"
+        | "PhysicsDirectSpaceState2D"
+        | "PhysicsDirectSpaceState2DExtension"
+        | "PhysicsDirectSpaceState3D"
+        | "PhysicsDirectSpaceState3DExtension"
+        | "PhysicsServer2D"
+        | "PhysicsServer2DExtension"
+        | "PhysicsServer2DManager"
+        | "PhysicsServer3D"
+        | "PhysicsServer3DExtension"
+        | "PhysicsServer3DManager"
+        | "TextServerExtension"
+        | "TextServerManager"
+
+        => true, _ => false
+    }
+}
+
 #[rustfmt::skip]
 #[allow(dead_code)] // remove once used.
 fn is_class_experimental(class_name: &TyName) -> bool {
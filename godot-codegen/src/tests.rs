@@ -4,9 +4,12 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use quote::quote;
+
 use crate::util::{
     parse_native_structures_format, to_pascal_case, to_snake_case, NativeStructuresField,
 };
+use crate::RustTy;
 
 #[test]
 fn test_pascal_conversion() {
@@ -188,6 +191,18 @@ fn test_parse_native_structures_format() {
         vec![native("int", "x"),],
     );
 
+    // Check that fixed-size arrays keep their size on the type.
+    assert_eq!(
+        parse_native_structures_format(
+            "PhysicsServer3DExtensionMotionCollision collisions[32];int collision_count"
+        )
+        .unwrap(),
+        vec![
+            native("PhysicsServer3DExtensionMotionCollision[32]", "collisions"),
+            native("int", "collision_count"),
+        ],
+    );
+
     let actual = parse_native_structures_format(
         "Vector3 position;Vector3 normal;Vector3 collider_velocity;Vector3 collider_angular_velocity;real_t depth;int local_shape;ObjectID collider_id;RID collider;int collider_shape"
     );
@@ -204,3 +219,16 @@ fn test_parse_native_structures_format() {
     ];
     assert_eq!(actual.unwrap(), expected);
 }
+
+#[test]
+fn test_object_return_is_optional() {
+    // Godot's API metadata does not tell which object returns can be null (e.g. Node::get_parent(), ResourceLoader::load()),
+    // so every object return value must be exposed as Option<Gd<T>>.
+    let ty = RustTy::EngineClass {
+        tokens: quote! { Gd<crate::engine::Node> },
+        class: "Node".to_string(),
+    };
+
+    let expected = quote! { -> Option<Gd<crate::engine::Node>> };
+    assert_eq!(ty.return_decl().to_string(), expected.to_string());
+}
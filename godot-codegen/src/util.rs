@@ -8,7 +8,7 @@ use crate::api_parser::{
     BuiltinClassMethod, Class, ClassConstant, ClassMethod, Enum, UtilityFunction,
 };
 use crate::central_generator::TypeNames;
-use crate::special_cases::is_builtin_scalar;
+use crate::special_cases::{is_builtin_scalar, is_class_level_server};
 use crate::{Context, GodotTy, ModName, RustTy, TyName};
 use proc_macro2::{Ident, Literal, TokenStream};
 use quote::{format_ident, quote, ToTokens};
@@ -56,6 +56,15 @@ impl ClassCodegenLevel {
         }
     }
 
+    /// Initialization level, at which user classes inheriting from a class of this level are registered.
+    pub fn to_init_level(self) -> TokenStream {
+        match self {
+            Self::Servers => quote! { crate::init::InitLevel::Servers },
+            Self::Scene | Self::Lazy => quote! { crate::init::InitLevel::Scene },
+            Self::Editor => quote! { crate::init::InitLevel::Editor },
+        }
+    }
+
     fn upper(self) -> &'static str {
         match self {
             Self::Servers => "Servers",
@@ -98,17 +107,11 @@ pub fn make_string_name(identifier: &str) -> TokenStream {
     }
 }
 
-pub fn make_sname_ptr(identifier: &str) -> TokenStream {
-    quote! {
-        string_names.fetch(#identifier)
-    }
-}
-
 pub fn get_api_level(class: &Class) -> ClassCodegenLevel {
     if class.name == "ThemeDB" {
         // registered in C++ register_scene_singletons(), after MODULE_INITIALIZATION_LEVEL_EDITOR happens.
         ClassCodegenLevel::Lazy
-    } else if class.name.ends_with("Server") {
+    } else if class.name.ends_with("Server") || is_class_level_server(&class.name) {
         ClassCodegenLevel::Servers
     } else if class.api_type == "core" {
         ClassCodegenLevel::Scene
@@ -123,136 +126,227 @@ pub fn get_api_level(class: &Class) -> ClassCodegenLevel {
 }
 
 pub fn make_enum_definition(enum_: &Enum) -> TokenStream {
-    // TODO enums which have unique ords could be represented as Rust enums
-    // This would allow exhaustive matches (or at least auto-completed matches + #[non_exhaustive]). But even without #[non_exhaustive],
-    // this might be a forward compatibility hazard, if Godot deprecates enumerators and adds new ones with existing ords.
-
     let enum_name = ident(&enum_.name);
 
-    let values = &enum_.values;
-    let mut enumerators = Vec::with_capacity(values.len());
-    // let mut matches = Vec::with_capacity(values.len());
-    let mut unique_ords = Vec::with_capacity(values.len());
-
-    for enumerator in values {
-        let name = make_enumerator_name(&enumerator.name, &enum_.name);
-        let ordinal = make_enumerator_ord(enumerator.value);
+    let type_def = if enum_.is_bitfield {
+        make_bitfield_type(enum_, &enum_name)
+    } else {
+        make_rust_enum_type(enum_, &enum_name)
+    };
 
-        enumerators.push(quote! {
-            pub const #name: Self = Self { ord: #ordinal };
-        });
-        // matches.push(quote! {
-        //     #ordinal => Some(Self::#name),
-        // });
-        unique_ords.push(enumerator.value);
-    }
-
-    // They are not necessarily in order
-    unique_ords.sort();
-    unique_ords.dedup();
-
-    let bitfield_ops = if enum_.is_bitfield {
-        let tokens = quote! {
-            // impl #enum_name {
-            //     pub const UNSET: Self = Self { ord: 0 };
-            // }
-            impl std::ops::BitOr for #enum_name {
-                type Output = Self;
-
-                fn bitor(self, rhs: Self) -> Self::Output {
-                    Self { ord: self.ord | rhs.ord }
-                }
+    let index_enum_impl = if let Some(enum_max) = try_count_index_enum(enum_) {
+        quote! {
+            impl crate::obj::IndexEnum for #enum_name {
+                const ENUMERATOR_COUNT: usize = #enum_max;
             }
-        };
-
-        Some(tokens)
+        }
     } else {
-        None
+        TokenStream::new()
     };
 
-    let try_from_ord = if enum_.is_bitfield {
-        quote! {
-            fn try_from_ord(ord: i32) -> Option<Self> {
-                Some(Self { ord })
+    // Public interface is i64, for consistency (and possibly forward compatibility?).
+    // TODO maybe generalize GodotFfi over EngineEnum trait
+    quote! {
+        #type_def
+        #index_enum_impl
+
+        impl sys::GodotFuncMarshal for #enum_name {
+            type Via = i64;
+            type FromViaError = sys::PrimitiveConversionError<i64, i32>;
+            type IntoViaError = std::convert::Infallible;
+
+            // The engine may return ordinals unknown to the bindings, e.g. if it is newer; they are kept instead of rejected.
+            fn try_from_via(via: Self::Via) -> std::result::Result<Self, Self::FromViaError> {
+                let ord = i32::try_from(via).map_err(|_| sys::PrimitiveConversionError::new(via))?;
+                Ok(<Self as crate::obj::EngineEnum>::from_ord(ord))
+            }
+
+            fn try_into_via(self) -> std::result::Result<Self::Via, Self::IntoViaError> {
+                Ok(<Self as crate::obj::EngineEnum>::ord(self).into())
+            }
+        }
+
+        impl std::convert::TryFrom<i64> for #enum_name {
+            type Error = sys::PrimitiveConversionError<i64, i32>;
+
+            /// Fails for integers that do not correspond to a known enumerator; the error retains the original value.
+            fn try_from(ord: i64) -> std::result::Result<Self, Self::Error> {
+                let err = sys::PrimitiveConversionError::new(ord);
+                let ord = i32::try_from(ord).map_err(|_| err)?;
+                <Self as crate::obj::EngineEnum>::try_from_ord(ord).ok_or(err)
             }
         }
+    }
+}
+
+/// Regular enums are mapped to Rust enums, so they can be matched exhaustively.
+///
+/// Like notification enums, an additional `Unknown(i32)` variant holds ordinals unknown to the bindings, so that values from a newer
+/// Godot version need not be rejected. The layout is thus not that of the C++ enum; native structures store such fields as `i32`.
+/// Variants carry no explicit discriminants, as ordinals need not be sorted or leave room for `Unknown`: use `ord()` instead of
+/// `as` casts.
+/// Godot sometimes has multiple enumerators with the same ordinal (renames, deprecations); only the first one becomes a
+/// variant, the others are exposed as associated constants aliasing it.
+fn make_rust_enum_type(enum_: &Enum, enum_name: &Ident) -> TokenStream {
+    let mut variants = Vec::with_capacity(enum_.values.len());
+    let mut variant_ords = Vec::with_capacity(enum_.values.len());
+    let mut aliases = Vec::new();
+
+    for enumerator in &enum_.values {
+        let name = make_enumerator_name(&enumerator.name, &enum_.name);
+        let ordinal = make_enumerator_ord(enumerator.value);
+
+        let original = enum_
+            .values
+            .iter()
+            .find(|other| other.value == enumerator.value)
+            .expect("enumerator itself is in list");
+
+        if original.name == enumerator.name {
+            variants.push(name);
+            variant_ords.push(ordinal);
+        } else {
+            let original_name = make_enumerator_name(&original.name, &enum_.name);
+            aliases.push(quote! {
+                pub const #name: Self = Self::#original_name;
+            });
+        }
+    }
+
+    let alias_impl = if aliases.is_empty() {
+        TokenStream::new()
     } else {
         quote! {
+            impl #enum_name {
+                #( #aliases )*
+            }
+        }
+    };
+
+    quote! {
+        #[allow(non_camel_case_types)]
+        #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+        pub enum #enum_name {
+            #(
+                #variants,
+            )*
+
+            /// Ordinal that is not known to the bindings, e.g. returned by a newer Godot version.
+            ///
+            /// Conversions never produce this for the ordinal of a known enumerator. If constructed manually with such an
+            /// ordinal, the value does not compare equal to the enumerator.
+            Unknown(i32),
+        }
+        #alias_impl
+        impl crate::obj::EngineEnum for #enum_name {
             fn try_from_ord(ord: i32) -> Option<Self> {
                 match ord {
-                    #( ord @ #unique_ords )|* => Some(Self { ord }),
+                    #(
+                        #variant_ords => Some(Self::#variants),
+                    )*
                     _ => None,
                 }
             }
-        }
-    };
 
-    let mut derives = vec!["Copy", "Clone", "Eq", "PartialEq", "Debug", "Hash"];
-
-    if enum_.is_bitfield {
-        derives.push("Default");
-    }
+            fn ord(self) -> i32 {
+                match self {
+                    #(
+                        Self::#variants => #variant_ords,
+                    )*
+                    Self::Unknown(ord) => ord,
+                }
+            }
 
-    let index_enum_impl = if let Some(enum_max) = try_count_index_enum(enum_) {
-        quote! {
-            impl crate::obj::IndexEnum for #enum_name {
-                const ENUMERATOR_COUNT: usize = #enum_max;
+            fn from_ord(ord: i32) -> Self {
+                Self::try_from_ord(ord).unwrap_or(Self::Unknown(ord))
             }
         }
-    } else {
-        TokenStream::new()
-    };
+    }
+}
+
+/// Bitfields can hold any combination of their flags, so they are represented as a newtype around the ordinal.
+fn make_bitfield_type(enum_: &Enum, enum_name: &Ident) -> TokenStream {
+    let mut enumerators = Vec::with_capacity(enum_.values.len());
+    let mut flag_names = Vec::with_capacity(enum_.values.len());
+    let mut flag_ords = Vec::with_capacity(enum_.values.len());
+
+    for enumerator in &enum_.values {
+        let name = make_enumerator_name(&enumerator.name, &enum_.name);
+        let ordinal = make_enumerator_ord(enumerator.value);
+
+        enumerators.push(quote! {
+            pub const #name: Self = Self { ord: #ordinal };
+        });
+        flag_names.push(name.to_string());
+        flag_ords.push(ordinal);
+    }
 
-    let derives = derives.into_iter().map(ident);
+    let enum_name_str = enum_name.to_string();
 
     // Enumerator ordinal stored as i32, since that's enough to hold all current values and the default repr in C++.
-    // Public interface is i64 though, for consistency (and possibly forward compatibility?).
-    // TODO maybe generalize GodotFfi over EngineEnum trait
     quote! {
         #[repr(transparent)]
-        #[derive(#( #derives ),*)]
+        #[derive(Copy, Clone, Eq, PartialEq, Hash, Default)]
         pub struct #enum_name {
             ord: i32
         }
         impl #enum_name {
-            #(
-                #enumerators
-            )*
+            #( #enumerators )*
+
+            /// Returns `true` if all bits set in `flags` are also set in `self`.
+            #[inline]
+            pub fn contains(self, flags: Self) -> bool {
+                self.ord & flags.ord == flags.ord
+            }
+
+            /// Returns `true` if no bits are set.
+            #[inline]
+            pub fn is_empty(self) -> bool {
+                self.ord == 0
+            }
         }
         impl crate::obj::EngineEnum for #enum_name {
-            // fn try_from_ord(ord: i32) -> Option<Self> {
-            //     match ord {
-            //         #(
-            //             #matches
-            //         )*
-            //         _ => None,
-            //     }
-            // }
-
-            #try_from_ord
+            fn try_from_ord(ord: i32) -> Option<Self> {
+                Some(Self { ord })
+            }
 
             fn ord(self) -> i32 {
                 self.ord
             }
         }
-        #index_enum_impl
+        impl std::ops::BitOr for #enum_name {
+            type Output = Self;
 
-        impl sys::GodotFuncMarshal for #enum_name {
-            type Via = i64;
-            type FromViaError = sys::PrimitiveConversionError<i64, i32>;
-            type IntoViaError = std::convert::Infallible;
+            fn bitor(self, rhs: Self) -> Self::Output {
+                Self { ord: self.ord | rhs.ord }
+            }
+        }
+        impl std::ops::BitOrAssign for #enum_name {
+            fn bitor_assign(&mut self, rhs: Self) {
+                *self = *self | rhs;
+            }
+        }
+        impl std::ops::BitAnd for #enum_name {
+            type Output = Self;
 
-            fn try_from_via(via: Self::Via) -> std::result::Result<Self, Self::FromViaError> {
-                let err = sys::PrimitiveConversionError::new(via);
-                let ord = i32::try_from(via).map_err(|_| err)?;
-                <Self as crate::obj::EngineEnum>::try_from_ord(ord).ok_or(err)
+            fn bitand(self, rhs: Self) -> Self::Output {
+                Self { ord: self.ord & rhs.ord }
+            }
+        }
+        impl std::ops::BitAndAssign for #enum_name {
+            fn bitand_assign(&mut self, rhs: Self) {
+                *self = *self & rhs;
+            }
         }
+        impl std::fmt::Debug for #enum_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                const FLAGS: &[(&str, i32)] = &[
+                    #( (#flag_names, #flag_ords), )*
+                ];
 
-            fn try_into_via(self) -> std::result::Result<Self::Via, Self::IntoViaError> {
-                Ok(<Self as crate::obj::EngineEnum>::ord(self).into())
+                crate::engine::debug_bitfield(f, #enum_name_str, self.ord, FLAGS)
             }
         }
-        #bitfield_ops
     }
 }
 
@@ -653,6 +747,12 @@ pub fn parse_native_structures_format(input: &str) -> Option<Vec<NativeStructure
                 field_name.truncate(index);
             }
 
+            // If the field is a fixed-size array, put the size on the type, not the name.
+            if let Some(index) = field_name.find('[') {
+                let size = field_name.split_off(index);
+                field_type.push_str(&size);
+            }
+
             Some(NativeStructuresField {
                 field_type,
                 field_name,
@@ -15,7 +15,10 @@ fn main() {
         std::fs::remove_dir_all(gen_path).unwrap_or_else(|e| panic!("failed to delete dir: {e}"));
     }
 
-    godot_codegen::generate_core_files(gen_path);
+    if !godot_codegen::restore_from_cache("core", gen_path) {
+        godot_codegen::generate_core_files(gen_path);
+        godot_codegen::store_in_cache("core", gen_path);
+    }
     println!("cargo:rerun-if-changed=build.rs");
 
     godot_bindings::emit_godot_version_cfg();
@@ -0,0 +1,86 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Global allocator backed by Godot's memory functions.
+
+use godot_ffi as sys;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::ffi::c_void;
+use std::ptr;
+
+/// Global allocator that routes Rust heap allocations through Godot's memory functions.
+///
+/// Memory allocated by Rust code then counts towards Godot's memory statistics (e.g. the _Static_ memory monitor in the editor
+/// debugger), which helps tracking down leaks, especially on platforms where native memory profilers are hard to come by.
+///
+/// To use it, declare it as the global allocator in your library crate:
+/// ```no_run
+/// use godot::allocator::GodotAllocator;
+///
+/// #[global_allocator]
+/// static ALLOCATOR: GodotAllocator = GodotAllocator;
+/// ```
+///
+/// Allocations made before Godot initializes the library (for example during class self-registration, which happens when the
+/// library is loaded) as well as allocations with an alignment larger than 8 bytes are served by the system allocator instead.
+pub struct GodotAllocator;
+
+// Each block is preceded by a header, whose first byte records which allocator served it. This is needed because blocks allocated
+// before initialization may be freed afterwards. The header is at least as large as the alignment, so the user pointer stays aligned.
+const MIN_HEADER_SIZE: usize = 16;
+
+// Godot's `Memory::alloc_static()` is based on `malloc()`, which only guarantees alignment of 8 bytes on all supported platforms.
+const MAX_GODOT_ALIGN: usize = 8;
+
+const FROM_SYSTEM: u8 = 0;
+const FROM_GODOT: u8 = 1;
+
+unsafe impl GlobalAlloc for GodotAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let header_size = header_size(layout);
+        let Some(total_size) = layout.size().checked_add(header_size) else {
+            return ptr::null_mut();
+        };
+
+        // Reading the binding from another thread is fine: it is assigned once during initialization, before threads are spawned.
+        let (block, origin) = if layout.align() <= MAX_GODOT_ALIGN && sys::is_initialized() {
+            let block = sys::interface_fn!(mem_alloc)(total_size) as *mut u8;
+            (block, FROM_GODOT)
+        } else {
+            let Ok(system_layout) = Layout::from_size_align(total_size, header_size) else {
+                return ptr::null_mut();
+            };
+            (System.alloc(system_layout), FROM_SYSTEM)
+        };
+
+        if block.is_null() {
+            return block;
+        }
+
+        *block = origin;
+        block.add(header_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let header_size = header_size(layout);
+        let block = ptr.sub(header_size);
+
+        if *block == FROM_GODOT {
+            sys::interface_fn!(mem_free)(block as *mut c_void);
+        } else {
+            let total_size = layout.size() + header_size;
+            System.dealloc(
+                block,
+                Layout::from_size_align_unchecked(total_size, header_size),
+            );
+        }
+    }
+}
+
+fn header_size(layout: Layout) -> usize {
+    layout.align().max(MIN_HEADER_SIZE)
+}
@@ -0,0 +1,382 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Lock-free communication between the game thread and the audio mix thread.
+//!
+//! Godot calls `AudioStreamPlaybackVirtual::mix()` on its audio thread, while the playback object is bound for the duration of
+//! the call. Binding the same object from the game thread at that time is a data race, and locks are no alternative: a mix
+//! callback that waits for the game thread causes audible dropouts.
+//!
+//! Instead, the playback and the game code share state through the types in this module, which never block and never allocate
+//! on the audio side:
+//! * [`AudioParam`] holds a single `f32` value such as volume or cutoff frequency. The last value set wins.
+//! * [`ring_buffer()`] passes a stream of values, e.g. note events or sample blocks, in order from one thread to the other.
+//!
+//! Create the shared state along with the playback, typically in `AudioStreamVirtual::instantiate_playback()`, and keep the game
+//! thread's end outside the playback object:
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use godot::prelude::*;
+//! use godot::audio::{ring_buffer, AudioParam, RingReceiver};
+//! use godot::engine::native::AudioFrame;
+//! use godot::engine::{audio_frames_mut, AudioStreamPlayback, AudioStreamPlaybackVirtual};
+//!
+//! #[derive(GodotClass)]
+//! #[class(base=AudioStreamPlayback)]
+//! struct Synth {
+//!     volume: Arc<AudioParam>,
+//!     notes: RingReceiver<f32>,
+//!     frequency: f32,
+//!     phase: f32,
+//! }
+//!
+//! #[godot_api]
+//! impl AudioStreamPlaybackVirtual for Synth {
+//!     unsafe fn mix(&mut self, buffer: *mut AudioFrame, rate_scale: f32, frames: i32) -> i32 {
+//!         while let Some(frequency) = self.notes.pop() {
+//!             self.frequency = frequency;
+//!         }
+//!
+//!         let volume = self.volume.get();
+//!         for frame in audio_frames_mut(buffer, frames) {
+//!             self.phase = (self.phase + self.frequency * rate_scale / 44100.0).fract();
+//!             let sample = (self.phase * std::f32::consts::TAU).sin() * volume;
+//!             frame.left = sample;
+//!             frame.right = sample;
+//!         }
+//!         frames
+//!     }
+//! }
+//!
+//! // On the game thread, e.g. in the stream's instantiate_playback():
+//! let volume = Arc::new(AudioParam::new(0.5));
+//! let (mut note_sender, notes) = ring_buffer::<f32>(64);
+//! let playback = Gd::with_base(|_base| Synth {
+//!     volume: volume.clone(),
+//!     notes,
+//!     frequency: 440.0,
+//!     phase: 0.0,
+//! });
+//!
+//! // Later, without binding the playback:
+//! volume.set(0.8);
+//! note_sender.push(880.0).ok();
+//! ```
+
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A single `f32` parameter, which can be set and read from any thread without locking.
+///
+/// Share it between the game thread and the playback with an `Arc`. Reads always return the most recently set value; there is
+/// no smoothing, so the mix loop should ramp towards the value itself if jumps are audible.
+pub struct AudioParam {
+    bits: AtomicU32,
+}
+
+impl AudioParam {
+    /// Creates a parameter with an initial value.
+    pub fn new(value: f32) -> Self {
+        Self {
+            bits: AtomicU32::new(value.to_bits()),
+        }
+    }
+
+    /// Returns the current value.
+    pub fn get(&self) -> f32 {
+        f32::from_bits(self.bits.load(Ordering::Relaxed))
+    }
+
+    /// Replaces the value.
+    pub fn set(&self, value: f32) {
+        self.bits.store(value.to_bits(), Ordering::Relaxed);
+    }
+}
+
+impl Default for AudioParam {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+impl fmt::Debug for AudioParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AudioParam({})", self.get())
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Ring buffer
+
+/// Creates a lock-free ring buffer holding up to `capacity` values, with one sending and one receiving end.
+///
+/// Each end can be moved to a different thread. Neither blocks: sending into a full buffer and receiving from an empty one fail
+/// immediately. The buffer is allocated here, so that no allocation happens while sending or receiving.
+///
+/// # Panics
+/// If `capacity` is 0.
+pub fn ring_buffer<T>(capacity: usize) -> (RingSender<T>, RingReceiver<T>)
+where
+    T: Copy + Send,
+{
+    assert!(capacity > 0, "ring buffer capacity must be positive");
+
+    let slots = (0..capacity)
+        .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+        .collect();
+
+    let ring = Arc::new(Ring {
+        slots,
+        read: AtomicUsize::new(0),
+        write: AtomicUsize::new(0),
+    });
+
+    let sender = RingSender { ring: ring.clone() };
+    let receiver = RingReceiver { ring };
+    (sender, receiver)
+}
+
+/// Storage shared by both ends.
+///
+/// `read` and `write` count all values ever received and sent, wrapping on overflow; the slot of a count is the count modulo
+/// the capacity. Only the receiver advances `read`, and only the sender advances `write`.
+struct Ring<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    read: AtomicUsize,
+    write: AtomicUsize,
+}
+
+// SAFETY: each slot is accessed by one end at a time: the sender only writes slots in `write..read + capacity`, the receiver only
+// reads slots in `read..write`. Publishing a count with Release after the slot access, and loading it with Acquire before the
+// next access, orders the two.
+unsafe impl<T: Send> Send for Ring<T> {}
+unsafe impl<T: Send> Sync for Ring<T> {}
+
+impl<T: Copy> Ring<T> {
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    fn len(&self) -> usize {
+        let write = self.write.load(Ordering::Acquire);
+        let read = self.read.load(Ordering::Acquire);
+        write.wrapping_sub(read)
+    }
+
+    fn slot(&self, count: usize) -> *mut MaybeUninit<T> {
+        self.slots[count % self.capacity()].get()
+    }
+}
+
+/// Sending end of a [`ring_buffer()`], e.g. on the game thread.
+pub struct RingSender<T> {
+    ring: Arc<Ring<T>>,
+}
+
+impl<T: Copy> RingSender<T> {
+    /// Appends `value`, or returns it if the buffer is full.
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        let write = self.ring.write.load(Ordering::Relaxed);
+        let read = self.ring.read.load(Ordering::Acquire);
+
+        if write.wrapping_sub(read) == self.ring.capacity() {
+            return Err(value);
+        }
+
+        // SAFETY: the slot is not in `read..write`, so the receiver does not access it.
+        unsafe { (*self.ring.slot(write)).write(value) };
+        self.ring
+            .write
+            .store(write.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+
+    /// Appends as many values from the start of `values` as fit, and returns their number.
+    pub fn push_slice(&mut self, values: &[T]) -> usize {
+        let write = self.ring.write.load(Ordering::Relaxed);
+        let read = self.ring.read.load(Ordering::Acquire);
+
+        let free = self.ring.capacity() - write.wrapping_sub(read);
+        let count = values.len().min(free);
+        for (i, value) in values[..count].iter().enumerate() {
+            // SAFETY: see push().
+            unsafe { (*self.ring.slot(write.wrapping_add(i))).write(*value) };
+        }
+
+        self.ring
+            .write
+            .store(write.wrapping_add(count), Ordering::Release);
+        count
+    }
+
+    /// Number of values that can currently be pushed.
+    pub fn free_len(&self) -> usize {
+        self.ring.capacity() - self.ring.len()
+    }
+
+    /// Maximum number of values in the buffer.
+    pub fn capacity(&self) -> usize {
+        self.ring.capacity()
+    }
+}
+
+impl<T> fmt::Debug for RingSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RingSender {{ capacity: {} }}", self.ring.slots.len())
+    }
+}
+
+/// Receiving end of a [`ring_buffer()`], e.g. in a playback's `mix()`.
+pub struct RingReceiver<T> {
+    ring: Arc<Ring<T>>,
+}
+
+impl<T: Copy> RingReceiver<T> {
+    /// Removes and returns the oldest value, or `None` if the buffer is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        let read = self.ring.read.load(Ordering::Relaxed);
+        let write = self.ring.write.load(Ordering::Acquire);
+
+        if read == write {
+            return None;
+        }
+
+        // SAFETY: the slot is in `read..write`, so the sender has initialized it and does not access it.
+        let value = unsafe { (*self.ring.slot(read)).assume_init() };
+        self.ring
+            .read
+            .store(read.wrapping_add(1), Ordering::Release);
+        Some(value)
+    }
+
+    /// Moves the oldest values into `out`, as many as are available and fit, and returns their number.
+    ///
+    /// In a `mix()` callback, this fills the frame buffer from samples produced elsewhere.
+    pub fn pop_slice(&mut self, out: &mut [T]) -> usize {
+        let read = self.ring.read.load(Ordering::Relaxed);
+        let write = self.ring.write.load(Ordering::Acquire);
+
+        let count = out.len().min(write.wrapping_sub(read));
+        for (i, value) in out[..count].iter_mut().enumerate() {
+            // SAFETY: see pop().
+            *value = unsafe { (*self.ring.slot(read.wrapping_add(i))).assume_init() };
+        }
+
+        self.ring
+            .read
+            .store(read.wrapping_add(count), Ordering::Release);
+        count
+    }
+
+    /// Number of values that can currently be popped.
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    /// Returns `true` if no values can currently be popped.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Maximum number of values in the buffer.
+    pub fn capacity(&self) -> usize {
+        self.ring.capacity()
+    }
+}
+
+impl<T> fmt::Debug for RingReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RingReceiver {{ capacity: {} }}", self.ring.slots.len())
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn audio_param() {
+        let param = AudioParam::new(0.25);
+        assert_eq!(param.get(), 0.25);
+
+        param.set(-1.5);
+        assert_eq!(param.get(), -1.5);
+        assert_eq!(AudioParam::default().get(), 0.0);
+    }
+
+    #[test]
+    fn ring_push_pop() {
+        let (mut sender, mut receiver) = ring_buffer::<i32>(3);
+        assert!(receiver.is_empty());
+        assert_eq!(receiver.pop(), None);
+
+        assert_eq!(sender.push(1), Ok(()));
+        assert_eq!(sender.push(2), Ok(()));
+        assert_eq!(sender.push(3), Ok(()));
+        assert_eq!(sender.push(4), Err(4));
+        assert_eq!(sender.free_len(), 0);
+
+        assert_eq!(receiver.pop(), Some(1));
+        assert_eq!(sender.push(5), Ok(()));
+
+        // Wraps around.
+        assert_eq!(receiver.len(), 3);
+        assert_eq!(receiver.pop(), Some(2));
+        assert_eq!(receiver.pop(), Some(3));
+        assert_eq!(receiver.pop(), Some(5));
+        assert_eq!(receiver.pop(), None);
+    }
+
+    #[test]
+    fn ring_slices() {
+        let (mut sender, mut receiver) = ring_buffer::<u8>(4);
+        assert_eq!(sender.push_slice(&[1, 2, 3]), 3);
+        assert_eq!(sender.push_slice(&[4, 5, 6]), 1);
+
+        let mut out = [0; 3];
+        assert_eq!(receiver.pop_slice(&mut out), 3);
+        assert_eq!(out, [1, 2, 3]);
+
+        assert_eq!(sender.push_slice(&[7, 8]), 2);
+        let mut out = [0; 8];
+        assert_eq!(receiver.pop_slice(&mut out), 3);
+        assert_eq!(out[..3], [4, 7, 8]);
+    }
+
+    #[test]
+    fn ring_across_threads() {
+        const COUNT: u32 = 100_000;
+        let (mut sender, mut receiver) = ring_buffer::<u32>(16);
+
+        let producer = std::thread::spawn(move || {
+            for i in 0..COUNT {
+                while sender.push(i).is_err() {
+                    std::thread::yield_now();
+                }
+            }
+        });
+
+        let mut expected = 0;
+        while expected < COUNT {
+            match receiver.pop() {
+                Some(value) => {
+                    assert_eq!(value, expected);
+                    expected += 1;
+                }
+                None => std::thread::yield_now(),
+            }
+        }
+
+        producer.join().unwrap();
+        assert!(receiver.is_empty());
+    }
+}
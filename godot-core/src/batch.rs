@@ -0,0 +1,374 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Recording of server calls, to be submitted later.
+//!
+//! Procedural rendering and physics code often issues thousands of `RenderingServer` or `PhysicsServer3D` calls per frame.
+//! A [`CommandBuffer`] records such calls as closures, possibly on worker threads, and replays them later on the main thread:
+//!
+//! ```no_run
+//! use godot::prelude::*;
+//! use godot::batch::CommandBuffer;
+//! use godot::engine::RenderingServer;
+//!
+//! # fn update(instances: Vec<(Rid, Transform3D)>) {
+//! let mut commands = CommandBuffer::<RenderingServer>::new();
+//! for (instance, transform) in instances {
+//!     commands.push(move |server| server.instance_set_transform(instance, transform));
+//! }
+//!
+//! commands.submit();
+//! # }
+//! ```
+//!
+//! A command buffer defers calls, it does not batch them: each recorded call still crosses into the engine once when the buffer
+//! is submitted, so it saves no FFI overhead compared to calling the server directly. It is useful to prepare calls off the
+//! main thread, or to apply them at a well-defined point of the frame.
+//!
+//! Actual batching requires server APIs that take many values at once. To update many instances of a multimesh,
+//! [`MultimeshBuffer`] collects all transforms in Rust, and hands them to Godot with a single `multimesh_set_buffer()` call.
+//!
+//! With threaded rendering or physics, Godot itself forwards server calls from the main thread to the server thread, so
+//! submitting on the main thread is sufficient. Worker threads can hand a buffer over with [`CommandBuffer::submit_on_main()`].
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::builtin::{Color, PackedFloat32Array, RealConv, Rid, Transform3D};
+use crate::engine::{
+    NavigationServer2D, NavigationServer3D, PhysicsServer2D, PhysicsServer3D, RenderingServer,
+};
+use crate::obj::{Gd, GodotClass};
+use crate::task;
+
+/// Engine server singleton, whose calls can be recorded in a [`CommandBuffer`].
+pub trait Server: GodotClass + private::Sealed {
+    /// Returns the singleton instance.
+    #[doc(hidden)]
+    fn server_singleton() -> Gd<Self>;
+}
+
+macro_rules! impl_server {
+    ($($Server:ident),* $(,)?) => {
+        $(
+            impl private::Sealed for $Server {}
+
+            impl Server for $Server {
+                fn server_singleton() -> Gd<Self> {
+                    Self::singleton()
+                }
+            }
+        )*
+    };
+}
+
+impl_server!(
+    RenderingServer,
+    PhysicsServer2D,
+    PhysicsServer3D,
+    NavigationServer2D,
+    NavigationServer3D,
+);
+
+mod private {
+    pub trait Sealed {}
+}
+
+type Command<S> = Box<dyn FnOnce(&mut S) + Send>;
+
+/// Calls to the server `S`, recorded to be submitted later.
+///
+/// Submitting performs one engine call per recorded command; see the [module documentation](self) for when this helps.
+///
+/// Recorded closures must be `Send`, so that buffers can be filled on worker threads. They should therefore capture values such
+/// as `Rid`, `Transform3D` or `Color`, and create strings or variants only when called.
+pub struct CommandBuffer<S: Server> {
+    commands: Vec<Command<S>>,
+    // fn(&mut S) is Send + Sync regardless of S.
+    _marker: PhantomData<fn(&mut S)>,
+}
+
+impl<S: Server> CommandBuffer<S> {
+    /// Creates an empty buffer.
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates an empty buffer with space for `capacity` commands.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            commands: Vec::with_capacity(capacity),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Records a call to the server.
+    pub fn push<F>(&mut self, command: F)
+    where
+        F: FnOnce(&mut S) + Send + 'static,
+    {
+        self.commands.push(Box::new(command));
+    }
+
+    /// Moves all commands of `other` to the end of this buffer, e.g. to combine buffers filled by several threads.
+    pub fn append(&mut self, other: &mut Self) {
+        self.commands.append(&mut other.commands);
+    }
+
+    /// Number of recorded commands.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns `true` if no commands have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Runs all commands in recording order, each as a separate server call. Must be called on the main thread.
+    pub fn submit(self) {
+        if self.commands.is_empty() {
+            return;
+        }
+
+        let mut server = S::server_singleton();
+        let server = &mut *server;
+        for command in self.commands {
+            command(server);
+        }
+    }
+
+    /// Submits the buffer on the main thread, at the next [`task::poll()`]. Can be called from any thread.
+    pub fn submit_on_main(self)
+    where
+        S: 'static,
+    {
+        task::call_on_main(move || self.submit());
+    }
+}
+
+impl CommandBuffer<RenderingServer> {
+    /// Records setting all instance data of `multimesh` at once, with a single call.
+    pub fn multimesh_set_buffer(&mut self, multimesh: Rid, buffer: MultimeshBuffer) {
+        self.push(move |server| buffer.submit_with(server, multimesh));
+    }
+}
+
+impl<S: Server> Default for CommandBuffer<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Server> fmt::Debug for CommandBuffer<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "CommandBuffer<{}> {{ len: {} }}",
+            S::class_name(),
+            self.commands.len()
+        )
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+/// Instance data of a 3D multimesh, laid out as `RenderingServer::multimesh_set_buffer()` expects it.
+///
+/// The format must match that of the multimesh, as set up with `multimesh_allocate_data()` with `MULTIMESH_TRANSFORM_3D`.
+/// Setting a buffer replaces the data of all instances, so instances that are not written keep the identity transform and
+/// white color.
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// # use godot::batch::MultimeshBuffer;
+/// # fn scatter(multimesh: Rid, positions: &[Vector3]) {
+/// let mut buffer = MultimeshBuffer::new(positions.len(), false, false);
+/// for (i, position) in positions.iter().enumerate() {
+///     buffer.set_transform(i, Transform3D::new(Basis::IDENTITY, *position));
+/// }
+///
+/// buffer.submit(multimesh);
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultimeshBuffer {
+    data: Vec<f32>,
+    stride: usize,
+    color_offset: Option<usize>,
+    custom_data_offset: Option<usize>,
+}
+
+impl MultimeshBuffer {
+    /// Floats per transform.
+    const TRANSFORM_LEN: usize = 12;
+
+    /// Creates a buffer for `instance_count` instances; `use_colors` and `use_custom_data` must match the multimesh.
+    pub fn new(instance_count: usize, use_colors: bool, use_custom_data: bool) -> Self {
+        let mut stride = Self::TRANSFORM_LEN;
+        let color_offset = use_colors.then(|| {
+            stride += 4;
+            stride - 4
+        });
+        let custom_data_offset = use_custom_data.then(|| {
+            stride += 4;
+            stride - 4
+        });
+
+        let mut buffer = Self {
+            data: vec![0.0; instance_count * stride],
+            stride,
+            color_offset,
+            custom_data_offset,
+        };
+
+        for index in 0..instance_count {
+            buffer.set_transform(index, Transform3D::IDENTITY);
+            if buffer.color_offset.is_some() {
+                buffer.set_color(index, Color::WHITE);
+            }
+        }
+
+        buffer
+    }
+
+    /// Number of instances.
+    pub fn instance_count(&self) -> usize {
+        self.data.len() / self.stride
+    }
+
+    /// Sets the transform of instance `index`.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    pub fn set_transform(&mut self, index: usize, transform: Transform3D) {
+        let rows = transform.basis.rows;
+        let origin = transform.origin;
+
+        let values = [
+            rows[0].x, rows[0].y, rows[0].z, origin.x, //
+            rows[1].x, rows[1].y, rows[1].z, origin.y, //
+            rows[2].x, rows[2].y, rows[2].z, origin.z,
+        ];
+
+        let instance = self.instance_mut(index);
+        for (dest, value) in instance.iter_mut().zip(values) {
+            *dest = value.as_f32();
+        }
+    }
+
+    /// Sets the color of instance `index`.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds, or the buffer was created without colors.
+    pub fn set_color(&mut self, index: usize, color: Color) {
+        let offset = self
+            .color_offset
+            .expect("MultimeshBuffer was created without colors");
+
+        self.write_color(index, offset, color);
+    }
+
+    /// Sets the custom data of instance `index`.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds, or the buffer was created without custom data.
+    pub fn set_custom_data(&mut self, index: usize, data: Color) {
+        let offset = self
+            .custom_data_offset
+            .expect("MultimeshBuffer was created without custom data");
+
+        self.write_color(index, offset, data);
+    }
+
+    /// Raw data, `instance_count() * stride` floats.
+    pub fn as_slice(&self) -> &[f32] {
+        &self.data
+    }
+
+    /// Sets all instance data of `multimesh` with a single server call. Must be called on the main thread.
+    pub fn submit(&self, multimesh: Rid) {
+        self.submit_with(&mut RenderingServer::singleton(), multimesh);
+    }
+
+    fn submit_with(&self, server: &mut RenderingServer, multimesh: Rid) {
+        server.multimesh_set_buffer(multimesh, PackedFloat32Array::from(self.data.as_slice()));
+    }
+
+    fn write_color(&mut self, index: usize, offset: usize, color: Color) {
+        let instance = self.instance_mut(index);
+        instance[offset..offset + 4].copy_from_slice(&[color.r, color.g, color.b, color.a]);
+    }
+
+    fn instance_mut(&mut self, index: usize) -> &mut [f32] {
+        let count = self.instance_count();
+        assert!(
+            index < count,
+            "MultimeshBuffer index {index} is out of bounds (instance count {count})"
+        );
+
+        let start = index * self.stride;
+        &mut self.data[start..start + self.stride]
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::builtin::{Basis, Vector3};
+
+    #[test]
+    fn multimesh_buffer_layout() {
+        let mut buffer = MultimeshBuffer::new(2, true, false);
+        assert_eq!(buffer.instance_count(), 2);
+        assert_eq!(buffer.as_slice().len(), 2 * 16);
+
+        // Untouched instances hold identity and white.
+        #[rustfmt::skip]
+        let identity = [
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            1.0, 1.0, 1.0, 1.0,
+        ];
+        assert_eq!(buffer.as_slice()[..16], identity);
+
+        let basis = Basis::from_rows(
+            Vector3::new(1.0, 2.0, 3.0),
+            Vector3::new(4.0, 5.0, 6.0),
+            Vector3::new(7.0, 8.0, 9.0),
+        );
+        buffer.set_transform(1, Transform3D::new(basis, Vector3::new(10.0, 11.0, 12.0)));
+        buffer.set_color(1, Color::from_rgba(0.1, 0.2, 0.3, 0.4));
+
+        #[rustfmt::skip]
+        let expected = [
+            1.0, 2.0, 3.0, 10.0,
+            4.0, 5.0, 6.0, 11.0,
+            7.0, 8.0, 9.0, 12.0,
+            0.1, 0.2, 0.3, 0.4,
+        ];
+        assert_eq!(buffer.as_slice()[16..], expected);
+    }
+
+    #[test]
+    fn multimesh_buffer_custom_data() {
+        let mut buffer = MultimeshBuffer::new(1, false, true);
+        assert_eq!(buffer.as_slice().len(), 16);
+
+        buffer.set_custom_data(0, Color::from_rgba(1.0, 2.0, 3.0, 4.0));
+        assert_eq!(buffer.as_slice()[12..], [1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn multimesh_buffer_without_colors() {
+        let mut buffer = MultimeshBuffer::new(1, false, false);
+        buffer.set_color(0, Color::WHITE);
+    }
+}
@@ -5,6 +5,7 @@
  */
 
 use godot_ffi as sys;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::ffi::CStr;
 use std::{fmt, sync};
@@ -14,7 +15,9 @@ use std::hash::{Hash, Hasher};
 use crate::builtin::*;
 
 // Why is this so ugly?
-// - Mutex: needed for global access (Sync).
+// - RwLock: needed for global access (Sync). Lookups of already cached names only take a shared lock.
+// - Shards: class names are looked up on every object construction and cast, possibly from several threads. Spreading them over
+//   independent locks keeps threads working with different classes from contending.
 // - Option: needed to initialize lazily, because HashMap::new() is not const.
 // - Box: needed for pointer stability (HashMap insertion may invalidate pointers -- with_capacity() would be an alternative,
 //   but we don't know how many classes).
@@ -22,8 +25,14 @@ use crate::builtin::*;
 // later adjustments.
 // We may also consider OnceLock with a static per class, but that needs to be code-generated (for #[derive] and engine classes), and
 // any manually registered classes would need to replicate it later.
-static CACHED_STRING_NAMES: sync::Mutex<Option<HashMap<ClassName, Box<StringName>>>> =
-    sync::Mutex::new(None);
+static CACHED_STRING_NAMES: [CacheShard; SHARD_COUNT] = [EMPTY_SHARD; SHARD_COUNT];
+
+type CacheShard = sync::RwLock<Option<HashMap<ClassName, Box<StringName>>>>;
+
+const SHARD_COUNT: usize = 16;
+
+#[allow(clippy::declare_interior_mutable_const)] // only used to initialize the static array.
+const EMPTY_SHARD: CacheShard = sync::RwLock::new(None);
 
 /// Name of a class registered with Godot.
 ///
@@ -31,6 +40,10 @@ static CACHED_STRING_NAMES: sync::Mutex<Option<HashMap<ClassName, Box<StringName
 ///
 /// You cannot construct instances of this type yourself; use [`GodotClass::class_name()`](crate::obj::GodotClass::class_name()).
 /// This struct is very cheap to copy.
+///
+/// # Thread safety
+/// All methods may be called from any thread. Conversions to Godot strings go through a global cache, which is sharded and only
+/// takes a shared lock once a class name has been cached. Threads accessing objects concurrently thus do not serialize on it.
 #[derive(Copy, Clone, Debug)]
 pub struct ClassName {
     // Could use small-array optimization for common string lengths.
@@ -77,9 +90,19 @@ impl ClassName {
         self.with_string_name(|s| s.string_sys())
     }
 
-    // Takes a closure because the mutex guard protects the reference; so the &StringName cannot leave the scope.
+    // Takes a closure because the lock guard protects the reference; so the &StringName cannot leave the scope.
     fn with_string_name<R>(&self, func: impl FnOnce(&StringName) -> R) -> R {
-        let mut guard = CACHED_STRING_NAMES.lock().unwrap();
+        let shard = &CACHED_STRING_NAMES[self.shard_index()];
+
+        // Fast path: name already cached, shared lock suffices.
+        {
+            let guard = shard.read().unwrap();
+            if let Some(value) = guard.as_ref().and_then(|map| map.get(self)) {
+                return func(value);
+            }
+        }
+
+        let mut guard = shard.write().unwrap();
         let map = guard.get_or_insert_with(HashMap::new);
 
         let value = map
@@ -89,6 +112,12 @@ impl ClassName {
         func(value)
     }
 
+    fn shard_index(&self) -> usize {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish() as usize % SHARD_COUNT
+    }
+
     fn load_string_name(&self) -> StringName {
         StringName::from(self.c_str.to_str().unwrap())
     }
@@ -34,7 +34,7 @@ pub trait VariantMetadata {
         PropertyInfo {
             variant_type: Self::variant_type(),
             class_name: Self::class_name(),
-            property_name: StringName::from(property_name),
+            property_name: registration::cached_string_name(property_name),
             hint: global::PropertyHint::PROPERTY_HINT_NONE,
             hint_string: GodotString::new(),
             usage: global::PropertyUsageFlags::PROPERTY_USAGE_DEFAULT,
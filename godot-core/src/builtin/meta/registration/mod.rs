@@ -8,3 +8,41 @@
 
 pub mod constant;
 pub mod method;
+
+use crate::builtin::StringName;
+use std::collections::HashMap;
+use std::sync;
+
+// Only filled during a registration pass (see `with_cached_names()`), so the StringNames are released before the engine shuts down.
+// `None` outside a pass.
+static CACHED_NAMES: sync::Mutex<Option<HashMap<String, StringName>>> = sync::Mutex::new(None);
+
+/// Returns a `StringName` for a method, property, signal, constant or parameter name.
+///
+/// The same names recur many times during class registration (e.g. getters are registered both as methods and as property accessors,
+/// and parameter names like `value` are shared across methods). Converting each of them only once saves repeated string conversions.
+/// Outside of registration, this is a plain conversion.
+#[doc(hidden)]
+pub fn cached_string_name(name: &str) -> StringName {
+    let mut guard = CACHED_NAMES.lock().unwrap();
+    let Some(map) = guard.as_mut() else {
+        return StringName::from(name);
+    };
+
+    if let Some(cached) = map.get(name) {
+        return cached.clone();
+    }
+
+    let string_name = StringName::from(name);
+    map.insert(name.to_string(), string_name.clone());
+    string_name
+}
+
+/// Runs one registration pass, during which [`cached_string_name()`] reuses names. The cache is cleared afterwards.
+pub(crate) fn with_cached_names<R>(registration: impl FnOnce() -> R) -> R {
+    *CACHED_NAMES.lock().unwrap() = Some(HashMap::new());
+    let result = registration();
+    *CACHED_NAMES.lock().unwrap() = None;
+
+    result
+}
@@ -21,11 +21,15 @@ pub trait VarcallSignatureTuple: PtrcallSignatureTuple {
     unsafe fn varcall(
         instance_ptr: sys::GDExtensionClassInstancePtr,
         args_ptr: *const sys::GDExtensionConstVariantPtr,
+        arg_count: sys::GDExtensionInt,
         ret: sys::GDExtensionVariantPtr,
         err: *mut sys::GDExtensionCallError,
-        func: fn(sys::GDExtensionClassInstancePtr, Self::Params) -> Self::Ret,
+        func: fn(sys::GDExtensionClassInstancePtr, Self::Params) -> CallResult<Self::Ret>,
         method_name: &str,
     );
+
+    /// Rust signature of the method, e.g. `fn add(i64, i64) -> i64`, for error messages.
+    fn format_signature(method_name: &str) -> String;
 }
 
 #[doc(hidden)]
@@ -39,12 +43,16 @@ pub trait PtrcallSignatureTuple {
         instance_ptr: sys::GDExtensionClassInstancePtr,
         args_ptr: *const sys::GDExtensionConstTypePtr,
         ret: sys::GDExtensionTypePtr,
-        func: fn(sys::GDExtensionClassInstancePtr, Self::Params) -> Self::Ret,
+        func: fn(sys::GDExtensionClassInstancePtr, Self::Params) -> CallResult<Self::Ret>,
         method_name: &str,
         call_type: sys::PtrcallType,
     );
 }
 
+/// Outcome of the Rust function behind a varcall or ptrcall. `Err` holds a message, which is printed as error and fails the call.
+#[doc(hidden)]
+pub type CallResult<R> = Result<R, String>;
+
 // impl<P, const N: usize> Sig for [P; N]
 // impl<P, T0> Sig for (T0)
 // where P: VariantMetadata {
@@ -63,6 +71,7 @@ pub trait PtrcallSignatureTuple {
 //
 use crate::builtin::meta::*;
 use crate::builtin::{FromVariant, ToVariant, Variant};
+use crate::diag::diag;
 
 use super::registration::method::MethodParamOrReturnInfo;
 
@@ -106,18 +115,45 @@ macro_rules! impl_varcall_signature_for_tuple {
             unsafe fn varcall(
                 instance_ptr: sys::GDExtensionClassInstancePtr,
                 args_ptr: *const sys::GDExtensionConstVariantPtr,
+                arg_count: sys::GDExtensionInt,
                 ret: sys::GDExtensionVariantPtr,
                 err: *mut sys::GDExtensionCallError,
-                func: fn(sys::GDExtensionClassInstancePtr, Self::Params) -> Self::Ret,
+                func: fn(sys::GDExtensionClassInstancePtr, Self::Params) -> CallResult<Self::Ret>,
                 method_name: &str,
             ) {
                 $crate::out!("varcall: {}", method_name);
+                #[cfg(feature = "profiling")]
+                let _timing = $crate::profiling::CallGuard::start(method_name);
+
+                // Godot does not check the count for extension methods; reading past the passed arguments would be UB.
+                if arg_count != $PARAM_COUNT {
+                    return arg_count_error::<Self>(method_name, arg_count, err);
+                }
 
                 let args = ($(
-                    unsafe { varcall_arg::<$Pn, $n>(args_ptr, method_name) },
+                    match unsafe { varcall_arg::<$Pn, $n>(args_ptr) } {
+                        Ok(arg) => arg,
+                        Err(variant) => {
+                            return param_type_error::<Self, $Pn>(method_name, $n, variant, err)
+                        }
+                    },
                 )*) ;
 
-                varcall_return::<$R>(func(instance_ptr, args), ret, err)
+                match func(instance_ptr, args) {
+                    Ok(ret_val) => varcall_return::<$R>(ret_val, ret, err),
+                    Err(message) => call_error(method_name, &message, err),
+                }
+            }
+
+            fn format_signature(method_name: &str) -> String {
+                let params: &[String] = &[$( short_type_name::<$Pn>(), )*];
+                let ret = short_type_name::<$R>();
+
+                if ret == "()" {
+                    format!("fn {method_name}({})", params.join(", "))
+                } else {
+                    format!("fn {method_name}({}) -> {ret}", params.join(", "))
+                }
             }
         }
     };
@@ -140,11 +176,13 @@ macro_rules! impl_ptrcall_signature_for_tuple {
                 instance_ptr: sys::GDExtensionClassInstancePtr,
                 args_ptr: *const sys::GDExtensionConstTypePtr,
                 ret: sys::GDExtensionTypePtr,
-                func: fn(sys::GDExtensionClassInstancePtr, Self::Params) -> Self::Ret,
+                func: fn(sys::GDExtensionClassInstancePtr, Self::Params) -> CallResult<Self::Ret>,
                 method_name: &str,
                 call_type: sys::PtrcallType,
             ) {
                 // $crate::out!("ptrcall: {}", method_name);
+                #[cfg(feature = "profiling")]
+                let _timing = $crate::profiling::CallGuard::start(method_name);
 
                 let args = ($(
                     unsafe { ptrcall_arg::<$Pn, $n>(args_ptr, method_name, call_type) },
@@ -153,29 +191,106 @@ macro_rules! impl_ptrcall_signature_for_tuple {
                 // SAFETY:
                 // `ret` is always a pointer to an initialized value of type $R
                 // TODO: double-check the above
-                ptrcall_return::<$R>(func(instance_ptr, args), ret, method_name, call_type)
+                match func(instance_ptr, args) {
+                    Ok(ret_val) => ptrcall_return::<$R>(ret_val, ret, method_name, call_type),
+
+                    // No error channel for ptrcalls; the return value keeps its default.
+                    Err(message) => crate::log::godot_error!("{method_name}: {message}"),
+                }
             }
         }
     };
 }
 
-/// Convert the `N`th argument of `args_ptr` into a value of type `P`.
+/// Convert the `N`th argument of `args_ptr` into a value of type `P`, or return the argument if it cannot be converted.
 ///
 /// # Safety
 /// - It must be safe to dereference the pointer at `args_ptr.offset(N)` .
-unsafe fn varcall_arg<P: FromVariant, const N: isize>(
+unsafe fn varcall_arg<'a, P: FromVariant, const N: isize>(
     args_ptr: *const sys::GDExtensionConstVariantPtr,
-    method_name: &str,
-) -> P {
+) -> Result<P, &'a Variant> {
     let variant = &*(*args_ptr.offset(N) as *mut Variant); // TODO from_var_sys
-    P::try_from_variant(variant)
-        .unwrap_or_else(|_| param_error::<P>(method_name, N as i32, variant))
+    P::try_from_variant(variant).map_err(|_| variant)
+}
+
+/// Fails a varcall with the wrong number of arguments.
+///
+/// With call validation (see [`ExtensionLibrary::validate_calls()`][crate::init::ExtensionLibrary::validate_calls]),
+/// a script error naming the Rust signature is printed, and the call error is reported to the caller. Otherwise, panics.
+///
+/// # Safety
+/// - It must be safe to write a `sys::GDExtensionCallError` once to `err`.
+unsafe fn arg_count_error<S: VarcallSignatureTuple>(
+    method_name: &str,
+    arg_count: sys::GDExtensionInt,
+    err: *mut sys::GDExtensionCallError,
+) {
+    let expected = S::PARAM_COUNT;
+    let message = format!(
+        "{method_name}: expected {expected} argument(s), but called with {arg_count}; Rust signature is `{}`",
+        S::format_signature(method_name)
+    );
+
+    if !crate::init::validate_calls() {
+        diag!(Error, "{message}");
+        panic!("{message}");
+    }
+
+    crate::log::godot_script_error!("{message}");
+    (*err).error = if arg_count > expected as sys::GDExtensionInt {
+        sys::GDEXTENSION_CALL_ERROR_TOO_MANY_ARGUMENTS
+    } else {
+        sys::GDEXTENSION_CALL_ERROR_TOO_FEW_ARGUMENTS
+    };
+    (*err).argument = expected as i32;
+    (*err).expected = expected as i32;
+}
+
+/// Fails a varcall whose argument at `index` cannot be converted to `P`.
+///
+/// Behaves like [`arg_count_error()`] with respect to call validation.
+///
+/// # Safety
+/// - It must be safe to write a `sys::GDExtensionCallError` once to `err`.
+unsafe fn param_type_error<S: VarcallSignatureTuple, P: VariantMetadata>(
+    method_name: &str,
+    index: i32,
+    arg: &Variant,
+    err: *mut sys::GDExtensionCallError,
+) {
+    if !crate::init::validate_calls() {
+        param_error::<P>(method_name, index, arg);
+    }
+
+    // Only the argument's type is printed; its value may not be ASCII.
+    let message = format!(
+        "{method_name}: cannot convert argument [{index}] of type {actual:?} to {param_ty}; Rust signature is `{signature}`",
+        actual = arg.get_type(),
+        param_ty = short_type_name::<P>(),
+        signature = S::format_signature(method_name),
+    );
+
+    crate::log::godot_script_error!("{message}");
+    (*err).error = sys::GDEXTENSION_CALL_ERROR_INVALID_ARGUMENT;
+    (*err).argument = index;
+    (*err).expected = P::variant_type().sys() as i32;
+}
+
+/// Fails a varcall whose Rust function returned `Err(message)`, e.g. a `#[func]` returning `Result`.
+///
+/// The return value stays nil. Unlike a panic, this does not depend on the panic policy, and works with `panic = "abort"`.
+///
+/// # Safety
+/// - It must be safe to write a `sys::GDExtensionCallError` once to `err`.
+unsafe fn call_error(method_name: &str, message: &str, err: *mut sys::GDExtensionCallError) {
+    crate::log::godot_error!("{method_name}: {message}");
+    (*err).error = sys::GDEXTENSION_CALL_ERROR_INVALID_METHOD;
 }
 
 /// Moves `ret_val` into `ret`.
 ///
 /// # Safety
-/// - `ret` must be a pointer to an initialized `Variant`.
+/// - `ret` must be a pointer to an initialized, nil `Variant`.
 /// - It must be safe to write a `Variant` once to `ret`.
 /// - It must be safe to write a `sys::GDExtensionCallError` once to `err`.
 unsafe fn varcall_return<R: ToVariant>(
@@ -183,8 +298,13 @@ unsafe fn varcall_return<R: ToVariant>(
     ret: sys::GDExtensionVariantPtr,
     err: *mut sys::GDExtensionCallError,
 ) {
-    let ret_variant = ret_val.to_variant(); // TODO write_sys
-    *(ret as *mut Variant) = ret_variant;
+    let ret = ret as *mut Variant;
+
+    // Godot passes a default-constructed (nil) Variant, which owns no resources. Overwriting it without running its destructor saves
+    // an FFI round-trip, which is noticeable for frequently called methods such as property getters.
+    debug_assert!((*ret).is_nil(), "varcall return slot must be nil");
+    std::ptr::write(ret, ret_val.to_variant());
+
     (*err).error = sys::GDEXTENSION_CALL_OK;
 }
 
@@ -221,13 +341,46 @@ unsafe fn ptrcall_return<R: sys::GodotFuncMarshal + std::fmt::Debug>(
 
 fn param_error<P>(method_name: &str, index: i32, arg: &impl Debug) -> ! {
     let param_ty = std::any::type_name::<P>();
+    diag!(
+        Error,
+        "{method_name}: cannot convert argument [{index}] {arg:?} to {param_ty}"
+    );
     panic!(
         "{method_name}: parameter [{index}] has type {param_ty}, which is unable to store argument {arg:?}",
     );
 }
 
+/// Type name without module paths, e.g. `Option<Gd<Node>>` instead of `core::option::Option<godot_core::obj::Gd<...>>`.
+fn short_type_name<T>() -> String {
+    let full = std::any::type_name::<T>();
+    let mut result = String::with_capacity(full.len());
+    let mut segment = String::new();
+
+    let mut chars = full.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ':' && chars.peek() == Some(&':') {
+            // Drop the path segment before `::`.
+            chars.next();
+            segment.clear();
+        } else if c.is_alphanumeric() || c == '_' {
+            segment.push(c);
+        } else {
+            result.push_str(&segment);
+            segment.clear();
+            result.push(c);
+        }
+    }
+
+    result.push_str(&segment);
+    result
+}
+
 fn return_error<R>(method_name: &str, arg: &impl Debug) -> ! {
     let return_ty = std::any::type_name::<R>();
+    diag!(
+        Error,
+        "{method_name}: cannot pass return value {arg:?} as {return_ty}"
+    );
     panic!("{method_name}: return type {return_ty} is unable to store value {arg:?}",);
 }
 
@@ -49,6 +49,13 @@ macro_rules! impl_packed_array {
         /// of the original. (Under the hood, Godot uses copy-on-write, so copies are still cheap
         /// to make.)
         ///
+        /// # Copy-on-write
+        ///
+        /// Cloning, converting to `Variant` and passing the array to Godot all share the same buffer. The buffer is
+        /// copied only when one of the sharing arrays is modified, which may happen at an unexpected point in
+        /// performance-sensitive code. Use `shares_buffer_with()` to check whether two arrays share their buffer, and
+        /// `make_unique()` to perform the copy at a point of your choosing.
+        ///
         /// # Thread safety
         ///
         /// Usage is safe if the `$PackedArray` is used on a single thread only. Concurrent reads on
@@ -168,6 +175,25 @@ macro_rules! impl_packed_array {
                 }
             }
 
+            /// Returns `true` if both arrays currently share the same buffer, i.e. neither has been written to since one was copied
+            /// from the other.
+            ///
+            /// Empty arrays have no buffer and never share one.
+            pub fn shares_buffer_with(&self, other: &Self) -> bool {
+                !self.is_empty() && !other.is_empty() && self.ptr(0) == other.ptr(0)
+            }
+
+            /// Ensures that this array owns its buffer exclusively, copying it now if it is shared with other arrays.
+            ///
+            /// Without this, the copy happens lazily on the first write. Calling this up front moves the cost out of
+            /// performance-sensitive code, for example before repeatedly writing to an array that was just received from Godot.
+            pub fn make_unique(&mut self) {
+                if !self.is_empty() {
+                    // Non-const indexing performs the copy-on-write.
+                    self.ptr_mut(0);
+                }
+            }
+
             /// Returns a copy of the value at the specified index.
             ///
             /// # Panics
@@ -629,3 +655,121 @@ impl_packed_array!(
         PartialEq => packed_color_array_operator_equal;
     },
 );
+
+/// Implements the `rayon`-based parallel helpers for a packed array type.
+///
+/// `PackedStringArray` is excluded: assigning or dropping its `GodotString` elements calls into Godot, which these helpers
+/// never do on worker threads. Moving strings between threads is fine, as documented in the `obj` module.
+#[cfg(feature = "rayon")]
+macro_rules! impl_packed_array_par {
+    ($PackedArray:ident, $Element:ty) => {
+        /// Parallel processing, available with the `rayon` feature.
+        ///
+        /// All methods obtain the buffer on the calling thread (performing a pending copy-on-write there), and then only hand
+        /// out plain Rust slices to the [rayon](https://docs.rs/rayon) thread pool. Godot is not called from worker threads.
+        /// To consume the returned iterators, import `rayon::prelude::*`.
+        impl $PackedArray {
+            /// Returns a parallel iterator over shared chunks of `chunk_size` elements, each paired with the index of its first
+            /// element. The last chunk may be shorter.
+            ///
+            /// # Panics
+            ///
+            /// If `chunk_size` is 0.
+            pub fn par_chunks(
+                &self,
+                chunk_size: usize,
+            ) -> impl IndexedParallelIterator<Item = (usize, &[$Element])> + '_ {
+                self.as_slice()
+                    .par_chunks(chunk_size)
+                    .enumerate()
+                    .map(move |(i, chunk)| (i * chunk_size, chunk))
+            }
+
+            /// Returns a parallel iterator over exclusive chunks of `chunk_size` elements, each paired with the index of its
+            /// first element. The last chunk may be shorter.
+            ///
+            /// ```no_run
+            /// # use godot::prelude::*;
+            /// use rayon::prelude::*;
+            ///
+            /// let mut heights = PackedFloat32Array::new();
+            /// heights.resize(512 * 512);
+            ///
+            /// heights.par_chunks_mut(512).for_each(|(offset, row)| {
+            ///     let y = (offset / 512) as f32;
+            ///     for (x, height) in row.iter_mut().enumerate() {
+            ///         *height = (x as f32 * 0.1).sin() * (y * 0.1).cos();
+            ///     }
+            /// });
+            /// ```
+            ///
+            /// # Panics
+            ///
+            /// If `chunk_size` is 0.
+            pub fn par_chunks_mut(
+                &mut self,
+                chunk_size: usize,
+            ) -> impl IndexedParallelIterator<Item = (usize, &mut [$Element])> + '_ {
+                self.as_mut_slice()
+                    .par_chunks_mut(chunk_size)
+                    .enumerate()
+                    .map(move |(i, chunk)| (i * chunk_size, chunk))
+            }
+
+            /// Updates every element in parallel, passing `f` its index and an exclusive reference to it.
+            pub fn par_update<F>(&mut self, f: F)
+            where
+                F: Fn(usize, &mut $Element) + Send + Sync,
+            {
+                self.as_mut_slice()
+                    .par_iter_mut()
+                    .enumerate()
+                    .for_each(|(i, element)| f(i, element));
+            }
+
+            /// Scatters chunks of `chunk_size` elements across the thread pool, and gathers the results of `f` in chunk order.
+            ///
+            /// `f` receives the index of the chunk's first element, and the chunk itself. This suits reductions whose partial
+            /// results are combined afterwards, e.g. per-chunk bounding boxes or audio peak levels.
+            ///
+            /// # Panics
+            ///
+            /// If `chunk_size` is 0.
+            pub fn par_map_chunks<R, F>(&self, chunk_size: usize, f: F) -> Vec<R>
+            where
+                R: Send,
+                F: Fn(usize, &[$Element]) -> R + Send + Sync,
+            {
+                self.par_chunks(chunk_size)
+                    .map(|(offset, chunk)| f(offset, chunk))
+                    .collect()
+            }
+
+            /// Creates an array of `len` elements, computing each one in parallel from its index.
+            pub fn par_from_fn<F>(len: usize, f: F) -> Self
+            where
+                F: Fn(usize) -> $Element + Send + Sync,
+            {
+                let mut array = Self::new();
+                array.resize(len);
+                array.par_update(|i, element| *element = f(i));
+                array
+            }
+        }
+    };
+}
+
+#[cfg(feature = "rayon")]
+mod par {
+    use super::*;
+    use rayon::prelude::*;
+
+    impl_packed_array_par!(PackedByteArray, u8);
+    impl_packed_array_par!(PackedInt32Array, i32);
+    impl_packed_array_par!(PackedInt64Array, i64);
+    impl_packed_array_par!(PackedFloat32Array, f32);
+    impl_packed_array_par!(PackedFloat64Array, f64);
+    impl_packed_array_par!(PackedVector2Array, Vector2);
+    impl_packed_array_par!(PackedVector3Array, Vector3);
+    impl_packed_array_par!(PackedColorArray, Color);
+}
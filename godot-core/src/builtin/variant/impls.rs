@@ -90,6 +90,32 @@ macro_rules! impl_variant_traits {
     };
 }
 
+/// Like `impl_variant_traits!`, for the types that Godot stores inline in a variant. Conversions do not call into Godot.
+macro_rules! impl_variant_traits_scalar {
+    ($T:ty, $variant_type:ident $(, $param_metadata:ident)?) => {
+        impl ToVariant for $T {
+            fn to_variant(&self) -> Variant {
+                // SAFETY: $T is the inline representation of $variant_type.
+                unsafe { Variant::from_scalar(VariantType::$variant_type, *self) }
+            }
+        }
+
+        impl FromVariant for $T {
+            fn try_from_variant(variant: &Variant) -> Result<Self, VariantConversionError> {
+                // SAFETY: $T is the inline representation of $variant_type.
+                unsafe { variant.try_to_scalar(VariantType::$variant_type) }
+                    .ok_or(VariantConversionError::BadType)
+            }
+        }
+
+        impl_variant_metadata!($T, $variant_type; $(
+            fn param_metadata() -> sys::GDExtensionClassMethodArgumentMetadata {
+                sys::$param_metadata
+            }
+        )?);
+    };
+}
+
 macro_rules! impl_variant_traits_int {
     ($T:ty, $param_metadata:ident) => {
         impl ToVariant for $T {
@@ -153,7 +179,7 @@ mod impls {
     use super::*;
 
     impl_variant_traits!(Aabb, aabb_to_variant, aabb_from_variant, Aabb);
-    impl_variant_traits!(bool, bool_to_variant, bool_from_variant, Bool);
+    impl_variant_traits_scalar!(bool, Bool);
     impl_variant_traits!(Basis, basis_to_variant, basis_from_variant, Basis);
     impl_variant_traits!(Callable, callable_to_variant, callable_from_variant, Callable);
     impl_variant_traits!(Vector2, vector2_to_variant, vector2_from_variant, Vector2);
@@ -186,7 +212,7 @@ mod impls {
     impl_variant_traits!(Transform3D, transform_3d_to_variant, transform_3d_from_variant, Transform3D);
     impl_variant_traits!(Dictionary, dictionary_to_variant, dictionary_from_variant, Dictionary);
 
-    impl_variant_traits!(i64, int_to_variant, int_from_variant, Int, GDEXTENSION_METHOD_ARGUMENT_METADATA_INT_IS_INT64);
+    impl_variant_traits_scalar!(i64, Int, GDEXTENSION_METHOD_ARGUMENT_METADATA_INT_IS_INT64);
     impl_variant_traits_int!(i8, GDEXTENSION_METHOD_ARGUMENT_METADATA_INT_IS_INT8);
     impl_variant_traits_int!(i16, GDEXTENSION_METHOD_ARGUMENT_METADATA_INT_IS_INT16);
     impl_variant_traits_int!(i32, GDEXTENSION_METHOD_ARGUMENT_METADATA_INT_IS_INT32);
@@ -196,7 +222,7 @@ mod impls {
     impl_variant_traits_int!(u32, GDEXTENSION_METHOD_ARGUMENT_METADATA_INT_IS_UINT32);
     // u64 is not supported, because it cannot be represented on GDScript side, and implicitly converting to i64 is error-prone.
 
-    impl_variant_traits!(f64, float_to_variant, float_from_variant, Float, GDEXTENSION_METHOD_ARGUMENT_METADATA_REAL_IS_DOUBLE);
+    impl_variant_traits_scalar!(f64, Float, GDEXTENSION_METHOD_ARGUMENT_METADATA_REAL_IS_DOUBLE);
     impl_variant_traits_float!(f32, GDEXTENSION_METHOD_ARGUMENT_METADATA_REAL_IS_FLOAT);
 }
 
@@ -5,6 +5,7 @@
  */
 
 use crate::builtin::{GodotString, StringName};
+use crate::error::CallError;
 use godot_ffi as sys;
 use godot_ffi::GodotFfi;
 use std::{fmt, ptr};
@@ -23,6 +24,9 @@ pub struct Variant {
     opaque: OpaqueVariant,
 }
 
+/// Offset of the value inside a Godot `Variant`: it follows the 4-byte type tag, and is aligned to 8 bytes on all platforms.
+const SCALAR_OFFSET: usize = 8;
+
 impl Variant {
     /// Create an empty variant (`null` value in GDScript).
     pub fn nil() -> Self {
@@ -101,10 +105,23 @@ impl Variant {
     /// * If the call causes an error.
     #[inline]
     pub fn call(&self, method: impl Into<StringName>, args: &[Variant]) -> Variant {
+        self.call_inner(method.into(), args)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Calls the specified `method` with the given `args`, returning an error if the call fails.
+    ///
+    /// Like [`call()`][Self::call], but reports the reason of a failed call as [`CallError`] instead of panicking.
+    #[inline]
+    pub fn try_call(
+        &self,
+        method: impl Into<StringName>,
+        args: &[Variant],
+    ) -> Result<Variant, CallError> {
         self.call_inner(method.into(), args)
     }
 
-    fn call_inner(&self, method: StringName, args: &[Variant]) -> Variant {
+    fn call_inner(&self, method: StringName, args: &[Variant]) -> Result<Variant, CallError> {
         let args_sys: Vec<_> = args.iter().map(|v| v.var_sys_const()).collect();
         let mut error = sys::default_call_error();
 
@@ -123,9 +140,9 @@ impl Variant {
 
         if error.error != sys::GDEXTENSION_CALL_OK {
             let arg_types: Vec<_> = args.iter().map(Variant::get_type).collect();
-            sys::panic_call_error(&error, "call", &arg_types);
+            return Err(CallError::from_sys(&method.to_string(), &error, &arg_types));
         }
-        result
+        Ok(result)
     }
 
     pub fn evaluate(&self, rhs: &Variant, op: VariantOperator) -> Option<Variant> {
@@ -181,6 +198,40 @@ impl Variant {
         unsafe { interface_fn!(variant_booleanize)(self.var_sys()) != 0 }
     }
 
+    /// Creates a variant holding a `bool`, `int` or `float`, without calling into Godot.
+    ///
+    /// Godot stores these types inline, behind the type tag, and they own no resources. Writing them directly avoids the FFI
+    /// call of the generic conversion, which matters for frequently called `#[func]`s and `#[var]` accessors.
+    ///
+    /// # Safety
+    /// `T` must be the inline representation of `variant_type`: `bool` for `Bool`, `i64` for `Int`, `f64` for `Float`.
+    pub(crate) unsafe fn from_scalar<T: Copy>(variant_type: VariantType, value: T) -> Self {
+        let mut variant = std::mem::MaybeUninit::<Self>::zeroed();
+        let ptr = variant.as_mut_ptr() as *mut u8;
+
+        ptr.cast::<sys::GDExtensionVariantType>()
+            .write(variant_type.sys());
+        ptr.add(SCALAR_OFFSET).cast::<T>().write(value);
+
+        variant.assume_init()
+    }
+
+    /// Reads a `bool`, `int` or `float` stored in this variant, without calling into Godot. Returns `None` if the variant holds
+    /// another type.
+    ///
+    /// # Safety
+    /// Same as [`from_scalar()`][Self::from_scalar].
+    pub(crate) unsafe fn try_to_scalar<T: Copy>(&self, variant_type: VariantType) -> Option<T> {
+        let ptr = self as *const Self as *const u8;
+
+        let actual = ptr.cast::<sys::GDExtensionVariantType>().read();
+        if actual != variant_type.sys() {
+            return None;
+        }
+
+        Some(ptr.add(SCALAR_OFFSET).cast::<T>().read())
+    }
+
     fn from_opaque(opaque: OpaqueVariant) -> Self {
         Self { opaque }
     }
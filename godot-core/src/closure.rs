@@ -0,0 +1,112 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Closures that Godot calls through a `Callable`, e.g. navigation avoidance callbacks or editor menu entries.
+//!
+//! Like custom performance monitors (see the `monitor` module), this works around the lack of Rust callables in Godot 4.1:
+//! Godot is given a `Callable` to a method of the internal dispatch object, with the closure's key bound as last argument.
+//! That method looks up and calls the closure stored here.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::builtin::{Array, Callable, ToVariant, Variant};
+use crate::engine::RefCounted;
+use crate::obj::{Gd, Share};
+
+/// Name of the dispatch method for callables without arguments.
+pub(crate) const METHOD_NAME: &str = "call_closure";
+
+/// Name of the dispatch method for callables with one argument.
+pub(crate) const ARG_METHOD_NAME: &str = "call_closure_with_arg";
+
+pub(crate) type ClosureFn = Box<dyn FnMut(Variant)>;
+
+thread_local! {
+    /// Closures by key. Godot invokes callables of editor and navigation APIs on the main thread.
+    static CLOSURES: RefCell<HashMap<String, ClosureFn>> = RefCell::new(HashMap::new());
+}
+
+/// Stores `f` under `key`, replacing a previous closure with the same key, and returns a callable without parameters that
+/// invokes it.
+pub(crate) fn callable<F>(key: String, f: F) -> Callable
+where
+    F: FnMut() + 'static,
+{
+    let bound_args = Array::from(&[key.to_variant()]);
+    insert(key, f);
+
+    crate::dispatch::callable(METHOD_NAME)
+        .as_inner()
+        .bindv(bound_args)
+}
+
+/// Like [`callable()`], but the closure is removed once Godot no longer holds the callable.
+///
+/// The callable targets the returned closure owner, which Godot keeps alive where it stores the callable (e.g. in the undo
+/// history). Keep the owner until the callable is stored; the closure is removed when the last reference to the owner is gone.
+pub(crate) fn owned_callable<F>(key: String, f: F) -> (Callable, Gd<RefCounted>)
+where
+    F: FnMut() + 'static,
+{
+    let bound_args = Array::from(&[key.to_variant()]);
+    insert(key.clone(), f);
+
+    let owner = crate::dispatch::closure_owner(key);
+    let callable = Callable::from_object_method(owner.share(), METHOD_NAME)
+        .as_inner()
+        .bindv(bound_args);
+
+    (callable, owner)
+}
+
+/// Like [`callable()`], but the callable takes one argument, which is passed on to `f`.
+pub(crate) fn callable_with_arg<F>(key: String, f: F) -> Callable
+where
+    F: FnMut(Variant) + 'static,
+{
+    let bound_args = Array::from(&[key.to_variant()]);
+    CLOSURES.with(|closures| closures.borrow_mut().insert(key, Box::new(f)));
+
+    crate::dispatch::callable(ARG_METHOD_NAME)
+        .as_inner()
+        .bindv(bound_args)
+}
+
+/// Stores `f` under `key`, like [`callable()`], for APIs that take an object and method name instead of a `Callable`.
+///
+/// Godot invokes the closure by calling [`METHOD_NAME`] on the dispatch object, with `key` as argument.
+pub(crate) fn insert<F>(key: String, mut f: F)
+where
+    F: FnMut() + 'static,
+{
+    CLOSURES.with(|closures| closures.borrow_mut().insert(key, Box::new(move |_| f())));
+}
+
+/// Drops the closure stored under `key`. Returns whether one was present.
+pub(crate) fn remove(key: &str) -> bool {
+    CLOSURES.with(|closures| closures.borrow_mut().remove(key).is_some())
+}
+
+/// Calls the closure stored under `key`, or returns `false` if there is none.
+pub(crate) fn call(key: &str, arg: Variant) -> bool {
+    // Take the closure out during the call, so that it can itself add or remove closures.
+    let Some(mut f) = CLOSURES.with(|closures| closures.borrow_mut().remove(key)) else {
+        return false;
+    };
+
+    // Keep the closure after a panic; it is reported by the dispatch method.
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(arg)));
+
+    CLOSURES.with(|closures| {
+        closures.borrow_mut().entry(key.to_string()).or_insert(f);
+    });
+
+    if let Err(panic) = result {
+        std::panic::resume_unwind(panic);
+    }
+    true
+}
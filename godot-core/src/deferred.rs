@@ -0,0 +1,49 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Queue for closures scheduled by [`Gd::run_deferred()`][crate::obj::Gd::run_deferred] and [`task::defer()`][crate::task::defer].
+//!
+//! Godot 4.1 cannot wrap Rust closures in a `Callable`. Instead, closures are stored here, and the internal dispatch object is
+//! asked to call its method via `call_deferred()`. That method then runs all pending closures.
+
+use std::cell::RefCell;
+
+use crate::builtin::StringName;
+
+/// Name of the method of the dispatch class.
+pub(crate) const METHOD_NAME: &str = "run_deferred";
+
+pub(crate) type DeferredFn = Box<dyn FnOnce()>;
+
+thread_local! {
+    /// Pending closures, in scheduling order. Deferred calls are always processed on the main thread.
+    static QUEUE: RefCell<Vec<DeferredFn>> = RefCell::new(Vec::new());
+}
+
+/// Enqueues a closure. If no other closure is pending, schedules a run at idle time.
+pub(crate) fn push(f: DeferredFn) {
+    let is_first = QUEUE.with(|queue| {
+        let mut queue = queue.borrow_mut();
+        queue.push(f);
+
+        queue.len() == 1
+    });
+
+    if is_first {
+        crate::dispatch::object().call_deferred(StringName::from(METHOD_NAME), &[]);
+    }
+}
+
+/// Runs all pending closures. A panic is printed as error, without affecting the closures after it.
+pub(crate) fn run() {
+    // Release the queue before running closures, as they may schedule new ones. Those run at the next idle time.
+    let pending = QUEUE.with(|queue| std::mem::take(&mut *queue.borrow_mut()));
+
+    for f in pending {
+        let _ =
+            crate::private::handle_panic(|| "deferred closure", std::panic::AssertUnwindSafe(f));
+    }
+}
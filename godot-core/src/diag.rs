@@ -0,0 +1,34 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Internal diagnostics, reported through the `log` crate when the `debug-log` feature is enabled.
+//!
+//! Records use the target `godot::diagnostics`, so they can be filtered independently of the user's own logging. Without the
+//! feature, `diag!` compiles to nothing.
+
+/// Target of all diagnostic records.
+#[cfg(feature = "debug-log")]
+pub const TARGET: &str = "godot::diagnostics";
+
+/// Reports an internal diagnostic at the given `log::Level` (`Error`, `Warn`, `Info`, `Debug` or `Trace`).
+#[cfg(feature = "debug-log")]
+macro_rules! diag {
+    ($level:ident, $($arg:tt)+) => {
+        ::log::log!(target: $crate::diag::TARGET, ::log::Level::$level, $($arg)+)
+    };
+}
+
+#[cfg(not(feature = "debug-log"))]
+macro_rules! diag {
+    // Arguments are still type-checked, but never evaluated.
+    ($level:ident, $($arg:tt)+) => {
+        if false {
+            let _ = ::std::format!($($arg)+);
+        }
+    };
+}
+
+pub(crate) use diag;
@@ -0,0 +1,396 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Internal class through which Godot calls back into Rust closures.
+//!
+//! Godot 4.1 cannot wrap Rust closures in a `Callable`. Instead, closures are stored on the Rust side (see the `closure`,
+//! `deferred`, `monitor`, `pool` and `task` modules), and Godot is given a `Callable` to a method of this class, with the closure's ID as
+//! argument. The class is registered once when the library is loaded, and a single instance of it is the target of all such
+//! callables. User classes thus need no hidden methods, and APIs taking closures need no object to route calls through.
+//!
+//! Closures whose lifetime Godot decides (e.g. those of undo history entries) are instead called through instances of a
+//! second, `RefCounted`-based class. Each such _closure owner_ removes its closure when Godot frees it.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::OnceLock;
+
+use godot_ffi as sys;
+use sys::interface_fn;
+
+use crate::builtin::meta::registration::method::MethodInfo;
+use crate::builtin::meta::{ClassName, PtrcallSignatureTuple, VarcallSignatureTuple};
+use crate::builtin::{Callable, GodotString, StringName, Variant};
+use crate::engine::global::MethodFlags;
+use crate::engine::{Object, RefCounted};
+use crate::obj::{Gd, GodotClass, InstanceId};
+
+/// Instance ID of the dispatch object, or 0 while the library is not initialized.
+static OBJECT_ID: AtomicI64 = AtomicI64::new(0);
+
+/// Returns a callable that invokes `method` of the dispatch object.
+///
+/// # Panics
+/// If the library has not been initialized yet.
+pub(crate) fn callable(method: &str) -> Callable {
+    Callable::from_object_method(object(), method)
+}
+
+/// The single instance of the dispatch class.
+///
+/// # Panics
+/// If the library has not been initialized yet.
+pub(crate) fn object() -> Gd<Object> {
+    let id = OBJECT_ID.load(Ordering::Acquire);
+    let id = InstanceId::try_from_i64(id)
+        .expect("Rust callbacks cannot be used before the GDExtension library is initialized");
+
+    Gd::from_instance_id(id)
+}
+
+/// Creates a closure owner, which is freed together with the last reference to it, and then removes the closure `key`.
+///
+/// Godot keeps `RefCounted` targets of stored callables alive, e.g. in the undo history. Routing a closure through its owner
+/// instead of the dispatch object thus ties the closure's lifetime to the callable's.
+pub(crate) fn closure_owner(key: String) -> Gd<RefCounted> {
+    let key = Box::into_raw(Box::new(key));
+
+    unsafe {
+        let object_ptr =
+            interface_fn!(classdb_construct_object)(RefCounted::class_name().string_sys());
+
+        // The instance data is the closure's key, released in free_closure_owner().
+        interface_fn!(object_set_instance)(
+            object_ptr,
+            closure_owner_class_name().string_sys(),
+            key.cast(),
+        );
+
+        Gd::<RefCounted>::from_obj_sys(object_ptr)
+    }
+}
+
+/// Registers the dispatch class with Godot and creates its instance. Called once, at the library's lowest initialization level.
+pub(crate) fn register() {
+    register_closure_owner_class();
+
+    let class_name = class_name();
+
+    let creation_info = sys::GDExtensionClassCreationInfo {
+        // No `create_instance_func`: the class cannot be instantiated from scripts.
+        free_instance_func: Some(free_instance),
+        ..crate::registry::default_creation_info()
+    };
+
+    unsafe {
+        interface_fn!(classdb_register_extension_class)(
+            sys::get_library(),
+            class_name.string_sys(),
+            Object::class_name().string_sys(),
+            std::ptr::addr_of!(creation_info),
+        );
+    }
+
+    register_method::<RunDeferred>(class_name);
+    register_method::<PollMonitor>(class_name);
+    register_method::<PollTasks>(class_name);
+    register_method::<SignalFired>(class_name);
+    register_method::<CoroutineCompleted>(class_name);
+    register_method::<RunPoolTask>(class_name);
+    register_method::<CallClosure>(class_name);
+    register_method::<CallClosureWithArg>(class_name);
+
+    let object = unsafe {
+        let object_ptr = interface_fn!(classdb_construct_object)(Object::class_name().string_sys());
+
+        // The class has no Rust instance data; its methods are static.
+        interface_fn!(object_set_instance)(
+            object_ptr,
+            class_name.string_sys(),
+            std::ptr::null_mut(),
+        );
+
+        Gd::<Object>::from_obj_sys(object_ptr)
+    };
+
+    OBJECT_ID.store(object.instance_id().to_i64(), Ordering::Release);
+}
+
+/// Frees the dispatch object and unregisters its class. Called when the library's lowest initialization level is unloaded.
+pub(crate) fn unregister() {
+    let id = OBJECT_ID.swap(0, Ordering::AcqRel);
+    if let Some(object) = InstanceId::try_from_i64(id).and_then(Gd::<Object>::try_from_instance_id)
+    {
+        object.free();
+    }
+
+    for class_name in [class_name(), closure_owner_class_name()] {
+        unsafe {
+            #[allow(clippy::let_unit_value)]
+            let _: () = interface_fn!(classdb_unregister_extension_class)(
+                sys::get_library(),
+                class_name.string_sys(),
+            );
+        }
+    }
+}
+
+fn register_closure_owner_class() {
+    let class_name = closure_owner_class_name();
+
+    let creation_info = sys::GDExtensionClassCreationInfo {
+        // No `create_instance_func`: instances are only created by closure_owner().
+        free_instance_func: Some(free_closure_owner),
+        ..crate::registry::default_creation_info()
+    };
+
+    unsafe {
+        interface_fn!(classdb_register_extension_class)(
+            sys::get_library(),
+            class_name.string_sys(),
+            RefCounted::class_name().string_sys(),
+            std::ptr::addr_of!(creation_info),
+        );
+    }
+
+    register_method::<CallClosure>(class_name);
+}
+
+/// Name of the dispatch class.
+///
+/// Each gdext library loaded into the same process registers its own class, so the name is made unique by the library's address.
+fn class_name() -> ClassName {
+    static CLASS_NAME: OnceLock<ClassName> = OnceLock::new();
+    *CLASS_NAME.get_or_init(|| unique_class_name("GdextDispatch"))
+}
+
+/// Name of the closure owner class, unique like [`class_name()`].
+fn closure_owner_class_name() -> ClassName {
+    static CLASS_NAME: OnceLock<ClassName> = OnceLock::new();
+    *CLASS_NAME.get_or_init(|| unique_class_name("GdextClosureOwner"))
+}
+
+fn unique_class_name(prefix: &str) -> ClassName {
+    let name = format!("{prefix}_{:x}\0", sys::get_library() as usize);
+
+    // Leaked once per library load; class names need static storage.
+    ClassName::from_ascii_cstr(Box::leak(name.into_bytes().into_boxed_slice()))
+}
+
+unsafe extern "C" fn free_instance(
+    _class_user_data: *mut std::ffi::c_void,
+    _instance: sys::GDExtensionClassInstancePtr,
+) {
+}
+
+unsafe extern "C" fn free_closure_owner(
+    _class_user_data: *mut std::ffi::c_void,
+    instance: sys::GDExtensionClassInstancePtr,
+) {
+    let key = Box::from_raw(instance.cast::<String>());
+
+    // Dropping the closure runs user code (destructors of captured values).
+    let _ = crate::private::handle_panic(
+        || format!("drop Rust closure `{key}`"),
+        || crate::closure::remove(&key),
+    );
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Methods
+
+/// Static method of the dispatch class, forwarding to the Rust side.
+trait DispatchMethod {
+    /// Return type followed by parameter types, as for `#[func]`.
+    type Sig: VarcallSignatureTuple;
+
+    const NAME: &'static str;
+    const PARAM_NAMES: &'static [&'static str];
+
+    fn call(
+        params: <Self::Sig as PtrcallSignatureTuple>::Params,
+    ) -> <Self::Sig as PtrcallSignatureTuple>::Ret;
+}
+
+/// Runs the closures scheduled by `Gd::run_deferred()` and `task::defer()`.
+struct RunDeferred;
+
+impl DispatchMethod for RunDeferred {
+    type Sig = ((),);
+    const NAME: &'static str = crate::deferred::METHOD_NAME;
+    const PARAM_NAMES: &'static [&'static str] = &[];
+
+    fn call((): ()) {
+        crate::deferred::run();
+    }
+}
+
+/// Polls the custom performance monitor `id`.
+struct PollMonitor;
+
+impl DispatchMethod for PollMonitor {
+    type Sig = (f64, StringName);
+    const NAME: &'static str = crate::monitor::METHOD_NAME;
+    const PARAM_NAMES: &'static [&'static str] = &["id"];
+
+    fn call((id,): (StringName,)) -> f64 {
+        let id = id.to_string();
+        crate::monitor::poll(&id)
+            .unwrap_or_else(|| panic!("no custom monitor `{id}` was added from Rust"))
+    }
+}
+
+/// Polls async tasks at the start of a frame.
+struct PollTasks;
+
+impl DispatchMethod for PollTasks {
+    type Sig = ((),);
+    const NAME: &'static str = crate::task::METHOD_NAME;
+    const PARAM_NAMES: &'static [&'static str] = &[];
+
+    fn call((): ()) {
+        crate::task::poll_frame();
+    }
+}
+
+/// Completes the awaited signal `id`.
+struct SignalFired;
+
+impl DispatchMethod for SignalFired {
+    type Sig = ((), i64);
+    const NAME: &'static str = crate::task::SIGNAL_METHOD_NAME;
+    const PARAM_NAMES: &'static [&'static str] = &["id"];
+
+    fn call((id,): (i64,)) {
+        crate::task::signal_fired(id as u64);
+    }
+}
+
+/// Completes the awaited coroutine `id` with `result`.
+struct CoroutineCompleted;
+
+impl DispatchMethod for CoroutineCompleted {
+    type Sig = ((), Variant, i64);
+    const NAME: &'static str = crate::task::COROUTINE_METHOD_NAME;
+    const PARAM_NAMES: &'static [&'static str] = &["result", "id"];
+
+    fn call((result, id): (Variant, i64)) {
+        crate::task::coroutine_completed(result, id as u64);
+    }
+}
+
+/// Runs element `index` of the pool task `id`, on a worker thread.
+struct RunPoolTask;
+
+impl DispatchMethod for RunPoolTask {
+    type Sig = ((), i64, i64);
+    const NAME: &'static str = crate::pool::METHOD_NAME;
+    const PARAM_NAMES: &'static [&'static str] = &["index", "id"];
+
+    fn call((index, id): (i64, i64)) {
+        crate::pool::run(id as u64, index as u32);
+    }
+}
+
+/// Calls the stored closure `key`, for callables without parameters.
+struct CallClosure;
+
+impl DispatchMethod for CallClosure {
+    type Sig = ((), GodotString);
+    const NAME: &'static str = crate::closure::METHOD_NAME;
+    const PARAM_NAMES: &'static [&'static str] = &["key"];
+
+    fn call((key,): (GodotString,)) {
+        call_closure(key, Variant::nil());
+    }
+}
+
+/// Calls the stored closure `key` with `arg`, for callables with one parameter.
+struct CallClosureWithArg;
+
+impl DispatchMethod for CallClosureWithArg {
+    type Sig = ((), Variant, GodotString);
+    const NAME: &'static str = crate::closure::ARG_METHOD_NAME;
+    const PARAM_NAMES: &'static [&'static str] = &["arg", "key"];
+
+    fn call((arg, key): (Variant, GodotString)) {
+        call_closure(key, arg);
+    }
+}
+
+fn call_closure(key: GodotString, arg: Variant) {
+    let key = key.to_string();
+    if !crate::closure::call(&key, arg) {
+        panic!("Rust closure `{key}` was already removed");
+    }
+}
+
+fn register_method<M: DispatchMethod>(class_name: ClassName) {
+    // SAFETY: `varcall` and `ptrcall` interpret their arguments according to `M::Sig`, and ignore the (null) instance.
+    let method_info = unsafe {
+        MethodInfo::from_signature::<M::Sig>(
+            class_name,
+            StringName::from(M::NAME),
+            Some(varcall::<M>),
+            Some(ptrcall::<M>),
+            MethodFlags::METHOD_FLAG_STATIC,
+            M::PARAM_NAMES,
+            Vec::new(),
+        )
+    };
+
+    method_info.register_extension_class_method();
+}
+
+unsafe extern "C" fn varcall<M: DispatchMethod>(
+    _method_data: *mut std::ffi::c_void,
+    instance_ptr: sys::GDExtensionClassInstancePtr,
+    args_ptr: *const sys::GDExtensionConstVariantPtr,
+    arg_count: sys::GDExtensionInt,
+    ret: sys::GDExtensionVariantPtr,
+    err: *mut sys::GDExtensionCallError,
+) {
+    let result = crate::private::handle_panic(
+        || format!("internal method `{}`", M::NAME),
+        || {
+            <M::Sig as VarcallSignatureTuple>::varcall(
+                instance_ptr,
+                args_ptr,
+                arg_count,
+                ret,
+                err,
+                |_, params| Ok(M::call(params)),
+                M::NAME,
+            )
+        },
+    );
+
+    if result.is_none() {
+        (*err).error = sys::GDEXTENSION_CALL_ERROR_INVALID_METHOD;
+        interface_fn!(variant_new_nil)(sys::AsUninit::as_uninit(ret));
+    }
+}
+
+unsafe extern "C" fn ptrcall<M: DispatchMethod>(
+    _method_data: *mut std::ffi::c_void,
+    instance_ptr: sys::GDExtensionClassInstancePtr,
+    args_ptr: *const sys::GDExtensionConstTypePtr,
+    ret: sys::GDExtensionTypePtr,
+) {
+    // No error channel for ptrcalls; the return value keeps its default.
+    let _ = crate::private::handle_panic(
+        || format!("internal method `{}`", M::NAME),
+        || {
+            <M::Sig as PtrcallSignatureTuple>::ptrcall(
+                instance_ptr,
+                args_ptr,
+                ret,
+                |_, params| Ok(M::call(params)),
+                M::NAME,
+                sys::PtrcallType::Standard,
+            )
+        },
+    );
+}
@@ -0,0 +1,141 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Documentation of user-defined classes, collected from `///` comments.
+//!
+//! `#[derive(GodotClass)]` and `#[godot_api]` gather the doc comments on classes, `#[var]`/`#[export]` properties,
+//! `#[func]`s and `#[signal]`s. [`gather_xml_docs()`] assembles them into the XML format which Godot uses for its own
+//! class reference (the files in `doc/classes`).
+//!
+//! From Godot 4.3 on, the docs are loaded into the editor help when the editor initializes the library, so that they show up
+//! in the help and in inspector tooltips. Earlier versions have no GDExtension API for this; with those, the XML can only be
+//! written to disk (e.g. from a tool script) to build docs for a project.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+#[cfg(since_api = "4.3")]
+use godot_ffi as sys;
+
+use crate::builtin::meta::ClassName;
+use crate::registry::{ClassPlugin, PluginComponent};
+
+/// Docs of a `#[derive(GodotClass)]` struct and its properties.
+///
+/// All strings are XML-escaped and already in their final form.
+#[doc(hidden)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct StructDocs {
+    /// First paragraph of the struct's doc comment.
+    pub brief: &'static str,
+
+    /// Remaining paragraphs of the struct's doc comment.
+    pub description: &'static str,
+
+    /// Sequence of `<member>` elements.
+    pub members: &'static str,
+}
+
+/// Docs of the `#[func]`s and `#[signal]`s in a `#[godot_api] impl` block.
+///
+/// All strings are XML-escaped and already in their final form.
+#[doc(hidden)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct InherentImplDocs {
+    /// Sequence of `<method>` elements.
+    pub methods: &'static str,
+
+    /// Sequence of `<signal>` elements.
+    pub signals: &'static str,
+}
+
+#[derive(Default)]
+struct DocPieces {
+    base: Option<ClassName>,
+    definition: StructDocs,
+    methods: String,
+    signals: String,
+}
+
+/// Returns the XML class reference of every user-defined class, one document per class.
+///
+/// Classes appear in unspecified order. Classes without any doc comments are still included, with empty descriptions.
+pub fn gather_xml_docs() -> impl Iterator<Item = String> {
+    let mut map = HashMap::<ClassName, DocPieces>::new();
+
+    crate::private::iterate_plugins(|plugin: &ClassPlugin| {
+        let pieces = map.entry(plugin.class_name).or_default();
+
+        match &plugin.component {
+            PluginComponent::ClassDef {
+                base_class_name,
+                docs,
+                ..
+            } => {
+                pieces.base = Some(*base_class_name);
+                if let Some(docs) = docs {
+                    pieces.definition = *docs;
+                }
+            }
+            PluginComponent::UserMethodBinds { docs, .. } => {
+                if let Some(docs) = docs {
+                    pieces.methods.push_str(docs.methods);
+                    pieces.signals.push_str(docs.signals);
+                }
+            }
+            PluginComponent::UserVirtuals { .. } => {}
+        }
+    });
+
+    map.into_iter()
+        .map(|(class_name, pieces)| make_class_xml(class_name, &pieces))
+}
+
+/// Loads the class reference of every user-defined class into the editor help. Called at the `Editor` init level.
+#[cfg(since_api = "4.3")]
+pub(crate) fn register() {
+    for xml in gather_xml_docs() {
+        // SAFETY: Godot copies the XML during the call; the pointer and length describe a valid UTF-8 buffer.
+        unsafe {
+            sys::interface_fn!(editor_help_load_xml_from_utf8_chars_and_len)(
+                xml.as_ptr() as *const std::ffi::c_char,
+                xml.len() as sys::GDExtensionInt,
+            );
+        }
+    }
+}
+
+fn make_class_xml(class_name: ClassName, pieces: &DocPieces) -> String {
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8" ?>"#);
+    xml.push('\n');
+
+    let inherits = match pieces.base {
+        Some(base) => format!(r#" inherits="{base}""#),
+        None => String::new(),
+    };
+
+    let StructDocs {
+        brief,
+        description,
+        members,
+    } = pieces.definition;
+
+    // Writing to a String cannot fail.
+    let _ = write!(
+        xml,
+        "<class name=\"{class_name}\"{inherits}>\n\
+         <brief_description>{brief}</brief_description>\n\
+         <description>{description}</description>\n\
+         <methods>{methods}</methods>\n\
+         <members>{members}</members>\n\
+         <signals>{signals}</signals>\n\
+         </class>\n",
+        methods = pieces.methods,
+        signals = pieces.signals,
+    );
+
+    xml
+}
@@ -7,12 +7,31 @@
 //! Godot engine classes and methods.
 
 // Re-exports of generated symbols
-use crate::builtin::{GodotString, NodePath};
+use crate::builtin::meta::{ClassName, VariantMetadata};
+use crate::builtin::{
+    dict, Array, Callable, FromVariant, GodotString, NodePath, PackedByteArray, StringName,
+    ToVariant, Variant,
+};
+#[cfg(since_api = "4.1")]
+use crate::builtin::{Rid, Vector2, Vector3};
+use crate::error::{
+    CallError, CallErrorKind, ConnectError, ConnectErrorKind, LoadError, LoadErrorKind,
+};
 use crate::obj::dom::EngineDomain;
-use crate::obj::{Gd, GodotClass, Inherits};
+use crate::obj::{mem, EngineEnum, Gd, GodotClass, Inherits, InstanceId, Share};
+use crate::task::{self, SignalFuture};
+use godot_ffi as sys;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::ptr;
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime};
+use sys::interface_fn;
 
-pub use crate::gen::central::global;
 pub use crate::gen::classes::*;
+pub use crate::gen::global;
 pub use crate::gen::utilities;
 
 /// Support for Godot _native structures_.
@@ -26,34 +45,467 @@ pub mod native {
     pub use crate::gen::native::*;
 }
 
+/// Views the buffer, which Godot passes to `AudioStreamPlaybackVirtual::mix()`, as a mutable slice.
+///
+/// Playbacks fill `frames` audio frames per call. Working on a slice instead of the raw pointer allows idiomatic and bounds-checked
+/// iteration in the mixing loop, without copying.
+///
+/// # Safety
+/// `buffer` must point to `frames` consecutive `AudioFrame` values, which are valid for writes and not accessed otherwise while
+/// the slice is alive. The arguments of `mix()` satisfy this for the duration of the call.
+pub unsafe fn audio_frames_mut<'a>(
+    buffer: *mut native::AudioFrame,
+    frames: i32,
+) -> &'a mut [native::AudioFrame] {
+    if buffer.is_null() || frames <= 0 {
+        return &mut [];
+    }
+
+    std::slice::from_raw_parts_mut(buffer, frames as usize)
+}
+
+/// Returns `true` if the code is running inside the editor, `false` if it runs in the game.
+///
+/// Equivalent to `Engine::singleton().is_editor_hint()`, but cached after the first call, since the value never changes.
+/// Typically used in `#[class(tool)]` classes to separate editor-time from game-time behavior. For whole functions, consider
+/// `#[func(tool_only)]` or `#[func(runtime_only)]` instead.
+pub fn is_editor_hint() -> bool {
+    // SAFETY: engine classes are only accessible after global library initialization.
+    let global_config = unsafe { sys::config() };
+    let is_editor = || Engine::singleton().is_editor_hint();
+
+    *global_config.is_editor.get_or_init(is_editor)
+}
+
+/// Returns the editor's `EditorInterface`, or `None` when not running in the editor.
+///
+/// Gives access to the selection, the file system dock, the edited scene and the play/stop controls, from any tool code, not only
+/// from `EditorPlugin` methods. Since Godot 4.2, `EditorInterface` is a singleton; before, it is only reachable through an
+/// `EditorPlugin`. This function covers both, so tool code compiles and behaves the same across versions.
+pub fn editor_interface() -> Option<Gd<EditorInterface>> {
+    if !is_editor_hint() {
+        return None;
+    }
+
+    editor_interface_impl()
+}
+
+/// Extension trait for calling methods and setting properties by name, reporting failures as [`CallError`].
+///
+/// Useful when the target is only known at runtime, e.g. in plugin or reflection scenarios: there is no need to check
+/// `has_method()` first, and a failed call does not silently evaluate to nil.
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// # fn notify(mut listener: Gd<Object>) {
+/// use godot::engine::ObjectExt;
+///
+/// match listener.try_call("on_event", &[42.to_variant()]) {
+///     Ok(result) => godot_print!("listener returned {result}"),
+///     Err(err) => godot_warn!("{err}"),
+/// }
+/// # }
+/// ```
+pub trait ObjectExt {
+    /// Calls `method` with `args`, returning its result.
+    ///
+    /// Unlike `Object::call()`, a missing method, wrong arguments or a failing method are returned as [`CallError`].
+    fn try_call(
+        &mut self,
+        method: impl Into<StringName>,
+        args: &[Variant],
+    ) -> Result<Variant, CallError>;
+
+    /// Calls `method` with `args` at the end of the current frame.
+    ///
+    /// Fails immediately if there is no method with that name. Argument errors can only be detected once the call is
+    /// made, and are then reported by Godot.
+    fn try_call_deferred(
+        &mut self,
+        method: impl Into<StringName>,
+        args: &[Variant],
+    ) -> Result<(), CallError>;
+
+    /// Sets `property` to `value`.
+    ///
+    /// Fails with [`CallErrorKind::InvalidProperty`] if there is no such property, or if it cannot hold `value`.
+    fn try_set(&mut self, property: impl Into<StringName>, value: Variant)
+        -> Result<(), CallError>;
+
+    /// Connects `signal` to `callable`.
+    ///
+    /// Unlike `Object::connect()`, which prints an error and returns a code, failures are returned as [`ConnectError`]:
+    /// the signal does not exist, the callable is invalid, or it is already connected.
+    fn try_connect(
+        &mut self,
+        signal: impl Into<StringName>,
+        callable: &Callable,
+    ) -> Result<(), ConnectError> {
+        self.try_connect_flags(signal, callable, 0)
+    }
+
+    /// Connects `signal` to `callable`, with `flags` being a combination of [`object::ConnectFlags`] ordinals.
+    ///
+    /// With `ConnectFlags::REFERENCE_COUNTED`, connecting the same callable again is allowed and increments the count.
+    fn try_connect_flags(
+        &mut self,
+        signal: impl Into<StringName>,
+        callable: &Callable,
+        flags: u32,
+    ) -> Result<(), ConnectError>;
+
+    /// Returns whether `callable` is connected to `signal`.
+    fn is_signal_connected(&self, signal: impl Into<StringName>, callable: &Callable) -> bool;
+
+    /// Disconnects `callable` from `signal`.
+    ///
+    /// Fails with [`ConnectErrorKind::NotConnected`] instead of printing an error, if there is no such connection.
+    fn try_disconnect(
+        &mut self,
+        signal: impl Into<StringName>,
+        callable: &Callable,
+    ) -> Result<(), ConnectError>;
+}
+
+impl<T> ObjectExt for Gd<T>
+where
+    T: GodotClass + Inherits<Object>,
+{
+    fn try_call(
+        &mut self,
+        method: impl Into<StringName>,
+        args: &[Variant],
+    ) -> Result<Variant, CallError> {
+        self.to_variant().try_call(method, args)
+    }
+
+    fn try_call_deferred(
+        &mut self,
+        method: impl Into<StringName>,
+        args: &[Variant],
+    ) -> Result<(), CallError> {
+        let method = method.into();
+        let mut object = self.share().upcast::<Object>();
+
+        if !object.has_method(method.clone()) {
+            return Err(CallError::new(
+                &method.to_string(),
+                CallErrorKind::InvalidMethod,
+            ));
+        }
+
+        object.call_deferred(method, args);
+        Ok(())
+    }
+
+    fn try_set(
+        &mut self,
+        property: impl Into<StringName>,
+        value: Variant,
+    ) -> Result<(), CallError> {
+        let property = property.into();
+        let variant = self.to_variant();
+
+        let mut valid = false as sys::GDExtensionBool;
+        unsafe {
+            interface_fn!(variant_set_named)(
+                variant.var_sys(),
+                property.string_sys(),
+                value.var_sys(),
+                ptr::addr_of_mut!(valid),
+            );
+        }
+
+        if valid == 0 {
+            return Err(CallError::new(
+                &property.to_string(),
+                CallErrorKind::InvalidProperty,
+            ));
+        }
+        Ok(())
+    }
+
+    fn try_connect_flags(
+        &mut self,
+        signal: impl Into<StringName>,
+        callable: &Callable,
+        flags: u32,
+    ) -> Result<(), ConnectError> {
+        let signal = signal.into();
+        let mut object = self.share().upcast::<Object>();
+        let fail = |kind| Err(ConnectError::new(&signal.to_string(), kind));
+
+        if !object.has_signal(signal.clone()) {
+            return fail(ConnectErrorKind::UnknownSignal);
+        }
+        if !callable.is_valid() {
+            return fail(ConnectErrorKind::InvalidCallable);
+        }
+
+        // Godot would print an error and return ERR_INVALID_PARAMETER; reference-counted connections may be repeated.
+        let ref_counted = object::ConnectFlags::REFERENCE_COUNTED.ord() as u32;
+        if flags & ref_counted == 0 && object.is_connected(signal.clone(), callable.clone()) {
+            return fail(ConnectErrorKind::AlreadyConnected);
+        }
+
+        let code = object
+            .connect_ex(signal.clone(), callable.clone())
+            .flags(flags)
+            .done();
+
+        if code != global::Error::OK {
+            return fail(ConnectErrorKind::Engine(code));
+        }
+        Ok(())
+    }
+
+    fn is_signal_connected(&self, signal: impl Into<StringName>, callable: &Callable) -> bool {
+        let object = self.share().upcast::<Object>();
+        object.is_connected(signal.into(), callable.clone())
+    }
+
+    fn try_disconnect(
+        &mut self,
+        signal: impl Into<StringName>,
+        callable: &Callable,
+    ) -> Result<(), ConnectError> {
+        let signal = signal.into();
+        let mut object = self.share().upcast::<Object>();
+
+        if !object.has_signal(signal.clone()) {
+            return Err(ConnectError::new(
+                &signal.to_string(),
+                ConnectErrorKind::UnknownSignal,
+            ));
+        }
+        if !object.is_connected(signal.clone(), callable.clone()) {
+            return Err(ConnectError::new(
+                &signal.to_string(),
+                ConnectErrorKind::NotConnected,
+            ));
+        }
+
+        object.disconnect(signal, callable.clone());
+        Ok(())
+    }
+}
+
 /// Extension trait for convenience functions on `PackedScene`
 pub trait PackedSceneExt {
     /// ⚠️ Instantiates the scene as type `T`, panicking if not found or bad type.
     ///
     /// # Panics
-    /// If the scene is not type `T` or inherited.
+    /// If the scene cannot be instantiated, or its root node is not type `T` or inherited. The message names the actual
+    /// class of the root node.
     fn instantiate_as<T>(&self) -> Gd<T>
     where
-        T: Inherits<Node>,
-    {
-        self.try_instantiate_as::<T>()
-            .unwrap_or_else(|| panic!("Failed to instantiate {to}", to = T::class_name()))
-    }
+        T: Inherits<Node>;
 
     /// Instantiates the scene as type `T` (fallible).
     ///
-    /// If the scene is not type `T` or inherited.
+    /// If the scene cannot be instantiated, or its root node is not type `T` or inherited, `None` is returned.
+    /// In the latter case, the instantiated nodes are freed again.
     fn try_instantiate_as<T>(&self) -> Option<Gd<T>>
     where
         T: Inherits<Node>;
 }
 
 impl PackedSceneExt for PackedScene {
+    fn instantiate_as<T>(&self) -> Gd<T>
+    where
+        T: Inherits<Node>,
+    {
+        instantiate_impl(self).unwrap_or_else(|err| panic!("{err}"))
+    }
+
     fn try_instantiate_as<T>(&self) -> Option<Gd<T>>
     where
         T: Inherits<Node>,
     {
-        self.instantiate().and_then(|gd| gd.try_cast::<T>())
+        instantiate_impl(self).ok()
+    }
+}
+
+/// Extension trait for spawning scenes into the running `SceneTree`.
+pub trait SceneTreeExt {
+    /// ⚠️ Instantiates `scene` as type `T` and adds it as child of the current scene, or of the root if there is none.
+    ///
+    /// # Panics
+    /// Same as [`PackedSceneExt::instantiate_as()`].
+    fn spawn_as<T>(&mut self, scene: &PackedScene) -> Gd<T>
+    where
+        T: Inherits<Node>;
+
+    /// Returns the nodes in `group`, cast to type `T`.
+    ///
+    /// The iterator works on a snapshot of the group, taken at the time of this call. Nodes that are not of type `T` or
+    /// inherited are handled according to `policy`.
+    ///
+    /// ```no_run
+    /// # use godot::prelude::*;
+    /// use godot::engine::CastPolicy;
+    ///
+    /// # fn hide_all(tree: Gd<SceneTree>) {
+    /// for mut enemy in tree.nodes_in_group_as::<Node2D>("enemies", CastPolicy::Skip) {
+    ///     enemy.hide();
+    /// }
+    /// # }
+    /// ```
+    fn nodes_in_group_as<T>(&self, group: impl Into<StringName>, policy: CastPolicy) -> NodesAs<T>
+    where
+        T: Inherits<Node>;
+
+    /// Completes after `duration` has passed, measured by a one-shot `SceneTreeTimer`.
+    ///
+    /// Like `await get_tree().create_timer(t).timeout` in GDScript, this lets tasks express sequences in straight-line code:
+    ///
+    /// ```no_run
+    /// # use godot::prelude::*;
+    /// # use godot::task;
+    /// # use std::time::Duration;
+    /// # fn intro(mut tree: Gd<SceneTree>, mut title: Gd<Node2D>) {
+    /// task::spawn(async move {
+    ///     title.show();
+    ///     tree.sleep(Duration::from_secs(2)).await.ok();
+    ///     title.hide();
+    /// });
+    /// # }
+    /// ```
+    ///
+    /// The timer follows `Engine.time_scale` and pauses with the tree, unless processing always. Requires
+    /// [`task::drive_each_frame()`][crate::task::drive_each_frame], see [`task::signal_future()`][crate::task::signal_future].
+    fn sleep(&mut self, duration: Duration) -> SignalFuture;
+}
+
+impl SceneTreeExt for SceneTree {
+    fn spawn_as<T>(&mut self, scene: &PackedScene) -> Gd<T>
+    where
+        T: Inherits<Node>,
+    {
+        let mut parent = match self.get_current_scene() {
+            Some(current) => current,
+            None => self
+                .get_root()
+                .expect("scene tree has no root window")
+                .upcast(),
+        };
+
+        parent.spawn_child_as(scene)
+    }
+
+    fn nodes_in_group_as<T>(&self, group: impl Into<StringName>, policy: CastPolicy) -> NodesAs<T>
+    where
+        T: Inherits<Node>,
+    {
+        NodesAs::new(self.get_nodes_in_group(group.into()), policy)
+    }
+
+    fn sleep(&mut self, duration: Duration) -> SignalFuture {
+        let timer = self
+            .create_timer(duration.as_secs_f64())
+            .expect("SceneTree::create_timer() returned null");
+
+        task::signal_future(&timer, "timeout")
+    }
+}
+
+/// Extension trait to await tweens from [tasks][crate::task].
+pub trait TweenExt {
+    /// Completes once the tween has finished all of its steps.
+    ///
+    /// A killed or infinitely looping tween never finishes; use [`SignalFuture::timeout()`] if that can happen. Requires
+    /// [`task::drive_each_frame()`][crate::task::drive_each_frame].
+    ///
+    /// ```no_run
+    /// # use godot::prelude::*;
+    /// # use godot::engine::TweenExt;
+    /// # use godot::task;
+    /// # fn fade_out(mut sprite: Gd<Node2D>) {
+    /// task::spawn(async move {
+    ///     let mut tween = sprite.create_tween().unwrap();
+    ///     tween.tween_property(sprite.share().upcast(), StringName::from("modulate:a").into(), 0.0_f64.to_variant(), 0.5);
+    ///     tween.finished().await.ok();
+    ///     sprite.queue_free();
+    /// });
+    /// # }
+    /// ```
+    fn finished(&self) -> SignalFuture;
+}
+
+impl TweenExt for Gd<Tween> {
+    fn finished(&self) -> SignalFuture {
+        task::signal_future(self, "finished")
+    }
+}
+
+/// Extension trait to await timers from [tasks][crate::task].
+pub trait TimerExt {
+    /// Completes at the next `timeout` of the timer.
+    ///
+    /// The timer is not started; for a repeating timer, each call awaits one more timeout. Requires
+    /// [`task::drive_each_frame()`][crate::task::drive_each_frame].
+    fn timeout(&self) -> SignalFuture;
+}
+
+impl TimerExt for Gd<Timer> {
+    fn timeout(&self) -> SignalFuture {
+        task::signal_future(self, "timeout")
+    }
+}
+
+/// How typed node queries treat nodes that do not have the requested type.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CastPolicy {
+    /// Leave out nodes of other types.
+    Skip,
+
+    /// Panic when reaching a node of another type.
+    Panic,
+}
+
+/// Iterator over a snapshot of nodes, cast to type `T`.
+///
+/// Returned by typed node queries such as [`SceneTreeExt::nodes_in_group_as()`].
+pub struct NodesAs<T> {
+    nodes: Array<Gd<Node>>,
+    next_idx: usize,
+    policy: CastPolicy,
+    _marker: PhantomData<T>,
+}
+
+impl<T> NodesAs<T> {
+    fn new(nodes: Array<Gd<Node>>, policy: CastPolicy) -> Self {
+        Self {
+            nodes,
+            next_idx: 0,
+            policy,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Iterator for NodesAs<T>
+where
+    T: Inherits<Node>,
+{
+    type Item = Gd<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next_idx < self.nodes.len() {
+            let node = self.nodes.get(self.next_idx);
+            self.next_idx += 1;
+
+            match self.policy {
+                CastPolicy::Skip => {
+                    if let Some(node) = node.try_cast::<T>() {
+                        return Some(node);
+                    }
+                }
+                CastPolicy::Panic => return Some(node.cast::<T>()),
+            }
+        }
+
+        None
     }
 }
 
@@ -67,36 +519,171 @@ pub trait NodeExt {
     where
         T: GodotClass + Inherits<Node>,
     {
-        let path = path.into();
-        let copy = path.clone(); // TODO avoid copy
-
-        self.try_get_node_as(path).unwrap_or_else(|| {
-            panic!(
-                "There is no node of type {ty} path `{copy}`",
-                ty = T::class_name()
-            )
-        })
+        self.try_get_node_as(path)
+            .unwrap_or_else(|err| panic!("{err}"))
     }
 
     /// Retrieves the node at path `path` (fallible).
     ///
-    /// If the node is not found, or if it does not have type `T` or inherited,
-    /// `None` will be returned.
-    fn try_get_node_as<T>(&self, path: impl Into<NodePath>) -> Option<Gd<T>>
+    /// If the node is not found, or if it does not have type `T` or inherited, the returned [`NodeError`] tells which
+    /// of the two happened.
+    fn try_get_node_as<T>(&self, path: impl Into<NodePath>) -> Result<Gd<T>, NodeError>
     where
         T: GodotClass + Inherits<Node>;
+
+    /// ⚠️ Instantiates `scene` as type `T` and adds it as child of this node.
+    ///
+    /// # Panics
+    /// Same as [`PackedSceneExt::instantiate_as()`].
+    fn spawn_child_as<T>(&mut self, scene: &PackedScene) -> Gd<T>
+    where
+        T: Inherits<Node>;
+
+    /// Returns all descendants of this node (children, grandchildren, ...), depth-first in tree order.
+    ///
+    /// Internal children are not included.
+    fn descendants(&self) -> Descendants;
+
+    /// Returns the parent of this node, its parent and so on, up to the root.
+    fn ancestors(&self) -> Ancestors;
+
+    /// Returns all descendants of type `T` or inherited, for which `predicate` returns true. In tree order.
+    ///
+    /// ```no_run
+    /// # use godot::prelude::*;
+    /// # fn visible_sprites(level: Gd<Node>) {
+    /// use godot::engine::Sprite2D;
+    ///
+    /// let sprites = level.find_children_by::<Sprite2D>(|sprite| sprite.is_visible());
+    /// # }
+    /// ```
+    fn find_children_by<T>(&self, mut predicate: impl FnMut(&Gd<T>) -> bool) -> Vec<Gd<T>>
+    where
+        T: Inherits<Node>,
+    {
+        self.descendants()
+            .filter_map(|node| node.try_cast::<T>())
+            .filter(|node| predicate(node))
+            .collect()
+    }
+}
+
+/// Depth-first iterator over the descendants of a node. Returned by [`NodeExt::descendants()`].
+pub struct Descendants {
+    /// Nodes still to visit, the next one last.
+    stack: Vec<Gd<Node>>,
+}
+
+impl Descendants {
+    fn new(node: &Node) -> Self {
+        let mut descendants = Self { stack: Vec::new() };
+        descendants.push_children(node);
+        descendants
+    }
+
+    fn push_children(&mut self, node: &Node) {
+        let children = node.get_children();
+        self.stack
+            .extend((0..children.len()).rev().map(|i| children.get(i)));
+    }
+}
+
+impl Iterator for Descendants {
+    type Item = Gd<Node>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_children(&node);
+
+        Some(node)
+    }
+}
+
+/// Iterator over the ancestors of a node, starting with its parent. Returned by [`NodeExt::ancestors()`].
+pub struct Ancestors {
+    next: Option<Gd<Node>>,
+}
+
+impl Iterator for Ancestors {
+    type Item = Gd<Node>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next.take()?;
+        self.next = node.get_parent();
+
+        Some(node)
+    }
+}
+
+/// Error returned by [`NodeExt::try_get_node_as()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum NodeError {
+    /// No node exists at `path`.
+    NotFound { path: NodePath },
+
+    /// The node at `path` has class `actual`, which is neither `expected` nor derived from it.
+    WrongType {
+        path: NodePath,
+        expected: ClassName,
+        actual: GodotString,
+    },
+}
+
+impl std::fmt::Display for NodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NodeError::NotFound { path } => write!(f, "no node found at path `{path}`"),
+            NodeError::WrongType {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "node at path `{path}` has class `{actual}`, which is not `{expected}` or derived"
+            ),
+        }
+    }
 }
 
+impl std::error::Error for NodeError {}
+
 impl NodeExt for Node {
-    fn try_get_node_as<T>(&self, path: impl Into<NodePath>) -> Option<Gd<T>>
+    fn try_get_node_as<T>(&self, path: impl Into<NodePath>) -> Result<Gd<T>, NodeError>
     where
         T: GodotClass + Inherits<Node>,
     {
         let path = path.into();
 
-        // TODO differentiate errors (not found, bad type) with Result
-        self.get_node_or_null(path)
-            .and_then(|node| node.try_cast::<T>())
+        let Some(node) = self.get_node_or_null(path.clone()) else {
+            return Err(NodeError::NotFound { path });
+        };
+
+        let actual = node.get_class();
+        node.try_cast::<T>().ok_or_else(|| NodeError::WrongType {
+            path,
+            expected: T::class_name(),
+            actual,
+        })
+    }
+
+    fn spawn_child_as<T>(&mut self, scene: &PackedScene) -> Gd<T>
+    where
+        T: Inherits<Node>,
+    {
+        let child = scene.instantiate_as::<T>();
+        self.add_child(&child);
+
+        child
+    }
+
+    fn descendants(&self) -> Descendants {
+        Descendants::new(self)
+    }
+
+    fn ancestors(&self) -> Ancestors {
+        Ancestors {
+            next: self.get_parent(),
+        }
     }
 }
 
@@ -104,7 +691,7 @@ impl<U> NodeExt for Gd<U>
 where
     U: GodotClass<Declarer = EngineDomain> + Inherits<Node>,
 {
-    fn try_get_node_as<T>(&self, path: impl Into<NodePath>) -> Option<Gd<T>>
+    fn try_get_node_as<T>(&self, path: impl Into<NodePath>) -> Result<Gd<T>, NodeError>
     where
         T: GodotClass + Inherits<Node>,
     {
@@ -119,6 +706,27 @@ where
 
         <Node as NodeExt>::try_get_node_as(&*node, path)
     }
+
+    fn spawn_child_as<T>(&mut self, scene: &PackedScene) -> Gd<T>
+    where
+        T: Inherits<Node>,
+    {
+        let mut node = self.share().upcast::<Node>();
+
+        <Node as NodeExt>::spawn_child_as(&mut *node, scene)
+    }
+
+    fn descendants(&self) -> Descendants {
+        let node = self.share().upcast::<Node>();
+
+        <Node as NodeExt>::descendants(&*node)
+    }
+
+    fn ancestors(&self) -> Ancestors {
+        let node = self.share().upcast::<Node>();
+
+        <Node as NodeExt>::ancestors(&*node)
+    }
 }
 
 /// Loads a resource from the filesystem located at `path`, panicking on error.
@@ -134,14 +742,14 @@ where
 /// ```
 ///
 /// # Panics
-/// If the resource cannot be loaded, or is not of type `T` or inherited.
+/// If the resource cannot be loaded, or is not of type `T` or inherited. The panic message tells the two cases apart,
+/// naming the actual class of the resource in the latter.
 #[inline]
 pub fn load<T>(path: impl Into<GodotString>) -> Gd<T>
 where
     T: GodotClass + Inherits<Resource>,
 {
-    let path = path.into();
-    load_impl(&path).unwrap_or_else(|| panic!("failed to load node at path `{path}`"))
+    load_impl(&path.into()).unwrap_or_else(|err| panic!("{err}"))
 }
 
 /// Loads a resource from the filesystem located at `path`.
@@ -149,7 +757,9 @@ where
 /// The resource is loaded on the method call (unless it's referenced already elsewhere, e.g. in another script or in the scene),
 /// which might cause slight delay, especially when loading scenes.
 ///
-/// If the resource cannot be loaded, or is not of type `T` or inherited, this method returns `None`.
+/// If the resource cannot be loaded, or is not of type `T` or inherited, this method returns an error. Its
+/// [`kind()`][LoadError::kind] tells apart a missing file ([`LoadErrorKind::NotFound`]), a failing loader
+/// ([`LoadErrorKind::Failed`]) and a resource of another class ([`LoadErrorKind::WrongClass`]).
 ///
 /// This method is a simplified version of [`ResourceLoader::load()`][crate::engine::ResourceLoader::load],
 /// which can be used for more advanced scenarios.
@@ -167,59 +777,1920 @@ where
 /// ```no_run
 /// use godot::prelude::*;
 ///
-/// if let Some(scene) = try_load::<PackedScene>("res://path/to/Main.tscn") {
-///     // all good
-/// } else {
-///     // handle error
+/// match try_load::<PackedScene>("res://path/to/Main.tscn") {
+///     Ok(scene) => { /* all good */ }
+///     Err(err) => godot_error!("{err}"),
 /// }
 /// ```
-// TODO Result to differentiate 2 errors
 #[inline]
-pub fn try_load<T>(path: impl Into<GodotString>) -> Option<Gd<T>>
+pub fn try_load<T>(path: impl Into<GodotString>) -> Result<Gd<T>, LoadError>
 where
     T: GodotClass + Inherits<Resource>,
 {
     load_impl(&path.into())
 }
 
-// ----------------------------------------------------------------------------------------------------------------------------------------------
-// Utilities for crate
+/// Starts loading the resource at `path` on a background thread.
+///
+/// The returned [`ThreadedLoad`] reports progress, and can be awaited from a [task][crate::task] or checked once per frame:
+///
+/// ```no_run
+/// use godot::prelude::*;
+/// use godot::engine::{load_threaded, ThreadedLoad};
+///
+/// # fn start() -> Result<(), godot::error::Error> {
+/// let mut level: Option<ThreadedLoad<PackedScene>> = Some(load_threaded("res://levels/Forest.tscn")?);
+///
+/// // In _process():
+/// if let Some(load) = level.take() {
+///     if load.is_finished() {
+///         let scene = load.get()?;
+///         // instantiate scene
+///     } else {
+///         godot_print!("loading: {:.0}%", load.progress() * 100.0);
+///         level = Some(load);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// This wraps `ResourceLoader::load_threaded_request()` and its companions, using the class of `T` as type hint. See
+/// [`load_threaded_ex()`] to load dependencies on sub-threads, or to bypass the resource cache.
+pub fn load_threaded<T>(path: impl Into<GodotString>) -> Result<ThreadedLoad<T>, LoadError>
+where
+    T: GodotClass + Inherits<Resource>,
+{
+    load_threaded_ex(path, false, resource_loader::CacheMode::CACHE_MODE_REUSE)
+}
 
-pub(crate) fn debug_string<T: GodotClass>(
-    ptr: &Gd<T>,
-    f: &mut std::fmt::Formatter<'_>,
-    ty: &str,
-) -> std::fmt::Result {
-    if let Some(id) = ptr.instance_id_or_none() {
-        let class: GodotString = ptr.as_object(|obj| Object::get_class(obj));
+/// Like [`load_threaded()`], with the remaining parameters of `ResourceLoader::load_threaded_request()`.
+///
+/// With `use_sub_threads`, dependencies of the resource are loaded on further threads. `cache_mode` determines whether the
+/// resource cache is used and updated, as for [`ResourceLoader::load()`].
+pub fn load_threaded_ex<T>(
+    path: impl Into<GodotString>,
+    use_sub_threads: bool,
+    cache_mode: resource_loader::CacheMode,
+) -> Result<ThreadedLoad<T>, LoadError>
+where
+    T: GodotClass + Inherits<Resource>,
+{
+    let path = path.into();
+    let code = ResourceLoader::singleton()
+        .load_threaded_request_ex(path.clone())
+        .type_hint(T::class_name().to_godot_string())
+        .use_sub_threads(use_sub_threads)
+        .cache_mode(cache_mode)
+        .done();
 
-        write!(f, "{ty} {{ id: {id}, class: {class} }}")
-    } else {
-        write!(f, "{ty} {{ freed obj }}")
+    if code != global::Error::OK {
+        return Err(LoadError::new(
+            &path.to_string(),
+            LoadErrorKind::Request(code),
+        ));
     }
-}
-
-pub(crate) fn display_string<T: GodotClass>(
-    ptr: &Gd<T>,
-    f: &mut std::fmt::Formatter<'_>,
-) -> std::fmt::Result {
-    let string: GodotString = ptr.as_object(Object::to_string);
 
-    <GodotString as std::fmt::Display>::fmt(&string, f)
+    Ok(ThreadedLoad {
+        path,
+        _marker: PhantomData,
+    })
 }
 
-// ----------------------------------------------------------------------------------------------------------------------------------------------
-// Implementation of this file
+/// Resource being loaded on a background thread, started by [`load_threaded()`].
+///
+/// Awaiting the handle yields the resource once loading has finished; the check happens once per frame while tasks are
+/// [driven][crate::task::drive_each_frame], otherwise in every [`task::poll()`][crate::task::poll].
+///
+/// Godot keeps the loaded resource until it is retrieved, with [`get()`][Self::get] or by awaiting. Dropping the handle does
+/// not cancel the load.
+#[must_use = "the loaded resource is kept by Godot until retrieved"]
+pub struct ThreadedLoad<T> {
+    path: GodotString,
+    _marker: PhantomData<T>,
+}
 
-// Separate function, to avoid constructing string twice
-// Note that more optimizations than that likely make no sense, as loading is quite expensive
-fn load_impl<T>(path: &GodotString) -> Option<Gd<T>>
+impl<T> ThreadedLoad<T>
 where
     T: GodotClass + Inherits<Resource>,
 {
-    ResourceLoader::singleton()
-        .load_ex(path.clone())
-        .type_hint(T::class_name().to_godot_string())
-        .done() // TODO unclone
-        .and_then(|res| res.try_cast::<T>())
+    /// Path of the resource.
+    pub fn path(&self) -> &GodotString {
+        &self.path
+    }
+
+    /// Fraction of the resource loaded so far, from 0.0 to 1.0.
+    pub fn progress(&self) -> f64 {
+        self.status().1
+    }
+
+    /// Returns `true` once loading has succeeded or failed, i.e. when [`get()`][Self::get] no longer blocks.
+    pub fn is_finished(&self) -> bool {
+        self.status().0 != resource_loader::ThreadLoadStatus::THREAD_LOAD_IN_PROGRESS
+    }
+
+    /// Returns the loaded resource, blocking until loading has finished.
+    pub fn get(self) -> Result<Gd<T>, LoadError> {
+        self.retrieve()
+    }
+
+    /// Queries Godot for the status and progress of the load.
+    fn status(&self) -> (resource_loader::ThreadLoadStatus, f64) {
+        // Godot writes the progress into the array passed as second argument.
+        let progress = Array::<Variant>::new();
+        let status = ResourceLoader::singleton()
+            .load_threaded_get_status_ex(self.path.clone())
+            .progress(progress.share())
+            .done();
+
+        let progress = progress
+            .first()
+            .and_then(|value| value.try_to::<f64>().ok())
+            .unwrap_or(0.0);
+
+        (status, progress)
+    }
+
+    fn retrieve(&self) -> Result<Gd<T>, LoadError> {
+        let path = self.path.to_string();
+        let status = self.status().0;
+
+        if status == resource_loader::ThreadLoadStatus::THREAD_LOAD_INVALID_RESOURCE {
+            return Err(LoadError::new(&path, LoadErrorKind::NotRequested));
+        }
+
+        // Blocks while still in progress, and returns null if loading failed.
+        let resource = ResourceLoader::singleton()
+            .load_threaded_get(self.path.clone())
+            .ok_or_else(|| LoadError::new(&path, LoadErrorKind::Failed))?;
+
+        let actual = resource.get_class().to_string();
+        resource
+            .try_cast::<T>()
+            .ok_or_else(|| LoadError::new(&path, LoadErrorKind::WrongClass { actual }))
+    }
+}
+
+impl<T> Future for ThreadedLoad<T>
+where
+    T: GodotClass + Inherits<Resource>,
+{
+    type Output = Result<Gd<T>, LoadError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.is_finished() {
+            Poll::Ready(self.retrieve())
+        } else {
+            task::wake_next_frame(cx.waker());
+            Poll::Pending
+        }
+    }
+}
+
+impl<T: GodotClass> std::fmt::Debug for ThreadedLoad<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ThreadedLoad<{}>({})", T::class_name(), self.path)
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Utilities for crate
+
+pub(crate) fn debug_string<T: GodotClass>(
+    ptr: &Gd<T>,
+    f: &mut std::fmt::Formatter<'_>,
+    ty: &str,
+) -> std::fmt::Result {
+    if let Some(id) = ptr.instance_id_or_none() {
+        let class: GodotString = ptr.as_object(|obj| Object::get_class(obj));
+
+        write!(f, "{ty} {{ id: {id}, class: {class} }}")
+    } else {
+        write!(f, "{ty} {{ freed obj }}")
+    }
+}
+
+pub(crate) fn display_string<T: GodotClass>(
+    ptr: &Gd<T>,
+    f: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result {
+    let string: GodotString = ptr.as_object(Object::to_string);
+
+    <GodotString as std::fmt::Display>::fmt(&string, f)
+}
+
+/// Formats a bitfield as `Name { FLAG_A | FLAG_B }`, used by the generated `Debug` impls.
+///
+/// If an enumerator matches the value exactly (e.g. for `0` or predefined combinations), only that one is printed.
+/// Otherwise, all contained single-bit flags are listed; bits without a name are appended in hex.
+pub(crate) fn debug_bitfield(
+    f: &mut std::fmt::Formatter<'_>,
+    ty: &str,
+    ord: i32,
+    flags: &[(&str, i32)],
+) -> std::fmt::Result {
+    if let Some((name, _)) = flags.iter().find(|(_, value)| *value == ord) {
+        return write!(f, "{ty} {{ {name} }}");
+    }
+
+    let mut remaining = ord;
+    let mut set_flags = Vec::new();
+    for &(name, value) in flags {
+        if value.count_ones() == 1 && remaining & value != 0 {
+            set_flags.push(name.to_string());
+            remaining &= !value;
+        }
+    }
+
+    if remaining != 0 || set_flags.is_empty() {
+        set_flags.push(format!("{remaining:#x}"));
+    }
+
+    write!(f, "{ty} {{ {} }}", set_flags.join(" | "))
+}
+
+/// Extension trait to wire editor UI entries of an `EditorPlugin` to Rust code.
+///
+/// Menu entries and commands either run a closure, or call a `#[func]` of the plugin itself, identified by name. Docks are
+/// plain `Control` nodes built in Rust. This avoids building `Callable`s by hand, as well as scenes and GDScript glue for the
+/// UI. Remove entries and docks again in `exit_tree()`.
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// use godot::engine::{editor_plugin::DockSlot, EditorPlugin, EditorPluginExt, EditorPluginVirtual, Label};
+///
+/// #[derive(GodotClass)]
+/// #[class(init, tool, base=EditorPlugin)]
+/// struct MyPlugin {
+///     dock: Option<Gd<Label>>,
+///
+///     #[base]
+///     base: Base<EditorPlugin>,
+/// }
+///
+/// #[godot_api]
+/// impl MyPlugin {
+///     #[func]
+///     fn regenerate(&mut self) { /* ... */ }
+/// }
+///
+/// #[godot_api]
+/// impl EditorPluginVirtual for MyPlugin {
+///     fn enter_tree(&mut self) {
+///         self.base.add_tool_menu_method("Regenerate level", "regenerate");
+///         self.base.add_command_palette_fn("Print hello", "my_plugin/hello", || godot_print!("Hello"));
+///
+///         let dock = Label::new_alloc();
+///         self.base.add_dock(DockSlot::DOCK_SLOT_RIGHT_UL, "Stats", dock.share());
+///         self.dock = Some(dock);
+///     }
+///
+///     fn exit_tree(&mut self) {
+///         self.base.remove_tool_menu_item("Regenerate level".into());
+///         self.base.remove_command_palette_method("my_plugin/hello");
+///
+///         if let Some(dock) = self.dock.take() {
+///             self.base.remove_dock(dock);
+///         }
+///     }
+/// }
+/// ```
+pub trait EditorPluginExt {
+    /// Adds an entry to the "Project > Tools" menu, which calls `method` on this plugin.
+    fn add_tool_menu_method(&mut self, name: impl Into<GodotString>, method: impl Into<StringName>);
+
+    /// Adds an entry to the "Project > Tools" menu, which calls `f`.
+    ///
+    /// The closure is kept until the entry is removed with [`remove_tool_menu_fn()`][Self::remove_tool_menu_fn].
+    fn add_tool_menu_fn<F>(&mut self, name: impl Into<GodotString>, f: F)
+    where
+        F: FnMut() + 'static;
+
+    /// Removes an entry added with [`add_tool_menu_fn()`][Self::add_tool_menu_fn], and drops its closure.
+    fn remove_tool_menu_fn(&mut self, name: impl Into<GodotString>);
+
+    /// Adds a command to the editor's command palette, which calls `method` on this plugin.
+    ///
+    /// `key_name` uniquely identifies the command, conventionally as `"plugin_name/command_name"`.
+    fn add_command_palette_method(
+        &mut self,
+        command_name: impl Into<GodotString>,
+        key_name: impl Into<GodotString>,
+        method: impl Into<StringName>,
+    );
+
+    /// Adds a command to the editor's command palette, which calls `f`.
+    ///
+    /// `key_name` uniquely identifies the command, conventionally as `"plugin_name/command_name"`. The closure is kept until
+    /// the command is removed with [`remove_command_palette_method()`][Self::remove_command_palette_method].
+    fn add_command_palette_fn<F>(
+        &mut self,
+        command_name: impl Into<GodotString>,
+        key_name: impl Into<GodotString>,
+        f: F,
+    ) where
+        F: FnMut() + 'static;
+
+    /// Removes a command added with [`add_command_palette_method()`][Self::add_command_palette_method] or
+    /// [`add_command_palette_fn()`][Self::add_command_palette_fn], dropping its closure if any.
+    fn remove_command_palette_method(&mut self, key_name: impl Into<GodotString>);
+
+    /// Adds `control` as dock to `slot`, with `title` as tab name.
+    fn add_dock<T>(
+        &mut self,
+        slot: editor_plugin::DockSlot,
+        title: impl Into<GodotString>,
+        control: Gd<T>,
+    ) where
+        T: GodotClass + Inherits<Control>;
+
+    /// Removes a dock added with [`add_dock()`][Self::add_dock], and frees its control.
+    fn remove_dock<T>(&mut self, control: Gd<T>)
+    where
+        T: GodotClass + Inherits<Control>;
+}
+
+impl<U> EditorPluginExt for Gd<U>
+where
+    U: GodotClass<Declarer = EngineDomain> + Inherits<EditorPlugin>,
+{
+    fn add_tool_menu_method(
+        &mut self,
+        name: impl Into<GodotString>,
+        method: impl Into<StringName>,
+    ) {
+        let callable = self.callable(method);
+        self.share()
+            .upcast::<EditorPlugin>()
+            .add_tool_menu_item(name.into(), callable);
+    }
+
+    fn add_tool_menu_fn<F>(&mut self, name: impl Into<GodotString>, f: F)
+    where
+        F: FnMut() + 'static,
+    {
+        let name = name.into();
+        let callable = crate::closure::callable(tool_menu_key(&name), f);
+        self.share()
+            .upcast::<EditorPlugin>()
+            .add_tool_menu_item(name, callable);
+    }
+
+    fn remove_tool_menu_fn(&mut self, name: impl Into<GodotString>) {
+        let name = name.into();
+        self.share()
+            .upcast::<EditorPlugin>()
+            .remove_tool_menu_item(name.clone());
+        crate::closure::remove(&tool_menu_key(&name));
+    }
+
+    fn add_command_palette_method(
+        &mut self,
+        command_name: impl Into<GodotString>,
+        key_name: impl Into<GodotString>,
+        method: impl Into<StringName>,
+    ) {
+        let callable = self.callable(method);
+        command_palette(self).add_command(command_name.into(), key_name.into(), callable);
+    }
+
+    fn add_command_palette_fn<F>(
+        &mut self,
+        command_name: impl Into<GodotString>,
+        key_name: impl Into<GodotString>,
+        f: F,
+    ) where
+        F: FnMut() + 'static,
+    {
+        let key_name = key_name.into();
+        let callable = crate::closure::callable(command_palette_key(&key_name), f);
+        command_palette(self).add_command(command_name.into(), key_name, callable);
+    }
+
+    fn remove_command_palette_method(&mut self, key_name: impl Into<GodotString>) {
+        let key_name = key_name.into();
+        command_palette(self).remove_command(key_name.clone());
+        crate::closure::remove(&command_palette_key(&key_name));
+    }
+
+    fn add_dock<T>(
+        &mut self,
+        slot: editor_plugin::DockSlot,
+        title: impl Into<GodotString>,
+        control: Gd<T>,
+    ) where
+        T: GodotClass + Inherits<Control>,
+    {
+        // Docks are tabs, named after their control.
+        let mut control = control.upcast::<Control>();
+        control.set_name(title.into());
+
+        self.share()
+            .upcast::<EditorPlugin>()
+            .add_control_to_dock(slot, control);
+    }
+
+    fn remove_dock<T>(&mut self, control: Gd<T>)
+    where
+        T: GodotClass + Inherits<Control>,
+    {
+        let mut control = control.upcast::<Control>();
+        self.share()
+            .upcast::<EditorPlugin>()
+            .remove_control_from_docks(control.share());
+        control.queue_free();
+    }
+}
+
+/// Extension trait to record undoable editor actions with a builder.
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// use godot::engine::{EditorUndoRedoManager, EditorUndoRedoManagerExt};
+///
+/// fn rename(undo_redo: &mut Gd<EditorUndoRedoManager>, node: &Gd<Node>, new_name: &str) {
+///     undo_redo
+///         .action("Rename node")
+///         .do_property(node, "name", new_name)
+///         .undo_property(node, "name", node.get_name())
+///         .commit();
+/// }
+/// ```
+pub trait EditorUndoRedoManagerExt {
+    /// Starts an action named `name`, which appears in the editor's history. Nothing is recorded before
+    /// [`commit()`][UndoRedoAction::commit] is called.
+    fn action(&mut self, name: impl Into<GodotString>) -> UndoRedoAction<'_>;
+}
+
+impl EditorUndoRedoManagerExt for EditorUndoRedoManager {
+    fn action(&mut self, name: impl Into<GodotString>) -> UndoRedoAction<'_> {
+        UndoRedoAction::new(UndoRedoTarget::Editor(self), name.into())
+    }
+}
+
+/// Extension trait to record undoable actions with a builder, for `UndoRedo` instances outside the editor.
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// # use std::{cell::Cell, rc::Rc};
+/// use godot::engine::{UndoRedo, UndoRedoExt};
+///
+/// let mut undo_redo = UndoRedo::new_alloc();
+/// let count = Rc::new(Cell::new(0));
+///
+/// let (inc, dec) = (count.clone(), count.clone());
+/// undo_redo
+///     .action("Increment")
+///     .do_fn(move || inc.set(inc.get() + 1))
+///     .undo_fn(move || dec.set(dec.get() - 1))
+///     .commit();
+///
+/// undo_redo.undo();
+/// assert_eq!(count.get(), 0);
+/// ```
+pub trait UndoRedoExt {
+    /// Starts an action named `name`. Nothing is recorded before [`commit()`][UndoRedoAction::commit] is called.
+    fn action(&mut self, name: impl Into<GodotString>) -> UndoRedoAction<'_>;
+}
+
+impl UndoRedoExt for UndoRedo {
+    fn action(&mut self, name: impl Into<GodotString>) -> UndoRedoAction<'_> {
+        UndoRedoAction::new(UndoRedoTarget::Plain(self), name.into())
+    }
+}
+
+/// Builder for an undoable action, created by [`EditorUndoRedoManagerExt::action()`] or [`UndoRedoExt::action()`].
+///
+/// Operations are collected in the builder, and only handed to Godot by [`commit()`][Self::commit]. Dropping the builder discards
+/// the action.
+#[must_use]
+pub struct UndoRedoAction<'a> {
+    target: UndoRedoTarget<'a>,
+    name: GodotString,
+    merge_mode: undo_redo::MergeMode,
+    operations: Vec<UndoRedoOperation>,
+}
+
+enum UndoRedoTarget<'a> {
+    Editor(&'a mut EditorUndoRedoManager),
+    Plain(&'a mut UndoRedo),
+}
+
+enum UndoRedoOperation {
+    DoMethod(Gd<Object>, StringName, Vec<Variant>),
+    UndoMethod(Gd<Object>, StringName, Vec<Variant>),
+    DoProperty(Gd<Object>, StringName, Variant),
+    UndoProperty(Gd<Object>, StringName, Variant),
+    DoFn(Box<dyn FnMut()>),
+    UndoFn(Box<dyn FnMut()>),
+}
+
+impl<'a> UndoRedoAction<'a> {
+    fn new(target: UndoRedoTarget<'a>, name: GodotString) -> Self {
+        Self {
+            target,
+            name,
+            merge_mode: undo_redo::MergeMode::MERGE_DISABLE,
+            operations: Vec::new(),
+        }
+    }
+
+    /// Merges consecutive actions with the same name, e.g. for continuous edits like dragging a slider.
+    pub fn merge_mode(mut self, merge_mode: undo_redo::MergeMode) -> Self {
+        self.merge_mode = merge_mode;
+        self
+    }
+
+    /// Calls `method` on `object` with `args` when the action is done or redone.
+    pub fn do_method<T>(
+        mut self,
+        object: &Gd<T>,
+        method: impl Into<StringName>,
+        args: &[Variant],
+    ) -> Self
+    where
+        T: GodotClass + Inherits<Object>,
+    {
+        let object = object.share().upcast();
+        self.operations.push(UndoRedoOperation::DoMethod(
+            object,
+            method.into(),
+            args.to_vec(),
+        ));
+        self
+    }
+
+    /// Calls `method` on `object` with `args` when the action is undone.
+    pub fn undo_method<T>(
+        mut self,
+        object: &Gd<T>,
+        method: impl Into<StringName>,
+        args: &[Variant],
+    ) -> Self
+    where
+        T: GodotClass + Inherits<Object>,
+    {
+        let object = object.share().upcast();
+        self.operations.push(UndoRedoOperation::UndoMethod(
+            object,
+            method.into(),
+            args.to_vec(),
+        ));
+        self
+    }
+
+    /// Calls `f` when the action is done or redone.
+    ///
+    /// The closure is dropped when the action leaves the history, e.g. when the history is cleared, or when an undone action
+    /// is replaced by a new one.
+    pub fn do_fn<F>(mut self, f: F) -> Self
+    where
+        F: FnMut() + 'static,
+    {
+        self.operations.push(UndoRedoOperation::DoFn(Box::new(f)));
+        self
+    }
+
+    /// Calls `f` when the action is undone.
+    ///
+    /// Like for [`do_fn()`][Self::do_fn], the closure is dropped when the action leaves the history.
+    pub fn undo_fn<F>(mut self, f: F) -> Self
+    where
+        F: FnMut() + 'static,
+    {
+        self.operations.push(UndoRedoOperation::UndoFn(Box::new(f)));
+        self
+    }
+
+    /// Sets `property` of `object` to `value` when the action is done or redone.
+    pub fn do_property<T>(
+        mut self,
+        object: &Gd<T>,
+        property: impl Into<StringName>,
+        value: impl ToVariant,
+    ) -> Self
+    where
+        T: GodotClass + Inherits<Object>,
+    {
+        let object = object.share().upcast();
+        self.operations.push(UndoRedoOperation::DoProperty(
+            object,
+            property.into(),
+            value.to_variant(),
+        ));
+        self
+    }
+
+    /// Sets `property` of `object` to `value` when the action is undone.
+    ///
+    /// Typically `value` is the current value of the property, read before committing.
+    pub fn undo_property<T>(
+        mut self,
+        object: &Gd<T>,
+        property: impl Into<StringName>,
+        value: impl ToVariant,
+    ) -> Self
+    where
+        T: GodotClass + Inherits<Object>,
+    {
+        let object = object.share().upcast();
+        self.operations.push(UndoRedoOperation::UndoProperty(
+            object,
+            property.into(),
+            value.to_variant(),
+        ));
+        self
+    }
+
+    /// Records the action in the history and executes its "do" operations.
+    pub fn commit(self) {
+        let Self {
+            target,
+            name,
+            merge_mode,
+            operations,
+        } = self;
+
+        match target {
+            UndoRedoTarget::Editor(manager) => {
+                commit_editor_action(manager, name, merge_mode, operations)
+            }
+            UndoRedoTarget::Plain(undo_redo) => {
+                commit_plain_action(undo_redo, name, merge_mode, operations)
+            }
+        }
+    }
+}
+
+fn commit_editor_action(
+    manager: &mut EditorUndoRedoManager,
+    name: GodotString,
+    merge_mode: undo_redo::MergeMode,
+    operations: Vec<UndoRedoOperation>,
+) {
+    // The editor picks the history (scene or global) from the context object. Closures are called on internal closure owners,
+    // which belong to the global history, so let the objects of the other operations decide if there are any.
+    let context = operations
+        .iter()
+        .find_map(|operation| match operation {
+            UndoRedoOperation::DoMethod(object, ..)
+            | UndoRedoOperation::UndoMethod(object, ..)
+            | UndoRedoOperation::DoProperty(object, ..)
+            | UndoRedoOperation::UndoProperty(object, ..) => Some(object.share()),
+            UndoRedoOperation::DoFn(_) | UndoRedoOperation::UndoFn(_) => None,
+        })
+        .unwrap_or_else(crate::dispatch::object);
+
+    manager
+        .create_action_ex(name, context)
+        .merge_mode(merge_mode)
+        .done();
+
+    // Closure owners are kept alive by the history from the time their method is added, see closure::owned_callable().
+    let mut closure_owners = Vec::new();
+
+    for operation in operations {
+        match operation {
+            UndoRedoOperation::DoMethod(object, method, args) => {
+                manager.add_do_method(object, method, &args);
+            }
+            UndoRedoOperation::UndoMethod(object, method, args) => {
+                manager.add_undo_method(object, method, &args);
+            }
+            UndoRedoOperation::DoProperty(object, property, value) => {
+                manager.add_do_property(object, property, value);
+            }
+            UndoRedoOperation::UndoProperty(object, property, value) => {
+                manager.add_undo_property(object, property, value);
+            }
+            UndoRedoOperation::DoFn(f) => {
+                let key = undo_redo_closure_key();
+                let owner = closure_owner(key.clone(), f);
+                manager.add_do_method(
+                    owner.share(),
+                    crate::closure::METHOD_NAME.into(),
+                    &[key.to_variant()],
+                );
+                closure_owners.push(owner);
+            }
+            UndoRedoOperation::UndoFn(f) => {
+                let key = undo_redo_closure_key();
+                let owner = closure_owner(key.clone(), f);
+                manager.add_undo_method(
+                    owner.share(),
+                    crate::closure::METHOD_NAME.into(),
+                    &[key.to_variant()],
+                );
+                closure_owners.push(owner);
+            }
+        }
+    }
+
+    manager.commit_action();
+}
+
+/// Stores `f` under `key`, for a method call of the undo history on the returned owner.
+fn closure_owner(key: String, f: Box<dyn FnMut()>) -> Gd<RefCounted> {
+    crate::closure::insert(key.clone(), f);
+    crate::dispatch::closure_owner(key)
+}
+
+fn commit_plain_action(
+    undo_redo: &mut UndoRedo,
+    name: GodotString,
+    merge_mode: undo_redo::MergeMode,
+    operations: Vec<UndoRedoOperation>,
+) {
+    undo_redo
+        .create_action_ex(name)
+        .merge_mode(merge_mode)
+        .done();
+
+    // See commit_editor_action().
+    let mut closure_owners = Vec::new();
+
+    for operation in operations {
+        match operation {
+            UndoRedoOperation::DoMethod(object, method, args) => {
+                undo_redo.add_do_method(method_callable(object, method, args));
+            }
+            UndoRedoOperation::UndoMethod(object, method, args) => {
+                undo_redo.add_undo_method(method_callable(object, method, args));
+            }
+            UndoRedoOperation::DoProperty(object, property, value) => {
+                undo_redo.add_do_property(object, property, value);
+            }
+            UndoRedoOperation::UndoProperty(object, property, value) => {
+                undo_redo.add_undo_property(object, property, value);
+            }
+            UndoRedoOperation::DoFn(f) => {
+                let (callable, owner) = crate::closure::owned_callable(undo_redo_closure_key(), f);
+                undo_redo.add_do_method(callable);
+                closure_owners.push(owner);
+            }
+            UndoRedoOperation::UndoFn(f) => {
+                let (callable, owner) = crate::closure::owned_callable(undo_redo_closure_key(), f);
+                undo_redo.add_undo_method(callable);
+                closure_owners.push(owner);
+            }
+        }
+    }
+
+    undo_redo.commit_action();
+}
+
+fn method_callable(object: Gd<Object>, method: StringName, args: Vec<Variant>) -> Callable {
+    let callable = Callable::from_object_method(object, method);
+    if args.is_empty() {
+        callable
+    } else {
+        callable.as_inner().bindv(Array::from(args.as_slice()))
+    }
+}
+
+/// Unique key for the closure of an undo/redo operation.
+fn undo_redo_closure_key() -> String {
+    static NEXT_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+    let id = NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    format!("undo_redo/{id}")
+}
+
+/// Extension trait for typed avoidance callbacks and path queries on `NavigationServer2D`.
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// use godot::engine::{NavigationServer2D, NavigationServer2DExt};
+///
+/// # fn steer(agent: Rid, mut body: Gd<Node2D>) {
+/// NavigationServer2D::singleton().agent_set_avoidance_fn(agent, move |safe_velocity| {
+///     let position = body.get_position();
+///     body.set_position(position + safe_velocity / 60.0);
+/// });
+/// # }
+/// ```
+#[cfg(since_api = "4.1")]
+pub trait NavigationServer2DExt {
+    /// Makes `f` the avoidance callback of `agent`, replacing a previous one.
+    ///
+    /// After each avoidance step, Godot calls `f` with the safe velocity. The closure is kept until
+    /// [`agent_clear_avoidance_fn()`][Self::agent_clear_avoidance_fn] is called, so call it before freeing the agent.
+    fn agent_set_avoidance_fn<F>(&mut self, agent: Rid, f: F)
+    where
+        F: FnMut(Vector2) + 'static;
+
+    /// Removes the avoidance callback of `agent`, if any, and drops its closure.
+    fn agent_clear_avoidance_fn(&mut self, agent: Rid);
+
+    /// Makes `method` on `receiver` the avoidance callback of `agent`.
+    ///
+    /// After each avoidance step, Godot calls the method with the safe velocity as its only argument. Godot 4.1 passes it as
+    /// `Vector3`, with the 2D velocity in `x` and `z`, so declare the parameter as `Variant` if the code must also run on later
+    /// versions. [`agent_set_avoidance_fn()`][Self::agent_set_avoidance_fn] does this conversion for you.
+    fn agent_set_avoidance_receiver<T>(
+        &mut self,
+        agent: Rid,
+        receiver: Gd<T>,
+        method: impl Into<StringName>,
+    ) where
+        T: GodotClass;
+
+    /// Removes the avoidance callback of `agent`, if any.
+    fn agent_clear_avoidance_receiver(&mut self, agent: Rid);
+
+    /// Returns the path from `from` to `to` on `map`, using regions of navigation layer 1.
+    ///
+    /// The path is empty if there is none, e.g. because the map has not been synchronized yet.
+    fn map_find_path(&self, map: Rid, from: Vector2, to: Vector2) -> Vec<Vector2>;
+
+    /// Returns the region or link of `map` closest to `point`, or `None` if the map has no regions.
+    fn map_find_owner(&self, map: Rid, point: Vector2) -> Option<Rid>;
+}
+
+#[cfg(since_api = "4.1")]
+impl NavigationServer2DExt for NavigationServer2D {
+    fn agent_set_avoidance_fn<F>(&mut self, agent: Rid, mut f: F)
+    where
+        F: FnMut(Vector2) + 'static,
+    {
+        let callback =
+            crate::closure::callable_with_arg(avoidance_key("2d", agent), move |velocity| {
+                // Godot 4.1 reports 2D velocities as Vector3 (x, 0, y).
+                let velocity = match velocity.try_to::<Vector3>() {
+                    Ok(velocity) => Vector2::new(velocity.x, velocity.z),
+                    Err(_) => velocity.to::<Vector2>(),
+                };
+                f(velocity);
+            });
+        self.agent_set_avoidance_callback(agent, callback);
+    }
+
+    fn agent_clear_avoidance_fn(&mut self, agent: Rid) {
+        self.agent_set_avoidance_callback(agent, Callable::invalid());
+        crate::closure::remove(&avoidance_key("2d", agent));
+    }
+
+    fn agent_set_avoidance_receiver<T>(
+        &mut self,
+        agent: Rid,
+        receiver: Gd<T>,
+        method: impl Into<StringName>,
+    ) where
+        T: GodotClass,
+    {
+        let callback = Callable::from_object_method(receiver, method);
+        self.agent_set_avoidance_callback(agent, callback);
+    }
+
+    fn agent_clear_avoidance_receiver(&mut self, agent: Rid) {
+        self.agent_set_avoidance_callback(agent, Callable::invalid());
+    }
+
+    fn map_find_path(&self, map: Rid, from: Vector2, to: Vector2) -> Vec<Vector2> {
+        self.map_get_path(map, from, to, true).to_vec()
+    }
+
+    fn map_find_owner(&self, map: Rid, point: Vector2) -> Option<Rid> {
+        let owner = self.map_get_closest_point_owner(map, point);
+        owner.is_valid().then_some(owner)
+    }
+}
+
+/// Extension trait for typed avoidance callbacks and path queries on `NavigationServer3D`.
+#[cfg(since_api = "4.1")]
+pub trait NavigationServer3DExt {
+    /// Makes `f` the avoidance callback of `agent`, replacing a previous one.
+    ///
+    /// After each avoidance step, Godot calls `f` with the safe velocity. The closure is kept until
+    /// [`agent_clear_avoidance_fn()`][Self::agent_clear_avoidance_fn] is called, so call it before freeing the agent.
+    fn agent_set_avoidance_fn<F>(&mut self, agent: Rid, f: F)
+    where
+        F: FnMut(Vector3) + 'static;
+
+    /// Removes the avoidance callback of `agent`, if any, and drops its closure.
+    fn agent_clear_avoidance_fn(&mut self, agent: Rid);
+
+    /// Makes `method` on `receiver` the avoidance callback of `agent`.
+    ///
+    /// After each avoidance step, Godot calls the method with the safe velocity (`Vector3`) as its only argument.
+    fn agent_set_avoidance_receiver<T>(
+        &mut self,
+        agent: Rid,
+        receiver: Gd<T>,
+        method: impl Into<StringName>,
+    ) where
+        T: GodotClass;
+
+    /// Removes the avoidance callback of `agent`, if any.
+    fn agent_clear_avoidance_receiver(&mut self, agent: Rid);
+
+    /// Returns the path from `from` to `to` on `map`, using regions of navigation layer 1.
+    ///
+    /// The path is empty if there is none, e.g. because the map has not been synchronized yet.
+    fn map_find_path(&self, map: Rid, from: Vector3, to: Vector3) -> Vec<Vector3>;
+
+    /// Returns the region or link of `map` closest to `point`, or `None` if the map has no regions.
+    fn map_find_owner(&self, map: Rid, point: Vector3) -> Option<Rid>;
+}
+
+#[cfg(since_api = "4.1")]
+impl NavigationServer3DExt for NavigationServer3D {
+    fn agent_set_avoidance_fn<F>(&mut self, agent: Rid, mut f: F)
+    where
+        F: FnMut(Vector3) + 'static,
+    {
+        let callback =
+            crate::closure::callable_with_arg(avoidance_key("3d", agent), move |velocity| {
+                f(velocity.to::<Vector3>());
+            });
+        self.agent_set_avoidance_callback(agent, callback);
+    }
+
+    fn agent_clear_avoidance_fn(&mut self, agent: Rid) {
+        self.agent_set_avoidance_callback(agent, Callable::invalid());
+        crate::closure::remove(&avoidance_key("3d", agent));
+    }
+
+    fn agent_set_avoidance_receiver<T>(
+        &mut self,
+        agent: Rid,
+        receiver: Gd<T>,
+        method: impl Into<StringName>,
+    ) where
+        T: GodotClass,
+    {
+        let callback = Callable::from_object_method(receiver, method);
+        self.agent_set_avoidance_callback(agent, callback);
+    }
+
+    fn agent_clear_avoidance_receiver(&mut self, agent: Rid) {
+        self.agent_set_avoidance_callback(agent, Callable::invalid());
+    }
+
+    fn map_find_path(&self, map: Rid, from: Vector3, to: Vector3) -> Vec<Vector3> {
+        self.map_get_path(map, from, to, true).to_vec()
+    }
+
+    fn map_find_owner(&self, map: Rid, point: Vector3) -> Option<Rid> {
+        let owner = self.map_get_closest_point_owner(map, point);
+        owner.is_valid().then_some(owner)
+    }
+}
+
+/// Project setting declared by the extension, with a statically known type.
+///
+/// Declaring a setting makes it show up in the Project Settings dialog, where it can be edited like any built-in setting.
+/// Register settings during [`ExtensionLibrary::on_level_init()`][crate::init::ExtensionLibrary::on_level_init], at
+/// `InitLevel::Scene` or later, so that they are known before the project is loaded.
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// use godot::engine::{global::PropertyHint, ProjectSetting};
+///
+/// fn max_speed() -> ProjectSetting<f64> {
+///     ProjectSetting::new("my_extension/movement/max_speed", 12.5)
+///         .with_hint(PropertyHint::PROPERTY_HINT_RANGE, "0,100,0.5")
+///         .basic()
+/// }
+///
+/// // At init:
+/// max_speed().register();
+///
+/// // Later:
+/// let speed: f64 = max_speed().get();
+/// ```
+#[derive(Clone, Debug)]
+pub struct ProjectSetting<T> {
+    name: GodotString,
+    default: T,
+    hint: global::PropertyHint,
+    hint_string: GodotString,
+    is_basic: bool,
+}
+
+impl<T> ProjectSetting<T>
+where
+    T: VariantMetadata + ToVariant + FromVariant,
+{
+    /// Declares a setting at path `name` (e.g. `"my_extension/category/setting"`) with the given default value.
+    pub fn new(name: impl Into<GodotString>, default: T) -> Self {
+        Self {
+            name: name.into(),
+            default,
+            hint: global::PropertyHint::PROPERTY_HINT_NONE,
+            hint_string: GodotString::new(),
+            is_basic: false,
+        }
+    }
+
+    /// Adds an editor hint, e.g. a range or an enumeration of allowed values.
+    pub fn with_hint(
+        mut self,
+        hint: global::PropertyHint,
+        hint_string: impl Into<GodotString>,
+    ) -> Self {
+        self.hint = hint;
+        self.hint_string = hint_string.into();
+        self
+    }
+
+    /// Shows the setting also when "Advanced Settings" is turned off.
+    pub fn basic(mut self) -> Self {
+        self.is_basic = true;
+        self
+    }
+
+    /// Path of the setting.
+    pub fn name(&self) -> &GodotString {
+        &self.name
+    }
+
+    /// Registers the setting with `ProjectSettings`.
+    ///
+    /// A value already stored in `project.godot` is kept. The default is used for the "revert" button in the editor, and
+    /// settings which equal their default are not written to `project.godot`.
+    pub fn register(&self) {
+        let mut settings = ProjectSettings::singleton();
+        let default = self.default.to_variant();
+
+        if !settings.has_setting(self.name.clone()) {
+            settings.set_setting(self.name.clone(), default.clone());
+        }
+
+        settings.set_initial_value(self.name.clone(), default);
+        settings.set_as_basic(self.name.clone(), self.is_basic);
+        settings.add_property_info(dict! {
+            "name": self.name.clone(),
+            "type": T::variant_type() as i32,
+            "hint": self.hint.ord(),
+            "hint_string": self.hint_string.clone(),
+        });
+    }
+
+    /// Current value of the setting, panicking if it has a different type.
+    ///
+    /// # Panics
+    /// If the stored value cannot be converted to `T`.
+    pub fn get(&self) -> T {
+        self.try_get().unwrap_or_else(|| {
+            panic!(
+                "project setting `{name}` does not have type {ty:?}",
+                name = self.name,
+                ty = T::variant_type()
+            )
+        })
+    }
+
+    /// Current value of the setting (fallible).
+    ///
+    /// Returns the default value if the setting is absent, and `None` if it cannot be converted to `T`.
+    pub fn try_get(&self) -> Option<T> {
+        let settings = ProjectSettings::singleton();
+        if !settings.has_setting(self.name.clone()) {
+            return T::try_from_variant(&self.default.to_variant()).ok();
+        }
+
+        T::try_from_variant(&settings.get_setting(self.name.clone())).ok()
+    }
+
+    /// Changes the value of the setting for the running project.
+    ///
+    /// Like other changes to `ProjectSettings`, this is only persisted by an explicit `ProjectSettings::save()`.
+    pub fn set(&self, value: T) {
+        ProjectSettings::singleton().set_setting(self.name.clone(), value.to_variant());
+    }
+}
+
+/// Extension trait returning `std::time` types from `Time`.
+pub trait TimeExt {
+    /// Time elapsed since the engine started, with microsecond precision.
+    ///
+    /// Monotonic and unaffected by `Engine.time_scale`; differences of two values measure real time, like [`Instant`][std::time::Instant].
+    fn ticks(&self) -> Duration;
+
+    /// Current wall-clock time of the system, as reported by Godot.
+    ///
+    /// Returns `None` if the time cannot be represented as `SystemTime`, e.g. for a clock set before the platform's minimum.
+    fn system_time(&self) -> Option<SystemTime>;
+}
+
+impl TimeExt for Time {
+    fn ticks(&self) -> Duration {
+        Duration::from_micros(self.get_ticks_usec())
+    }
+
+    fn system_time(&self) -> Option<SystemTime> {
+        let secs = self.get_unix_time_from_system();
+
+        // Negative for clocks set before 1970.
+        if secs >= 0.0 {
+            let since_epoch = Duration::try_from_secs_f64(secs).ok()?;
+            SystemTime::UNIX_EPOCH.checked_add(since_epoch)
+        } else {
+            let before_epoch = Duration::try_from_secs_f64(-secs).ok()?;
+            SystemTime::UNIX_EPOCH.checked_sub(before_epoch)
+        }
+    }
+}
+
+/// Extension trait for frame counters and timing of `Engine`, with unsigned and `std::time` types.
+pub trait EngineExt {
+    /// Number of process frames since the engine started.
+    fn process_frame_count(&self) -> u64;
+
+    /// Number of physics frames since the engine started.
+    fn physics_frame_count(&self) -> u64;
+
+    /// Number of frames drawn since the engine started.
+    fn drawn_frame_count(&self) -> u64;
+
+    /// Duration of one physics step, i.e. the fixed `delta` of `_physics_process()` (without time scale).
+    ///
+    /// Returns `None` if `physics_ticks_per_second` is not positive.
+    fn physics_step(&self) -> Option<Duration>;
+}
+
+impl EngineExt for Engine {
+    fn process_frame_count(&self) -> u64 {
+        self.get_process_frames()
+    }
+
+    fn physics_frame_count(&self) -> u64 {
+        self.get_physics_frames()
+    }
+
+    fn drawn_frame_count(&self) -> u64 {
+        self.get_frames_drawn() as u64
+    }
+
+    fn physics_step(&self) -> Option<Duration> {
+        let ticks_per_second = self.get_physics_ticks_per_second();
+        if ticks_per_second <= 0 {
+            return None;
+        }
+
+        Duration::try_from_secs_f64(1.0 / ticks_per_second as f64).ok()
+    }
+}
+
+/// Extension trait for custom monitors of `Performance`, polling Rust closures.
+pub trait PerformanceExt {
+    /// ⚠️ Adds a custom monitor, whose value is obtained by calling `poll` whenever Godot requests it.
+    ///
+    /// Monitors appear in the editor's _Monitors_ tab while the project runs. An `id` of the form `"category/name"` puts the
+    /// monitor in its own category, otherwise it is listed under _Custom_.
+    ///
+    /// The closure is kept until the monitor is removed with [`remove_monitor()`][Self::remove_monitor], so it must not keep
+    /// objects alive that are freed before.
+    ///
+    /// ```no_run
+    /// # use godot::prelude::*;
+    /// use godot::engine::{Performance, PerformanceExt};
+    ///
+    /// #[derive(GodotClass)]
+    /// #[class(init, base=Node)]
+    /// struct JobSystem {
+    ///     queue: Vec<u32>,
+    /// }
+    ///
+    /// # fn publish(jobs: Gd<JobSystem>) {
+    /// Performance::singleton().add_monitor("jobs/queue_depth", move || {
+    ///     jobs.bind().queue.len() as f64
+    /// });
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// If a monitor with the same `id` already exists.
+    fn add_monitor<F>(&mut self, id: impl Into<StringName>, poll: F)
+    where
+        F: FnMut() -> f64 + 'static;
+
+    /// Removes a custom monitor added by [`add_monitor()`][Self::add_monitor], dropping its closure.
+    ///
+    /// Returns `false` if no such monitor exists.
+    fn remove_monitor(&mut self, id: impl Into<StringName>) -> bool;
+}
+
+impl PerformanceExt for Performance {
+    fn add_monitor<F>(&mut self, id: impl Into<StringName>, poll: F)
+    where
+        F: FnMut() -> f64 + 'static,
+    {
+        let id = id.into();
+        assert!(
+            !self.has_custom_monitor(id.clone()),
+            "custom monitor `{id}` already exists"
+        );
+
+        crate::monitor::insert(id.to_string(), Box::new(poll));
+
+        let callable = crate::dispatch::callable(crate::monitor::METHOD_NAME);
+        self.add_custom_monitor_ex(id.clone(), callable)
+            .arguments(Array::from(&[id.to_variant()]))
+            .done();
+    }
+
+    fn remove_monitor(&mut self, id: impl Into<StringName>) -> bool {
+        let id = id.into();
+        let had_closure = crate::monitor::remove(&id.to_string());
+
+        if had_closure && self.has_custom_monitor(id.clone()) {
+            self.remove_custom_monitor(id);
+        }
+        had_closure
+    }
+}
+
+/// Extension trait to run Rust closures on the engine's `WorkerThreadPool`.
+///
+/// Godot uses the pool itself, e.g. for resource loading and navigation. Submitting work there instead of spawning own threads
+/// keeps the number of busy threads in line with the CPU cores.
+///
+/// Every task must eventually be joined, so that Godot releases it.
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// use godot::engine::{WorkerThreadPool, WorkerThreadPoolExt};
+///
+/// # fn run() {
+/// let mut pool = WorkerThreadPool::singleton();
+/// let task = pool.add_rust_task(|| (0..1_000_000u64).sum::<u64>());
+///
+/// // ... other work on the main thread ...
+/// let sum = task.join().expect("task panicked");
+/// # }
+/// ```
+#[cfg(since_api = "4.1")]
+pub trait WorkerThreadPoolExt {
+    /// Runs `task` on a worker thread. Its result is returned by [`PoolTask::join()`].
+    fn add_rust_task<F, R>(&mut self, task: F) -> PoolTask<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static;
+
+    /// Runs `task` once for every index in `0..elements`, spread across worker threads.
+    fn add_rust_group_task<F>(&mut self, elements: u32, task: F) -> PoolGroupTask
+    where
+        F: Fn(u32) + Send + Sync + 'static;
+}
+
+#[cfg(since_api = "4.1")]
+impl WorkerThreadPoolExt for WorkerThreadPool {
+    fn add_rust_task<F, R>(&mut self, task: F) -> PoolTask<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let result = std::sync::Arc::new(Mutex::new(None));
+        let sender = result.clone();
+
+        // Called exactly once, but stored as Fn, like group tasks.
+        let task = Mutex::new(Some(task));
+        let closure_id = crate::pool::insert(std::sync::Arc::new(move |_index: u32| {
+            let Some(task) = task.lock().unwrap().take() else {
+                return;
+            };
+            let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(task));
+            *sender.lock().unwrap() = Some(outcome);
+        }));
+
+        // Called with no arguments; the method receives (index, closure ID).
+        let callable = pool_callable(crate::builtin::varray![0, closure_id as i64]);
+        let task_id = self.add_task(callable);
+
+        PoolTask {
+            task_id,
+            closure_id,
+            result,
+        }
+    }
+
+    fn add_rust_group_task<F>(&mut self, elements: u32, task: F) -> PoolGroupTask
+    where
+        F: Fn(u32) + Send + Sync + 'static,
+    {
+        let panic = std::sync::Arc::new(Mutex::new(None));
+        let sender = panic.clone();
+
+        let closure_id = crate::pool::insert(std::sync::Arc::new(move |index: u32| {
+            if let Err(err) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| task(index)))
+            {
+                // Keep the first panic only.
+                let mut panic = sender.lock().unwrap();
+                if panic.is_none() {
+                    *panic = Some(err);
+                }
+            }
+        }));
+
+        // Called with the element index; the method receives (index, closure ID).
+        let callable = pool_callable(crate::builtin::varray![closure_id as i64]);
+        let group_id = self.add_group_task(callable, elements as i64);
+
+        PoolGroupTask {
+            group_id,
+            closure_id,
+            panic,
+        }
+    }
+}
+
+#[cfg(since_api = "4.1")]
+fn pool_callable(bound_args: crate::builtin::VariantArray) -> Callable {
+    crate::dispatch::callable(crate::pool::METHOD_NAME)
+        .as_inner()
+        .bindv(bound_args)
+}
+
+/// Task on the `WorkerThreadPool`, started by [`WorkerThreadPoolExt::add_rust_task()`].
+#[cfg(since_api = "4.1")]
+#[must_use = "pool tasks must be joined, so that Godot releases them"]
+pub struct PoolTask<R> {
+    task_id: i64,
+    closure_id: u64,
+    result: std::sync::Arc<Mutex<Option<std::thread::Result<R>>>>,
+}
+
+#[cfg(since_api = "4.1")]
+impl<R> PoolTask<R> {
+    /// Whether the task has completed, so that [`join()`][Self::join] does not block.
+    pub fn is_finished(&self) -> bool {
+        WorkerThreadPool::singleton().is_task_completed(self.task_id)
+    }
+
+    /// Blocks until the task has completed, and returns its result, or the payload of its panic.
+    ///
+    /// # Panics
+    /// If the task never ran.
+    pub fn join(self) -> std::thread::Result<R> {
+        WorkerThreadPool::singleton().wait_for_task_completion(self.task_id);
+        crate::pool::remove(self.closure_id);
+
+        let result = self.result.lock().unwrap().take();
+        result.unwrap_or_else(|| panic!("pool task {} did not run", self.task_id))
+    }
+}
+
+/// Group task on the `WorkerThreadPool`, started by [`WorkerThreadPoolExt::add_rust_group_task()`].
+#[cfg(since_api = "4.1")]
+#[must_use = "pool tasks must be joined, so that Godot releases them"]
+pub struct PoolGroupTask {
+    group_id: i64,
+    closure_id: u64,
+    panic: std::sync::Arc<Mutex<Option<Box<dyn std::any::Any + Send>>>>,
+}
+
+#[cfg(since_api = "4.1")]
+impl PoolGroupTask {
+    /// Whether all elements have been processed, so that [`join()`][Self::join] does not block.
+    pub fn is_finished(&self) -> bool {
+        WorkerThreadPool::singleton().is_group_task_completed(self.group_id)
+    }
+
+    /// Number of elements processed so far.
+    pub fn processed_count(&self) -> u32 {
+        WorkerThreadPool::singleton().get_group_processed_element_count(self.group_id) as u32
+    }
+
+    /// Blocks until all elements have been processed. Returns the payload of the first panic, if any element panicked.
+    pub fn join(self) -> std::thread::Result<()> {
+        WorkerThreadPool::singleton().wait_for_group_task_completion(self.group_id);
+        crate::pool::remove(self.closure_id);
+
+        match self.panic.lock().unwrap().take() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Extension trait for launch arguments and environment of `OS`, with Rust strings.
+///
+/// User arguments are those after a `--` (or `++`) separator on the command line, e.g. for `godot --headless -- --port 7000`
+/// they are `["--port", "7000"]`. Godot does not interpret them, so they are the place for a project's own options.
+pub trait OsExt {
+    /// All command-line arguments passed to the engine, except the executable and user arguments.
+    fn cmdline_args(&self) -> Vec<String>;
+
+    /// Command-line arguments after the `--` or `++` separator.
+    fn user_args(&self) -> Vec<String>;
+
+    /// Value of the user argument `--{name}`, given either as `--{name}=value` or as `--{name} value`.
+    ///
+    /// Returns `None` if the argument is absent or has no value.
+    fn user_arg(&self, name: &str) -> Option<String>;
+
+    /// Whether the user argument `--{name}` is present, with or without value.
+    fn has_user_flag(&self, name: &str) -> bool;
+
+    /// Parses the user arguments into `P`, e.g. a dedicated server configuration.
+    fn parse_user_args<P: FromUserArgs>(&self) -> Result<P, P::Err>;
+
+    /// Value of an environment variable, or `None` if it is not set.
+    fn env_var(&self, name: &str) -> Option<String>;
+}
+
+impl OsExt for Os {
+    fn cmdline_args(&self) -> Vec<String> {
+        to_strings(self.get_cmdline_args())
+    }
+
+    fn user_args(&self) -> Vec<String> {
+        to_strings(self.get_cmdline_user_args())
+    }
+
+    fn user_arg(&self, name: &str) -> Option<String> {
+        find_user_arg(&self.user_args(), name).flatten()
+    }
+
+    fn has_user_flag(&self, name: &str) -> bool {
+        find_user_arg(&self.user_args(), name).is_some()
+    }
+
+    fn parse_user_args<P: FromUserArgs>(&self) -> Result<P, P::Err> {
+        P::from_user_args(self.user_args())
+    }
+
+    fn env_var(&self, name: &str) -> Option<String> {
+        if self.has_environment(name.into()) {
+            Some(self.get_environment(name.into()).to_string())
+        } else {
+            None
+        }
+    }
+}
+
+/// Types that can be constructed from the user arguments of the command line.
+///
+/// This is the integration point for argument parsers. With [clap](https://docs.rs/clap), for example:
+///
+/// ```ignore
+/// use clap::Parser;
+/// use godot::engine::{FromUserArgs, Os, OsExt};
+///
+/// #[derive(Parser)]
+/// struct ServerConfig {
+///     #[arg(long, default_value_t = 7000)]
+///     port: u16,
+/// }
+///
+/// impl FromUserArgs for ServerConfig {
+///     type Err = clap::Error;
+///
+///     fn from_user_args(args: Vec<String>) -> Result<Self, Self::Err> {
+///         // clap expects the program name first.
+///         Self::try_parse_from(std::iter::once("godot".to_string()).chain(args))
+///     }
+/// }
+///
+/// let config: ServerConfig = Os::singleton().parse_user_args()?;
+/// ```
+pub trait FromUserArgs: Sized {
+    type Err;
+
+    /// Parses the user arguments, which are passed without the `--` separator and without program name.
+    fn from_user_args(args: Vec<String>) -> Result<Self, Self::Err>;
+}
+
+fn to_strings(array: crate::builtin::PackedStringArray) -> Vec<String> {
+    array
+        .as_slice()
+        .iter()
+        .map(GodotString::to_string)
+        .collect()
+}
+
+/// Finds `--{name}` in `args`. Returns `Some(None)` if present without value.
+fn find_user_arg(args: &[String], name: &str) -> Option<Option<String>> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let Some(rest) = arg
+            .strip_prefix("--")
+            .and_then(|rest| rest.strip_prefix(name))
+        else {
+            continue;
+        };
+
+        if let Some(value) = rest.strip_prefix('=') {
+            return Some(Some(value.to_string()));
+        }
+        if rest.is_empty() {
+            let value = iter.clone().next().filter(|next| !next.starts_with("--"));
+            return Some(value.cloned());
+        }
+    }
+
+    None
+}
+
+/// Selects the class `T` as the project's main loop, overriding the `application/run/main_loop_type` project setting.
+///
+/// This allows games to run on a custom `SceneTree` subclass, or headless simulations and servers to be driven entirely
+/// by a Rust `MainLoop` subclass. Godot instantiates the main loop through `ClassDB` after the `Scene` level is initialized,
+/// so call this from [`ExtensionLibrary::on_level_init()`][crate::init::ExtensionLibrary::on_level_init] at that level,
+/// and make sure `T` has an `init` constructor. The setting is only changed in memory.
+///
+/// Has no effect in the editor, which always runs its own main loop.
+pub fn set_main_loop<T>()
+where
+    T: GodotClass + Inherits<MainLoop>,
+{
+    if is_editor_hint() {
+        return;
+    }
+
+    ProjectSettings::singleton().set_setting(
+        "application/run/main_loop_type".into(),
+        T::class_name().to_godot_string().to_variant(),
+    );
+}
+
+/// Engine singletons registered through [`register_singleton()`], in registration order.
+static SINGLETONS: Mutex<Vec<(StringName, InstanceId)>> = Mutex::new(Vec::new());
+
+/// Registers a manually managed object as engine singleton, making it globally accessible.
+///
+/// GDScript can access the object directly by `name`, Rust code through `Engine::singleton().get_singleton(name)`.
+/// Ownership passes to gdext: the object is unregistered and freed when the library is deinitialized, after
+/// [`ExtensionLibrary::on_level_deinit()`][crate::init::ExtensionLibrary::on_level_deinit] of the first level being unloaded.
+/// To remove it earlier, call [`unregister_singleton()`].
+///
+/// Register singletons in `ExtensionLibrary::on_level_init()`, at a level where the object's class is already registered.
+///
+/// # Panics
+/// If a singleton with the same name already exists.
+pub fn register_singleton<T, M>(name: impl Into<StringName>, instance: Gd<T>)
+where
+    T: GodotClass<Mem = M> + Inherits<Object>,
+    M: mem::PossiblyManual + mem::Memory,
+{
+    let name = name.into();
+    let mut engine = Engine::singleton();
+    assert!(
+        !engine.has_singleton(name.clone()),
+        "engine singleton `{name}` already exists"
+    );
+
+    let instance_id = instance.instance_id();
+    engine.register_singleton(name.clone(), instance.upcast());
+
+    SINGLETONS.lock().unwrap().push((name, instance_id));
+}
+
+/// Unregisters and frees a singleton previously added with [`register_singleton()`].
+///
+/// Returns `false` if no such singleton was registered through gdext.
+pub fn unregister_singleton(name: impl Into<StringName>) -> bool {
+    let name = name.into();
+
+    let entry = {
+        let mut singletons = SINGLETONS.lock().unwrap();
+        let index = singletons.iter().position(|(n, _)| *n == name);
+        index.map(|i| singletons.remove(i))
+    };
+
+    match entry {
+        Some((name, instance_id)) => {
+            unregister_and_free(name, instance_id);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Unregisters all singletons in reverse registration order. Called by gdext during deinitialization.
+///
+/// Leaves [`SINGLETONS`] empty and without allocated buffer, so that no names or memory outlive the engine.
+pub(crate) fn unregister_all_singletons() {
+    // Taken out before unregistering, so the lock is not held while Godot frees the instances (which may run user code).
+    let singletons = std::mem::take(&mut *SINGLETONS.lock().unwrap());
+
+    for (name, instance_id) in singletons.into_iter().rev() {
+        unregister_and_free(name, instance_id);
+    }
+}
+
+fn unregister_and_free(name: StringName, instance_id: InstanceId) {
+    Engine::singleton().unregister_singleton(name);
+
+    if let Some(instance) = Gd::<Object>::try_from_instance_id(instance_id) {
+        instance.free();
+    }
+}
+
+/// Autoload nodes found by [`try_autoload()`], to skip the tree lookup on subsequent calls.
+static AUTOLOADS: Mutex<Vec<(StringName, InstanceId)>> = Mutex::new(Vec::new());
+
+/// ⚠️ Retrieves the autoload (project-wide singleton node) called `name` as type `T`, panicking if not found or bad type.
+///
+/// See [`try_autoload()`] for details.
+///
+/// ```no_run
+/// use godot::prelude::*;
+/// use godot::engine::autoload;
+///
+/// let state = autoload::<Node>("GameState");
+/// ```
+///
+/// # Panics
+/// If there is no autoload with that name, or if it does not have type `T` or inherited.
+pub fn autoload<T>(name: impl Into<StringName>) -> Gd<T>
+where
+    T: GodotClass + Inherits<Node>,
+{
+    try_autoload(name).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Retrieves the autoload (project-wide singleton node) called `name` as type `T`.
+///
+/// Autoloads configured in the project settings -- scenes as well as scripts in GDScript or Rust -- are direct children
+/// of the scene tree's root, so this is equivalent to `get_node_as::<T>("/root/<name>")`. The found node is cached by
+/// name, and reused for as long as it is alive. Only a freed node causes a new lookup; autoloads are not expected to be
+/// moved or renamed.
+pub fn try_autoload<T>(name: impl Into<StringName>) -> Result<Gd<T>, NodeError>
+where
+    T: GodotClass + Inherits<Node>,
+{
+    let name = name.into();
+    let path = NodePath::from(format!("/root/{name}"));
+
+    let cached = AUTOLOADS
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, instance_id)| *instance_id);
+
+    if let Some(node) = cached.and_then(Gd::<T>::try_from_instance_id) {
+        return Ok(node);
+    }
+
+    let root = Engine::singleton()
+        .get_main_loop()
+        .and_then(|main_loop| main_loop.try_cast::<SceneTree>())
+        .and_then(|tree| tree.get_root());
+
+    let Some(root) = root else {
+        return Err(NodeError::NotFound { path });
+    };
+
+    let node = root.try_get_node_as::<T>(path)?;
+
+    let mut autoloads = AUTOLOADS.lock().unwrap();
+    autoloads.retain(|(n, _)| *n != name);
+    autoloads.push((name, node.instance_id()));
+
+    Ok(node)
+}
+
+/// Forgets all autoloads found by [`try_autoload()`]. Called by gdext during deinitialization, to release the cached names.
+pub(crate) fn clear_autoload_cache() {
+    AUTOLOADS.lock().unwrap().clear();
+}
+
+/// Decodes an image from the contents of a PNG, JPEG, WebP, BMP or TGA file.
+///
+/// Together with [`include_bytes!`], this allows shipping images inside the extension library, without `res://` files.
+/// The format is detected from the file signature; data without a known signature is treated as TGA.
+///
+/// Returns `None` if the data cannot be decoded.
+///
+/// ```no_run
+/// use godot::engine::image_from_bytes;
+///
+/// let icon = image_from_bytes(include_bytes!("../assets/icon.png")).expect("valid PNG");
+/// ```
+pub fn image_from_bytes(bytes: &[u8]) -> Option<Gd<Image>> {
+    let mut image = Image::new();
+    let buffer = PackedByteArray::from(bytes);
+
+    let err = match bytes {
+        [0x89, b'P', b'N', b'G', ..] => image.load_png_from_buffer(buffer),
+        [0xFF, 0xD8, 0xFF, ..] => image.load_jpg_from_buffer(buffer),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => {
+            image.load_webp_from_buffer(buffer)
+        }
+        [b'B', b'M', ..] => image.load_bmp_from_buffer(buffer),
+        _ => image.load_tga_from_buffer(buffer),
+    };
+
+    (err == global::Error::OK).then_some(image)
+}
+
+/// Creates an audio stream from the contents of a WAV file.
+///
+/// Supports uncompressed PCM data with 8 or 16 bits per sample, in mono or stereo -- the formats that `AudioStreamWAV` can
+/// play without conversion. Import options of the editor (looping, trimming, compression) are not applied.
+///
+/// Returns `None` if the data is not a WAV file in one of the supported formats.
+pub fn audio_stream_from_wav_bytes(bytes: &[u8]) -> Option<Gd<AudioStreamWav>> {
+    let wav = WavData::parse(bytes)?;
+
+    let (format, data) = match wav.bits_per_sample {
+        // WAV stores 8-bit samples as unsigned, Godot expects them signed.
+        8 => (
+            audio_stream_wav::Format::FORMAT_8_BITS,
+            wav.samples.iter().map(|s| s ^ 0x80).collect::<Vec<u8>>(),
+        ),
+        16 => (
+            audio_stream_wav::Format::FORMAT_16_BITS,
+            wav.samples.to_vec(),
+        ),
+        _ => return None,
+    };
+
+    let mut stream = AudioStreamWav::new();
+    stream.set_format(format);
+    stream.set_mix_rate(i32::try_from(wav.sample_rate).ok()?);
+    stream.set_stereo(wav.channels == 2);
+    stream.set_data(PackedByteArray::from(data.as_slice()));
+
+    Some(stream)
+}
+
+/// Parses JSON text into a `JSON` resource, e.g. from [`include_str!`].
+///
+/// Returns `None` on syntax errors. Use `JSON::parse()` directly to obtain the error message and line.
+pub fn json_from_str(text: &str) -> Option<Gd<Json>> {
+    let mut json = Json::new();
+    let err = json.parse(text.into());
+
+    (err == global::Error::OK).then_some(json)
+}
+
+/// Creates a font from the contents of a font file (TTF, OTF, WOFF, WOFF2, ...), e.g. from [`include_bytes!`].
+///
+/// The data is not validated here; the font server reports invalid data once the font is first used.
+pub fn font_from_bytes(bytes: &[u8]) -> Gd<FontFile> {
+    let mut font = FontFile::new();
+    font.set_data(PackedByteArray::from(bytes));
+
+    font
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Implementation of this file
+
+/// Key of the closure of a "Project > Tools" menu entry.
+fn tool_menu_key(name: &GodotString) -> String {
+    format!("editor/tool_menu/{name}")
+}
+
+/// Key of the closure of a command palette entry.
+fn command_palette_key(key_name: &GodotString) -> String {
+    format!("editor/command_palette/{key_name}")
+}
+
+fn command_palette<U>(plugin: &Gd<U>) -> Gd<EditorCommandPalette>
+where
+    U: GodotClass + Inherits<EditorPlugin>,
+{
+    plugin
+        .share()
+        .upcast::<EditorPlugin>()
+        .get_editor_interface()
+        .and_then(|interface| interface.get_command_palette())
+        .expect("editor command palette is only available in the editor")
+}
+
+/// Key of the avoidance closure of a navigation agent.
+#[cfg(since_api = "4.1")]
+fn avoidance_key(dimension: &str, agent: Rid) -> String {
+    format!("navigation_{dimension}/avoidance/{}", agent.to_u64())
+}
+
+#[cfg(since_api = "4.2")]
+fn editor_interface_impl() -> Option<Gd<EditorInterface>> {
+    Some(EditorInterface::singleton())
+}
+
+#[cfg(before_api = "4.2")]
+fn editor_interface_impl() -> Option<Gd<EditorInterface>> {
+    // EditorPlugin::get_editor_interface() returns the single C++ instance, independent of the plugin. A short-lived plugin object,
+    // which is never added to the tree, is thus enough to access it.
+    let plugin = EditorPlugin::new_alloc();
+    let interface = plugin.get_editor_interface();
+    plugin.free();
+
+    interface
+}
+
+fn instantiate_impl<T>(scene: &PackedScene) -> Result<Gd<T>, String>
+where
+    T: Inherits<Node>,
+{
+    let Some(root) = scene.instantiate() else {
+        return Err(format!(
+            "failed to instantiate scene `{}`",
+            scene.get_path()
+        ));
+    };
+
+    // try_cast() consumes the node; remember its ID to free the orphaned tree on mismatch.
+    let actual_class = root.get_class();
+    let instance_id = root.instance_id();
+
+    root.try_cast::<T>().ok_or_else(|| {
+        if let Some(root) = Gd::<Node>::try_from_instance_id(instance_id) {
+            root.free();
+        }
+
+        format!(
+            "root node of scene `{path}` has class `{actual_class}`, which is not `{expected}` or derived",
+            path = scene.get_path(),
+            expected = T::class_name(),
+        )
+    })
+}
+
+// Separate function, to avoid constructing string twice
+// Note that more optimizations than that likely make no sense, as loading is quite expensive
+fn load_impl<T>(path: &GodotString) -> Result<Gd<T>, LoadError>
+where
+    T: GodotClass + Inherits<Resource>,
+{
+    let mut loader = ResourceLoader::singleton();
+    if !loader.exists(path.clone()) {
+        return Err(LoadError::new(&path.to_string(), LoadErrorKind::NotFound));
+    }
+
+    let Some(resource) = loader
+        .load_ex(path.clone())
+        .type_hint(T::class_name().to_godot_string())
+        .done()
+    // TODO unclone
+    else {
+        return Err(LoadError::new(&path.to_string(), LoadErrorKind::Failed));
+    };
+
+    // Type hint is only a hint; loaders may still return a different class, so validate the cast.
+    let actual = resource.get_class().to_string();
+    resource
+        .try_cast::<T>()
+        .ok_or_else(|| LoadError::new(&path.to_string(), LoadErrorKind::WrongClass { actual }))
+}
+
+/// Uncompressed PCM contents of a WAV file.
+struct WavData<'a> {
+    channels: u16,
+    sample_rate: u32,
+    bits_per_sample: u16,
+    samples: &'a [u8],
+}
+
+impl<'a> WavData<'a> {
+    fn parse(bytes: &'a [u8]) -> Option<Self> {
+        if bytes.get(0..4)? != b"RIFF" || bytes.get(8..12)? != b"WAVE" {
+            return None;
+        }
+
+        let u16_at = |chunk: &[u8], pos: usize| {
+            Some(u16::from_le_bytes(
+                chunk.get(pos..pos + 2)?.try_into().ok()?,
+            ))
+        };
+        let u32_at = |chunk: &[u8], pos: usize| {
+            Some(u32::from_le_bytes(
+                chunk.get(pos..pos + 4)?.try_into().ok()?,
+            ))
+        };
+
+        let mut format = None;
+        let mut samples = None;
+
+        let mut pos = 12;
+        while pos + 8 <= bytes.len() {
+            let id = &bytes[pos..pos + 4];
+            let size = u32_at(bytes, pos + 4)? as usize;
+            let chunk = bytes.get(pos + 8..)?.get(..size)?;
+
+            match id {
+                b"fmt " => {
+                    // Only plain PCM (format tag 1) is supported.
+                    if u16_at(chunk, 0)? != 1 {
+                        return None;
+                    }
+                    format = Some((u16_at(chunk, 2)?, u32_at(chunk, 4)?, u16_at(chunk, 14)?));
+                }
+                b"data" => samples = Some(chunk),
+                _ => {}
+            }
+
+            // Chunks are padded to even size.
+            pos += 8 + size + size % 2;
+        }
+
+        let (channels, sample_rate, bits_per_sample) = format?;
+        if !(1..=2).contains(&channels) {
+            return None;
+        }
+
+        Some(Self {
+            channels,
+            sample_rate,
+            bits_per_sample,
+            samples: samples?,
+        })
+    }
 }
@@ -0,0 +1,734 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Error types of fallible gdext operations.
+//!
+//! Each kind of failure has its own type: [`CallError`] for dynamic method calls, [`ConvertError`] for conversions from
+//! `Variant`, [`IoError`] for engine operations reporting Godot's `Error` enum, [`NodeError`] for node lookups,
+//! [`ConnectError`] for signal connections, [`SignalError`] for awaited signals, [`LoadError`] for resources loaded from
+//! the filesystem, [`HttpError`] for HTTP requests, and [`FreedError`] for access to freed objects.
+//! All implement [`std::error::Error`], so they work with `?` and crates like `anyhow` or `thiserror`.
+//!
+//! [`Error`] unifies them, for functions that combine several gdext operations:
+//!
+//! ```no_run
+//! use godot::prelude::*;
+//! use godot::error::Error;
+//!
+//! fn read_score(config: &Dictionary) -> Result<i64, Error> {
+//!     let score = config.get("score").unwrap_or_default().try_to::<i64>()?;
+//!     Ok(score)
+//! }
+//! ```
+//!
+//! [`capture()`] collects the errors and warnings reported while running a closure, e.g. to assert on them in tests.
+
+use std::cell::RefCell;
+use std::fmt;
+
+use godot_ffi as sys;
+
+use crate::builtin::meta::ClassName;
+use crate::builtin::VariantType;
+use crate::engine::global;
+use crate::obj::InstanceId;
+
+pub use crate::builtin::VariantConversionError as ConvertError;
+pub use crate::engine::NodeError;
+
+/// Any error of a gdext operation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// A method could not be called dynamically.
+    Call(CallError),
+
+    /// A `Variant` could not be converted to the requested type.
+    Convert(ConvertError),
+
+    /// An engine operation returned an error code.
+    Io(IoError),
+
+    /// A node could not be found, or has the wrong type.
+    Node(NodeError),
+
+    /// A signal could not be connected or disconnected.
+    Connect(ConnectError),
+
+    /// An awaited signal was not emitted.
+    Signal(SignalError),
+
+    /// A resource could not be loaded.
+    Load(LoadError),
+
+    /// An HTTP request did not receive a response.
+    Http(HttpError),
+
+    /// An object was accessed after being freed.
+    Freed(FreedError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Call(err) => err.fmt(f),
+            Error::Convert(err) => err.fmt(f),
+            Error::Io(err) => err.fmt(f),
+            Error::Node(err) => err.fmt(f),
+            Error::Connect(err) => err.fmt(f),
+            Error::Signal(err) => err.fmt(f),
+            Error::Load(err) => err.fmt(f),
+            Error::Http(err) => err.fmt(f),
+            Error::Freed(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Call(err) => Some(err),
+            Error::Convert(err) => Some(err),
+            Error::Io(err) => Some(err),
+            Error::Node(err) => Some(err),
+            Error::Connect(err) => Some(err),
+            Error::Signal(err) => Some(err),
+            Error::Load(err) => Some(err),
+            Error::Http(err) => Some(err),
+            Error::Freed(err) => Some(err),
+        }
+    }
+}
+
+impl From<CallError> for Error {
+    fn from(err: CallError) -> Self {
+        Error::Call(err)
+    }
+}
+
+impl From<ConvertError> for Error {
+    fn from(err: ConvertError) -> Self {
+        Error::Convert(err)
+    }
+}
+
+impl From<IoError> for Error {
+    fn from(err: IoError) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<NodeError> for Error {
+    fn from(err: NodeError) -> Self {
+        Error::Node(err)
+    }
+}
+
+impl From<ConnectError> for Error {
+    fn from(err: ConnectError) -> Self {
+        Error::Connect(err)
+    }
+}
+
+impl From<SignalError> for Error {
+    fn from(err: SignalError) -> Self {
+        Error::Signal(err)
+    }
+}
+
+impl From<LoadError> for Error {
+    fn from(err: LoadError) -> Self {
+        Error::Load(err)
+    }
+}
+
+impl From<HttpError> for Error {
+    fn from(err: HttpError) -> Self {
+        Error::Http(err)
+    }
+}
+
+impl From<FreedError> for Error {
+    fn from(err: FreedError) -> Self {
+        Error::Freed(err)
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+/// Failed dynamic call of a method, e.g. through `Object::call()`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CallError {
+    method_name: String,
+    kind: CallErrorKind,
+}
+
+/// Reason of a [`CallError`], as reported by Godot.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CallErrorKind {
+    /// No method with this name exists, or the method failed (e.g. a Rust `#[func]` panicked or returned `Err`).
+    InvalidMethod,
+
+    /// The argument at `index` (0-based) has type `actual`, which cannot be converted to `expected`.
+    InvalidArgument {
+        index: usize,
+        expected: VariantType,
+        actual: VariantType,
+    },
+
+    /// More arguments were passed than the method accepts.
+    TooManyArguments { expected: usize, actual: usize },
+
+    /// Fewer arguments were passed than the method requires.
+    TooFewArguments { expected: usize, actual: usize },
+
+    /// The method was called on a null instance.
+    InstanceIsNull,
+
+    /// A non-const method was called on a const instance.
+    MethodNotConst,
+
+    /// A property could not be set: it does not exist, or cannot hold the value. [`CallError::method_name()`] is the
+    /// property name in this case.
+    InvalidProperty,
+
+    /// Error code not known to gdext.
+    Unknown(i64),
+}
+
+impl CallError {
+    pub(crate) fn new(method_name: &str, kind: CallErrorKind) -> Self {
+        Self {
+            method_name: method_name.to_string(),
+            kind,
+        }
+    }
+
+    /// Translates the error Godot reported for a call with arguments of type `arg_types`.
+    pub(crate) fn from_sys(
+        method_name: &str,
+        err: &sys::GDExtensionCallError,
+        arg_types: &[VariantType],
+    ) -> Self {
+        let sys::GDExtensionCallError {
+            error,
+            argument,
+            expected,
+        } = *err;
+
+        let actual = arg_types.len();
+        let kind = match error {
+            sys::GDEXTENSION_CALL_ERROR_INVALID_METHOD => CallErrorKind::InvalidMethod,
+            sys::GDEXTENSION_CALL_ERROR_INVALID_ARGUMENT => CallErrorKind::InvalidArgument {
+                index: argument as usize,
+                expected: VariantType::from_sys(expected as sys::GDExtensionVariantType),
+                actual: arg_types
+                    .get(argument as usize)
+                    .copied()
+                    .unwrap_or(VariantType::Nil),
+            },
+            sys::GDEXTENSION_CALL_ERROR_TOO_MANY_ARGUMENTS => CallErrorKind::TooManyArguments {
+                expected: argument as usize,
+                actual,
+            },
+            sys::GDEXTENSION_CALL_ERROR_TOO_FEW_ARGUMENTS => CallErrorKind::TooFewArguments {
+                expected: argument as usize,
+                actual,
+            },
+            sys::GDEXTENSION_CALL_ERROR_INSTANCE_IS_NULL => CallErrorKind::InstanceIsNull,
+            sys::GDEXTENSION_CALL_ERROR_METHOD_NOT_CONST => CallErrorKind::MethodNotConst,
+            other => CallErrorKind::Unknown(i64::from(other)),
+        };
+
+        Self::new(method_name, kind)
+    }
+
+    /// Name of the method that was called.
+    pub fn method_name(&self) -> &str {
+        &self.method_name
+    }
+
+    /// Why the call failed.
+    pub fn kind(&self) -> CallErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for CallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let method = &self.method_name;
+        match self.kind {
+            CallErrorKind::InvalidMethod => write!(f, "call to `{method}` failed: method not found or failed"),
+            CallErrorKind::InvalidArgument {
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "call to `{method}` failed: cannot convert argument #{} from {actual:?} to {expected:?}",
+                index + 1
+            ),
+            CallErrorKind::TooManyArguments { expected, actual } => write!(
+                f,
+                "call to `{method}` failed: too many arguments; expected {expected}, but called with {actual}"
+            ),
+            CallErrorKind::TooFewArguments { expected, actual } => write!(
+                f,
+                "call to `{method}` failed: too few arguments; expected {expected}, but called with {actual}"
+            ),
+            CallErrorKind::InstanceIsNull => write!(f, "call to `{method}` failed: instance is null"),
+            CallErrorKind::MethodNotConst => write!(f, "call to `{method}` failed: method is not const"),
+            CallErrorKind::InvalidProperty => write!(
+                f,
+                "setting `{method}` failed: property not found, or value has incompatible type"
+            ),
+            CallErrorKind::Unknown(code) => {
+                write!(f, "call to `{method}` failed: unknown reason (error code {code})")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CallError {}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+/// Failed engine operation, carrying Godot's [`Error`][global::Error] code.
+///
+/// Many engine methods return `global::Error` instead of a `Result`. [`IoError::check()`] turns such a code into a `Result`,
+/// to be used with `?`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct IoError {
+    code: global::Error,
+}
+
+impl IoError {
+    /// Returns `Ok(())` for `global::Error::OK`, and an `IoError` with the code otherwise.
+    ///
+    /// ```no_run
+    /// use godot::engine::ProjectSettings;
+    /// use godot::error::IoError;
+    ///
+    /// fn save_settings() -> Result<(), IoError> {
+    ///     IoError::check(ProjectSettings::singleton().save())
+    /// }
+    /// ```
+    pub fn check(code: global::Error) -> Result<(), IoError> {
+        if code == global::Error::OK {
+            Ok(())
+        } else {
+            Err(IoError { code })
+        }
+    }
+
+    /// Godot's error code; never `global::Error::OK`.
+    pub fn code(&self) -> global::Error {
+        self.code
+    }
+}
+
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "engine operation failed with {:?}", self.code)
+    }
+}
+
+impl std::error::Error for IoError {}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+/// Failed connection or disconnection of a signal, returned by [`ObjectExt`][crate::engine::ObjectExt] methods.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConnectError {
+    signal: String,
+    kind: ConnectErrorKind,
+}
+
+/// Reason of a [`ConnectError`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ConnectErrorKind {
+    /// The object has no signal with this name.
+    UnknownSignal,
+
+    /// The callable is null, or its target object has been freed.
+    InvalidCallable,
+
+    /// The callable is already connected to the signal.
+    AlreadyConnected,
+
+    /// The callable is not connected to the signal (on disconnect).
+    NotConnected,
+
+    /// Godot rejected the connection with another error code.
+    Engine(global::Error),
+}
+
+impl ConnectError {
+    pub(crate) fn new(signal: &str, kind: ConnectErrorKind) -> Self {
+        Self {
+            signal: signal.to_string(),
+            kind,
+        }
+    }
+
+    /// Name of the signal.
+    pub fn signal(&self) -> &str {
+        &self.signal
+    }
+
+    /// Why the operation failed.
+    pub fn kind(&self) -> ConnectErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for ConnectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let signal = &self.signal;
+        match self.kind {
+            ConnectErrorKind::UnknownSignal => write!(f, "signal `{signal}` does not exist"),
+            ConnectErrorKind::InvalidCallable => {
+                write!(f, "cannot connect signal `{signal}` to an invalid callable")
+            }
+            ConnectErrorKind::AlreadyConnected => {
+                write!(f, "callable is already connected to signal `{signal}`")
+            }
+            ConnectErrorKind::NotConnected => {
+                write!(f, "callable is not connected to signal `{signal}`")
+            }
+            ConnectErrorKind::Engine(code) => {
+                write!(f, "connecting signal `{signal}` failed with {code:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConnectError {}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+/// Awaited signal that was not emitted, returned by [`SignalFuture`][crate::task::SignalFuture].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum SignalError {
+    /// The signal could not be connected.
+    Connect(ConnectError),
+
+    /// The timeout elapsed before the signal was emitted.
+    TimedOut,
+
+    /// The emitting object was freed before the signal was emitted.
+    EmitterFreed,
+}
+
+impl fmt::Display for SignalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignalError::Connect(err) => err.fmt(f),
+            SignalError::TimedOut => write!(f, "timed out waiting for signal"),
+            SignalError::EmitterFreed => write!(f, "object was freed while waiting for signal"),
+        }
+    }
+}
+
+impl std::error::Error for SignalError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SignalError::Connect(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+/// Failed load of a resource, returned by [`try_load()`][crate::engine::try_load] and
+/// [`ThreadedLoad`][crate::engine::ThreadedLoad].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoadError {
+    path: String,
+    kind: LoadErrorKind,
+}
+
+/// Reason of a [`LoadError`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum LoadErrorKind {
+    /// No resource exists at the path.
+    NotFound,
+
+    /// Godot rejected the load request, e.g. because the path does not exist.
+    Request(global::Error),
+
+    /// The loader failed, e.g. on a corrupted file or a missing dependency.
+    Failed,
+
+    /// Godot has no request for this path, e.g. because its result was already retrieved elsewhere.
+    NotRequested,
+
+    /// The resource was loaded, but its class is not the requested one or derived.
+    WrongClass {
+        /// Class of the loaded resource.
+        actual: String,
+    },
+}
+
+impl LoadError {
+    pub(crate) fn new(path: &str, kind: LoadErrorKind) -> Self {
+        Self {
+            path: path.to_string(),
+            kind,
+        }
+    }
+
+    /// Path of the resource.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Why the load failed.
+    pub fn kind(&self) -> &LoadErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = &self.path;
+        match &self.kind {
+            LoadErrorKind::Request(code) => {
+                write!(
+                    f,
+                    "request to load resource at path `{path}` failed with {code:?}"
+                )
+            }
+            LoadErrorKind::NotFound => write!(f, "no resource found at path `{path}`"),
+            LoadErrorKind::Failed => write!(f, "failed to load resource at path `{path}`"),
+            LoadErrorKind::NotRequested => {
+                write!(f, "no pending load request for resource at path `{path}`")
+            }
+            LoadErrorKind::WrongClass { actual } => {
+                write!(f, "resource at path `{path}` has class `{actual}`, which is not the requested one or derived")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+/// HTTP request without response, returned by [`HttpFuture`][crate::http::HttpFuture].
+///
+/// Responses with error status codes, such as 404, are not `HttpError`s.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HttpError {
+    url: String,
+    kind: HttpErrorKind,
+}
+
+/// Reason of an [`HttpError`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum HttpErrorKind {
+    /// The URL is malformed, or its scheme is not `http` or `https`.
+    InvalidUrl,
+
+    /// The host name could not be resolved.
+    CantResolve,
+
+    /// No connection to the host could be established.
+    CantConnect,
+
+    /// The TLS handshake failed, e.g. because of an untrusted certificate.
+    Tls,
+
+    /// The connection was lost before the response was complete.
+    Connection,
+
+    /// The timeout elapsed before the response was complete.
+    TimedOut,
+
+    /// Godot rejected the request with an error code.
+    Engine(global::Error),
+}
+
+impl HttpError {
+    pub(crate) fn new(url: &str, kind: HttpErrorKind) -> Self {
+        Self {
+            url: url.to_string(),
+            kind,
+        }
+    }
+
+    /// URL of the request.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Why the request failed.
+    pub fn kind(&self) -> HttpErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let url = &self.url;
+        match self.kind {
+            HttpErrorKind::InvalidUrl => write!(f, "invalid HTTP URL `{url}`"),
+            HttpErrorKind::CantResolve => write!(f, "cannot resolve host of `{url}`"),
+            HttpErrorKind::CantConnect => write!(f, "cannot connect to host of `{url}`"),
+            HttpErrorKind::Tls => write!(f, "TLS handshake failed for `{url}`"),
+            HttpErrorKind::Connection => write!(f, "connection lost during request to `{url}`"),
+            HttpErrorKind::TimedOut => write!(f, "request to `{url}` timed out"),
+            HttpErrorKind::Engine(code) => write!(f, "request to `{url}` failed with {code:?}"),
+        }
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+/// Access to an object that has already been freed, returned by [`Gd::checked()`][crate::obj::Gd::checked].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct FreedError {
+    class_name: ClassName,
+    instance_id: Option<InstanceId>,
+}
+
+impl FreedError {
+    pub(crate) fn new(class_name: ClassName, instance_id: Option<InstanceId>) -> Self {
+        Self {
+            class_name,
+            instance_id,
+        }
+    }
+
+    /// Static class of the `Gd` through which the object was accessed.
+    pub fn class_name(&self) -> ClassName {
+        self.class_name
+    }
+
+    /// Instance ID the object had, if still known.
+    pub fn instance_id(&self) -> Option<InstanceId> {
+        self.instance_id
+    }
+}
+
+impl fmt::Display for FreedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.instance_id {
+            Some(id) => write!(
+                f,
+                "object {id} of class `{}` has been freed",
+                self.class_name
+            ),
+            None => write!(f, "object of class `{}` has been freed", self.class_name),
+        }
+    }
+}
+
+impl std::error::Error for FreedError {}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Capturing of reported errors
+
+/// Severity of a message reported during [`capture()`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Severity {
+    /// Reported through `godot_error!`, e.g. a panicking `#[func]`.
+    Error,
+
+    /// Reported through `godot_warn!`.
+    Warning,
+
+    /// Reported through `godot_script_error!`.
+    ScriptError,
+}
+
+/// Error or warning reported during [`capture()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CapturedMessage {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl fmt::Display for CapturedMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.severity, self.message)
+    }
+}
+
+thread_local! {
+    /// One list per active `capture()` scope on this thread, innermost last.
+    static CAPTURE_SCOPES: RefCell<Vec<Vec<CapturedMessage>>> = RefCell::new(Vec::new());
+}
+
+/// Runs `code`, collecting errors and warnings reported on this thread instead of printing them.
+///
+/// Returns the result of `code` together with the collected messages, in the order they were reported. This lets tests and
+/// tools assert on failures, rather than scraping the output:
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// # fn test(mut obj: Gd<Object>) {
+/// use godot::engine::ObjectExt;
+/// use godot::error::{capture, Severity};
+///
+/// let (_result, messages) = capture(|| obj.try_call("might_fail", &[]));
+/// assert!(messages.iter().all(|msg| msg.severity != Severity::Error));
+/// # }
+/// ```
+///
+/// Captured are messages of `godot_error!`, `godot_warn!` and `godot_script_error!`, including those gdext reports itself:
+/// panics in `#[func]` and virtual methods, and `Err` values returned from `#[func]`s. These are captured even when the Rust
+/// code was invoked through an engine call, as long as it runs on the same thread.
+///
+/// Godot 4.1 offers extensions no hook into the engine's own error reporting. Messages printed by engine code or by scripts
+/// (e.g. GDScript `push_error()`) are therefore not captured, and still printed.
+///
+/// Scopes can be nested; a message is only captured by the innermost one.
+pub fn capture<R>(code: impl FnOnce() -> R) -> (R, Vec<CapturedMessage>) {
+    /// Closes the scope also when `code` panics.
+    struct ScopeGuard;
+
+    impl Drop for ScopeGuard {
+        fn drop(&mut self) {
+            CAPTURE_SCOPES.with(|scopes| scopes.borrow_mut().pop());
+        }
+    }
+
+    CAPTURE_SCOPES.with(|scopes| scopes.borrow_mut().push(Vec::new()));
+    let guard = ScopeGuard;
+
+    let result = code();
+
+    let messages = CAPTURE_SCOPES.with(|scopes| {
+        let mut scopes = scopes.borrow_mut();
+        std::mem::take(scopes.last_mut().expect("capture scope present"))
+    });
+    drop(guard);
+
+    (result, messages)
+}
+
+/// Adds the message to the innermost `capture()` scope. Returns false if there is none, i.e. the message should be printed.
+pub(crate) fn capture_message(severity: Severity, message: &str) -> bool {
+    CAPTURE_SCOPES.with(|scopes| match scopes.borrow_mut().last_mut() {
+        Some(scope) => {
+            scope.push(CapturedMessage {
+                severity,
+                message: message.to_string(),
+            });
+            true
+        }
+        None => false,
+    })
+}
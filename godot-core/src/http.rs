@@ -0,0 +1,416 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! HTTP requests as futures, to be awaited in [tasks][crate::task].
+//!
+//! ```no_run
+//! use godot::prelude::*;
+//! use godot::{http, task};
+//!
+//! task::spawn(async {
+//!     match http::get("https://example.com/scores.json").await {
+//!         Ok(response) if response.is_success() => godot_print!("{}", response.text()),
+//!         Ok(response) => godot_warn!("server responded with {}", response.status()),
+//!         Err(err) => godot_warn!("{err}"),
+//!     }
+//! });
+//! ```
+//!
+//! Requests run on Godot's `HTTPClient`, which is polled once per frame while tasks are
+//! [driven][crate::task::drive_each_frame] (otherwise in every [`task::poll()`][crate::task::poll]). This is the same mechanism
+//! that the `HTTPRequest` node uses, but needs no node in the scene tree and no signal connections. TLS is used for `https://`
+//! URLs.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use crate::builtin::{GodotString, PackedByteArray, PackedStringArray, ToVariant};
+use crate::engine::global::Error;
+use crate::engine::{http_client, HttpClient};
+use crate::error::{HttpError, HttpErrorKind};
+use crate::obj::{EngineEnum, Gd};
+use crate::task;
+
+/// Sends a `GET` request to `url`.
+pub fn get(url: impl Into<String>) -> HttpFuture {
+    Request::new(http_client::Method::METHOD_GET, url).send()
+}
+
+/// Sends a `POST` request with `body` to `url`.
+pub fn post(url: impl Into<String>, body: &[u8]) -> HttpFuture {
+    Request::new(http_client::Method::METHOD_POST, url)
+        .body(body)
+        .send()
+}
+
+/// HTTP request to be sent, for methods and headers beyond [`get()`] and [`post()`].
+///
+/// ```no_run
+/// # use godot::http::Request;
+/// # use godot::engine::http_client::Method;
+/// # async fn submit(token: &str, score: &str) -> Result<(), godot::error::Error> {
+/// let response = Request::new(Method::METHOD_PUT, "https://example.com/scores/42")
+///     .header("Authorization", &format!("Bearer {token}"))
+///     .header("Content-Type", "application/json")
+///     .body(score.as_bytes())
+///     .send()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[must_use = "requests are only sent with send()"]
+pub struct Request {
+    method: http_client::Method,
+    url: String,
+    headers: Vec<String>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    /// Creates a request without headers and body.
+    pub fn new(method: http_client::Method, url: impl Into<String>) -> Self {
+        Self {
+            method,
+            url: url.into(),
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    /// Adds a header.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push(format!("{name}: {value}"));
+        self
+    }
+
+    /// Sets the body.
+    pub fn body(mut self, body: &[u8]) -> Self {
+        self.body = body.to_vec();
+        self
+    }
+
+    /// Connects to the server, and returns a future that completes with the response.
+    ///
+    /// Must be called on the main thread.
+    pub fn send(self) -> HttpFuture {
+        let mut future = HttpFuture {
+            url: self.url.clone(),
+            client: None,
+            request: None,
+            body: Vec::new(),
+            deadline: None,
+            result: None,
+        };
+
+        let Some(url) = Url::parse(&self.url) else {
+            future.result = Some(Err(HttpError::new(&self.url, HttpErrorKind::InvalidUrl)));
+            return future;
+        };
+
+        // `tls_options` defaults to null, which `Gd` parameters cannot express (#156). A dynamic call lets Godot apply its defaults,
+        // i.e. TLS with the system's trusted certificates for `https://`.
+        let mut client = HttpClient::new();
+        let code = client.call(
+            "connect_to_host".into(),
+            &[
+                format!("{}://{}", url.scheme, url.host).to_variant(),
+                url.port.map_or(-1, i64::from).to_variant(),
+            ],
+        );
+        let code = Error::from_ord(code.to::<i32>());
+
+        if code != Error::OK {
+            let err = HttpError::new(&self.url, HttpErrorKind::Engine(code));
+            future.result = Some(Err(err));
+            return future;
+        }
+
+        future.client = Some(client);
+        future.request = Some((self, url.path));
+        future
+    }
+}
+
+/// Future returned by [`get()`], [`post()`] and [`Request::send()`].
+///
+/// Dropping the future closes the connection.
+#[must_use = "futures do nothing unless awaited"]
+pub struct HttpFuture {
+    url: String,
+    client: Option<Gd<HttpClient>>,
+    /// Request and its path, until sent.
+    request: Option<(Request, String)>,
+    body: Vec<u8>,
+    deadline: Option<Instant>,
+    /// Outcome known before polling, e.g. an invalid URL.
+    result: Option<Result<Response, HttpError>>,
+}
+
+impl HttpFuture {
+    /// Fails with [`HttpErrorKind::TimedOut`] if the response has not been received within `duration` from now.
+    pub fn timeout(mut self, duration: Duration) -> Self {
+        self.deadline = Some(Instant::now() + duration);
+        self
+    }
+
+    /// Advances the connection as far as possible without blocking. Returns `None` while the response is incomplete.
+    fn advance(&mut self) -> Option<Result<Response, HttpError>> {
+        use http_client::Status;
+
+        let client = self.client.as_mut()?;
+        client.poll();
+
+        let kind = match client.get_status() {
+            Status::STATUS_RESOLVING | Status::STATUS_CONNECTING | Status::STATUS_REQUESTING => {
+                return None;
+            }
+
+            Status::STATUS_CONNECTED if self.request.is_some() => {
+                let (request, path) = self.request.take().expect("checked above");
+                let headers = request
+                    .headers
+                    .iter()
+                    .map(|header| GodotString::from(header.as_str()))
+                    .collect::<PackedStringArray>();
+
+                let code = client.request_raw(
+                    request.method,
+                    path.into(),
+                    headers,
+                    PackedByteArray::from(request.body.as_slice()),
+                );
+                if code == Error::OK {
+                    return None;
+                }
+                HttpErrorKind::Engine(code)
+            }
+
+            Status::STATUS_BODY => {
+                // Read all chunks that have arrived so far.
+                while client.get_status() == Status::STATUS_BODY {
+                    let chunk = client.read_response_body_chunk();
+                    if chunk.is_empty() {
+                        break;
+                    }
+                    self.body.extend_from_slice(chunk.as_slice());
+                }
+                return None;
+            }
+
+            // Response complete, with the connection kept alive or closed by the server.
+            Status::STATUS_CONNECTED | Status::STATUS_DISCONNECTED if client.has_response() => {
+                let headers = client
+                    .get_response_headers()
+                    .as_slice()
+                    .iter()
+                    .filter_map(|line| {
+                        let line = line.to_string();
+                        let (name, value) = line.split_once(':')?;
+                        Some((name.trim().to_string(), value.trim().to_string()))
+                    })
+                    .collect();
+
+                return Some(Ok(Response {
+                    status: client.get_response_code() as u16,
+                    headers,
+                    body: std::mem::take(&mut self.body),
+                }));
+            }
+
+            Status::STATUS_CANT_RESOLVE => HttpErrorKind::CantResolve,
+            Status::STATUS_CANT_CONNECT => HttpErrorKind::CantConnect,
+            Status::STATUS_TLS_HANDSHAKE_ERROR => HttpErrorKind::Tls,
+            _ => HttpErrorKind::Connection,
+        };
+
+        Some(Err(HttpError::new(&self.url, kind)))
+    }
+
+    fn close(&mut self) {
+        if let Some(mut client) = self.client.take() {
+            client.close();
+        }
+    }
+}
+
+impl Future for HttpFuture {
+    type Output = Result<Response, HttpError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(result) = self.result.take() {
+            return Poll::Ready(result);
+        }
+
+        let result = match self.advance() {
+            Some(result) => result,
+            None if self
+                .deadline
+                .map_or(false, |deadline| Instant::now() >= deadline) =>
+            {
+                Err(HttpError::new(&self.url, HttpErrorKind::TimedOut))
+            }
+            None => {
+                task::wake_next_frame(cx.waker());
+                return Poll::Pending;
+            }
+        };
+
+        self.close();
+        Poll::Ready(result)
+    }
+}
+
+impl Drop for HttpFuture {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+/// Response to an HTTP request.
+///
+/// Any status code counts as response, including errors such as 404; check [`is_success()`][Self::is_success].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Response {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    /// HTTP status code, e.g. 200.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Returns `true` for status codes 200 to 299.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Headers as name-value pairs, in the order received.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Value of the first header named `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Body as bytes.
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Body as text. Invalid UTF-8 is replaced with `U+FFFD`.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// Moves the body out of the response.
+    pub fn into_body(self) -> Vec<u8> {
+        self.body
+    }
+}
+
+/// Parts of a URL, as needed by `HTTPClient`.
+#[derive(Debug, Eq, PartialEq)]
+struct Url {
+    scheme: &'static str,
+    host: String,
+    port: Option<u16>,
+    /// Path and query, starting with `/`.
+    path: String,
+}
+
+impl Url {
+    fn parse(url: &str) -> Option<Self> {
+        let (scheme, rest) = url.split_once("://")?;
+        let scheme = match scheme.to_ascii_lowercase().as_str() {
+            "http" => "http",
+            "https" => "https",
+            _ => return None,
+        };
+
+        let (authority, path) = match rest.find(['/', '?']) {
+            Some(index) => (&rest[..index], &rest[index..]),
+            None => (rest, "/"),
+        };
+        let path = if path.starts_with('?') {
+            format!("/{path}")
+        } else {
+            path.to_string()
+        };
+
+        // The port follows the last colon, unless that is part of a bracketed IPv6 address.
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !port.ends_with(']') => (host, Some(port.parse().ok()?)),
+            _ => (authority, None),
+        };
+
+        if host.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            scheme,
+            host: host.to_string(),
+            port,
+            path,
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn url(scheme: &'static str, host: &str, port: Option<u16>, path: &str) -> Option<Url> {
+        Some(Url {
+            scheme,
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+        })
+    }
+
+    #[test]
+    fn url_parse() {
+        assert_eq!(
+            Url::parse("https://example.com"),
+            url("https", "example.com", None, "/")
+        );
+        assert_eq!(
+            Url::parse("HTTP://localhost:8080/api/scores?top=10"),
+            url("http", "localhost", Some(8080), "/api/scores?top=10")
+        );
+        assert_eq!(
+            Url::parse("http://example.com?q=1"),
+            url("http", "example.com", None, "/?q=1")
+        );
+        assert_eq!(
+            Url::parse("http://[::1]:3000/"),
+            url("http", "[::1]", Some(3000), "/")
+        );
+        assert_eq!(Url::parse("http://[::1]/"), url("http", "[::1]", None, "/"));
+    }
+
+    #[test]
+    fn url_parse_invalid() {
+        assert_eq!(Url::parse("example.com"), None);
+        assert_eq!(Url::parse("ftp://example.com/file"), None);
+        assert_eq!(Url::parse("http:///path"), None);
+        assert_eq!(Url::parse("http://example.com:port/"), None);
+    }
+}
@@ -6,7 +6,10 @@
 
 use godot_ffi as sys;
 
+use crate::builtin::meta::ClassName;
 use std::cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{OnceLock, RwLock};
 
 #[doc(hidden)]
 // TODO consider body safe despite unsafe function, and explicitly mark unsafe {} locations
@@ -25,11 +28,22 @@ pub unsafe fn __gdext_load_library<E: ExtensionLibrary>(
             tool_only_in_editor,
             is_editor: cell::OnceCell::new(),
         };
+        let _ = PANIC_POLICY.set(E::panic_policy());
+        VALIDATE_CALLS.store(E::validate_calls(), Ordering::Relaxed);
+        crate::obj::thread_audit::set_main_thread();
+        if E::report_leaks() {
+            crate::leaks::enable();
+        }
+
+        if let Err(message) = sys::initialize(interface_or_get_proc_address, library, config) {
+            // Godot's logging functions are not loaded; it reports the failed initialization itself.
+            eprintln!("ERROR: {message}");
+            return 0;
+        }
 
-        sys::initialize(interface_or_get_proc_address, library, config);
+        warn_missing_interface_functions();
 
         // Currently no way to express failure; could be exposed to E if necessary.
-        // No early exit, unclear if Godot still requires output parameters to be set.
         let success = true;
 
         let godot_init_params = sys::GDExtensionInitialization {
@@ -50,6 +64,26 @@ pub unsafe fn __gdext_load_library<E: ExtensionLibrary>(
     is_success.unwrap_or(0)
 }
 
+/// Warns if the Godot binary did not provide some of the functions declared in the compiled-against header.
+///
+/// This can happen with custom or pre-release engine builds, whose API changed under the same version. Initialization
+/// continues, so that extensions not using these functions keep working.
+fn warn_missing_interface_functions() {
+    // SAFETY: called on the main thread, after initialization.
+    let missing = unsafe { sys::missing_interface_functions() };
+    if missing.is_empty() {
+        return;
+    }
+
+    let count = missing.len();
+    let version = sys::GdextBuild::godot_static_version_string();
+    let functions = missing.join(", ");
+    crate::log::godot_warn!(
+        "the running Godot binary lacks {count} GDExtension function(s) of the API gdext was compiled against ({version}): \
+        {functions}.\nFunctionality relying on them will panic when used."
+    );
+}
+
 unsafe extern "C" fn ffi_initialize_layer<E: ExtensionLibrary>(
     _userdata: *mut std::ffi::c_void,
     init_level: sys::GDExtensionInitializationLevel,
@@ -59,7 +93,7 @@ unsafe extern "C" fn ffi_initialize_layer<E: ExtensionLibrary>(
 
     // Swallow panics. TODO consider crashing if gdext init fails.
     let _ = crate::private::handle_panic(ctx, || {
-        gdext_on_level_init(level);
+        gdext_on_level_init::<E>(level);
         E::on_level_init(level);
     });
 }
@@ -74,36 +108,67 @@ unsafe extern "C" fn ffi_deinitialize_layer<E: ExtensionLibrary>(
     // Swallow panics.
     let _ = crate::private::handle_panic(ctx, || {
         E::on_level_deinit(level);
-        gdext_on_level_deinit(level);
+        gdext_on_level_deinit::<E>(level);
+
+        // Lowest level is unloaded last; all objects should be gone by now.
+        if level == E::min_level() {
+            crate::leaks::report();
+        }
     });
 }
 
 /// Tasks needed to be done by gdext internally upon loading an initialization level. Called before user code.
-fn gdext_on_level_init(level: InitLevel) {
+fn gdext_on_level_init<E: ExtensionLibrary>(level: InitLevel) {
     // SAFETY: we are in the main thread, during initialization, no other logic is happening.
     // TODO: in theory, a user could start a thread in one of the early levels, and run concurrent code that messes with the global state
     // (e.g. class registration). This would break the assumption that the load_class_method_table() calls are exclusive.
     // We could maybe protect globals with a mutex until initialization is complete, and then move it to a directly-accessible, read-only static.
     unsafe {
+        // The first level that the library receives; the dispatch class only needs `Object`, available at all levels.
+        if level == E::min_level() {
+            crate::dispatch::register();
+        }
+
         match level {
             InitLevel::Core => {}
             InitLevel::Servers => {
                 sys::load_class_method_table(sys::ClassApiLevel::Server);
+                crate::auto_register_classes(level, E::min_level(), E::should_register_class);
             }
             InitLevel::Scene => {
                 sys::load_class_method_table(sys::ClassApiLevel::Scene);
-                crate::auto_register_classes();
+                crate::auto_register_classes(level, E::min_level(), E::should_register_class);
             }
             InitLevel::Editor => {
                 sys::load_class_method_table(sys::ClassApiLevel::Editor);
+                crate::auto_register_classes(level, E::min_level(), E::should_register_class);
+
+                // The editor help exists from this level on, after classes of all levels are registered.
+                #[cfg(since_api = "4.3")]
+                crate::docs::register();
             }
         }
     }
 }
 
 /// Tasks needed to be done by gdext internally upon unloading an initialization level. Called after user code.
-fn gdext_on_level_deinit(_level: InitLevel) {
-    // No logic at the moment.
+fn gdext_on_level_deinit<E: ExtensionLibrary>(level: InitLevel) {
+    // Singletons may be instances of classes from any level, so remove them before the first class is unregistered.
+    crate::engine::unregister_all_singletons();
+
+    // The cached autoload names are StringNames, which must not outlive the engine.
+    crate::engine::clear_autoload_cache();
+
+    // No classes are auto-registered at `Core`, but manually registered ones are removed there if it is the minimum level.
+    crate::unregister_classes(level, E::min_level());
+
+    // Last level to be unloaded.
+    if level == E::min_level() {
+        crate::dispatch::unregister();
+
+        // Must happen while the engine is alive, in case the global allocator is GodotAllocator.
+        crate::storage::release_storage_arenas();
+    }
 }
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
@@ -147,10 +212,58 @@ pub unsafe trait ExtensionLibrary {
     /// Determines the initialization level at which the extension is loaded (`Scene` by default).
     ///
     /// If the level is lower than [`InitLevel::Scene`], the engine needs to be restarted to take effect.
+    ///
+    /// Libraries providing custom servers (e.g. a physics server inheriting `PhysicsServer3DExtension`) must return
+    /// [`InitLevel::Servers`], so that their classes are registered before Godot instantiates its servers.
     fn min_level() -> InitLevel {
         InitLevel::Scene
     }
 
+    /// Determines what happens when a `#[func]` or virtual method override panics.
+    ///
+    /// Applies to all classes of the extension, unless overridden per class with `#[class(on_panic = ...)]`. The default
+    /// [`PanicPolicy::ReturnError`] reports the panic and makes the call fail for the caller.
+    fn panic_policy() -> PanicPolicy {
+        PanicPolicy::ReturnError
+    }
+
+    /// Whether dynamic calls of `#[func]` methods are validated before running them (`true` by default).
+    ///
+    /// When GDScript calls a `#[func]` with the wrong number of arguments, or with an argument not convertible to the Rust
+    /// parameter type, a script error naming the method's Rust signature is printed. The call then fails like a call to a
+    /// built-in method would, so GDScript reports the offending line.
+    ///
+    /// If disabled, such calls panic inside the argument conversion instead, and are handled according to the
+    /// [`panic_policy()`][Self::panic_policy].
+    fn validate_calls() -> bool {
+        true
+    }
+
+    /// Decides whether a class declared with `#[derive(GodotClass)]` is registered with Godot.
+    ///
+    /// Called once per class, at the init-level the class is registered (`Scene`, `Servers` for classes inheriting server classes,
+    /// or `Editor` for classes inheriting editor classes).
+    /// Returning `false` leaves the class unknown to Godot, without having run any of its registration code. This allows a single
+    /// library to ship several class sets and enable them at runtime, e.g. depending on the platform or on whether the editor is running.
+    ///
+    /// A class must not be excluded if another registered class inherits from it.
+    fn should_register_class(_class_name: ClassName) -> bool {
+        true
+    }
+
+    /// Whether to report objects of Rust classes that are still alive when the library is unloaded (`false` by default).
+    ///
+    /// If enabled, every instance of a `#[derive(GodotClass)]` class is tracked from creation to destruction. On unload, each
+    /// remaining one is printed as a warning with its class and instance ID. This catches forgotten `free()` calls on
+    /// manually-managed objects, as well as `RefCounted` reference cycles. In debug builds, the warning also contains the
+    /// backtrace of the creation site, if backtraces are enabled through the `RUST_BACKTRACE` environment variable.
+    ///
+    /// Tracking adds some overhead to the creation and destruction of each object, so this is meant for development.
+    /// A common choice is `cfg!(debug_assertions)`.
+    fn report_leaks() -> bool {
+        false
+    }
+
     /// Custom logic when a certain init-level of Godot is loaded.
     ///
     /// This will only be invoked for levels >= [`Self::min_level()`], in ascending order. Use `if` or `match` to hook to specific levels.
@@ -161,6 +274,8 @@ pub unsafe trait ExtensionLibrary {
     /// Custom logic when a certain init-level of Godot is unloaded.
     ///
     /// This will only be invoked for levels >= [`Self::min_level()`], in descending order. Use `if` or `match` to hook to specific levels.
+    ///
+    /// Classes registered at `level` are still available during this call; gdext unregisters them afterwards.
     fn on_level_deinit(_level: InitLevel) {
         // Nothing by default.
     }
@@ -192,6 +307,132 @@ pub enum EditorRunBehavior {
     AllClasses,
 }
 
+/// Determines what happens when a `#[func]` or virtual method override panics.
+///
+/// Panics never unwind into Godot; they are caught at the boundary of each call. The policy decides how the call then ends.
+/// Set it for the whole extension with [`ExtensionLibrary::panic_policy()`], or per class with `#[class(on_panic = ...)]`,
+/// e.g. `#[class(on_panic = Abort)]`.
+///
+/// Virtual methods and calls from Godot's engine code (ptrcalls) have no error channel: with `ReturnError`, they behave like
+/// `LogAndContinue`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PanicPolicy {
+    /// Prints the panic as Godot error and fails the call, returning `null`.
+    ///
+    /// GDScript callers report the failed call as script error, pointing to the calling line. This is the default.
+    ReturnError,
+
+    /// Prints the panic as Godot error, then returns the default value of the return type (`null` for dynamic calls).
+    ///
+    /// The caller continues as if the call had succeeded.
+    LogAndContinue,
+
+    /// Prints the panic as Godot warning, then returns the default value of the return type (`null` for dynamic calls).
+    ///
+    /// Like `LogAndContinue`, but for code where panics are expected and harmless: the report is a single warning line
+    /// rather than an error.
+    ReturnDefault,
+
+    /// Prints the panic as Godot error and aborts the process.
+    ///
+    /// Prevents the game from continuing in an inconsistent state, at the cost of a crash.
+    Abort,
+}
+
+/// Policy of the extension, set on library load.
+static PANIC_POLICY: OnceLock<PanicPolicy> = OnceLock::new();
+
+/// Effective policy for a class with the given `#[class(on_panic)]` setting.
+pub(crate) fn panic_policy(class_policy: Option<PanicPolicy>) -> PanicPolicy {
+    class_policy.unwrap_or_else(|| {
+        PANIC_POLICY
+            .get()
+            .copied()
+            .unwrap_or(PanicPolicy::ReturnError)
+    })
+}
+
+/// Information about a panic in a `#[func]` or virtual method, passed to the hook registered with [`set_panic_hook()`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct PanicContext<'a> {
+    /// Class whose method panicked.
+    pub class_name: ClassName,
+
+    /// Name of the method, as registered with Godot (e.g. `_process` for virtual methods).
+    pub method_name: &'a str,
+
+    /// Panic message, if the payload was a string.
+    pub message: Option<&'a str>,
+
+    /// Source file and line of the panic, if known.
+    pub location: Option<(&'a str, u32)>,
+
+    /// How the call ends after the hook returns.
+    pub policy: PanicPolicy,
+}
+
+/// Panic hook, as registered with [`set_panic_hook()`].
+pub type PanicHook = Box<dyn Fn(&PanicContext) + Send + Sync>;
+
+static PANIC_HOOK: RwLock<Option<PanicHook>> = RwLock::new(None);
+
+/// Registers a function that is invoked whenever a `#[func]` or virtual method of this extension panics.
+///
+/// Panics are always caught at the boundary to Godot, so that a single failing callback (e.g. `_process`) does not take
+/// down the game or editor. The hook additionally receives the class and method context, e.g. to collect crash telemetry.
+/// It runs after the panic has been caught, before the [`PanicPolicy`] is applied. Registering a new hook replaces the
+/// previous one.
+///
+/// ```no_run
+/// use godot::init::set_panic_hook;
+///
+/// set_panic_hook(|ctx| {
+///     eprintln!("{}::{} panicked: {:?}", ctx.class_name, ctx.method_name, ctx.message);
+/// });
+/// ```
+pub fn set_panic_hook<F>(hook: F)
+where
+    F: Fn(&PanicContext) + Send + Sync + 'static,
+{
+    // A panicking hook cannot poison the lock, see call_panic_hook().
+    *PANIC_HOOK.write().unwrap() = Some(Box::new(hook));
+}
+
+/// Unregisters the panic hook and returns it, if any.
+///
+/// Allows to restore a previous hook after temporarily replacing it, by passing it to [`set_panic_hook()`] again.
+pub fn take_panic_hook() -> Option<PanicHook> {
+    PANIC_HOOK.write().unwrap().take()
+}
+
+/// Unregisters the panic hook, if any. Panics are then only handled according to the [`PanicPolicy`].
+pub fn clear_panic_hook() {
+    drop(take_panic_hook());
+}
+
+/// Invokes the registered panic hook, if any. Panics in the hook itself are caught and printed.
+pub(crate) fn call_panic_hook(context: &PanicContext) {
+    let Ok(hook) = PANIC_HOOK.read() else {
+        return;
+    };
+
+    if let Some(hook) = hook.as_ref() {
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| hook(context)));
+        if result.is_err() {
+            crate::log::godot_error!("panic hook itself panicked");
+        }
+    }
+}
+
+/// Whether `#[func]` varcalls are validated, set on library load.
+static VALIDATE_CALLS: AtomicBool = AtomicBool::new(true);
+
+pub(crate) fn validate_calls() -> bool {
+    VALIDATE_CALLS.load(Ordering::Relaxed)
+}
+
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 
 /// Stage of the Godot initialization process.
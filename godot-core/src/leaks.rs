@@ -0,0 +1,135 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Tracking of live Rust-backed objects, to report leaks when the library is unloaded.
+//!
+//! Only active if [`ExtensionLibrary::report_leaks()`][crate::init::ExtensionLibrary::report_leaks] returns true.
+
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use godot_ffi as sys;
+use sys::interface_fn;
+
+use crate::builtin::meta::ClassName;
+use crate::log::godot_warn;
+use crate::obj::InstanceId;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+struct LiveObject {
+    class_name: ClassName,
+    instance_id: Option<InstanceId>,
+    backtrace: Option<Backtrace>,
+}
+
+/// Live objects, by address of their instance storage.
+///
+/// Objects are created and destroyed on any thread, so the map is sharded by address: threads spawning objects concurrently
+/// mostly lock different shards.
+fn live_objects(instance_ptr: sys::GDExtensionClassInstancePtr) -> &'static LiveShard {
+    // Storages are at least 16 bytes apart; the low bits carry no information.
+    let index = (instance_ptr as usize >> 4) % SHARD_COUNT;
+    &all_live_objects()[index]
+}
+
+fn all_live_objects() -> &'static [LiveShard; SHARD_COUNT] {
+    static LIVE_OBJECTS: OnceLock<[LiveShard; SHARD_COUNT]> = OnceLock::new();
+    LIVE_OBJECTS.get_or_init(|| std::array::from_fn(|_| LiveShard::default()))
+}
+
+type LiveShard = Mutex<HashMap<usize, LiveObject>>;
+
+const SHARD_COUNT: usize = 16;
+
+pub(crate) fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Registers a newly created instance of `class_name`, whose storage is at `instance_ptr`.
+pub(crate) fn on_create(
+    class_name: ClassName,
+    instance_ptr: sys::GDExtensionClassInstancePtr,
+    object_ptr: sys::GDExtensionObjectPtr,
+) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let id = unsafe { interface_fn!(object_get_instance_id)(object_ptr) };
+
+    // Creation sites are only captured in debug builds, and only if enabled through RUST_BACKTRACE.
+    let backtrace = if cfg!(debug_assertions) {
+        Some(Backtrace::capture())
+    } else {
+        None
+    };
+
+    let object = LiveObject {
+        class_name,
+        instance_id: InstanceId::try_from_u64(id),
+        backtrace,
+    };
+
+    live_objects(instance_ptr)
+        .lock()
+        .expect("leak registry poisoned")
+        .insert(instance_ptr as usize, object);
+}
+
+/// Unregisters the instance whose storage is at `instance_ptr`, as it is being destroyed.
+pub(crate) fn on_free(instance_ptr: sys::GDExtensionClassInstancePtr) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    live_objects(instance_ptr)
+        .lock()
+        .expect("leak registry poisoned")
+        .remove(&(instance_ptr as usize));
+}
+
+/// Prints a warning for each object that is still alive, then stops tracking.
+pub(crate) fn report() {
+    if !ENABLED.swap(false, Ordering::Relaxed) {
+        return;
+    }
+
+    let mut leaked: Vec<LiveObject> = all_live_objects()
+        .iter()
+        .flat_map(|shard| std::mem::take(&mut *shard.lock().expect("leak registry poisoned")))
+        .map(|(_, object)| object)
+        .collect();
+    if leaked.is_empty() {
+        return;
+    }
+
+    leaked.sort_by_key(|object| object.instance_id.map(InstanceId::to_i64));
+
+    godot_warn!(
+        "{} Rust object(s) still alive when unloading the extension:",
+        leaked.len()
+    );
+
+    for object in leaked {
+        let id = match object.instance_id {
+            Some(id) => id.to_string(),
+            None => "?".to_string(),
+        };
+
+        match object.backtrace {
+            Some(backtrace) if backtrace.status() == BacktraceStatus::Captured => {
+                godot_warn!(
+                    "  {} (instance {id}), created at:\n{backtrace}",
+                    object.class_name
+                )
+            }
+            _ => godot_warn!("  {} (instance {id})", object.class_name),
+        }
+    }
+}
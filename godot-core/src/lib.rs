@@ -4,15 +4,31 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+mod closure;
+mod deferred;
+mod diag;
+mod dispatch;
+mod leaks;
+mod monitor;
+mod pool;
 mod registry;
 mod storage;
 
+pub mod allocator;
+pub mod audio;
+pub mod batch;
 pub mod builder;
 pub mod builtin;
+pub mod docs;
+pub mod error;
+pub mod http;
 pub mod init;
 pub mod log;
 pub mod obj;
+#[cfg(feature = "profiling")]
+pub mod profiling;
 pub mod property;
+pub mod task;
 
 pub use godot_ffi as sys;
 #[doc(hidden)]
@@ -39,6 +55,7 @@ pub mod engine;
 #[allow(clippy::wrong_self_convention)] // to_string() is const
 #[allow(clippy::upper_case_acronyms)] // TODO remove this line once we transform names
 #[allow(unreachable_code, clippy::unimplemented)] // TODO remove once #153 is implemented
+#[allow(deprecated)] // generated code uses deprecated engine APIs internally
 mod gen;
 
 #[doc(hidden)]
@@ -48,13 +65,25 @@ pub mod private {
     pub trait You_forgot_the_attribute__godot_api {}
     pub use crate::property::Cannot_export_without_godot_api_impl;
 
+    // Without the `threads` feature, instance storage is not synchronized; this makes #[class(thread_safe)] a compile error.
+    #[allow(non_camel_case_types)]
+    pub trait Thread_safe_classes_require_the_threads_feature {}
+    #[cfg(feature = "threads")]
+    impl<T> Thread_safe_classes_require_the_threads_feature for T {}
+
     use std::sync::{Arc, Mutex};
 
     pub use crate::gen::classes::class_macros;
-    pub use crate::registry::{callbacks, ClassPlugin, ErasedRegisterFn, PluginComponent};
+    pub use crate::registry::{
+        callbacks, registered_level, ClassPlugin, ErasedRegisterFn, PluginComponent,
+    };
     pub use crate::storage::as_storage;
     pub use godot_ffi::out;
 
+    use crate::builtin::meta::{CallResult, ClassName};
+    use crate::builtin::{ToVariant, Variant};
+    use crate::init::{PanicContext, PanicPolicy};
+    use crate::obj::Gd;
     use crate::{log, sys};
 
     sys::plugin_registry!(pub __GODOT_PLUGIN_REGISTRY: ClassPlugin);
@@ -65,6 +94,7 @@ pub mod private {
 
     pub struct ClassConfig {
         pub is_tool: bool,
+        pub panic_policy: Option<PanicPolicy>,
     }
 
     pub fn is_class_inactive(is_tool: bool) -> bool {
@@ -74,17 +104,50 @@ pub mod private {
 
         // SAFETY: only invoked after global library initialization.
         let global_config = unsafe { sys::config() };
-        let is_editor = || crate::engine::Engine::singleton().is_editor_hint();
 
         global_config.tool_only_in_editor //.
-            && *global_config.is_editor.get_or_init(is_editor)
+            && crate::engine::is_editor_hint()
     }
 
-    fn print_panic(err: Box<dyn std::any::Any + Send>) {
+    /// Panics if a `#[func(tool_only)]` is called outside the editor, or a `#[func(runtime_only)]` inside it.
+    pub fn ensure_func_context(method_name: &str, tool_only: bool) {
+        let is_editor = crate::engine::is_editor_hint();
+
+        if tool_only && !is_editor {
+            panic!("#[func(tool_only)] `{method_name}` can only be called in the editor");
+        }
+        if !tool_only && is_editor {
+            panic!("#[func(runtime_only)] `{method_name}` cannot be called in the editor");
+        }
+    }
+
+    /// Hands a message of `godot_error!` and similar macros to the innermost `godot::error::capture()` scope, if any.
+    ///
+    /// Returns whether the message was captured; if not, it should be printed.
+    pub fn capture_message(godot_fn: &str, message: &str) -> bool {
+        use crate::error::Severity;
+
+        let severity = match godot_fn {
+            "print_error" => Severity::Error,
+            "print_warning" => Severity::Warning,
+            "print_script_error" => Severity::ScriptError,
+            _ => return false,
+        };
+
+        crate::error::capture_message(severity, message)
+    }
+
+    fn panic_message(err: &(dyn std::any::Any + Send)) -> Option<&str> {
         if let Some(s) = err.downcast_ref::<&'static str>() {
-            print_panic_message(s);
-        } else if let Some(s) = err.downcast_ref::<String>() {
-            print_panic_message(s.as_str());
+            Some(s)
+        } else {
+            err.downcast_ref::<String>().map(String::as_str)
+        }
+    }
+
+    fn print_panic(err: Box<dyn std::any::Any + Send>) {
+        if let Some(msg) = panic_message(err.as_ref()) {
+            print_panic_message(msg);
         } else {
             log::godot_error!("Rust panic of type ID {:?}", err.type_id());
         }
@@ -112,6 +175,104 @@ pub mod private {
     /// Returns `None` if a panic occurred, and `Some(result)` with the result of `code` otherwise.
     #[must_use]
     pub fn handle_panic<E, F, R, S>(error_context: E, code: F) -> Option<R>
+    where
+        E: FnOnce() -> S,
+        F: FnOnce() -> R + std::panic::UnwindSafe,
+        S: std::fmt::Display,
+    {
+        handle_panic_with(PanicPolicy::ReturnError, None, error_context, code).ok()
+    }
+
+    /// Executes `code` of a `#[func]` or virtual method, handling a panic according to the class's `#[class(on_panic)]` setting
+    /// or the extension's [`PanicPolicy`].
+    ///
+    /// Returns `Err(policy)` with the effective policy if a panic occurred, so that the caller can fail the call if required.
+    /// The hook registered with [`set_panic_hook()`][crate::init::set_panic_hook] is notified of the panic.
+    pub fn handle_func_panic<E, F, R, S>(
+        class_policy: Option<PanicPolicy>,
+        class_name: fn() -> ClassName,
+        error_context: E,
+        code: F,
+    ) -> Result<R, PanicPolicy>
+    where
+        E: FnOnce() -> S,
+        F: FnOnce() -> R + std::panic::UnwindSafe,
+        S: std::fmt::Display,
+    {
+        let policy = crate::init::panic_policy(class_policy);
+
+        #[cfg(all(debug_assertions, not(feature = "threads")))]
+        CALLBACK_DEPTH.with(|depth| depth.set(depth.get() + 1));
+
+        let result = handle_panic_with(policy, Some(class_name), error_context, code);
+
+        #[cfg(all(debug_assertions, not(feature = "threads")))]
+        CALLBACK_DEPTH.with(|depth| depth.set(depth.get() - 1));
+
+        result
+    }
+
+    #[cfg(all(debug_assertions, not(feature = "threads")))]
+    thread_local! {
+        /// Number of `#[func]` and virtual method calls from Godot currently on this thread's stack.
+        static CALLBACK_DEPTH: std::cell::Cell<usize> = std::cell::Cell::new(0);
+    }
+
+    /// How deeply nested the current code is in calls from Godot into Rust; 0 outside of any callback.
+    #[cfg(all(debug_assertions, not(feature = "threads")))]
+    pub(crate) fn callback_depth() -> usize {
+        CALLBACK_DEPTH.with(|depth| depth.get())
+    }
+
+    /// Converts the result of a `#[func]` returning `Result`. An `Err` fails the call, with the error as message.
+    ///
+    /// Unlike panics, errors are reported without source location, and regardless of the panic policy.
+    pub fn func_result<T, E: std::fmt::Display>(result: Result<T, E>) -> CallResult<T> {
+        result.map_err(|err| err.to_string())
+    }
+
+    /// Converts the result of a `#[func(err_as_variant)]` to a `Variant` holding either the `Ok` or the `Err` value. Never fails.
+    pub fn func_result_to_variant<T: ToVariant, E: ToVariant>(
+        result: Result<T, E>,
+    ) -> CallResult<Variant> {
+        let variant = match result {
+            Ok(value) => value.to_variant(),
+            Err(err) => err.to_variant(),
+        };
+        Ok(variant)
+    }
+
+    /// Starts the future of an `async` `#[func]`, returning the object that GDScript awaits. `convert` is applied to its output.
+    ///
+    /// If `convert` fails, the error is printed and the `completed` signal is never emitted, like for a panicking task.
+    pub fn spawn_async_func<F, C, R>(
+        method_name: &'static str,
+        future: F,
+        convert: C,
+    ) -> Gd<crate::engine::RefCounted>
+    where
+        F: std::future::Future + 'static,
+        C: FnOnce(F::Output) -> CallResult<R> + 'static,
+        R: ToVariant,
+    {
+        crate::task::spawn_emitting(async move {
+            match convert(future.await) {
+                Ok(value) => Some(value.to_variant()),
+                Err(message) => {
+                    log::godot_error!("{method_name}: {message}");
+                    None
+                }
+            }
+        })
+    }
+
+    /// Returns `Err(policy)` with the effective policy if a panic occurred.
+    fn handle_panic_with<E, F, R, S>(
+        policy: PanicPolicy,
+        class_name: Option<fn() -> ClassName>,
+        error_context: E,
+        code: F,
+    ) -> Result<R, PanicPolicy>
     where
         E: FnOnce() -> S,
         F: FnOnce() -> R + std::panic::UnwindSafe,
@@ -140,22 +301,60 @@ pub mod private {
         std::panic::set_hook(prev_hook);
 
         match panic {
-            Ok(result) => Some(result),
+            Ok(result) => Ok(result),
             Err(err) => {
                 // Flush, to make sure previous Rust output (e.g. test announcement, or debug prints during app) have been printed
                 // TODO write custom panic handler and move this there, before panic backtrace printing
                 flush_stdout();
 
                 let guard = info.lock().unwrap();
-                let info = guard.as_ref().expect("no panic info available");
+                let info = guard.as_ref();
+                let context = error_context();
+
+                if let Some(class_name) = class_name {
+                    let method_name = context.to_string();
+                    crate::init::call_panic_hook(&PanicContext {
+                        class_name: class_name(),
+                        method_name: &method_name,
+                        message: panic_message(err.as_ref()),
+                        location: info.map(|info| (info.file.as_str(), info.line)),
+                        policy,
+                    });
+                }
+
+                let info = info.expect("no panic info available");
+                crate::diag::diag!(
+                    Error,
+                    "panic caught at FFI boundary ({}:{}): {context}",
+                    info.file,
+                    info.line
+                );
+
+                if policy == PanicPolicy::ReturnDefault {
+                    // Panic is expected by the class; report it as a single warning instead of an error.
+                    log::godot_warn!(
+                        "Rust function panicked in file {} at line {}; returning default value. Context: {}\n  Panic msg: {}",
+                        info.file,
+                        info.line,
+                        context,
+                        panic_message(err.as_ref()).unwrap_or("(no message)")
+                    );
+                    return Err(policy);
+                }
+
                 log::godot_error!(
                     "Rust function panicked in file {} at line {}. Context: {}",
                     info.file,
                     info.line,
-                    error_context()
+                    context
                 );
                 print_panic(err);
-                None
+
+                if policy == PanicPolicy::Abort {
+                    flush_stdout();
+                    std::process::abort();
+                }
+                Err(policy)
             }
         }
     }
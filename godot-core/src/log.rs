@@ -11,20 +11,25 @@ macro_rules! inner_godot_msg {
     ($godot_fn:ident; $fmt:literal $(, $args:expr)* $(,)?) => {
     //($($args:tt),* $(,)?) => {
         unsafe {
-            let msg = format!("{}\0", format_args!($fmt $(, $args)*));
-            assert!(msg.is_ascii(), "godot_error: message must be ASCII");
+            let msg = format!($fmt $(, $args)*);
 
-            // Check whether engine is loaded, otherwise fall back to stderr.
-            if $crate::sys::is_initialized() {
+            // Inside godot::error::capture(), collect instead of printing.
+            // Otherwise, check whether engine is loaded, or fall back to stderr.
+            if $crate::private::capture_message(stringify!($godot_fn), &msg) {
+                // Captured.
+            } else if $crate::sys::is_initialized() {
+                // Messages may contain user input, e.g. paths or `Err` values of a `#[func]`.
+                let c_msg = $crate::log::to_ascii_c_string(&msg);
                 $crate::sys::interface_fn!($godot_fn)(
-                    $crate::sys::c_str_from_str(&msg),
-                    $crate::sys::c_str(b"<function unset>\0"),
+                    c_msg.as_ptr(),
+                    // Rust has no function_name!(); the module path at least locates the call site.
+                    $crate::sys::c_str_from_str(concat!(module_path!(), "\0")),
                     $crate::sys::c_str_from_str(concat!(file!(), "\0")),
                     line!() as i32,
                     false as $crate::sys::GDExtensionBool, // whether to create a toast notification in editor
                 );
             } else {
-                eprintln!("[{}] {}", stringify!($godot_fn), &msg[..msg.len() - 1]);
+                eprintln!("[{}] {}", stringify!($godot_fn), msg);
             }
         }
     };
@@ -73,20 +78,77 @@ macro_rules! godot_print {
     };
 }
 
-pub use crate::{godot_error, godot_print, godot_script_error, godot_warn};
+/// Prints to the Godot console, interpreting BBCode tags like `[b]` or `[color=red]`.
+///
+/// The editor's output panel shows the formatting; terminals get ANSI escape codes where supported.
+///
+/// _Godot equivalent: @GlobalScope.print_rich()_
+#[macro_export]
+macro_rules! godot_print_rich {
+    ($fmt:literal $(, $args:expr)* $(,)?) => {
+        $crate::log::print_rich(&[
+            $crate::builtin::Variant::from(
+                $crate::builtin::GodotString::from(
+                    format!($fmt $(, $args)*)
+                )
+            )
+        ])
+    };
+}
+
+/// Prints to the Godot console and the OS standard error stream, shown in red in the editor.
+///
+/// Unlike [`godot_error!`], this does not record the call site and does not show up in the debugger's error list.
+///
+/// _Godot equivalent: @GlobalScope.printerr()_
+#[macro_export]
+macro_rules! godot_printerr {
+    ($fmt:literal $(, $args:expr)* $(,)?) => {
+        $crate::log::printerr(&[
+            $crate::builtin::Variant::from(
+                $crate::builtin::GodotString::from(
+                    format!($fmt $(, $args)*)
+                )
+            )
+        ])
+    };
+}
+
+pub use crate::{
+    godot_error, godot_print, godot_print_rich, godot_printerr, godot_script_error, godot_warn,
+};
 
 use crate::builtin::{StringName, Variant};
 use crate::sys::{self, GodotFfi};
 
 /// Prints to the Godot console, used by the godot_print! macro.
 pub fn print(varargs: &[Variant]) {
+    call_print_utility("print", varargs);
+}
+
+/// Prints BBCode-formatted text to the Godot console, used by the godot_print_rich! macro.
+pub fn print_rich(varargs: &[Variant]) {
+    call_print_utility("print_rich", varargs);
+}
+
+/// Prints to the Godot console and stderr, used by the godot_printerr! macro.
+pub fn printerr(varargs: &[Variant]) {
+    call_print_utility("printerr", varargs);
+}
+
+// TODO use generated methods, but figure out how print() with zero args can be called
+// crate::engine::utilities::print(head, rest);
+fn call_print_utility(name: &str, varargs: &[Variant]) {
+    // All vararg print functions share the signature, thus the hash.
+    const PRINT_HASH: i64 = 2648703342;
+
     unsafe {
-        let method_name = StringName::from("print");
+        let method_name = StringName::from(name);
         let call_fn = sys::interface_fn!(variant_get_ptr_utility_function)(
             method_name.string_sys(),
-            2648703342i64,
+            PRINT_HASH,
         );
-        let call_fn = call_fn.unwrap_unchecked();
+        let call_fn = call_fn.unwrap_or_else(|| panic!("utility function `{name}` not found"));
 
         let mut args = Vec::new();
         args.extend(varargs.iter().map(Variant::sys_const));
@@ -96,7 +158,127 @@ pub fn print(varargs: &[Variant]) {
             call_fn(return_ptr, args_ptr, args.len() as i32);
         });
     }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Bridge for the `log` crate
+
+/// Logger for the [`log`](https://docs.rs/log) crate, forwarding records to the Godot console.
+///
+/// Errors and warnings are pushed to Godot's debugger like [`godot_error!`] and [`godot_warn!`], with the source location of the
+/// `log` macro invocation. Other levels are printed like [`godot_print!`], prefixed with level and target. Records less severe than
+/// the configured level are discarded.
+///
+/// This makes output of Rust libraries that use `log` visible in the editor. Install it once, e.g. in
+/// [`ExtensionLibrary::on_level_init()`][crate::init::ExtensionLibrary::on_level_init]:
+///
+/// ```no_run
+/// use godot::log::GodotLogger;
+///
+/// GodotLogger::init(log::LevelFilter::Info).expect("no other logger installed");
+/// ```
+#[cfg(feature = "log")]
+#[derive(Debug)]
+pub struct GodotLogger {
+    max_level: ::log::LevelFilter,
+}
+
+#[cfg(feature = "log")]
+impl GodotLogger {
+    /// Creates a logger which forwards records up to `max_level`.
+    pub const fn new(max_level: ::log::LevelFilter) -> Self {
+        Self { max_level }
+    }
+
+    /// Installs a `GodotLogger` as the global logger of the `log` crate and sets its maximum level.
+    ///
+    /// Fails if another logger has been installed before.
+    pub fn init(max_level: ::log::LevelFilter) -> Result<(), ::log::SetLoggerError> {
+        // The logger lives for the rest of the program; leaking it avoids requiring log's `std` feature.
+        let logger: &'static Self = Box::leak(Box::new(Self::new(max_level)));
+
+        ::log::set_logger(logger)?;
+        ::log::set_max_level(max_level);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "log")]
+impl ::log::Log for GodotLogger {
+    fn enabled(&self, metadata: &::log::Metadata) -> bool {
+        metadata.level() <= self.max_level
+    }
+
+    fn log(&self, record: &::log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let msg = format!("{}: {}", record.target(), record.args());
+        let file = record.file().unwrap_or("<unknown>");
+        let line = record.line().unwrap_or(0);
+
+        match record.level() {
+            ::log::Level::Error => push_message(false, &msg, file, line),
+            ::log::Level::Warn => push_message(true, &msg, file, line),
+            level => {
+                if sys::is_initialized() {
+                    print(&[Variant::from(crate::builtin::GodotString::from(format!(
+                        "[{level}] {msg}"
+                    )))]);
+                } else {
+                    eprintln!("[{level}] {msg}");
+                }
+            }
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Pushes an error or warning with an arbitrary source location to Godot's debugger.
+#[cfg(feature = "log")]
+fn push_message(is_warning: bool, msg: &str, file: &str, line: u32) {
+    if !sys::is_initialized() {
+        let kind = if is_warning { "WARNING" } else { "ERROR" };
+        eprintln!("[{kind}] {msg} (at {file}:{line})");
+        return;
+    }
+
+    let msg = to_ascii_c_string(msg);
+    let function = to_ascii_c_string("<log>");
+    let file = to_ascii_c_string(file);
+    let line = i32::try_from(line).unwrap_or(i32::MAX);
+
+    // SAFETY: all strings are valid and null-terminated for the duration of the call.
+    unsafe {
+        let print_fn = if is_warning {
+            sys::interface_fn!(print_warning)
+        } else {
+            sys::interface_fn!(print_error)
+        };
+
+        print_fn(
+            msg.as_ptr(),
+            function.as_ptr(),
+            file.as_ptr(),
+            line,
+            false as sys::GDExtensionBool,
+        );
+    }
+}
+
+/// Godot interprets the C strings of `print_error` as Latin-1; escape everything beyond ASCII, as well as null bytes.
+#[doc(hidden)]
+pub fn to_ascii_c_string(s: &str) -> std::ffi::CString {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_ascii() && c != '\0' {
+            escaped.push(c);
+        } else {
+            escaped.extend(c.escape_unicode());
+        }
+    }
 
-    // TODO use generated method, but figure out how print() with zero args can be called
-    // crate::engine::utilities::print(head, rest);
+    std::ffi::CString::new(escaped).expect("null bytes are escaped")
 }
@@ -0,0 +1,46 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Closures of custom performance monitors, added by [`PerformanceExt::add_monitor()`][crate::engine::PerformanceExt::add_monitor].
+//!
+//! Like `Gd::run_deferred()`, this works around the lack of Rust callables in Godot 4.1: the `Performance` singleton
+//! is given a `Callable` to a method of the internal dispatch object, with the monitor ID as argument.
+//! That method looks up and polls the closure stored here.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Name of the method of the dispatch class.
+pub(crate) const METHOD_NAME: &str = "poll_monitor";
+
+pub(crate) type MonitorFn = Box<dyn FnMut() -> f64>;
+
+thread_local! {
+    /// Closures by monitor ID. Godot polls monitors on the main thread.
+    static MONITORS: RefCell<HashMap<String, MonitorFn>> = RefCell::new(HashMap::new());
+}
+
+/// Stores the closure for a monitor, replacing a previous one with the same ID.
+pub(crate) fn insert(id: String, f: MonitorFn) {
+    MONITORS.with(|monitors| monitors.borrow_mut().insert(id, f));
+}
+
+/// Drops the closure for a monitor. Returns whether one was present.
+pub(crate) fn remove(id: &str) -> bool {
+    MONITORS.with(|monitors| monitors.borrow_mut().remove(id).is_some())
+}
+
+/// Polls the monitor's closure, or returns `None` if no monitor with this ID exists.
+pub(crate) fn poll(id: &str) -> Option<f64> {
+    // Take the closure out during the call, so that it can itself add or remove monitors.
+    let mut f = MONITORS.with(|monitors| monitors.borrow_mut().remove(id))?;
+    let value = f();
+
+    MONITORS.with(|monitors| {
+        monitors.borrow_mut().entry(id.to_string()).or_insert(f);
+    });
+    Some(value)
+}
@@ -4,10 +4,14 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use crate::obj::cap::WithBaseField;
 use crate::obj::Gd;
 use crate::obj::GodotClass;
+use crate::obj::Share;
+use crate::storage::Suspended;
 use crate::{engine, sys};
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
 use std::mem::ManuallyDrop;
 use std::ops::{Deref, DerefMut};
 
@@ -84,3 +88,52 @@ impl<T: GodotClass> DerefMut for Base<T> {
         &mut self.obj
     }
 }
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+/// Exclusive access to the base object of a user instance, returned by [`WithBaseField::base_mut()`].
+///
+/// While this guard is alive, the instance itself is inaccessible, as the guard borrows `&mut self`. Engine callbacks into the
+/// same instance (e.g. `enter_tree()` triggered by `add_child()` on the base) can thus bind it again, instead of panicking.
+pub struct BaseMut<'a, T: WithBaseField> {
+    // Restores the exclusive borrow on drop. Declared before `base`, so it is dropped first and never touches a storage that
+    // releasing `base` might have destroyed.
+    _suspended: Option<Suspended<'a>>,
+    // Own pointer rather than a reference into the instance: a re-entrant callback may obtain a new `&mut T`, which must not
+    // alias anything held here.
+    base: Gd<T::Base>,
+    // The borrow of the instance is only kept as a token, so that the instance cannot be used through it meanwhile.
+    _instance: PhantomData<&'a mut T>,
+}
+
+impl<'a, T: WithBaseField> BaseMut<'a, T> {
+    pub(crate) fn new(instance: &'a mut T) -> Self {
+        let instance_ptr: *const T = instance;
+        let base = instance.__godot_base_mut().share();
+
+        // SAFETY: the base object is the one the instance is attached to, so its storage (if already created) holds a `T`.
+        // The storage outlives the borrow of the instance.
+        let storage = unsafe { crate::storage::try_storage_of::<T>(base.obj_sys()) };
+        let suspended = storage.and_then(|storage| storage.suspend_exclusive(instance_ptr));
+
+        Self {
+            _suspended: suspended,
+            base,
+            _instance: PhantomData,
+        }
+    }
+}
+
+impl<T: WithBaseField> Deref for BaseMut<'_, T> {
+    type Target = Gd<T::Base>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl<T: WithBaseField> DerefMut for BaseMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
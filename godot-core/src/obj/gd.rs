@@ -20,6 +20,7 @@ use crate::builtin::meta::{ClassName, VariantMetadata};
 use crate::builtin::{
     Callable, FromVariant, StringName, ToVariant, Variant, VariantConversionError,
 };
+use crate::error::FreedError;
 use crate::obj::dom::Domain as _;
 use crate::obj::mem::Memory as _;
 use crate::obj::{cap, dom, mem, EngineEnum, GodotClass, Inherits, Share};
@@ -140,7 +141,10 @@ where
     /// * If another `Gd` smart pointer pointing to the same Rust instance has a live `GdMut` guard bound.
     /// * If there is an ongoing function call from GDScript to Rust, which currently holds a `&mut T`
     ///   reference to the user instance. This can happen through re-entrancy (Rust -> GDScript -> Rust call).
+    ///
+    /// In debug builds, the panic message names the location of the conflicting guard, and whether this is a re-entrant call.
     // Note: possible names: write/read, hold/hold_mut, r/w, r/rw, ...
+    #[track_caller]
     pub fn bind(&self) -> GdRef<T> {
         GdRef::from_cell(self.storage().get())
     }
@@ -156,10 +160,69 @@ where
     /// * If another `Gd` smart pointer pointing to the same Rust instance has a live `GdRef` or `GdMut` guard bound.
     /// * If there is an ongoing function call from GDScript to Rust, which currently holds a `&T` or `&mut T`
     ///   reference to the user instance. This can happen through re-entrancy (Rust -> GDScript -> Rust call).
+    ///
+    /// In debug builds, the panic message names the location of the conflicting guard, and whether this is a re-entrant call.
+    #[track_caller]
     pub fn bind_mut(&mut self) -> GdMut<T> {
         GdMut::from_cell(self.storage().get_mut())
     }
 
+    /// Returns a shared reference to the user instance, without runtime borrow checks.
+    ///
+    /// For hot paths where profiling shows that [`bind()`][Self::bind] is too costly. With the `threads` feature, the lock is
+    /// still taken briefly to obtain the reference.
+    ///
+    /// # Safety
+    /// As long as the returned reference is alive, no `&mut T` to the same instance may exist. This includes guards returned by
+    /// `bind_mut()`, and `&mut self` of `#[func]` methods called from Godot, e.g. through re-entrancy.
+    pub unsafe fn bind_unchecked(&self) -> &T {
+        &*self.storage().get_unchecked()
+    }
+
+    /// Returns an exclusive reference to the user instance, without runtime borrow checks.
+    ///
+    /// For hot paths where profiling shows that [`bind_mut()`][Self::bind_mut] is too costly. With the `threads` feature, the
+    /// lock is still taken briefly to obtain the reference.
+    ///
+    /// # Safety
+    /// As long as the returned reference is alive, no other reference to the same instance may exist. This includes guards
+    /// returned by `bind()` or `bind_mut()` on any `Gd` pointing to the instance, and `&self`/`&mut self` of `#[func]` methods
+    /// called from Godot, e.g. through re-entrancy.
+    pub unsafe fn bind_mut_unchecked(&mut self) -> &mut T {
+        &mut *self.storage().get_mut_unchecked()
+    }
+
+    /// Runs `f` on the user instance at idle time, like `call_deferred()` with a Rust closure.
+    ///
+    /// This spares a dedicated `#[func]` and its name as string. Closures run in scheduling order, with exclusive access to the
+    /// instance. They are not run if the object is freed before. To defer a closure that is not bound to an object, use
+    /// [`task::defer()`][crate::task::defer].
+    ///
+    /// ```no_run
+    /// # use godot::prelude::*;
+    /// #[derive(GodotClass)]
+    /// #[class(init, base=Node)]
+    /// struct Spawner {
+    ///     spawned: u32,
+    /// }
+    ///
+    /// # fn schedule(spawner: Gd<Spawner>) {
+    /// spawner.run_deferred(|this: &mut Spawner| this.spawned += 1);
+    /// # }
+    /// ```
+    pub fn run_deferred<F>(&self, f: F)
+    where
+        F: FnOnce(&mut T) + 'static,
+    {
+        let instance_id = self.instance_id();
+
+        crate::deferred::push(Box::new(move || {
+            if let Some(mut this) = Gd::<T>::try_from_instance_id(instance_id) {
+                f(&mut this.bind_mut());
+            }
+        }));
+    }
+
     /// Storage object associated with the extension instance.
     pub(crate) fn storage(&self) -> &InstanceStorage<T> {
         // SAFETY: instance pointer belongs to this instance. We only get a shared reference, no exclusive access, so even
@@ -285,6 +348,45 @@ impl<T: GodotClass> Gd<T> {
         })
     }
 
+    /// Dereferences this pointer, or returns an error if the object has been freed.
+    ///
+    /// Meant for long-lived references to manually-managed objects that may be freed elsewhere, e.g. a node that the scene
+    /// destroys with `queue_free()`. Instead of panicking on access, the freed object can be handled like any other error:
+    ///
+    /// ```no_run
+    /// # use godot::prelude::*;
+    /// # fn follow(target: &Gd<Node2D>, this: &mut Node2D) -> Result<(), godot::error::FreedError> {
+    /// let position = target.checked()?.get_global_position();
+    /// this.look_at(position);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The check and the following call are not atomic: the object must not be freed concurrently by another thread.
+    pub fn checked(&self) -> Result<&<Self as Deref>::Target, FreedError> {
+        self.check_alive()?;
+        Ok(self.deref())
+    }
+
+    /// Mutably dereferences this pointer, or returns an error if the object has been freed.
+    ///
+    /// See [`checked()`][Self::checked] for details.
+    pub fn checked_mut(&mut self) -> Result<&mut <Self as Deref>::Target, FreedError> {
+        self.check_alive()?;
+        Ok(self.deref_mut())
+    }
+
+    fn check_alive(&self) -> Result<(), FreedError> {
+        // Read before the validity check, which forgets the ID of dead objects.
+        let cached_id = self.instance_id_or_none_unchecked();
+
+        if self.is_instance_valid() {
+            Ok(())
+        } else {
+            Err(FreedError::new(T::class_name(), cached_id))
+        }
+    }
+
     /// Checks if this smart pointer points to a live object (read description!).
     ///
     /// Using this method is often indicative of bad design -- you should dispose of your pointers once an object is
@@ -404,6 +506,28 @@ impl<T: GodotClass> Gd<T> {
         sys::ptr_then(cast_object_ptr, |ptr| Gd::from_obj_sys_weak(ptr))
     }
 
+    /// Panics if this is a node inside the scene tree, and the current thread is not the main thread.
+    #[cfg(feature = "debug-threads")]
+    fn audit_thread_access(&self) {
+        if crate::obj::thread_audit::is_main_thread() {
+            return;
+        }
+
+        let Some(node) = (unsafe { self.ffi_cast::<engine::Node>() }) else {
+            return;
+        };
+
+        // Not through Deref, which would run this check again.
+        // SAFETY: same layout assumption as in Deref.
+        let node_ref = unsafe { std::mem::transmute::<&OpaqueObject, &engine::Node>(&node.opaque) };
+        let in_tree = node_ref.is_inside_tree();
+        std::mem::forget(node); // no ownership transfer
+
+        if in_tree {
+            crate::obj::thread_audit::panic_tree_access(&T::class_name().to_string());
+        }
+    }
+
     pub(crate) fn as_ref_counted<R>(&self, apply: impl Fn(&mut engine::RefCounted) -> R) -> R {
         debug_assert!(
             self.is_instance_valid(),
@@ -483,6 +607,9 @@ where
     /// Not calling `free()` on manually-managed instances causes memory leaks, unless their ownership is delegated, for
     /// example to the node tree in case of nodes.
     ///
+    /// With the `debug-liveness` feature, the call site is recorded, and later dereferences of other `Gd` pointers to the same
+    /// object panic with its backtrace.
+    ///
     /// # Panics
     /// * When the referred-to object has already been destroyed.
     /// * When this is invoked on an upcast `Gd<Object>` that dynamically points to a reference-counted type (i.e. operation not supported).
@@ -502,6 +629,9 @@ where
             "called free() on already destroyed object"
         );
 
+        #[cfg(feature = "debug-liveness")]
+        crate::obj::liveness::record_free(self.instance_id());
+
         // This destroys the Storage instance, no need to run destructor again
         unsafe {
             interface_fn!(object_destroy)(self.obj_sys());
@@ -518,6 +648,11 @@ impl<T: GodotClass> Deref for Gd<T> {
     type Target = <<T as GodotClass>::Declarer as dom::Domain>::DerefTarget<T>;
 
     fn deref(&self) -> &Self::Target {
+        #[cfg(feature = "debug-liveness")]
+        crate::obj::liveness::check_alive(self);
+        #[cfg(feature = "debug-threads")]
+        self.audit_thread_access();
+
         // SAFETY:
         //
         // This relies on `Gd<Node3D>.opaque` having the layout as `Node3D` (as an example),
@@ -536,6 +671,11 @@ impl<T: GodotClass> Deref for Gd<T> {
 
 impl<T: GodotClass> DerefMut for Gd<T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
+        #[cfg(feature = "debug-liveness")]
+        crate::obj::liveness::check_alive(self);
+        #[cfg(feature = "debug-threads")]
+        self.audit_thread_access();
+
         // SAFETY: see also Deref
         //
         // The resulting `&mut T` is transmuted from `&mut OpaqueObject`, i.e. a *pointer* to the `opaque` field.
@@ -4,33 +4,22 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-#[cfg(not(feature = "threads"))]
-use std::cell;
 use std::fmt::Debug;
 use std::ops::{Deref, DerefMut};
-#[cfg(feature = "threads")]
-use std::sync;
+
+use crate::storage::{MutGuard, RefGuard};
 
 /// Immutably/shared bound reference guard for a [`Gd`][crate::obj::Gd] smart pointer.
 ///
 /// See [`Gd::bind`][crate::obj::Gd::bind] for usage.
 #[derive(Debug)]
 pub struct GdRef<'a, T> {
-    #[cfg(not(feature = "threads"))]
-    cell_ref: cell::Ref<'a, T>,
-
-    #[cfg(feature = "threads")]
-    cell_ref: sync::RwLockReadGuard<'a, T>,
+    // Shared guard of the borrow-tracking cell (single-threaded) or RwLockReadGuard (`threads`).
+    cell_ref: RefGuard<'a, T>,
 }
 
 impl<'a, T> GdRef<'a, T> {
-    #[cfg(not(feature = "threads"))]
-    pub(crate) fn from_cell(cell_ref: cell::Ref<'a, T>) -> Self {
-        Self { cell_ref }
-    }
-
-    #[cfg(feature = "threads")]
-    pub(crate) fn from_cell(cell_ref: sync::RwLockReadGuard<'a, T>) -> Self {
+    pub(crate) fn from_cell(cell_ref: RefGuard<'a, T>) -> Self {
         Self { cell_ref }
     }
 }
@@ -52,21 +41,12 @@ impl<T> Deref for GdRef<'_, T> {
 /// See [`Gd::bind_mut`][crate::obj::Gd::bind_mut] for usage.
 #[derive(Debug)]
 pub struct GdMut<'a, T> {
-    #[cfg(not(feature = "threads"))]
-    cell_ref: cell::RefMut<'a, T>,
-
-    #[cfg(feature = "threads")]
-    cell_ref: sync::RwLockWriteGuard<'a, T>,
+    // Exclusive guard of the borrow-tracking cell (single-threaded) or RwLockWriteGuard (`threads`).
+    cell_ref: MutGuard<'a, T>,
 }
 
 impl<'a, T> GdMut<'a, T> {
-    #[cfg(not(feature = "threads"))]
-    pub(crate) fn from_cell(cell_ref: cell::RefMut<'a, T>) -> Self {
-        Self { cell_ref }
-    }
-
-    #[cfg(feature = "threads")]
-    pub(crate) fn from_cell(cell_ref: sync::RwLockWriteGuard<'a, T>) -> Self {
+    pub(crate) fn from_cell(cell_ref: MutGuard<'a, T>) -> Self {
         Self { cell_ref }
     }
 }
@@ -0,0 +1,74 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Liveness checks for manually-managed objects, enabled by the `debug-liveness` feature.
+//!
+//! `Gd::free()` records where each object was freed. Dereferencing a `Gd` whose object no longer exists then panics with
+//! that backtrace, instead of crashing somewhere inside Godot.
+
+use std::backtrace::Backtrace;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use crate::engine::utilities;
+use crate::obj::mem::Memory as _;
+use crate::obj::{Gd, GodotClass, InstanceId};
+
+/// Backtraces of `Gd::free()` calls, by instance ID of the freed object.
+///
+/// Entries are never removed: instance IDs are not reused by Godot, and the map only grows with the number of freed objects.
+fn freed_sites() -> &'static Mutex<HashMap<InstanceId, Backtrace>> {
+    static FREED_SITES: OnceLock<Mutex<HashMap<InstanceId, Backtrace>>> = OnceLock::new();
+    FREED_SITES.get_or_init(Default::default)
+}
+
+/// Remembers the call site of `Gd::free()` for the object `id`.
+pub(crate) fn record_free(id: InstanceId) {
+    let backtrace = Backtrace::force_capture();
+
+    freed_sites()
+        .lock()
+        .expect("liveness registry poisoned")
+        .insert(id, backtrace);
+}
+
+/// Panics if `obj` points to an object which has been freed, naming the site where that happened.
+///
+/// Ref-counted objects are kept alive by the `Gd` itself and are not checked.
+pub(crate) fn check_alive<T: GodotClass>(obj: &Gd<T>) {
+    // Read before any other access: a failed validity check may clear the cached ID.
+    let cached_id = obj.instance_id_or_none_unchecked();
+
+    match T::Mem::is_ref_counted(obj) {
+        Some(true) => return,
+        Some(false) => {
+            let alive = cached_id.map_or(false, |id| utilities::is_instance_id_valid(id.to_i64()));
+            if alive {
+                return;
+            }
+        }
+        None => {} // dynamic type, already dead
+    }
+
+    let class_name = T::class_name();
+    let Some(id) = cached_id else {
+        panic!("Gd<{class_name}> used after its object was freed");
+    };
+
+    // Lock released before panicking, so the registry is not poisoned.
+    let freed_site = match freed_sites()
+        .lock()
+        .expect("liveness registry poisoned")
+        .get(&id)
+    {
+        Some(backtrace) => format!("Object was freed at:\n{backtrace}"),
+        None => "Object was not freed through Gd::free(); it may have been freed by Godot \
+            (e.g. queue_free() or a freed parent node)."
+            .to_string(),
+    };
+
+    panic!("Gd<{class_name}> used after its object (instance {id}) was freed.\n{freed_site}");
+}
@@ -9,15 +9,36 @@
 //! The most important symbols in this module are:
 //! * [`GodotClass`], which is implemented for every class that Godot can work with (either engine- or user-provided).
 //! * [`Gd`], a smart pointer that manages instances of Godot classes.
+//!
+//! # Thread safety
+//!
+//! [`Gd`] is neither `Send` nor `Sync`. Objects generally belong to the main thread: nodes inside the scene tree must only be
+//! accessed from there, and instances of Rust classes track their `bind()`/`bind_mut()` borrows with a `RefCell`. To refer to
+//! an object from another thread, send a [`ThreadHandle`] or an [`InstanceId`] instead, and resolve it on the main thread.
+//!
+//! With the `threads` feature, instance storage uses a `RwLock` instead of a `RefCell`. Classes declared with
+//! `#[class(thread_safe)]` (see [`cap::ThreadSafe`]) may then be resolved and bound on any thread, e.g. by a worker that updates
+//! a shared data model.
+//!
+//! Built-in types are `Send` and `Sync`. Strings and packed arrays are copy-on-write with atomic reference counts, so they can
+//! be moved between threads freely. `Array` and `Dictionary` are shared by reference, and Godot does not synchronize concurrent
+//! modifications of the same container. A `Variant` holding an object follows the rules for objects.
 
 mod base;
 mod gd;
 mod guards;
 mod instance_id;
+#[cfg(feature = "debug-liveness")]
+mod liveness;
+mod script;
+pub(crate) mod thread_audit;
+mod thread_handle;
 mod traits;
 
 pub use base::*;
 pub use gd::*;
 pub use guards::*;
 pub use instance_id::*;
+pub use script::*;
+pub use thread_handle::*;
 pub use traits::*;
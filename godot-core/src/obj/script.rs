@@ -0,0 +1,533 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::c_void;
+
+use godot_ffi as sys;
+
+use crate::builtin::meta::PropertyInfo;
+use crate::builtin::{GodotString, StringName, Variant, VariantType};
+use crate::engine::global::MethodFlags;
+use crate::engine::{Script, ScriptLanguage};
+use crate::obj::{EngineEnum, Gd};
+
+#[cfg(since_api = "4.2")]
+use crate::builtin::{dict, Array, Dictionary};
+#[cfg(since_api = "4.2")]
+use crate::engine::Object;
+#[cfg(since_api = "4.2")]
+use sys::GodotFfi;
+
+/// Implemented by instances of custom scripts, which are attached to objects.
+///
+/// Together with [`ScriptExtension`][crate::engine::ScriptExtension] and
+/// [`ScriptLanguageExtension`][crate::engine::ScriptLanguageExtension], this allows implementing scripting languages (or data-driven
+/// "script" systems) in Rust. Your `ScriptExtension` implementation creates instances in its virtual `instance_create()` method,
+/// by passing a `ScriptInstance` to [`create_script_instance()`] and returning the resulting pointer.
+///
+/// Godot calls into the instance whenever the scripted object is accessed: property reads/writes, method calls and notifications.
+///
+/// # Re-entrancy
+/// Like [`Gd::bind_mut()`], mutating methods (`set`, `call`, `on_notification`) exclusively borrow the instance. If such a method
+/// calls back into Godot, which in turn accesses the same script instance, the inner access fails: an error is printed and Godot
+/// receives a failure value (e.g. `set` returns `false`, `call` reports `GDEXTENSION_CALL_ERROR_INVALID_METHOD`).
+///
+/// The same applies to panics in any method; they never unwind into the engine.
+pub trait ScriptInstance {
+    /// Sets the property `name` to `value`. Returns `false` if the script has no such property.
+    fn set(&mut self, name: StringName, value: &Variant) -> bool;
+
+    /// Returns the value of the property `name`, or `None` if the script has no such property.
+    fn get(&self, name: StringName) -> Option<Variant>;
+
+    /// Properties declared by the script, as shown in the inspector.
+    fn get_property_list(&self) -> Vec<PropertyInfo>;
+
+    /// Methods declared by the script.
+    fn get_method_list(&self) -> Vec<ScriptMethodInfo>;
+
+    /// Calls the script method `method`.
+    ///
+    /// On failure, returns the call error to report to the caller, e.g. `sys::GDEXTENSION_CALL_ERROR_INVALID_METHOD`.
+    fn call(
+        &mut self,
+        method: StringName,
+        args: &[&Variant],
+    ) -> Result<Variant, sys::GDExtensionCallErrorType>;
+
+    /// Whether this is a placeholder instance, i.e. one that only stores property values (e.g. for scripts not running in the editor).
+    fn is_placeholder(&self) -> bool;
+
+    /// Whether the script declares a method named `method`.
+    fn has_method(&self, method: StringName) -> bool;
+
+    /// The script resource of which this is an instance.
+    fn get_script(&self) -> &Gd<Script>;
+
+    /// Type of the property `name`, or `None` if the script has no such property.
+    fn get_property_type(&self, name: StringName) -> Option<VariantType>;
+
+    /// String representation of the scripted object, used by `str()` and `print()`.
+    ///
+    /// Returning `None` falls back to the default representation of the object.
+    fn to_string(&self) -> Option<GodotString> {
+        None
+    }
+
+    /// The language of the script.
+    fn get_language(&self) -> Gd<ScriptLanguage>;
+
+    /// Called when the scripted object receives a notification.
+    fn on_notification(&mut self, what: i32) {
+        let _ = what;
+    }
+}
+
+/// Method declared by a script, as reported by [`ScriptInstance::get_method_list()`].
+#[derive(Debug)]
+pub struct ScriptMethodInfo {
+    pub method_name: StringName,
+    pub return_type: PropertyInfo,
+    pub arguments: Vec<PropertyInfo>,
+    pub flags: MethodFlags,
+}
+
+/// Creates a script instance to be returned from `ScriptExtensionVirtual::instance_create()`.
+///
+/// Ownership of `rust_instance` is transferred to Godot, which destroys it together with the scripted object.
+pub fn create_script_instance<T: ScriptInstance>(rust_instance: T) -> *mut c_void {
+    // Godot keeps the pointer to the info struct for the instance's lifetime. Generic statics are not possible, so the info is stored
+    // alongside the instance data.
+    let info = sys::GDExtensionScriptInstanceInfo {
+        set_func: Some(script_instance_info::set::<T>),
+        get_func: Some(script_instance_info::get::<T>),
+        get_property_list_func: Some(script_instance_info::get_property_list::<T>),
+        free_property_list_func: Some(script_instance_info::free_property_list::<T>),
+        property_can_revert_func: None,
+        property_get_revert_func: None,
+        get_owner_func: None,
+        get_property_state_func: None,
+        get_method_list_func: Some(script_instance_info::get_method_list::<T>),
+        free_method_list_func: Some(script_instance_info::free_method_list::<T>),
+        get_property_type_func: Some(script_instance_info::get_property_type::<T>),
+        has_method_func: Some(script_instance_info::has_method::<T>),
+        call_func: Some(script_instance_info::call::<T>),
+        notification_func: Some(script_instance_info::notification::<T>),
+        to_string_func: Some(script_instance_info::to_string::<T>),
+        refcount_incremented_func: None,
+        refcount_decremented_func: None,
+        get_script_func: Some(script_instance_info::get_script::<T>),
+        is_placeholder_func: Some(script_instance_info::is_placeholder::<T>),
+        set_fallback_func: None,
+        get_fallback_func: None,
+        get_language_func: Some(script_instance_info::get_language::<T>),
+        free_func: Some(script_instance_info::free::<T>),
+    };
+
+    let data = Box::new(ScriptInstanceData {
+        inner: RefCell::new(rust_instance),
+        info,
+        property_lists: RefCell::new(HashMap::new()),
+        method_lists: RefCell::new(HashMap::new()),
+    });
+
+    let info_ptr = &data.info as *const sys::GDExtensionScriptInstanceInfo;
+    let data_ptr = Box::into_raw(data);
+
+    // SAFETY: `info_ptr` points into the boxed data, which stays alive until Godot calls `free_func`.
+    unsafe { sys::interface_fn!(script_instance_create)(info_ptr, data_ptr as *mut c_void) }
+}
+
+/// Creates a placeholder script instance, which only stores property values and does not run any script code.
+///
+/// Godot uses placeholders for scripts that cannot run in the current context, e.g. non-`@tool` scripts in the editor. Return the
+/// pointer from `ScriptExtensionVirtual::placeholder_instance_create()`, and keep it in sync with the script's properties using
+/// [`update_placeholder_script_instance()`].
+#[cfg(since_api = "4.2")]
+pub fn create_placeholder_script_instance(
+    language: Gd<ScriptLanguage>,
+    script: Gd<Script>,
+    owner: Gd<Object>,
+) -> *mut c_void {
+    // SAFETY: all three objects are alive for the duration of the call; Godot takes its own reference to the script.
+    unsafe {
+        sys::interface_fn!(placeholder_script_instance_create)(
+            language.obj_sys(),
+            script.obj_sys(),
+            owner.obj_sys(),
+        )
+    }
+}
+
+/// Updates the property list and values of a placeholder created by [`create_placeholder_script_instance()`].
+///
+/// Properties not contained in `values` keep their default value.
+///
+/// # Safety
+/// `placeholder` must be a pointer returned by [`create_placeholder_script_instance()`], whose object is still alive.
+#[cfg(since_api = "4.2")]
+pub unsafe fn update_placeholder_script_instance(
+    placeholder: *mut c_void,
+    properties: &[PropertyInfo],
+    values: &Dictionary,
+) {
+    let properties: Array<Dictionary> = properties
+        .iter()
+        .map(|property| {
+            dict! {
+                "name": property.property_name.clone(),
+                "type": property.variant_type as i32,
+                "class_name": property.class_name.to_string_name(),
+                "hint": property.hint.ord(),
+                "hint_string": property.hint_string.clone(),
+                "usage": property.usage.ord(),
+            }
+        })
+        .collect();
+
+    sys::interface_fn!(placeholder_script_instance_update)(
+        placeholder,
+        properties.sys(),
+        values.sys(),
+    );
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Implementation
+
+struct ScriptInstanceData<T: ScriptInstance> {
+    inner: RefCell<T>,
+    info: sys::GDExtensionScriptInstanceInfo,
+
+    // Lists handed out to Godot, kept alive until Godot frees them. Keyed by the pointer passed to Godot.
+    property_lists: RefCell<HashMap<*const sys::GDExtensionPropertyInfo, OwnedPropertyList>>,
+    method_lists: RefCell<HashMap<*const sys::GDExtensionMethodInfo, OwnedMethodList>>,
+}
+
+struct OwnedPropertyList {
+    _infos: Vec<PropertyInfo>,
+    _sys: Box<[sys::GDExtensionPropertyInfo]>,
+}
+
+struct OwnedMethodList {
+    _infos: Vec<ScriptMethodInfo>,
+    _arguments: Vec<Box<[sys::GDExtensionPropertyInfo]>>,
+    _sys: Box<[sys::GDExtensionMethodInfo]>,
+}
+
+mod script_instance_info {
+    use super::*;
+
+    /// # Safety
+    /// `instance` must be a pointer created by [`create_script_instance()`] and not yet freed.
+    unsafe fn instance_data<'a, T: ScriptInstance>(
+        instance: sys::GDExtensionScriptInstanceDataPtr,
+    ) -> &'a ScriptInstanceData<T> {
+        &*(instance as *const ScriptInstanceData<T>)
+    }
+
+    /// Clones a string name owned by Godot.
+    unsafe fn borrow_string_name(name: sys::GDExtensionConstStringNamePtr) -> StringName {
+        (*(name as *const StringName)).clone()
+    }
+
+    /// Runs the body of a callback. A panic (including a conflicting borrow of the instance) is printed to Godot, and `failure`
+    /// is returned instead, so the panic never unwinds into the engine.
+    fn guard<R>(callback: &str, failure: R, code: impl FnOnce() -> R) -> R {
+        crate::private::handle_panic(
+            || format!("ScriptInstance::{callback}()"),
+            std::panic::AssertUnwindSafe(code),
+        )
+        .unwrap_or(failure)
+    }
+
+    pub(super) unsafe extern "C" fn set<T: ScriptInstance>(
+        instance: sys::GDExtensionScriptInstanceDataPtr,
+        name: sys::GDExtensionConstStringNamePtr,
+        value: sys::GDExtensionConstVariantPtr,
+    ) -> sys::GDExtensionBool {
+        let ok = guard("set", false, || {
+            let name = borrow_string_name(name);
+            let value = &*(value as *const Variant);
+
+            instance_data::<T>(instance)
+                .inner
+                .borrow_mut()
+                .set(name, value)
+        });
+
+        ok as sys::GDExtensionBool
+    }
+
+    pub(super) unsafe extern "C" fn get<T: ScriptInstance>(
+        instance: sys::GDExtensionScriptInstanceDataPtr,
+        name: sys::GDExtensionConstStringNamePtr,
+        ret: sys::GDExtensionVariantPtr,
+    ) -> sys::GDExtensionBool {
+        let ok = guard("get", false, || {
+            let name = borrow_string_name(name);
+
+            match instance_data::<T>(instance).inner.borrow().get(name) {
+                Some(value) => {
+                    // Godot passes an initialized Variant; assignment drops the previous value.
+                    *(ret as *mut Variant) = value;
+                    true
+                }
+                None => false,
+            }
+        });
+
+        ok as sys::GDExtensionBool
+    }
+
+    pub(super) unsafe extern "C" fn get_property_list<T: ScriptInstance>(
+        instance: sys::GDExtensionScriptInstanceDataPtr,
+        out_count: *mut u32,
+    ) -> *const sys::GDExtensionPropertyInfo {
+        *out_count = 0;
+
+        guard("get_property_list", std::ptr::null(), || {
+            let data = instance_data::<T>(instance);
+            let infos = data.inner.borrow().get_property_list();
+
+            let sys_list: Box<[_]> = infos.iter().map(PropertyInfo::property_sys).collect();
+            let list_ptr = sys_list.as_ptr();
+            let count = u32::try_from(sys_list.len()).expect("property list too long");
+
+            data.property_lists.borrow_mut().insert(
+                list_ptr,
+                OwnedPropertyList {
+                    _infos: infos,
+                    _sys: sys_list,
+                },
+            );
+
+            *out_count = count;
+            list_ptr
+        })
+    }
+
+    pub(super) unsafe extern "C" fn free_property_list<T: ScriptInstance>(
+        instance: sys::GDExtensionScriptInstanceDataPtr,
+        list: *const sys::GDExtensionPropertyInfo,
+    ) {
+        guard("free_property_list", (), || {
+            instance_data::<T>(instance)
+                .property_lists
+                .borrow_mut()
+                .remove(&list);
+        })
+    }
+
+    pub(super) unsafe extern "C" fn get_method_list<T: ScriptInstance>(
+        instance: sys::GDExtensionScriptInstanceDataPtr,
+        out_count: *mut u32,
+    ) -> *const sys::GDExtensionMethodInfo {
+        *out_count = 0;
+
+        guard("get_method_list", std::ptr::null(), || {
+            let data = instance_data::<T>(instance);
+            let infos = data.inner.borrow().get_method_list();
+
+            let mut arguments: Vec<Box<[_]>> = infos
+                .iter()
+                .map(|method| {
+                    method
+                        .arguments
+                        .iter()
+                        .map(PropertyInfo::property_sys)
+                        .collect()
+                })
+                .collect();
+
+            let sys_list: Box<[_]> = infos
+                .iter()
+                .zip(arguments.iter_mut())
+                .map(|(method, args)| sys::GDExtensionMethodInfo {
+                    name: method.method_name.string_sys(),
+                    return_value: method.return_type.property_sys(),
+                    flags: method.flags.ord() as u32,
+                    id: 0,
+                    argument_count: u32::try_from(args.len()).expect("argument list too long"),
+                    arguments: args.as_mut_ptr(),
+                    default_argument_count: 0,
+                    default_arguments: std::ptr::null_mut(),
+                })
+                .collect();
+            let list_ptr = sys_list.as_ptr();
+            let count = u32::try_from(sys_list.len()).expect("method list too long");
+
+            data.method_lists.borrow_mut().insert(
+                list_ptr,
+                OwnedMethodList {
+                    _infos: infos,
+                    _arguments: arguments,
+                    _sys: sys_list,
+                },
+            );
+
+            *out_count = count;
+            list_ptr
+        })
+    }
+
+    pub(super) unsafe extern "C" fn free_method_list<T: ScriptInstance>(
+        instance: sys::GDExtensionScriptInstanceDataPtr,
+        list: *const sys::GDExtensionMethodInfo,
+    ) {
+        guard("free_method_list", (), || {
+            instance_data::<T>(instance)
+                .method_lists
+                .borrow_mut()
+                .remove(&list);
+        })
+    }
+
+    pub(super) unsafe extern "C" fn get_property_type<T: ScriptInstance>(
+        instance: sys::GDExtensionScriptInstanceDataPtr,
+        name: sys::GDExtensionConstStringNamePtr,
+        is_valid: *mut sys::GDExtensionBool,
+    ) -> sys::GDExtensionVariantType {
+        let ty = guard("get_property_type", None, || {
+            let name = borrow_string_name(name);
+            instance_data::<T>(instance)
+                .inner
+                .borrow()
+                .get_property_type(name)
+        });
+
+        *is_valid = ty.is_some() as sys::GDExtensionBool;
+        ty.unwrap_or(VariantType::Nil).sys()
+    }
+
+    pub(super) unsafe extern "C" fn has_method<T: ScriptInstance>(
+        instance: sys::GDExtensionScriptInstanceDataPtr,
+        method: sys::GDExtensionConstStringNamePtr,
+    ) -> sys::GDExtensionBool {
+        let has = guard("has_method", false, || {
+            let method = borrow_string_name(method);
+            instance_data::<T>(instance)
+                .inner
+                .borrow()
+                .has_method(method)
+        });
+
+        has as sys::GDExtensionBool
+    }
+
+    pub(super) unsafe extern "C" fn call<T: ScriptInstance>(
+        instance: sys::GDExtensionScriptInstanceDataPtr,
+        method: sys::GDExtensionConstStringNamePtr,
+        args: *const sys::GDExtensionConstVariantPtr,
+        arg_count: sys::GDExtensionInt,
+        ret: sys::GDExtensionVariantPtr,
+        err: *mut sys::GDExtensionCallError,
+    ) {
+        // A panicking method is reported like a method that does not exist, which Godot turns into a script error.
+        let result = guard(
+            "call",
+            Err(sys::GDEXTENSION_CALL_ERROR_INVALID_METHOD),
+            || {
+                let method = borrow_string_name(method);
+                let args: Vec<&Variant> = if arg_count == 0 {
+                    Vec::new()
+                } else {
+                    std::slice::from_raw_parts(args, arg_count as usize)
+                        .iter()
+                        .map(|arg| &*(*arg as *const Variant))
+                        .collect()
+                };
+
+                instance_data::<T>(instance)
+                    .inner
+                    .borrow_mut()
+                    .call(method, &args)
+            },
+        );
+
+        match result {
+            Ok(value) => {
+                *(ret as *mut Variant) = value;
+                (*err).error = sys::GDEXTENSION_CALL_OK;
+            }
+            Err(error) => (*err).error = error,
+        }
+    }
+
+    pub(super) unsafe extern "C" fn notification<T: ScriptInstance>(
+        instance: sys::GDExtensionScriptInstanceDataPtr,
+        what: i32,
+    ) {
+        guard("on_notification", (), || {
+            instance_data::<T>(instance)
+                .inner
+                .borrow_mut()
+                .on_notification(what);
+        })
+    }
+
+    pub(super) unsafe extern "C" fn to_string<T: ScriptInstance>(
+        instance: sys::GDExtensionScriptInstanceDataPtr,
+        is_valid: *mut sys::GDExtensionBool,
+        out_string: sys::GDExtensionStringPtr,
+    ) {
+        let string = guard("to_string", None, || {
+            instance_data::<T>(instance).inner.borrow().to_string()
+        });
+
+        match string {
+            Some(string) => {
+                // Transfer ownership to Godot
+                string.move_string_ptr(out_string);
+                *is_valid = true as sys::GDExtensionBool;
+            }
+            None => *is_valid = false as sys::GDExtensionBool,
+        }
+    }
+
+    pub(super) unsafe extern "C" fn get_script<T: ScriptInstance>(
+        instance: sys::GDExtensionScriptInstanceDataPtr,
+    ) -> sys::GDExtensionObjectPtr {
+        // Godot wraps the pointer in a new Ref<Script>, which takes its own reference.
+        guard("get_script", std::ptr::null_mut(), || {
+            instance_data::<T>(instance)
+                .inner
+                .borrow()
+                .get_script()
+                .obj_sys()
+        })
+    }
+
+    pub(super) unsafe extern "C" fn is_placeholder<T: ScriptInstance>(
+        instance: sys::GDExtensionScriptInstanceDataPtr,
+    ) -> sys::GDExtensionBool {
+        let placeholder = guard("is_placeholder", false, || {
+            instance_data::<T>(instance).inner.borrow().is_placeholder()
+        });
+
+        placeholder as sys::GDExtensionBool
+    }
+
+    pub(super) unsafe extern "C" fn get_language<T: ScriptInstance>(
+        instance: sys::GDExtensionScriptInstanceDataPtr,
+    ) -> sys::GDExtensionScriptLanguagePtr {
+        // Script languages are registered singletons and outlive their instances; no ownership is transferred.
+        guard("get_language", std::ptr::null_mut(), || {
+            let language = instance_data::<T>(instance).inner.borrow().get_language();
+            language.obj_sys() as sys::GDExtensionScriptLanguagePtr
+        })
+    }
+
+    pub(super) unsafe extern "C" fn free<T: ScriptInstance>(
+        instance: sys::GDExtensionScriptInstanceDataPtr,
+    ) {
+        guard("free", (), || {
+            let _drop = Box::from_raw(instance as *mut ScriptInstanceData<T>);
+        })
+    }
+}
@@ -0,0 +1,63 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Main thread tracking, and thread-access auditing enabled by the `debug-threads` feature.
+//!
+//! Two kinds of access are audited:
+//! * Instances of Rust classes are not thread-safe (unless the `threads` feature is enabled): they may only be bound and
+//!   reference-counted on the thread that created them.
+//! * Nodes inside the scene tree may only be accessed from the main thread, as required by Godot.
+
+use std::sync::OnceLock;
+use std::thread::{self, ThreadId};
+
+static MAIN_THREAD: OnceLock<ThreadId> = OnceLock::new();
+
+/// Remembers the current thread as main thread. Called when the library is loaded.
+pub(crate) fn set_main_thread() {
+    let _ = MAIN_THREAD.set(thread::current().id());
+}
+
+/// Whether the current thread is the main thread; also true before the library is loaded.
+pub(crate) fn is_main_thread() -> bool {
+    MAIN_THREAD
+        .get()
+        .map_or(true, |main| *main == thread::current().id())
+}
+
+/// Panics if the current thread is not `owner`, naming the `operation` on an instance of `class_name`.
+#[cfg(feature = "debug-threads")]
+#[cfg_attr(feature = "threads", allow(dead_code))] // instance storage is thread-safe then
+#[track_caller]
+pub(crate) fn check_owner_thread(owner: ThreadId, operation: &str, class_name: &str) {
+    let current = thread::current();
+    if current.id() != owner {
+        panic!(
+            "{operation} on `{class_name}` instance from thread {}, but the instance was created on thread {owner:?}.\n  \
+            Rust instances are not thread-safe; use call_deferred() to run code on the owning thread.",
+            describe(&current)
+        );
+    }
+}
+
+/// Panics because a node inside the scene tree is accessed off the main thread.
+#[cfg(feature = "debug-threads")]
+#[track_caller]
+pub(crate) fn panic_tree_access(class_name: &str) -> ! {
+    panic!(
+        "access to `{class_name}` inside the scene tree from thread {}, which is not the main thread.\n  \
+        The active scene tree is not thread-safe; use call_deferred() to run code on the main thread.",
+        describe(&thread::current())
+    );
+}
+
+#[cfg(feature = "debug-threads")]
+fn describe(thread: &thread::Thread) -> String {
+    match thread.name() {
+        Some(name) => format!("{:?} ({name})", thread.id()),
+        None => format!("{:?}", thread.id()),
+    }
+}
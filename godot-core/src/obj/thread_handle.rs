@@ -0,0 +1,110 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::obj::cap::ThreadSafe;
+use crate::obj::{Gd, GodotClass, InstanceId};
+
+/// Reference to an object that can be sent to, and shared between, threads.
+///
+/// `Gd<T>` is neither `Send` nor `Sync`, as most objects must not be touched by several threads at once. A `ThreadHandle` only
+/// stores the instance ID, so a worker thread can carry it around and hand it back (e.g. through a channel) to the main thread,
+/// which turns it into a `Gd<T>` again with [`get()`][Self::get].
+///
+/// Like an instance ID, the handle does not keep the object alive: `get()` returns `None` once the object has been freed.
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// # use godot::obj::ThreadHandle;
+/// # fn spawn(enemy: Gd<Node3D>) {
+/// let handle = ThreadHandle::new(&enemy);
+/// let (sender, receiver) = std::sync::mpsc::channel();
+///
+/// std::thread::spawn(move || {
+///     let path = vec![Vector3::ZERO]; // expensive computation
+///     sender.send((handle, path)).unwrap();
+/// });
+///
+/// // Later, on the main thread:
+/// for (handle, path) in receiver.try_iter() {
+///     if let Some(mut enemy) = handle.get() {
+///         enemy.set_position(path[0]);
+///     }
+/// }
+/// # }
+/// ```
+pub struct ThreadHandle<T: GodotClass> {
+    instance_id: InstanceId,
+    // fn() -> T is Send + Sync regardless of T.
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: GodotClass> ThreadHandle<T> {
+    /// Creates a handle to the object behind `obj`.
+    ///
+    /// # Panics
+    /// If `obj` is dead.
+    pub fn new(obj: &Gd<T>) -> Self {
+        Self {
+            instance_id: obj.instance_id(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Instance ID of the referred-to object.
+    pub fn instance_id(&self) -> InstanceId {
+        self.instance_id
+    }
+
+    /// Returns the object, if it is still alive. Must be called on the main thread.
+    ///
+    /// # Panics
+    /// If the current thread is not the main thread. For classes declared with `#[class(thread_safe)]`, use
+    /// [`get_on_any_thread()`][Self::get_on_any_thread] instead.
+    pub fn get(&self) -> Option<Gd<T>> {
+        assert!(
+            crate::obj::thread_audit::is_main_thread(),
+            "ThreadHandle<{}>::get() called off the main thread",
+            T::class_name()
+        );
+
+        Gd::try_from_instance_id(self.instance_id)
+    }
+
+    /// Returns the object, if it is still alive. May be called on any thread.
+    ///
+    /// Only available for `#[class(thread_safe)]` classes, whose instances synchronize `bind()` and `bind_mut()` internally.
+    pub fn get_on_any_thread(&self) -> Option<Gd<T>>
+    where
+        T: ThreadSafe,
+    {
+        Gd::try_from_instance_id(self.instance_id)
+    }
+}
+
+impl<T: GodotClass> Clone for ThreadHandle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: GodotClass> Copy for ThreadHandle<T> {}
+
+impl<T: GodotClass> PartialEq for ThreadHandle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.instance_id == other.instance_id
+    }
+}
+
+impl<T: GodotClass> Eq for ThreadHandle<T> {}
+
+impl<T: GodotClass> fmt::Debug for ThreadHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ThreadHandle<{}>({})", T::class_name(), self.instance_id)
+    }
+}
@@ -6,9 +6,10 @@
 
 use crate::builder::ClassBuilder;
 use crate::builtin::GodotString;
-use crate::obj::Base;
+use crate::obj::{Base, BaseMut, Gd};
 
 use crate::builtin::meta::ClassName;
+use crate::init::InitLevel;
 use godot_ffi as sys;
 
 /// Makes `T` eligible to be managed by Godot and stored in [`Gd<T>`][crate::obj::Gd] pointers.
@@ -34,6 +35,15 @@ where
     /// Defines the memory strategy.
     type Mem: mem::Memory;
 
+    /// During which initialization level this class is available/should be initialized with Godot.
+    ///
+    /// Is `None` if the class has complicated initialization requirements, and generally cannot be inherited
+    /// from (currently only for `()`, the "base" of `Object`).
+    ///
+    /// User classes inherit this level from their base class, so that e.g. `EditorPlugin` subclasses are only
+    /// registered once Godot has loaded its editor classes.
+    const INIT_LEVEL: Option<InitLevel>;
+
     /// The name of the class, under which it is registered in Godot.
     ///
     /// This may deviate from the Rust struct name: `HttpRequest::class_name().as_str() == "HTTPRequest"`.
@@ -60,6 +70,7 @@ unsafe impl GodotClass for () {
     type Base = ();
     type Declarer = dom::EngineDomain;
     type Mem = mem::ManualMemory;
+    const INIT_LEVEL: Option<InitLevel> = None;
 
     fn class_name() -> ClassName {
         ClassName::none()
@@ -122,6 +133,45 @@ pub trait Inherits<Base>: GodotClass {}
 
 impl<T: GodotClass> Inherits<T> for T {}
 
+/// Object argument accepted by engine methods, for any class inheriting `Base`.
+///
+/// Generated engine APIs take `impl AsObjectArg<Base>` instead of `Gd<Base>` for object parameters. This lets you pass
+/// objects of derived classes directly, without calling [`Gd::upcast()`] at every call site:
+/// ```no_run
+/// # use godot::prelude::*;
+/// let mut parent = Node::new_alloc();
+/// let child = Node3D::new_alloc();
+///
+/// parent.add_child(&child); // borrowed: shares the reference
+/// parent.add_child(child);  // owned: moves the reference
+/// ```
+///
+/// Implemented for `Gd<T>` and `&Gd<T>`, where `T: Inherits<Base>`.
+pub trait AsObjectArg<Base: GodotClass> {
+    /// Converts the argument into a smart pointer to the base class.
+    fn into_object_arg(self) -> Gd<Base>;
+}
+
+impl<T, Base> AsObjectArg<Base> for Gd<T>
+where
+    T: Inherits<Base>,
+    Base: GodotClass,
+{
+    fn into_object_arg(self) -> Gd<Base> {
+        self.upcast()
+    }
+}
+
+impl<T, Base> AsObjectArg<Base> for &Gd<T>
+where
+    T: Inherits<Base>,
+    Base: GodotClass,
+{
+    fn into_object_arg(self) -> Gd<Base> {
+        self.share().upcast()
+    }
+}
+
 /// Trait implemented for all objects that inherit from `Resource` or `Node`.
 ///
 /// Those are the only objects you can export to the editor.
@@ -135,12 +185,16 @@ pub trait EngineClass: GodotClass {
 
 /// Auto-implemented for all engine-provided enums.
 pub trait EngineEnum: Copy {
+    /// Enumerator with the given ordinal, or `None` if there is no such enumerator.
     fn try_from_ord(ord: i32) -> Option<Self>;
 
     /// Ordinal value of the enumerator, as specified in Godot.
     /// This is not necessarily unique.
     fn ord(self) -> i32;
 
+    /// Like [`try_from_ord()`][Self::try_from_ord], but panics if there is no such enumerator.
+    ///
+    /// Generated engine enums never panic: they map unknown ordinals to their `Unknown` variant.
     fn from_ord(ord: i32) -> Self {
         Self::try_from_ord(ord)
             .unwrap_or_else(|| panic!("ordinal {ord} does not map to any enumerator"))
@@ -166,7 +220,8 @@ pub trait IndexEnum: EngineEnum {
 
     /// Converts the enumerator to `usize`, which can be used as an array index.
     ///
-    /// Note that two enumerators may have the same index, if they have the same ordinal.
+    /// Note that two enumerators may have the same index, if they have the same ordinal. The `Unknown` variant is not an
+    /// enumerator; its index may be out of range.
     fn to_index(self) -> usize {
         self.ord() as usize
     }
@@ -203,6 +258,52 @@ pub mod cap {
 
         #[doc(hidden)]
         fn __godot_base_mut(&mut self) -> &mut Gd<Self::Base>;
+
+        /// Returns the base object for calls that may re-enter this instance.
+        ///
+        /// Inside a `#[func]` or virtual method, the instance is bound as `&mut self`. An engine method on the base that
+        /// synchronously calls back into the same instance would bind it again and panic. Through the returned guard, the outer
+        /// borrow is suspended instead, so that the callback succeeds:
+        ///
+        /// ```no_run
+        /// # use godot::prelude::*;
+        /// #[derive(GodotClass)]
+        /// #[class(init, base=Node)]
+        /// struct Spawner {
+        ///     #[base]
+        ///     base: Base<Node>,
+        /// }
+        ///
+        /// #[godot_api]
+        /// impl NodeVirtual for Spawner {
+        ///     fn ready(&mut self) {
+        ///         // Adding a child notifies this node, which may run `on_notification()` or GDScript callbacks on it.
+        ///         self.base_mut().add_child(Node::new_alloc());
+        ///     }
+        /// }
+        /// ```
+        ///
+        /// Re-entrant calls are only allowed with borrow tracking of a single-threaded build; with the `threads` feature, the
+        /// guard behaves like `self.base`.
+        fn base_mut(&mut self) -> BaseMut<'_, Self> {
+            BaseMut::new(self)
+        }
+    }
+
+    /// Trait implemented for user classes declared with `#[class(thread_safe)]`.
+    ///
+    /// Instances of such classes may be accessed from several threads, e.g. through
+    /// [`ThreadHandle::get_on_any_thread()`][crate::obj::ThreadHandle::get_on_any_thread]. `#[derive(GodotClass)]` checks that all
+    /// fields except `#[base]` are `Send` and `Sync`. Borrows through `bind()` and `bind_mut()` are synchronized by a `RwLock`,
+    /// which requires the `threads` feature; without it, `#[class(thread_safe)]` does not compile.
+    ///
+    /// The `#[base]` object is not covered: engine methods are only safe to call from other threads if Godot documents them so.
+    ///
+    /// # Safety
+    /// Internal; implemented by the proc macros.
+    pub unsafe trait ThreadSafe:
+        GodotClass + crate::private::Thread_safe_classes_require_the_threads_feature
+    {
     }
 
     // TODO Evaluate whether we want this public or not
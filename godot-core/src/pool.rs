@@ -0,0 +1,56 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Closures of tasks on the engine's thread pool, submitted through `WorkerThreadPoolExt`.
+//!
+//! Like `Gd::run_deferred()`, this works around the lack of Rust callables in Godot 4.1: the `WorkerThreadPool` is given a
+//! `Callable` to a static method of the internal dispatch object, bound to the closure ID. That method runs
+//! on a worker thread, and calls the closure stored here with the element index.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Name of the method of the dispatch class.
+pub(crate) const METHOD_NAME: &str = "run_pool_task";
+
+pub(crate) type PoolFn = Arc<dyn Fn(u32) + Send + Sync>;
+
+/// Closures by ID. Global, since they are called from worker threads.
+static CLOSURES: OnceLock<Mutex<HashMap<u64, PoolFn>>> = OnceLock::new();
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+fn closures() -> &'static Mutex<HashMap<u64, PoolFn>> {
+    CLOSURES.get_or_init(Default::default)
+}
+
+/// Stores the closure of a task, and returns its ID.
+#[cfg_attr(before_api = "4.1", allow(dead_code))]
+pub(crate) fn insert(f: PoolFn) -> u64 {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    closures().lock().unwrap().insert(id, f);
+    id
+}
+
+/// Drops the closure of a task, once the task has completed.
+#[cfg_attr(before_api = "4.1", allow(dead_code))]
+pub(crate) fn remove(id: u64) {
+    let f = closures().lock().unwrap().remove(&id);
+
+    // Drop outside the lock, as captured values may run arbitrary code.
+    drop(f);
+}
+
+/// Calls the closure `id` for element `index`. Called on a worker thread, by the dispatch class.
+pub(crate) fn run(id: u64, index: u32) {
+    // Clone out of the map, so that elements of a group task run concurrently.
+    let f = closures().lock().unwrap().get(&id).cloned();
+
+    match f {
+        Some(f) => f(index),
+        None => panic!("no pool task {id} was submitted from Rust"),
+    }
+}
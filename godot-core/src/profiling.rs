@@ -0,0 +1,160 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Timing of Rust functions called by Godot.
+//!
+//! With the `profiling` feature, every call from Godot into a `#[func]` or a virtual method override is timed. The timings are
+//! accumulated per function and can be read with [`take_timings()`], or sent once per frame to Godot's debugger with
+//! [`add_frame_data()`], under the profiler name [`PROFILER_NAME`].
+//!
+//! Godot's own profiler panels do not show this data: they only display the engine's built-in profilers. The frame data
+//! reaches the editor as a debugger message, which an `EditorDebuggerPlugin` has to capture and display itself.
+
+use crate::builtin::{StringName, ToVariant, VariantArray};
+use crate::engine::EngineDebugger;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Name of the profiler under which [`add_frame_data()`] publishes its data.
+pub const PROFILER_NAME: &str = "gdext";
+
+type Timings = HashMap<String, CallTiming>;
+
+/// Timings of every thread that has been called into, so that [`take_timings()`] can collect them.
+static THREAD_TIMINGS: Mutex<Vec<Arc<Mutex<Timings>>>> = Mutex::new(Vec::new());
+
+thread_local! {
+    /// Timings of the current thread. Its lock is only contended while [`take_timings()`] runs.
+    static TIMINGS: Arc<Mutex<Timings>> = {
+        let timings = Arc::new(Mutex::new(HashMap::new()));
+        THREAD_TIMINGS.lock().unwrap().push(timings.clone());
+        timings
+    };
+}
+
+/// Accumulated time spent in one function since the last [`take_timings()`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct CallTiming {
+    /// How often the function was called.
+    pub call_count: u32,
+
+    /// Total time spent in the function, including nested calls.
+    pub total_time: Duration,
+}
+
+/// Returns the timings collected on all threads since the last call, and resets them.
+pub fn take_timings() -> Vec<(String, CallTiming)> {
+    let mut merged = Timings::new();
+
+    let mut threads = THREAD_TIMINGS.lock().unwrap();
+    for timings in threads.iter() {
+        for (name, timing) in timings.lock().unwrap().drain() {
+            let entry = merged.entry(name).or_default();
+            entry.call_count += timing.call_count;
+            entry.total_time += timing.total_time;
+        }
+    }
+
+    // Threads that have exited no longer hold a reference; their timings have just been collected.
+    threads.retain(|timings| Arc::strong_count(timings) > 1);
+
+    merged.into_iter().collect()
+}
+
+/// Sends the timings collected since the last call to Godot's debugger, and resets them.
+///
+/// Meant to be called once per frame, e.g. from `process()` of an autoload node. Data is only sent while the
+/// [`PROFILER_NAME`] profiler is enabled in the debugger; it is sent as one flat array of `[name, call_count, total_seconds, ...]`.
+/// See the [module documentation](self) for how to display it.
+pub fn add_frame_data() {
+    let timings = take_timings();
+
+    let mut debugger = EngineDebugger::singleton();
+    if !debugger.is_profiling(StringName::from(PROFILER_NAME)) {
+        return;
+    }
+
+    let mut data = VariantArray::new();
+    for (name, timing) in timings {
+        data.push(name.to_variant());
+        data.push(timing.call_count.to_variant());
+        data.push(timing.total_time.as_secs_f64().to_variant());
+    }
+
+    debugger.profiler_add_frame_data(StringName::from(PROFILER_NAME), data);
+}
+
+/// Measures the time until it is dropped, and adds it to the timings of `method_name`.
+pub(crate) struct CallGuard<'a> {
+    method_name: &'a str,
+    start: Instant,
+}
+
+impl<'a> CallGuard<'a> {
+    pub fn start(method_name: &'a str) -> Self {
+        Self {
+            method_name,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for CallGuard<'_> {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+
+        // Fails during thread teardown, when the thread-local is already destroyed; such a call is not recorded.
+        let _ = TIMINGS.try_with(|timings| {
+            let mut map = timings.lock().unwrap();
+
+            // Avoid allocating the key for every call; only the first call of each function in a frame inserts.
+            if let Some(timing) = map.get_mut(self.method_name) {
+                timing.call_count += 1;
+                timing.total_time += elapsed;
+            } else {
+                let timing = CallTiming {
+                    call_count: 1,
+                    total_time: elapsed,
+                };
+                map.insert(self.method_name.to_string(), timing);
+            }
+        });
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn timings_merged_across_threads() {
+        let record = |count: u32| {
+            for _ in 0..count {
+                let _timing = CallGuard::start("profiling_test_method");
+            }
+        };
+
+        record(2);
+        std::thread::spawn(move || record(3)).join().unwrap();
+
+        let timings = take_timings();
+        let (_, timing) = timings
+            .iter()
+            .find(|(name, _)| name == "profiling_test_method")
+            .expect("timings recorded");
+        assert_eq!(timing.call_count, 5);
+
+        // Taking resets the timings.
+        let timings = take_timings();
+        assert!(timings
+            .iter()
+            .all(|(name, _)| name != "profiling_test_method"));
+    }
+}
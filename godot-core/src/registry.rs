@@ -15,9 +15,13 @@ use sys::interface_fn;
 
 use crate::builtin::meta::ClassName;
 use crate::builtin::StringName;
+use crate::diag::diag;
+use crate::docs::{InherentImplDocs, StructDocs};
+use crate::init::InitLevel;
 use crate::out;
 use std::any::Any;
 use std::collections::HashMap;
+use std::sync::Mutex;
 use std::{fmt, ptr};
 
 // TODO(bromeon): some information coming from the proc-macro API is deferred through PluginComponent, while others is directly
@@ -53,6 +57,9 @@ pub enum PluginComponent {
     ClassDef {
         base_class_name: ClassName,
 
+        /// Initialization level of the base class; determines when the class is registered with Godot.
+        init_level: Option<InitLevel>,
+
         /// Godot low-level`create` function, wired up to library-generated `init`
         generated_create_fn: Option<
             unsafe extern "C" fn(
@@ -64,6 +71,9 @@ pub enum PluginComponent {
             _class_user_data: *mut std::ffi::c_void,
             instance: sys::GDExtensionClassInstancePtr,
         ),
+
+        /// Doc comments on the struct and its properties
+        docs: Option<StructDocs>,
     },
 
     /// Collected from `#[godot_api] impl MyClass`
@@ -72,6 +82,9 @@ pub enum PluginComponent {
         ///
         /// Always present since that's the entire point of this `impl` block.
         generated_register_fn: ErasedRegisterFn,
+
+        /// Doc comments on `#[func]` and `#[signal]` items
+        docs: Option<InherentImplDocs>,
     },
 
     /// Collected from `#[godot_api] impl GodotExt for MyClass`
@@ -113,10 +126,17 @@ pub enum PluginComponent {
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 
+/// Classes registered by this library, in registration order.
+///
+/// Holds the level that [`auto_register_classes()`] registered a class at, or `None` for classes registered manually through
+/// [`register_class()`].
+static LOADED_CLASSES: Mutex<Vec<(Option<InitLevel>, ClassName)>> = Mutex::new(Vec::new());
+
 #[derive(Debug)]
 struct ClassRegistrationInfo {
     class_name: ClassName,
     parent_class_name: Option<ClassName>,
+    init_level: Option<InitLevel>,
     generated_register_fn: Option<ErasedRegisterFn>,
     user_register_fn: Option<ErasedRegisterFn>,
     godot_params: sys::GDExtensionClassCreationInfo,
@@ -147,20 +167,35 @@ pub fn register_class<
         ..default_creation_info()
     };
 
+    let class_name = T::class_name();
     register_class_raw(ClassRegistrationInfo {
-        class_name: T::class_name(),
+        class_name,
         parent_class_name: Some(T::Base::class_name()),
+        init_level: T::INIT_LEVEL,
         generated_register_fn: None,
         user_register_fn: Some(ErasedRegisterFn {
             raw: callbacks::register_class_by_builder::<T>,
         }),
         godot_params,
     });
+
+    LOADED_CLASSES.lock().unwrap().push((None, class_name));
 }
 
-/// Lets Godot know about all classes that have self-registered through the plugin system.
-pub fn auto_register_classes() {
-    out!("Auto-register classes...");
+/// Lets Godot know about all classes that have self-registered through the plugin system, and belong to `init_level`.
+///
+/// Classes are registered at the level of their engine base class. Since extensions are loaded at `Scene` level by
+/// default, all classes below `Editor` are registered at `Scene`; classes inheriting editor classes follow at `Editor`.
+/// Classes inheriting server classes (e.g. custom physics servers) are registered at `Servers`, if the library is loaded at
+/// that level (`min_level`).
+///
+/// Classes for which `filter` returns `false` are skipped.
+pub fn auto_register_classes(
+    init_level: InitLevel,
+    min_level: InitLevel,
+    filter: fn(ClassName) -> bool,
+) {
+    out!("Auto-register classes at level `{init_level:?}`...");
 
     // Note: many errors are already caught by the compiler, before this runtime validation even takes place:
     // * missing #[derive(GodotClass)] or impl GodotClass for T
@@ -182,12 +217,59 @@ pub fn auto_register_classes() {
 
     //out!("Class-map: {map:#?}");
 
+    crate::builtin::meta::registration::with_cached_names(|| {
+        register_level_classes(map, init_level, min_level, filter)
+    });
+
+    out!("All classes for level `{init_level:?}` auto-registered.");
+}
+
+fn register_level_classes(
+    map: HashMap<ClassName, ClassRegistrationInfo>,
+    init_level: InitLevel,
+    min_level: InitLevel,
+    filter: fn(ClassName) -> bool,
+) {
     for info in map.into_values() {
+        let class_level = registration_level(info.init_level, min_level);
+        if class_level != init_level {
+            diag!(
+                Trace,
+                "class `{}` is registered at level {class_level:?}, not {init_level:?}",
+                info.class_name
+            );
+            continue;
+        }
+
+        if !filter(info.class_name) {
+            out!("Skip class:       {}", info.class_name);
+            diag!(
+                Info,
+                "class `{}` not registered: excluded by ExtensionLibrary::should_register_class()",
+                info.class_name
+            );
+            continue;
+        }
+
         out!("Register class:   {}", info.class_name);
+        let class_name = info.class_name;
         register_class_raw(info);
+
+        LOADED_CLASSES
+            .lock()
+            .unwrap()
+            .push((Some(init_level), class_name));
     }
+}
 
-    out!("All classes auto-registered.");
+/// Level at which the class was registered by [`auto_register_classes()`], or `None` if it is not (or manually) registered.
+pub fn registered_level(class_name: ClassName) -> Option<InitLevel> {
+    LOADED_CLASSES
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(_, name)| *name == class_name)
+        .and_then(|(level, _)| *level)
 }
 
 /// Populate `c` with all the relevant data from `component` (depending on component type).
@@ -197,10 +279,13 @@ fn fill_class_info(component: PluginComponent, c: &mut ClassRegistrationInfo) {
     match component {
         PluginComponent::ClassDef {
             base_class_name,
+            init_level,
             generated_create_fn,
             free_fn,
+            docs: _,
         } => {
             c.parent_class_name = Some(base_class_name);
+            c.init_level = init_level;
             fill_into(
                 &mut c.godot_params.create_instance_func,
                 generated_create_fn,
@@ -210,6 +295,7 @@ fn fill_class_info(component: PluginComponent, c: &mut ClassRegistrationInfo) {
 
         PluginComponent::UserMethodBinds {
             generated_register_fn,
+            docs: _,
         } => {
             c.generated_register_fn = Some(generated_register_fn);
         }
@@ -232,6 +318,19 @@ fn fill_class_info(component: PluginComponent, c: &mut ClassRegistrationInfo) {
     // out!();
 }
 
+/// Level at which a class with the given base-class level is registered.
+///
+/// Never lower than `min_level`, as Godot does not invoke the library for earlier levels.
+fn registration_level(class_level: Option<InitLevel>, min_level: InitLevel) -> InitLevel {
+    let level = match class_level {
+        Some(InitLevel::Editor) => InitLevel::Editor,
+        Some(InitLevel::Servers) => InitLevel::Servers,
+        _ => InitLevel::Scene,
+    };
+
+    level.max(min_level)
+}
+
 /// If `src` is occupied, it moves the value into `dst`, while ensuring that no previous value is present in `dst`.
 fn fill_into<T>(dst: &mut Option<T>, src: Option<T>) {
     match (dst, src) {
@@ -241,14 +340,68 @@ fn fill_into<T>(dst: &mut Option<T>, src: Option<T>) {
     }
 }
 
+/// Unregisters all classes that [`auto_register_classes()`] registered at `init_level`.
+///
+/// Classes are removed from Godot's ClassDB in reverse registration order. When `init_level` is `min_level`, the last level
+/// to be unloaded, all remaining classes are removed as well, including those registered through [`register_class()`].
+/// This leaves no dangling function pointers into the library once it is unloaded.
+///
+/// This is groundwork for library reloading, not the reloading protocol itself: instances of the classes are neither
+/// serialized before unloading nor restored afterwards, and the 4.1 GDExtension API has no reload callbacks to drive it.
+pub fn unregister_classes(init_level: InitLevel, min_level: InitLevel) {
+    let mut loaded = LOADED_CLASSES.lock().unwrap();
+
+    let mut index = loaded.len();
+    while index > 0 {
+        index -= 1;
+
+        let (level, class_name) = loaded[index];
+        if level != Some(init_level) && init_level != min_level {
+            continue;
+        }
+
+        out!("Unregister class: {class_name}");
+        diag!(
+            Debug,
+            "unregister class `{class_name}` at level {init_level:?}"
+        );
+        loaded.remove(index);
+
+        unsafe {
+            #[allow(clippy::let_unit_value)]
+            let _: () = interface_fn!(classdb_unregister_extension_class)(
+                sys::get_library(),
+                class_name.string_sys(),
+            );
+        }
+    }
+}
+
 /// Registers a class with given the dynamic type information `info`.
 fn register_class_raw(info: ClassRegistrationInfo) {
     // First register class...
 
     let class_name = info.class_name;
-    let parent_class_name = info
-        .parent_class_name
-        .expect("class defined (parent_class_name)");
+    let Some(parent_class_name) = info.parent_class_name else {
+        diag!(
+            Error,
+            "class `{class_name}` has a #[godot_api] impl, but no #[derive(GodotClass)] struct was registered"
+        );
+        panic!("class defined (parent_class_name)");
+    };
+
+    diag!(
+        Debug,
+        "register class `{class_name}` (base `{parent_class_name}`, level {:?})",
+        info.init_level
+    );
+    if info.godot_params.create_instance_func.is_none() {
+        diag!(
+            Warn,
+            "class `{class_name}` has no init function; Godot cannot instantiate it. \
+             Use #[class(init)] or implement `init` in the virtual-method impl."
+        );
+    }
 
     unsafe {
         // Try to register class...
@@ -263,6 +416,13 @@ fn register_class_raw(info: ClassRegistrationInfo) {
         // ...then see if it worked.
         // This is necessary because the above registration does not report errors (apart from console output).
         let tag = interface_fn!(classdb_get_class_tag)(class_name.string_sys());
+        if tag.is_null() {
+            diag!(
+                Error,
+                "Godot rejected class `{class_name}` with base `{parent_class_name}`; \
+                 is the base class known and not already registered under this name?"
+            );
+        }
         assert!(
             !tag.is_null(),
             "failed to register class `{class_name}`; check preceding Godot stderr messages",
@@ -318,6 +478,7 @@ pub mod callbacks {
         let instance = InstanceStorage::<T>::construct(user_instance);
         let instance_ptr = instance.into_raw();
         let instance_ptr = instance_ptr as sys::GDExtensionClassInstancePtr;
+        crate::leaks::on_create(class_name, instance_ptr, base_ptr);
 
         let binding_data_callbacks = crate::storage::nop_instance_callbacks();
         unsafe {
@@ -344,6 +505,7 @@ pub mod callbacks {
             storage.mark_destroyed_by_godot();
         } // Ref no longer valid once next statement is executed.
 
+        crate::leaks::on_free(instance);
         crate::storage::destroy_storage::<T>(instance);
     }
 
@@ -356,7 +518,21 @@ pub mod callbacks {
         let method_name = borrowed_string.to_string();
         std::mem::forget(borrowed_string);
 
-        T::__virtual_call(method_name.as_str())
+        let virtual_fn = T::__virtual_call(method_name.as_str());
+        match virtual_fn {
+            Some(_) => diag!(
+                Debug,
+                "virtual `{method_name}` dispatched to `{}`",
+                T::class_name()
+            ),
+            None => diag!(
+                Trace,
+                "virtual `{method_name}` not overridden by `{}`",
+                T::class_name()
+            ),
+        }
+
+        virtual_fn
     }
 
     pub unsafe extern "C" fn to_string<T: cap::GodotToString>(
@@ -441,13 +617,14 @@ fn default_registration_info(class_name: ClassName) -> ClassRegistrationInfo {
     ClassRegistrationInfo {
         class_name,
         parent_class_name: None,
+        init_level: None,
         generated_register_fn: None,
         user_register_fn: None,
         godot_params: default_creation_info(),
     }
 }
 
-fn default_creation_info() -> sys::GDExtensionClassCreationInfo {
+pub(crate) fn default_creation_info() -> sys::GDExtensionClassCreationInfo {
     sys::GDExtensionClassCreationInfo {
         is_abstract: false as u8,
         is_virtual: false as u8,
@@ -468,3 +645,48 @@ fn default_creation_info() -> sys::GDExtensionClassCreationInfo {
         class_userdata: ptr::null_mut(),
     }
 }
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn registration_level_follows_base_class() {
+        let min_level = InitLevel::Servers;
+
+        assert_eq!(registration_level(None, min_level), InitLevel::Scene);
+        assert_eq!(
+            registration_level(Some(InitLevel::Servers), min_level),
+            InitLevel::Servers
+        );
+        assert_eq!(
+            registration_level(Some(InitLevel::Scene), min_level),
+            InitLevel::Scene
+        );
+        assert_eq!(
+            registration_level(Some(InitLevel::Editor), min_level),
+            InitLevel::Editor
+        );
+    }
+
+    #[test]
+    fn registration_level_not_below_min_level() {
+        // Server classes of a library loaded at `Scene` level are registered with all other classes.
+        assert_eq!(
+            registration_level(Some(InitLevel::Servers), InitLevel::Scene),
+            InitLevel::Scene
+        );
+
+        // Godot does not invoke a library loaded at `Editor` level for `Scene`.
+        assert_eq!(
+            registration_level(Some(InitLevel::Scene), InitLevel::Editor),
+            InitLevel::Editor
+        );
+        assert_eq!(
+            registration_level(None, InitLevel::Editor),
+            InitLevel::Editor
+        );
+    }
+}
@@ -4,11 +4,14 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use crate::diag::diag;
 use crate::obj::GodotClass;
 use crate::out;
 use godot_ffi as sys;
 
+use std::alloc::Layout;
 use std::any::type_name;
+use std::ptr::NonNull;
 
 #[derive(Copy, Clone, Debug)]
 pub enum Lifecycle {
@@ -34,13 +37,23 @@ mod single_threaded {
 
     use super::Lifecycle;
 
+    pub use super::user_cell::{MutGuard, RefGuard, Suspended, UserCell};
+
     /// Manages storage and lifecycle of user's extension class instances.
     pub struct InstanceStorage<T: GodotClass> {
-        user_instance: cell::RefCell<T>,
+        user_instance: UserCell<T>,
 
         // Declared after `user_instance`, is dropped last
         pub lifecycle: cell::Cell<Lifecycle>,
         godot_ref_count: cell::Cell<u32>,
+
+        // Most recent bind()/bind_mut(), reported if a later one conflicts with it.
+        #[cfg(debug_assertions)]
+        borrow_site: cell::Cell<Option<super::BorrowSite>>,
+
+        // Thread that created the instance; the only one allowed to access it.
+        #[cfg(feature = "debug-threads")]
+        owner_thread: std::thread::ThreadId,
     }
 
     /// For all Godot extension classes
@@ -49,13 +62,20 @@ mod single_threaded {
             out!("    Storage::construct             <{}>", type_name::<T>());
 
             Self {
-                user_instance: cell::RefCell::new(user_instance),
+                user_instance: UserCell::new(user_instance),
                 lifecycle: cell::Cell::new(Lifecycle::Alive),
                 godot_ref_count: cell::Cell::new(1),
+                #[cfg(debug_assertions)]
+                borrow_site: cell::Cell::new(None),
+                #[cfg(feature = "debug-threads")]
+                owner_thread: std::thread::current().id(),
             }
         }
 
         pub(crate) fn on_inc_ref(&self) {
+            #[cfg(feature = "debug-threads")]
+            self.audit_thread("reference increment");
+            super::check_inc_ref::<T>(self.lifecycle.get());
             let refc = self.godot_ref_count.get() + 1;
             self.godot_ref_count.set(refc);
 
@@ -68,6 +88,9 @@ mod single_threaded {
         }
 
         pub(crate) fn on_dec_ref(&self) {
+            #[cfg(feature = "debug-threads")]
+            self.audit_thread("reference decrement");
+            super::check_dec_ref::<T>(self.godot_ref_count.get());
             let refc = self.godot_ref_count.get() - 1;
             self.godot_ref_count.set(refc);
 
@@ -79,31 +102,90 @@ mod single_threaded {
             );
         }
 
-        pub fn get(&self) -> cell::Ref<T> {
-            self.user_instance.try_borrow().unwrap_or_else(|_e| {
+        #[track_caller]
+        pub fn get(&self) -> RefGuard<T> {
+            #[cfg(feature = "debug-threads")]
+            self.audit_thread("Gd<T>::bind()");
+            let guard = self.user_instance.try_borrow().unwrap_or_else(|| {
                 panic!(
                     "Gd<T>::bind() failed, already bound; T = {}.\n  \
                      Make sure there is no &mut T live at the time.\n  \
-                     This often occurs when calling a GDScript function/signal from Rust, which then calls again Rust code.",
-                    type_name::<T>()
+                     This often occurs when calling a GDScript function/signal from Rust, which then calls again Rust code.{}",
+                    type_name::<T>(),
+                    self.conflict_note()
                 )
-            })
+            });
+
+            #[cfg(debug_assertions)]
+            self.borrow_site.set(Some(super::BorrowSite::here(false)));
+            guard
         }
 
-        pub fn get_mut(&self) -> cell::RefMut<T> {
-            self.user_instance.try_borrow_mut().unwrap_or_else(|_e| {
+        #[track_caller]
+        pub fn get_mut(&self) -> MutGuard<T> {
+            #[cfg(feature = "debug-threads")]
+            self.audit_thread("Gd<T>::bind_mut()");
+            let guard = self.user_instance.try_borrow_mut().unwrap_or_else(|| {
                 panic!(
                     "Gd<T>::bind_mut() failed, already bound; T = {}.\n  \
                      Make sure there is no &T or &mut T live at the time.\n  \
-                     This often occurs when calling a GDScript function/signal from Rust, which then calls again Rust code.",
-                    type_name::<T>()
+                     This often occurs when calling a GDScript function/signal from Rust, which then calls again Rust code.{}",
+                    type_name::<T>(),
+                    self.conflict_note()
                 )
-            })
+            });
+
+            #[cfg(debug_assertions)]
+            self.borrow_site.set(Some(super::BorrowSite::here(true)));
+            guard
+        }
+
+        /// Pointer to the user instance, bypassing borrow tracking.
+        ///
+        /// Dereferencing it is only sound while no conflicting guard or reference exists.
+        pub fn get_unchecked(&self) -> *const T {
+            self.user_instance.as_ptr()
+        }
+
+        /// Like [`get_unchecked()`][Self::get_unchecked], for writing.
+        pub fn get_mut_unchecked(&self) -> *mut T {
+            self.user_instance.as_ptr()
+        }
+
+        /// Suspends the exclusive borrow through which `instance` was obtained, until the returned guard is dropped.
+        ///
+        /// Meanwhile, engine callbacks can bind the instance again. Returns `None` if `instance` is not the one stored here,
+        /// or if it is not exclusively borrowed.
+        pub(crate) fn suspend_exclusive(&self, instance: *const T) -> Option<Suspended<'_>> {
+            self.user_instance.suspend_exclusive(instance)
+        }
+
+        /// In debug builds, describes where the conflicting guard was taken.
+        #[cfg(debug_assertions)]
+        fn conflict_note(&self) -> String {
+            match self.borrow_site.get() {
+                Some(site) => format!("\n  {site}"),
+                None => String::new(),
+            }
+        }
+
+        #[cfg(not(debug_assertions))]
+        fn conflict_note(&self) -> &'static str {
+            ""
         }
 
         pub(super) fn godot_ref_count(&self) -> u32 {
             self.godot_ref_count.get()
         }
+
+        #[cfg(feature = "debug-threads")]
+        fn audit_thread(&self, operation: &str) {
+            crate::obj::thread_audit::check_owner_thread(
+                self.owner_thread,
+                operation,
+                type_name::<T>(),
+            );
+        }
     }
 }
 
@@ -143,6 +225,10 @@ mod multi_threaded {
         }
     }
 
+    pub type RefGuard<'a, T> = sync::RwLockReadGuard<'a, T>;
+    pub type MutGuard<'a, T> = sync::RwLockWriteGuard<'a, T>;
+    pub use super::NeverSuspended as Suspended;
+
     /// Manages storage and lifecycle of user's extension class instances.
     pub struct InstanceStorage<T: GodotClass> {
         user_instance: sync::RwLock<T>,
@@ -165,6 +251,7 @@ mod multi_threaded {
         }
 
         pub(crate) fn on_inc_ref(&self) {
+            super::check_inc_ref::<T>(self.lifecycle.get());
             self.godot_ref_count.fetch_add(1, Ordering::Relaxed);
             out!(
                 "    Storage::on_inc_ref (rc={})     <{}>", // -- {:?}",
@@ -175,7 +262,8 @@ mod multi_threaded {
         }
 
         pub(crate) fn on_dec_ref(&self) {
-            self.godot_ref_count.fetch_sub(1, Ordering::Relaxed);
+            let prev = self.godot_ref_count.fetch_sub(1, Ordering::Relaxed);
+            super::check_dec_ref::<T>(prev);
             out!(
                 "  | Storage::on_dec_ref (rc={})     <{}>", // -- {:?}",
                 self.godot_ref_count(),
@@ -184,7 +272,7 @@ mod multi_threaded {
             );
         }
 
-        pub fn get(&self) -> sync::RwLockReadGuard<T> {
+        pub fn get(&self) -> RefGuard<T> {
             self.user_instance.read().unwrap_or_else(|_e| {
                 panic!(
                     "Gd<T>::bind() failed, already bound; T = {}.\n  \
@@ -195,7 +283,7 @@ mod multi_threaded {
             })
         }
 
-        pub fn get_mut(&self) -> sync::RwLockWriteGuard<T> {
+        pub fn get_mut(&self) -> MutGuard<T> {
             self.user_instance.write().unwrap_or_else(|_e| {
                 panic!(
                     "Gd<T>::bind_mut() failed, already bound; T = {}.\n  \
@@ -206,10 +294,29 @@ mod multi_threaded {
             })
         }
 
+        /// Pointer to the user instance, for access without a guard.
+        ///
+        /// `RwLock` offers no way to reach its data without locking, so the lock is held while obtaining the pointer.
+        pub fn get_unchecked(&self) -> *const T {
+            let guard = self.get();
+            &*guard as *const T
+        }
+
+        /// Like [`get_unchecked()`][Self::get_unchecked], for writing.
+        pub fn get_mut_unchecked(&self) -> *mut T {
+            let mut guard = self.get_mut();
+            &mut *guard as *mut T
+        }
+
         pub(super) fn godot_ref_count(&self) -> u32 {
             self.godot_ref_count.load(Ordering::Relaxed)
         }
 
+        /// A `RwLock` cannot be released and re-acquired by another guard, so borrows are never suspended.
+        pub(crate) fn suspend_exclusive(&self, _instance: *const T) -> Option<Suspended<'_>> {
+            None
+        }
+
         // fn __static_type_check() {
         //     enforce_sync::<InstanceStorage<T>>();
         // }
@@ -230,7 +337,13 @@ mod multi_threaded {
 impl<T: GodotClass> InstanceStorage<T> {
     #[must_use]
     pub fn into_raw(self) -> *mut Self {
-        Box::into_raw(Box::new(self))
+        let ptr = arena::allocate(Layout::new::<Self>())
+            .cast::<Self>()
+            .as_ptr();
+
+        // SAFETY: `ptr` is valid for writes and properly aligned for `Self`.
+        unsafe { ptr.write(self) };
+        ptr
     }
 
     pub fn mark_destroyed_by_godot(&self) {
@@ -277,6 +390,254 @@ impl<T: GodotClass> Drop for InstanceStorage<T> {
     }
 }
 
+/// Borrow-tracking cell of single-threaded instance storage.
+#[cfg(not(feature = "threads"))]
+mod user_cell {
+    use std::cell::{Cell, UnsafeCell};
+    use std::fmt;
+    use std::marker::PhantomData;
+    use std::ops::{Deref, DerefMut};
+    use std::ptr::NonNull;
+
+    /// Exclusive borrow state.
+    const EXCLUSIVE: isize = -1;
+
+    /// Like `RefCell`, but an exclusive borrow can be suspended while its `&mut T` is provably unused.
+    ///
+    /// This is what makes `base_mut()` work: the guard it returns holds `&mut self`, so the instance is not accessible through
+    /// the outer borrow until the guard is dropped. An engine callback into the same instance can thus borrow it anew.
+    pub struct UserCell<T> {
+        value: UnsafeCell<T>,
+        /// Number of shared borrows, or `EXCLUSIVE`.
+        state: Cell<isize>,
+    }
+
+    impl<T> UserCell<T> {
+        pub fn new(value: T) -> Self {
+            Self {
+                value: UnsafeCell::new(value),
+                state: Cell::new(0),
+            }
+        }
+
+        pub fn try_borrow(&self) -> Option<RefGuard<T>> {
+            let state = self.state.get();
+            if state == EXCLUSIVE {
+                return None;
+            }
+
+            self.state.set(state + 1);
+            Some(RefGuard {
+                value: self.ptr(),
+                state: &self.state,
+                _marker: PhantomData,
+            })
+        }
+
+        pub fn try_borrow_mut(&self) -> Option<MutGuard<T>> {
+            if self.state.get() != 0 {
+                return None;
+            }
+
+            self.state.set(EXCLUSIVE);
+            Some(MutGuard {
+                value: self.ptr(),
+                state: &self.state,
+                _marker: PhantomData,
+            })
+        }
+
+        pub fn suspend_exclusive(&self, instance: *const T) -> Option<Suspended<'_>> {
+            if self.value.get() as *const T != instance || self.state.get() != EXCLUSIVE {
+                return None;
+            }
+
+            self.state.set(0);
+            Some(Suspended { state: &self.state })
+        }
+
+        pub fn as_ptr(&self) -> *mut T {
+            self.value.get()
+        }
+
+        fn ptr(&self) -> NonNull<T> {
+            // SAFETY: UnsafeCell::get() is never null.
+            unsafe { NonNull::new_unchecked(self.value.get()) }
+        }
+    }
+
+    /// Shared borrow of a [`UserCell`].
+    pub struct RefGuard<'a, T> {
+        value: NonNull<T>,
+        state: &'a Cell<isize>,
+        _marker: PhantomData<&'a T>,
+    }
+
+    impl<T> Deref for RefGuard<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            // SAFETY: the state counts this shared borrow, so there is no exclusive one.
+            unsafe { self.value.as_ref() }
+        }
+    }
+
+    impl<T> Drop for RefGuard<'_, T> {
+        fn drop(&mut self) {
+            self.state.set(self.state.get() - 1);
+        }
+    }
+
+    impl<T: fmt::Debug> fmt::Debug for RefGuard<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(&**self, f)
+        }
+    }
+
+    /// Exclusive borrow of a [`UserCell`].
+    pub struct MutGuard<'a, T> {
+        value: NonNull<T>,
+        state: &'a Cell<isize>,
+        _marker: PhantomData<&'a mut T>,
+    }
+
+    impl<T> Deref for MutGuard<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            // SAFETY: the state marks this borrow as exclusive.
+            unsafe { self.value.as_ref() }
+        }
+    }
+
+    impl<T> DerefMut for MutGuard<'_, T> {
+        fn deref_mut(&mut self) -> &mut T {
+            // SAFETY: the state marks this borrow as exclusive.
+            unsafe { self.value.as_mut() }
+        }
+    }
+
+    impl<T> Drop for MutGuard<'_, T> {
+        fn drop(&mut self) {
+            // 0 rather than the previous state: a re-borrow during suspension ends in the suspended state.
+            self.state.set(0);
+        }
+    }
+
+    impl<T: fmt::Debug> fmt::Debug for MutGuard<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(&**self, f)
+        }
+    }
+
+    /// Suspended exclusive borrow of a [`UserCell`], restored on drop.
+    pub struct Suspended<'a> {
+        state: &'a Cell<isize>,
+    }
+
+    impl Drop for Suspended<'_> {
+        fn drop(&mut self) {
+            // A borrow taken during suspension must not outlive it, or it would alias the restored &mut T.
+            if self.state.get() != 0 && !std::thread::panicking() {
+                panic!(
+                    "instance is still bound when base_mut() guard is dropped; \
+                    guards obtained while it was alive must not outlive it"
+                );
+            }
+
+            self.state.set(EXCLUSIVE);
+        }
+    }
+}
+
+/// Stand-in for a suspended borrow, in storage variants that never suspend.
+#[cfg(feature = "threads")]
+pub struct NeverSuspended<'a> {
+    _marker: std::marker::PhantomData<&'a ()>,
+}
+
+/// Storage of the user instance attached to `obj`, or `None` if it has none (yet), e.g. during `init`.
+///
+/// # Safety
+/// `obj` must point to a live object, whose instance (if any) is of type `T`. The returned lifetime is unbounded.
+pub(crate) unsafe fn try_storage_of<'u, T: GodotClass>(
+    obj: sys::GDExtensionObjectPtr,
+) -> Option<&'u InstanceStorage<T>> {
+    let callbacks = nop_instance_callbacks();
+    let token = sys::get_library() as *mut std::ffi::c_void;
+    let binding = sys::interface_fn!(object_get_instance_binding)(obj, token, &callbacks);
+
+    if binding.is_null() {
+        None
+    } else {
+        Some(as_storage::<T>(binding))
+    }
+}
+
+/// Location of a `bind()` or `bind_mut()` guard, to help finding the cause of a borrow conflict.
+#[cfg(all(debug_assertions, not(feature = "threads")))]
+#[derive(Copy, Clone)]
+struct BorrowSite {
+    location: &'static std::panic::Location<'static>,
+    exclusive: bool,
+    callback_depth: usize,
+}
+
+#[cfg(all(debug_assertions, not(feature = "threads")))]
+impl BorrowSite {
+    #[track_caller]
+    fn here(exclusive: bool) -> Self {
+        Self {
+            location: std::panic::Location::caller(),
+            exclusive,
+            callback_depth: crate::private::callback_depth(),
+        }
+    }
+}
+
+#[cfg(all(debug_assertions, not(feature = "threads")))]
+impl std::fmt::Display for BorrowSite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let guard = if self.exclusive { "&mut T" } else { "&T" };
+        write!(f, "Conflicting {guard} was last taken at {}", self.location)?;
+
+        // The conflicting guard is held by a caller further up the stack, if we are now in a nested callback.
+        if crate::private::callback_depth() > self.callback_depth {
+            write!(
+                f,
+                ", outside the current engine callback: this is a re-entrant call (Rust -> engine -> Rust).\n  \
+                If the outer call goes through the base object, `self.base_mut()` instead of `self.base` allows it."
+            )
+        } else if self.callback_depth > 0 {
+            write!(f, ", inside an engine callback.")
+        } else {
+            write!(f, ".")
+        }
+    }
+}
+
+/// Reports a reference being taken to an instance that Godot is already destroying.
+fn check_inc_ref<T: GodotClass>(lifecycle: Lifecycle) {
+    if !matches!(lifecycle, Lifecycle::Alive) {
+        diag!(
+            Warn,
+            "reference taken to `{}` instance in lifecycle state {lifecycle:?}",
+            type_name::<T>()
+        );
+    }
+}
+
+/// Reports a reference being released when the count (before decrementing) is already zero.
+fn check_dec_ref<T: GodotClass>(refc_before: u32) {
+    if refc_before == 0 {
+        diag!(
+            Error,
+            "reference count of `{}` instance released below zero",
+            type_name::<T>()
+        );
+    }
+}
+
 /// Interprets the opaque pointer as pointing to `InstanceStorage<T>`.
 ///
 /// Note: returns reference with unbounded lifetime; intended for local usage
@@ -293,9 +654,223 @@ pub unsafe fn as_storage<'u, T: GodotClass>(
 /// # Safety
 /// `instance_ptr` is assumed to point to a valid instance. This function must only be invoked once for a pointer.
 pub unsafe fn destroy_storage<T: GodotClass>(instance_ptr: sys::GDExtensionClassInstancePtr) {
-    let _drop = Box::from_raw(instance_ptr as *mut InstanceStorage<T>);
+    let ptr = instance_ptr as *mut InstanceStorage<T>;
+    std::ptr::drop_in_place(ptr);
+
+    arena::deallocate(
+        NonNull::new_unchecked(ptr.cast()),
+        Layout::new::<InstanceStorage<T>>(),
+    );
 }
 
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Allocation
+
+/// Arena allocation of instance storages.
+///
+/// Spawn-heavy workloads (e.g. bullets or particles as nodes) create and destroy many instances of the same class. Instead of
+/// one heap allocation per instance, storages live in slots of chunks that hold [`arena::SLOTS_PER_CHUNK`] instances each,
+/// with one arena per storage layout. Slots of destroyed instances are reused for the next instance of the same layout.
+///
+/// Chunks are only returned to the allocator by [`arena::release_all()`] when the library is deinitialized, while the engine
+/// (and thus a possible `GodotAllocator`) is still alive.
+///
+/// # Thread safety
+/// Instances may be created and destroyed on any thread. Arenas are sharded: each thread is assigned one of
+/// [`arena::SHARD_COUNT`] shards, with separate locks, so that threads spawning objects concurrently (e.g. from a thread pool)
+/// rarely contend. A storage may be destroyed on another thread than it was created on; its slot then becomes available in
+/// the destroying thread's shard. Only [`arena::release_all()`] locks all shards at once.
+mod arena {
+    use std::alloc::{self, Layout};
+    use std::ptr::NonNull;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    use crate::out;
+
+    /// Number of instance storages allocated at once.
+    pub const SLOTS_PER_CHUNK: usize = 64;
+
+    /// Number of independently locked arena sets.
+    pub const SHARD_COUNT: usize = 8;
+
+    /// Memory block owned by an arena.
+    struct Block(NonNull<u8>);
+
+    // SAFETY: blocks are plain memory without thread affinity; access is synchronized through the shard locks.
+    unsafe impl Send for Block {}
+
+    pub(super) struct Arena {
+        slot_layout: Layout,
+        chunks: Vec<Block>,
+        free_slots: Vec<Block>,
+        /// Slots allocated minus slots deallocated in this arena. Negative if slots of other shards were returned here;
+        /// the sum over all shards is the number of live storages of this layout.
+        live_slots: isize,
+    }
+
+    impl Arena {
+        pub(super) fn new(slot_layout: Layout) -> Self {
+            Self {
+                slot_layout: slot_layout.pad_to_align(),
+                chunks: Vec::new(),
+                free_slots: Vec::new(),
+                live_slots: 0,
+            }
+        }
+
+        pub(super) fn allocate(&mut self) -> NonNull<u8> {
+            if self.free_slots.is_empty() {
+                self.grow();
+            }
+
+            let slot = self
+                .free_slots
+                .pop()
+                .expect("arena has free slots after growing");
+            self.live_slots += 1;
+            slot.0
+        }
+
+        /// # Safety
+        /// `slot` must have been returned by `allocate()` of an arena with the same layout, and must not be used afterwards.
+        pub(super) unsafe fn deallocate(&mut self, slot: NonNull<u8>) {
+            self.live_slots -= 1;
+            self.free_slots.push(Block(slot));
+        }
+
+        pub(super) fn live_slots(&self) -> isize {
+            self.live_slots
+        }
+
+        /// Returns all chunks to the allocator.
+        ///
+        /// # Safety
+        /// No slot of this arena may be in use, and no slot may be in the free list of another arena.
+        pub(super) unsafe fn release(&mut self) {
+            let chunk_layout = self.chunk_layout();
+            self.free_slots.clear();
+            self.live_slots = 0;
+            for chunk in self.chunks.drain(..) {
+                alloc::dealloc(chunk.0.as_ptr(), chunk_layout);
+            }
+        }
+
+        fn grow(&mut self) {
+            let chunk_layout = self.chunk_layout();
+
+            // SAFETY: layout is never zero-sized, as InstanceStorage contains at least lifecycle and refcount.
+            let chunk = unsafe { alloc::alloc(chunk_layout) };
+            let chunk =
+                NonNull::new(chunk).unwrap_or_else(|| alloc::handle_alloc_error(chunk_layout));
+
+            // Reversed, so that slots are handed out in address order.
+            let slot_size = self.slot_layout.size();
+            let slots = (0..SLOTS_PER_CHUNK).rev().map(|i| {
+                // SAFETY: the offset stays within the chunk.
+                Block(unsafe { NonNull::new_unchecked(chunk.as_ptr().add(i * slot_size)) })
+            });
+
+            self.free_slots.extend(slots);
+            self.chunks.push(Block(chunk));
+        }
+
+        fn chunk_layout(&self) -> Layout {
+            Layout::from_size_align(
+                self.slot_layout.size() * SLOTS_PER_CHUNK,
+                self.slot_layout.align(),
+            )
+            .expect("valid chunk layout")
+        }
+    }
+
+    /// One arena per storage layout and shard. There are only few distinct layouts (one per registered class at most), so a
+    /// linear search is fine.
+    static SHARDS: [Shard; SHARD_COUNT] = [EMPTY_SHARD; SHARD_COUNT];
+
+    type Shard = Mutex<Vec<Arena>>;
+
+    #[allow(clippy::declare_interior_mutable_const)] // only used to initialize the static array.
+    const EMPTY_SHARD: Shard = Mutex::new(Vec::new());
+
+    /// Shard of the current thread; threads are assigned round-robin.
+    fn current_shard() -> &'static Shard {
+        static NEXT_SHARD: AtomicUsize = AtomicUsize::new(0);
+
+        thread_local! {
+            static SHARD_INDEX: usize = NEXT_SHARD.fetch_add(1, Ordering::Relaxed) % SHARD_COUNT;
+        }
+
+        // Thread-local storage is unavailable while the thread exits; any shard is correct, just possibly contended.
+        let index = SHARD_INDEX.try_with(|index| *index).unwrap_or(0);
+        &SHARDS[index]
+    }
+
+    fn with_arena<R>(layout: Layout, f: impl FnOnce(&mut Arena) -> R) -> R {
+        let slot_layout = layout.pad_to_align();
+        let mut arenas = current_shard().lock().unwrap();
+
+        let index = match arenas.iter().position(|a| a.slot_layout == slot_layout) {
+            Some(index) => index,
+            None => {
+                arenas.push(Arena::new(slot_layout));
+                arenas.len() - 1
+            }
+        };
+
+        f(&mut arenas[index])
+    }
+
+    pub fn allocate(layout: Layout) -> NonNull<u8> {
+        with_arena(layout, Arena::allocate)
+    }
+
+    /// # Safety
+    /// `slot` must have been returned by `allocate(layout)`, its contents must have been dropped, and it must not be used afterwards.
+    pub unsafe fn deallocate(slot: NonNull<u8>, layout: Layout) {
+        // Arenas of the same layout in all shards hand out interchangeable slots.
+        with_arena(layout, |arena| arena.deallocate(slot))
+    }
+
+    /// Returns the memory of all arenas to the allocator. Called by gdext when the library is deinitialized.
+    ///
+    /// Layouts with storages still in use (instances that Godot has not destroyed yet) are kept, as their memory cannot be
+    /// reclaimed safely.
+    pub(crate) fn release_all() {
+        // Locked in index order; no other code holds more than one shard lock.
+        let mut shards: Vec<_> = SHARDS.iter().map(|s| s.lock().unwrap()).collect();
+
+        let mut layouts: Vec<(Layout, isize)> = Vec::new();
+        for arena in shards.iter().flat_map(|arenas| arenas.iter()) {
+            match layouts.iter_mut().find(|(l, _)| *l == arena.slot_layout) {
+                Some((_, live)) => *live += arena.live_slots(),
+                None => layouts.push((arena.slot_layout, arena.live_slots())),
+            }
+        }
+
+        for (layout, live) in layouts {
+            if live != 0 {
+                out!("    Storage arena kept, {live} instances alive");
+                continue;
+            }
+
+            for arenas in shards.iter_mut() {
+                arenas.retain_mut(|arena| {
+                    if arena.slot_layout != layout {
+                        return true;
+                    }
+
+                    // SAFETY: no storage of this layout is alive, and the arenas of all shards are released together.
+                    unsafe { arena.release() };
+                    false
+                });
+            }
+        }
+    }
+}
+
+pub(crate) use arena::release_all as release_storage_arenas;
+
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 // Callbacks
 
@@ -331,3 +906,79 @@ extern "C" fn reference_callback(
 ) -> sys::GDExtensionBool {
     true as u8
 }
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::arena::{Arena, SLOTS_PER_CHUNK};
+    use std::alloc::Layout;
+
+    #[test]
+    fn arena_reuses_slots() {
+        let mut arena = Arena::new(Layout::new::<[u64; 3]>());
+
+        let first = arena.allocate();
+        let second = arena.allocate();
+        assert_ne!(first, second);
+        assert_eq!(arena.live_slots(), 2);
+
+        // SAFETY: allocated above, not used afterwards.
+        unsafe { arena.deallocate(first) };
+        assert_eq!(arena.allocate(), first);
+
+        // SAFETY: allocated above, not used afterwards.
+        unsafe {
+            arena.deallocate(first);
+            arena.deallocate(second);
+        }
+        assert_eq!(arena.live_slots(), 0);
+
+        // SAFETY: no slot in use.
+        unsafe { arena.release() };
+    }
+
+    #[test]
+    fn arena_grows_by_chunks() {
+        let mut arena = Arena::new(Layout::new::<u32>());
+
+        let slots: Vec<_> = (0..SLOTS_PER_CHUNK + 1).map(|_| arena.allocate()).collect();
+        let mut addresses: Vec<_> = slots.iter().map(|slot| slot.as_ptr() as usize).collect();
+        addresses.sort_unstable();
+        addresses.dedup();
+        assert_eq!(addresses.len(), SLOTS_PER_CHUNK + 1);
+
+        for slot in slots {
+            // SAFETY: allocated above, not used afterwards.
+            unsafe { arena.deallocate(slot) };
+        }
+
+        // SAFETY: no slot in use.
+        unsafe { arena.release() };
+    }
+
+    #[test]
+    fn arena_slot_freed_in_other_shard() {
+        let layout = Layout::new::<[u8; 24]>();
+        let mut creating = Arena::new(layout);
+        let mut destroying = Arena::new(layout);
+
+        let slot = creating.allocate();
+
+        // SAFETY: allocated above, not used afterwards.
+        unsafe { destroying.deallocate(slot) };
+        assert_eq!(destroying.allocate(), slot);
+
+        // SAFETY: allocated above, not used afterwards.
+        unsafe { destroying.deallocate(slot) };
+
+        // Balances add up to the number of live slots.
+        assert_eq!(creating.live_slots() + destroying.live_slots(), 0);
+
+        // SAFETY: no slot in use; both arenas are released together.
+        unsafe {
+            destroying.release();
+            creating.release();
+        }
+    }
+}
@@ -0,0 +1,866 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Async tasks, polled on the main thread as part of Godot's frame loop.
+//!
+//! [`spawn()`] runs a future until its first `.await` that is not ready, like a GDScript coroutine. From then on, the task is
+//! polled again whenever it has been woken, which happens in [`poll()`]. Instead of calling `poll()` manually, e.g. from a
+//! `_process()` override, [`drive_each_frame()`] lets the scene tree do so at the start of every frame:
+//!
+//! ```no_run
+//! use godot::prelude::*;
+//! use godot::task;
+//!
+//! # fn start() {
+//! task::drive_each_frame();
+//!
+//! task::spawn(async {
+//!     for _ in 0..60 {
+//!         task::next_frame().await;
+//!     }
+//!     godot_print!("one second (at 60 FPS) has passed");
+//! });
+//! # }
+//! ```
+//!
+//! Like Godot 4.1 callables, the frame loop cannot call Rust closures directly. `drive_each_frame()` thus connects the
+//! tree's `process_frame` signal to a method of gdext's internal dispatch object.
+//!
+//! [`signal_future()`] completes when an object emits a signal. Combined with [`SignalFuture::timeout()`], waits for network
+//! responses or animations cannot hang forever:
+//!
+//! ```no_run
+//! # use godot::prelude::*;
+//! # use godot::engine::HttpRequest;
+//! # use godot::task;
+//! # use std::time::Duration;
+//! # fn fetch(request: Gd<HttpRequest>) {
+//! task::spawn(async move {
+//!     let completed = task::signal_future(&request, "request_completed");
+//!     match completed.timeout(Duration::from_secs(5)).await {
+//!         Ok(()) => godot_print!("response received"),
+//!         Err(err) => godot_warn!("{err}"),
+//!     }
+//! });
+//! # }
+//! ```
+//!
+//! For timers and tweens, [`SceneTreeExt::sleep()`][crate::engine::SceneTreeExt::sleep],
+//! [`TimerExt::timeout()`][crate::engine::TimerExt::timeout] and [`TweenExt::finished()`][crate::engine::TweenExt::finished]
+//! return such futures directly.
+//!
+//! GDScript coroutines called from Rust are awaited through [`coroutine_future()`].
+//!
+//! A `#[func]` can also be an `async fn`, as long as it takes no `self`. It then returns an object to GDScript, whose
+//! `completed` signal carries the function's result (see [`spawn_awaitable()`]).
+//!
+//! [`defer()`] runs a closure at idle time, like `call_deferred()`. Other threads can hand work to the main thread with
+//! [`call_on_main()`], which runs at the same point as tasks. For a stream of
+//! values, e.g. results of a background job, [`channel()`] returns a receiver that is drained in `_process()` or dispatched to a
+//! callback.
+//!
+//! This is not a general-purpose runtime: there is no I/O reactor and no thread pool. Tasks are `!Send` and live on the
+//! main thread, so they can freely use `Gd` pointers. Wakers may be invoked from any thread, though.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{mpsc, Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::time::{Duration, Instant};
+
+use crate::builtin::{
+    dict, varray, Callable, GodotString, StringName, ToVariant, Variant, VariantArray,
+};
+use crate::engine::{object, utilities, Engine, Object, RefCounted, SceneTree};
+use crate::error::{ConnectError, ConnectErrorKind, SignalError};
+use crate::obj::{EngineEnum, Gd, Inherits, InstanceId, Share};
+
+/// Names of the methods of the dispatch class.
+pub(crate) const METHOD_NAME: &str = "poll_tasks";
+pub(crate) const SIGNAL_METHOD_NAME: &str = "signal_fired";
+pub(crate) const COROUTINE_METHOD_NAME: &str = "coroutine_completed";
+
+/// Name of the signal emitted by the objects of [`spawn_awaitable()`].
+pub const COMPLETED_SIGNAL: &str = "completed";
+
+type BoxedTask = Pin<Box<dyn Future<Output = ()>>>;
+
+thread_local! {
+    /// Pending tasks by ID. Removed while being polled, so that tasks can spawn or cancel others.
+    static TASKS: RefCell<HashMap<u64, BoxedTask>> = RefCell::new(HashMap::new());
+    static NEXT_ID: Cell<u64> = Cell::new(0);
+
+    /// Number of frames driven so far, and the wakers waiting for the next one.
+    static FRAME: Cell<u64> = Cell::new(0);
+    static FRAME_WAITERS: RefCell<Vec<Waker>> = RefCell::new(Vec::new());
+
+    /// Whether drive_each_frame() has been called.
+    static DRIVEN: Cell<bool> = Cell::new(false);
+
+    /// State of pending signal futures, by ID.
+    static SIGNALS: RefCell<HashMap<u64, SignalState>> = RefCell::new(HashMap::new());
+}
+
+#[derive(Default)]
+struct SignalState {
+    fired: bool,
+    waker: Option<Waker>,
+    /// Argument of the signal, for coroutines.
+    value: Option<Variant>,
+}
+
+/// IDs of woken tasks, in wake order. Global, since wakers are `Send` and may be invoked from other threads.
+static WOKEN: Mutex<Vec<u64>> = Mutex::new(Vec::new());
+
+/// Closures queued by [`call_on_main()`], possibly from other threads.
+static MAIN_QUEUE: Mutex<Vec<Box<dyn FnOnce() + Send>>> = Mutex::new(Vec::new());
+
+struct TaskWaker {
+    id: u64,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        WOKEN.lock().unwrap().push(self.id);
+    }
+}
+
+/// Handle to a task started by [`spawn()`].
+///
+/// Dropping the handle does not cancel the task.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaskHandle {
+    id: u64,
+}
+
+impl TaskHandle {
+    /// Whether the task has run to completion, or has been cancelled.
+    pub fn is_finished(&self) -> bool {
+        TASKS.with(|tasks| !tasks.borrow().contains_key(&self.id))
+    }
+
+    /// Drops the task's future, so it is never polled again.
+    ///
+    /// Has no effect if the task has already finished. A task cannot cancel itself while it is being polled.
+    pub fn cancel(&self) {
+        // Drop outside the borrow, as the future's destructor may access tasks.
+        let task = TASKS.with(|tasks| tasks.borrow_mut().remove(&self.id));
+        drop(task);
+    }
+}
+
+/// Starts a task on the main thread, running `future` until it first awaits something that is not ready.
+///
+/// The task is then polled by [`poll()`] whenever it has been woken. Panics inside the task are printed as errors and end the
+/// task, without affecting others.
+///
+/// Must be called from the main thread; tasks spawned on other threads are never polled.
+pub fn spawn<F>(future: F) -> TaskHandle
+where
+    F: Future<Output = ()> + 'static,
+{
+    let id = next_id();
+    poll_task(id, Box::pin(future));
+
+    TaskHandle { id }
+}
+
+/// Starts a task like [`spawn()`], and returns an object that emits `completed(result)` once `future` has resolved.
+///
+/// This is how `async` `#[func]`s are exposed to GDScript, which awaits the signal like that of a `SceneTreeTimer`:
+///
+/// ```gdscript
+/// var path = await Pathfinder.find_path(from, to).completed
+/// ```
+///
+/// The signal is emitted at the earliest in the next [`poll()`], so that the caller can connect to it even if `future` is
+/// ready immediately. It is never emitted if the task panics or is cancelled.
+pub fn spawn_awaitable<F>(future: F) -> Gd<RefCounted>
+where
+    F: Future + 'static,
+    F::Output: ToVariant,
+{
+    spawn_emitting(async move { Some(future.await.to_variant()) })
+}
+
+/// Like [`spawn_awaitable()`], but only emits `completed` if `future` resolves to `Some`.
+pub(crate) fn spawn_emitting<F>(future: F) -> Gd<RefCounted>
+where
+    F: Future<Output = Option<Variant>> + 'static,
+{
+    let mut awaitable = RefCounted::new();
+    awaitable
+        .add_user_signal_ex(COMPLETED_SIGNAL.into())
+        .arguments(varray![dict! { "name": "result" }])
+        .done();
+
+    // The task keeps the object alive until the signal is emitted.
+    let mut emitter = awaitable.share();
+    spawn(async move {
+        let Some(result) = future.await else {
+            return;
+        };
+        YieldNow { yielded: false }.await;
+        emitter.emit_signal(COMPLETED_SIGNAL.into(), &[result]);
+    });
+
+    awaitable
+}
+
+/// Runs `f` on the main thread at idle time, like `call_deferred()` with a Rust closure.
+///
+/// Closures run in the order they were deferred, after the current frame's processing. A panic is printed as error, without
+/// affecting the closures after it. To run a closure on a user object, [`Gd::run_deferred()`] passes it the instance.
+///
+/// Must be called from the main thread; use [`call_on_main()`] on other threads.
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// # use godot::task;
+/// # fn respawn(mut player: Gd<Node2D>) {
+/// // Changing the tree while it is being iterated is not allowed.
+/// task::defer(move || player.set_position(Vector2::ZERO));
+/// # }
+/// ```
+pub fn defer<F>(f: F)
+where
+    F: FnOnce() + 'static,
+{
+    crate::deferred::push(Box::new(f));
+}
+
+/// Queues `f` to run on the main thread, during the next [`poll()`].
+///
+/// Can be called from any thread, e.g. by a background job that hands its results to the scene tree. Closures run in the order
+/// they were queued. A panic is printed as error, without affecting the closures after it.
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// # use godot::engine::Label;
+/// # use godot::task;
+/// # fn generate(label: Gd<Label>) {
+/// let label_id = label.instance_id();
+/// std::thread::spawn(move || {
+///     let text = format!("{} levels generated", 42); // expensive computation
+///
+///     task::call_on_main(move || {
+///         if let Some(mut label) = Gd::<Label>::try_from_instance_id(label_id) {
+///             label.set_text(text.into());
+///         }
+///     });
+/// });
+/// # }
+/// ```
+pub fn call_on_main<F>(f: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    MAIN_QUEUE.lock().unwrap().push(Box::new(f));
+}
+
+/// Runs closures queued by [`call_on_main()`], then polls all tasks that have been woken since the last poll. Returns the number
+/// of pending tasks.
+///
+/// Closures queued and tasks woken during this call run at the next one, so a task that wakes itself cannot stall the frame.
+pub fn poll() -> usize {
+    let queued = std::mem::take(&mut *MAIN_QUEUE.lock().unwrap());
+    for f in queued {
+        let _ = crate::private::handle_panic(
+            || "closure queued by call_on_main()",
+            std::panic::AssertUnwindSafe(f),
+        );
+    }
+
+    let woken = std::mem::take(&mut *WOKEN.lock().unwrap());
+
+    let mut polled = Vec::with_capacity(woken.len());
+    for id in woken {
+        if polled.contains(&id) {
+            continue;
+        }
+        polled.push(id);
+
+        // Already finished or cancelled tasks are no longer present.
+        if let Some(task) = TASKS.with(|tasks| tasks.borrow_mut().remove(&id)) {
+            poll_task(id, task);
+        }
+    }
+
+    TASKS.with(|tasks| tasks.borrow().len())
+}
+
+/// Resumes tasks waiting in [`next_frame()`], then polls woken tasks. Called once per frame by [`drive_each_frame()`].
+pub(crate) fn poll_frame() {
+    FRAME.with(|frame| frame.set(frame.get() + 1));
+
+    let waiters = FRAME_WAITERS.with(|waiters| std::mem::take(&mut *waiters.borrow_mut()));
+    for waker in waiters {
+        waker.wake();
+    }
+
+    poll();
+}
+
+/// Polls tasks at the start of every frame, through a connection to the scene tree's `process_frame` signal.
+///
+/// The connection lasts until the library is unloaded. Calling this again has no effect.
+///
+/// # Panics
+/// If the main loop is not a `SceneTree`.
+pub fn drive_each_frame() {
+    let mut tree = Engine::singleton()
+        .get_main_loop()
+        .and_then(|main_loop| main_loop.try_cast::<SceneTree>())
+        .expect("drive_each_frame() requires the main loop to be a SceneTree");
+
+    let callable = crate::dispatch::callable(METHOD_NAME);
+    if !tree.is_connected("process_frame".into(), callable.clone()) {
+        tree.connect("process_frame".into(), callable);
+    }
+
+    DRIVEN.with(|driven| driven.set(true));
+}
+
+/// Completes once the next frame has started (see [`drive_each_frame()`]).
+///
+/// Frames are only counted while tasks are driven; calling [`poll()`] manually does not advance them.
+pub fn next_frame() -> NextFrame {
+    NextFrame {
+        frame: FRAME.with(Cell::get),
+    }
+}
+
+/// Future returned by [`next_frame()`].
+#[must_use = "futures do nothing unless awaited"]
+pub struct NextFrame {
+    frame: u64,
+}
+
+impl Future for NextFrame {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if FRAME.with(Cell::get) > self.frame {
+            return Poll::Ready(());
+        }
+
+        FRAME_WAITERS.with(|waiters| waiters.borrow_mut().push(cx.waker().clone()));
+        Poll::Pending
+    }
+}
+
+/// Wakes `waker` at the start of the next frame, for futures that check a condition once per frame.
+///
+/// Without [`drive_each_frame()`], frames are not counted, so the waker is woken right away instead; the future is then checked
+/// again in the next [`poll()`].
+pub(crate) fn wake_next_frame(waker: &Waker) {
+    if DRIVEN.with(Cell::get) {
+        FRAME_WAITERS.with(|waiters| waiters.borrow_mut().push(waker.clone()));
+    } else {
+        waker.wake_by_ref();
+    }
+}
+
+/// Returns pending once and wakes the task, so that it is resumed in the next [`poll()`].
+struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+
+        self.yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// Completes once `emitter` emits `signal`.
+///
+/// The signal's arguments are not delivered, as Godot 4.1 cannot pass them to Rust closures. The future fails if `emitter` is
+/// freed before emitting, and, with [`timeout()`][SignalFuture::timeout], if the signal is not emitted in time. Dropping the
+/// future (e.g. by cancelling its task) disconnects the signal.
+pub fn signal_future<T>(emitter: &Gd<T>, signal: impl Into<StringName>) -> SignalFuture
+where
+    T: Inherits<Object>,
+{
+    let signal = signal.into();
+    let mut emitter = emitter.share().upcast::<Object>();
+    let id = next_id();
+
+    let mut future = SignalFuture {
+        id,
+        emitter_id: emitter.instance_id(),
+        signal: signal.clone(),
+        connection: None,
+        deadline: None,
+        result: None,
+    };
+
+    let Some(arg_count) = signal_arg_count(&emitter, &signal) else {
+        let err = ConnectError::new(&signal.to_string(), ConnectErrorKind::UnknownSignal);
+        future.result = Some(Err(SignalError::Connect(err)));
+        return future;
+    };
+
+    // Drop the signal's arguments, then pass the ID: the dispatch method is called as `signal_fired(id)`.
+    let callable = crate::dispatch::callable(SIGNAL_METHOD_NAME)
+        .as_inner()
+        .bindv(varray![id as i64])
+        .as_inner()
+        .unbind(arg_count as i64);
+
+    let one_shot = object::ConnectFlags::ONE_SHOT.ord() as u32;
+    let code = emitter
+        .connect_ex(signal.clone(), callable.clone())
+        .flags(one_shot)
+        .done();
+
+    if code != crate::engine::global::Error::OK {
+        let err = ConnectError::new(&signal.to_string(), ConnectErrorKind::Engine(code));
+        future.result = Some(Err(SignalError::Connect(err)));
+        return future;
+    }
+
+    SIGNALS.with(|signals| signals.borrow_mut().insert(id, SignalState::default()));
+    future.connection = Some(callable);
+    future
+}
+
+/// Marks the signal future `id` as completed. Called by the dispatch class.
+pub(crate) fn signal_fired(id: u64) {
+    let waker = SIGNALS.with(|signals| {
+        let mut signals = signals.borrow_mut();
+        let state = signals.get_mut(&id)?;
+        state.fired = true;
+        state.waker.take()
+    });
+
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+}
+
+/// Future returned by [`signal_future()`].
+#[must_use = "futures do nothing unless awaited"]
+pub struct SignalFuture {
+    id: u64,
+    emitter_id: InstanceId,
+    signal: StringName,
+    /// Connected callable, if the connection succeeded and the signal has not yet been emitted.
+    connection: Option<Callable>,
+    deadline: Option<Instant>,
+    /// Outcome known before polling, e.g. a failed connection.
+    result: Option<Result<(), SignalError>>,
+}
+
+impl SignalFuture {
+    /// Fails with [`SignalError::TimedOut`] if the signal is not emitted within `duration` from now.
+    ///
+    /// The deadline is checked once per frame, so it requires [`drive_each_frame()`].
+    pub fn timeout(mut self, duration: Duration) -> Self {
+        self.deadline = Some(Instant::now() + duration);
+        self
+    }
+
+    /// Removes the connection and the state of this future, if still present.
+    fn disconnect(&mut self) {
+        SIGNALS.with(|signals| signals.borrow_mut().remove(&self.id));
+
+        let Some(callable) = self.connection.take() else {
+            return;
+        };
+        if let Some(mut emitter) = Gd::<Object>::try_from_instance_id(self.emitter_id) {
+            if emitter.is_connected(self.signal.clone(), callable.clone()) {
+                emitter.disconnect(self.signal.clone(), callable);
+            }
+        }
+    }
+}
+
+impl Future for SignalFuture {
+    type Output = Result<(), SignalError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(result) = self.result.take() {
+            return Poll::Ready(result);
+        }
+
+        let fired = SIGNALS.with(|signals| {
+            let mut signals = signals.borrow_mut();
+            let state = signals.entry(self.id).or_default();
+            state.waker = Some(cx.waker().clone());
+            state.fired
+        });
+
+        let result = if fired {
+            // One-shot connection is already removed by Godot.
+            self.connection = None;
+            Ok(())
+        } else if !utilities::is_instance_id_valid(self.emitter_id.to_i64()) {
+            self.connection = None;
+            Err(SignalError::EmitterFreed)
+        } else if self
+            .deadline
+            .map_or(false, |deadline| Instant::now() >= deadline)
+        {
+            Err(SignalError::TimedOut)
+        } else {
+            // Check again for a freed emitter or elapsed deadline in the next frame.
+            wake_next_frame(cx.waker());
+            return Poll::Pending;
+        };
+
+        self.disconnect();
+        Poll::Ready(result)
+    }
+}
+
+impl Drop for SignalFuture {
+    fn drop(&mut self) {
+        self.disconnect();
+    }
+}
+
+/// Completes with the return value of a GDScript coroutine, given what the call of the coroutine returned.
+///
+/// Calling a GDScript function that `await`s from Rust does not wait for it: the call returns a `GDScriptFunctionState` object
+/// at the first `await` instead, which emits `completed` with the return value once the function has finished. This future
+/// awaits that signal. If `returned` is any other value, the function has already finished and the future completes with it.
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// # use godot::task;
+/// # fn play(mut cutscene: Gd<Node>) {
+/// task::spawn(async move {
+///     let returned = cutscene.call("play_intro".into(), &[]);
+///     match task::coroutine_future(returned).await {
+///         Ok(skipped) => godot_print!("intro done, skipped: {skipped}"),
+///         Err(err) => godot_warn!("{err}"),
+///     }
+/// });
+/// # }
+/// ```
+///
+/// A coroutine whose object is freed while it is suspended never finishes; use [`timeout()`][CoroutineFuture::timeout] if that
+/// can happen.
+pub fn coroutine_future(returned: Variant) -> CoroutineFuture {
+    let state = returned
+        .try_to::<Gd<Object>>()
+        .ok()
+        .filter(|object| object.is_class("GDScriptFunctionState".into()));
+
+    let Some(mut state) = state else {
+        return CoroutineFuture {
+            id: next_id(),
+            state: None,
+            deadline: None,
+            result: Some(Ok(returned)),
+        };
+    };
+
+    let id = next_id();
+    let mut future = CoroutineFuture {
+        id,
+        state: None,
+        deadline: None,
+        result: None,
+    };
+
+    // The dispatch method is called as `coroutine_completed(result, id)`.
+    let callable = crate::dispatch::callable(COROUTINE_METHOD_NAME)
+        .as_inner()
+        .bindv(varray![id as i64]);
+
+    let one_shot = object::ConnectFlags::ONE_SHOT.ord() as u32;
+    let code = state
+        .connect_ex(COMPLETED_SIGNAL.into(), callable.clone())
+        .flags(one_shot)
+        .done();
+
+    if code != crate::engine::global::Error::OK {
+        let err = ConnectError::new(COMPLETED_SIGNAL, ConnectErrorKind::Engine(code));
+        future.result = Some(Err(SignalError::Connect(err)));
+        return future;
+    }
+
+    SIGNALS.with(|signals| signals.borrow_mut().insert(id, SignalState::default()));
+    future.state = Some((state, callable));
+    future
+}
+
+/// Stores the result of the coroutine future `id`, and marks it as completed. Called by the dispatch class.
+pub(crate) fn coroutine_completed(result: Variant, id: u64) {
+    let waker = SIGNALS.with(|signals| {
+        let mut signals = signals.borrow_mut();
+        let state = signals.get_mut(&id)?;
+        state.fired = true;
+        state.value = Some(result);
+        state.waker.take()
+    });
+
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+}
+
+/// Future returned by [`coroutine_future()`].
+#[must_use = "futures do nothing unless awaited"]
+pub struct CoroutineFuture {
+    id: u64,
+    /// Function state, kept alive until completion, and the callable connected to its `completed` signal.
+    state: Option<(Gd<Object>, Callable)>,
+    deadline: Option<Instant>,
+    /// Outcome known before polling, e.g. an already returned value.
+    result: Option<Result<Variant, SignalError>>,
+}
+
+impl CoroutineFuture {
+    /// Fails with [`SignalError::TimedOut`] if the coroutine does not finish within `duration` from now.
+    ///
+    /// The deadline is checked once per frame, so it requires [`drive_each_frame()`].
+    pub fn timeout(mut self, duration: Duration) -> Self {
+        self.deadline = Some(Instant::now() + duration);
+        self
+    }
+
+    /// Removes the connection and the state of this future, if still present.
+    fn disconnect(&mut self) {
+        SIGNALS.with(|signals| signals.borrow_mut().remove(&self.id));
+
+        let Some((mut state, callable)) = self.state.take() else {
+            return;
+        };
+        if state.is_connected(COMPLETED_SIGNAL.into(), callable.clone()) {
+            state.disconnect(COMPLETED_SIGNAL.into(), callable);
+        }
+    }
+}
+
+impl Future for CoroutineFuture {
+    type Output = Result<Variant, SignalError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(result) = self.result.take() {
+            return Poll::Ready(result);
+        }
+
+        let value = SIGNALS.with(|signals| {
+            let mut signals = signals.borrow_mut();
+            let state = signals.entry(self.id).or_default();
+            state.waker = Some(cx.waker().clone());
+            state.value.take()
+        });
+
+        let result = if let Some(value) = value {
+            // One-shot connection is already removed by Godot.
+            self.state = None;
+            Ok(value)
+        } else if let Some(deadline) = self.deadline {
+            if Instant::now() < deadline {
+                // Check the deadline again in the next frame.
+                wake_next_frame(cx.waker());
+                return Poll::Pending;
+            }
+            Err(SignalError::TimedOut)
+        } else {
+            return Poll::Pending;
+        };
+
+        self.disconnect();
+        Poll::Ready(result)
+    }
+}
+
+impl Drop for CoroutineFuture {
+    fn drop(&mut self) {
+        self.disconnect();
+    }
+}
+
+/// Creates a channel to send values from any thread to the main thread.
+///
+/// The [`Receiver`] can be drained at a fixed point, e.g. with [`try_iter()`][Receiver::try_iter] in `_process()`, or be
+/// handed a callback with [`dispatch()`][Receiver::dispatch]:
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// # use godot::engine::ProgressBar;
+/// # use godot::task;
+/// # fn start(mut progress: Gd<ProgressBar>) {
+/// let (sender, receiver) = task::channel::<f64>();
+///
+/// std::thread::spawn(move || {
+///     for percent in 0..=100 {
+///         // ... load a chunk ...
+///         sender.send(percent as f64).ok();
+///     }
+/// });
+///
+/// receiver.dispatch(move |percent| progress.set_value(percent));
+/// # }
+/// ```
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let (sender, receiver) = mpsc::channel();
+    let waker = Arc::new(Mutex::new(None));
+
+    let sender = Sender {
+        inner: sender,
+        waker: waker.clone(),
+    };
+    let receiver = Receiver {
+        inner: receiver,
+        waker,
+    };
+
+    (sender, receiver)
+}
+
+/// Sending half of a [`channel()`], which can be cloned and moved to other threads.
+pub struct Sender<T> {
+    inner: mpsc::Sender<T>,
+    /// Waker of the task awaiting the receiver, if any.
+    waker: Arc<Mutex<Option<Waker>>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends `value` to the receiver, or gives it back if the receiver has been dropped.
+    pub fn send(&self, value: T) -> Result<(), T> {
+        self.inner.send(value).map_err(|err| err.0)?;
+        self.wake();
+        Ok(())
+    }
+
+    fn wake(&self) {
+        let waker = self.waker.lock().unwrap().take();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            waker: self.waker.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        // If this was the last sender, an awaiting receiver completes.
+        self.wake();
+    }
+}
+
+/// Receiving half of a [`channel()`], used on the main thread.
+pub struct Receiver<T> {
+    inner: mpsc::Receiver<T>,
+    waker: Arc<Mutex<Option<Waker>>>,
+}
+
+impl<T> Receiver<T> {
+    /// Iterates over the values sent so far, without blocking.
+    pub fn try_iter(&self) -> mpsc::TryIter<'_, T> {
+        self.inner.try_iter()
+    }
+
+    /// Completes with the next value, or with `None` once all senders have been dropped.
+    pub fn recv(&mut self) -> Recv<'_, T> {
+        Recv { receiver: self }
+    }
+
+    /// Spawns a task that calls `callback` on the main thread for each value, at the next [`poll()`] after it was sent.
+    ///
+    /// The task ends once all senders have been dropped, or when `callback` panics. Cancel it to stop receiving.
+    pub fn dispatch<F>(mut self, mut callback: F) -> TaskHandle
+    where
+        T: 'static,
+        F: FnMut(T) + 'static,
+    {
+        spawn(async move {
+            while let Some(value) = self.recv().await {
+                callback(value);
+            }
+        })
+    }
+}
+
+/// Future returned by [`Receiver::recv()`].
+#[must_use = "futures do nothing unless awaited"]
+pub struct Recv<'a, T> {
+    receiver: &'a mut Receiver<T>,
+}
+
+impl<T> Future for Recv<'_, T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let receiver = &self.receiver;
+
+        match receiver.inner.try_recv() {
+            Ok(value) => return Poll::Ready(Some(value)),
+            Err(mpsc::TryRecvError::Disconnected) => return Poll::Ready(None),
+            Err(mpsc::TryRecvError::Empty) => {}
+        }
+
+        *receiver.waker.lock().unwrap() = Some(cx.waker().clone());
+
+        // A value may have been sent before the waker was registered.
+        match receiver.inner.try_recv() {
+            Ok(value) => Poll::Ready(Some(value)),
+            Err(mpsc::TryRecvError::Disconnected) => Poll::Ready(None),
+            Err(mpsc::TryRecvError::Empty) => Poll::Pending,
+        }
+    }
+}
+
+/// Number of arguments of `signal`, or `None` if `object` has no such signal.
+fn signal_arg_count(object: &Gd<Object>, signal: &StringName) -> Option<usize> {
+    let signal = GodotString::from(signal);
+
+    object.get_signal_list().iter_shared().find_map(|info| {
+        let name = info.get("name")?.try_to::<GodotString>().ok()?;
+        if name != signal {
+            return None;
+        }
+
+        let args = info.get("args")?.try_to::<VariantArray>().ok()?;
+        Some(args.len())
+    })
+}
+
+fn next_id() -> u64 {
+    NEXT_ID.with(|next| {
+        let id = next.get();
+        next.set(id + 1);
+        id
+    })
+}
+
+fn poll_task(id: u64, mut task: BoxedTask) {
+    let waker = Waker::from(Arc::new(TaskWaker { id }));
+    let mut cx = Context::from_waker(&waker);
+
+    let result = crate::private::handle_panic(
+        || format!("async task {id}"),
+        std::panic::AssertUnwindSafe(|| task.as_mut().poll(&mut cx)),
+    );
+
+    // Completed and panicked tasks are dropped.
+    if result == Some(Poll::Pending) {
+        TASKS.with(|tasks| tasks.borrow_mut().insert(id, task));
+    }
+}
@@ -21,9 +21,13 @@ fn main() {
     let rs_path = gen_path.join("gdextension_interface.rs");
 
     godot_bindings::clear_dir(gen_path, &mut watch);
-    godot_bindings::write_gdextension_headers(&h_path, &rs_path, &mut watch);
-
-    godot_codegen::generate_sys_files(gen_path, &h_path, &mut watch);
+    if godot_codegen::restore_from_cache("ffi", gen_path) {
+        watch.record("restore_from_cache");
+    } else {
+        godot_bindings::write_gdextension_headers(&h_path, &rs_path, &mut watch);
+        godot_codegen::generate_sys_files(gen_path, &h_path, &mut watch);
+        godot_codegen::store_in_cache("ffi", gen_path);
+    }
 
     watch.write_stats_to(&gen_path.join("ffi-stats.txt"));
     println!("cargo:rerun-if-changed=build.rs");
@@ -36,3 +36,31 @@ pub(crate) trait BindingCompat {
     /// Return the interface, either as-is from the header (legacy) or code-generated (modern API).
     fn load_interface(&self) -> sys::GDExtensionInterface;
 }
+
+/// Returns an error if the Godot binary is older than the version gdext was compiled against.
+///
+/// GDExtension is only backwards compatible: a newer engine can load extensions built for an older API, but not vice versa.
+/// Structs passed across FFI (e.g. class creation info) may have changed size, and functions added later are missing.
+/// Patch versions do not change the API, so only major and minor versions are compared.
+pub(crate) fn ensure_runtime_not_older(
+    runtime: &sys::GDExtensionGodotVersion,
+) -> Result<(), String> {
+    let (static_major, static_minor, _) = sys::GdextBuild::godot_static_version_triple();
+    let static_api = (static_major as u32, static_minor as u32);
+    let runtime_api = (runtime.major, runtime.minor);
+
+    if runtime_api >= static_api {
+        return Ok(());
+    }
+
+    let static_version = sys::GdextBuild::godot_static_version_string();
+    let runtime_version = unsafe { std::ffi::CStr::from_ptr(runtime.string) }.to_string_lossy();
+
+    Err(format!(
+        "gdext was compiled against Godot {static_major}.{static_minor} ({static_version}),\n\
+        but loaded by an older Godot binary ({runtime_version}).\n\
+        \n\
+        Run the extension with Godot {static_major}.{static_minor} or newer, or recompile it against\n\
+        the older version (see `custom-godot` feature)."
+    ))
+}
@@ -93,6 +93,7 @@ struct GodotBinding {
 
 struct GdextRuntimeMetadata {
     godot_version: GDExtensionGodotVersion,
+    missing_functions: Vec<&'static str>,
 }
 
 pub struct GdextConfig {
@@ -113,11 +114,13 @@ static mut BINDING: Option<GodotBinding> = None;
 /// - The `library` pointer must be the pointer given by Godot at initialisation.
 /// - This function must not be called from multiple threads.
 /// - This function must be called before any use of [`get_library`].
+///
+/// Returns an error message, without initializing anything, if the Godot binary is older than the API gdext was compiled against.
 pub unsafe fn initialize(
     compat: InitCompat,
     library: GDExtensionClassLibraryPtr,
     config: GdextConfig,
-) {
+) -> Result<(), String> {
     out!("Initialize gdext...");
 
     out!(
@@ -131,26 +134,30 @@ pub unsafe fn initialize(
     let version = compat.runtime_version();
     out!("Godot version of GDExtension API at runtime: {version:?}");
 
+    // Function pointers and struct layouts of a newer API cannot be provided by an older binary -> refuse with both versions.
+    compat::ensure_runtime_not_older(&version)?;
+
     let interface = compat.load_interface();
     out!("Loaded interface.");
 
+    // Custom or pre-release engine builds may lack functions under the same version; reported by godot-core once logging works.
+    #[cfg(since_api = "4.1")]
+    let missing_functions = interface.missing_functions();
+    #[cfg(before_api = "4.1")]
+    let missing_functions = Vec::new();
+
     let global_method_table = BuiltinLifecycleTable::load(&interface);
     out!("Loaded global method table.");
 
-    let mut string_names = StringCache::new(&interface, &global_method_table);
-
-    let builtin_method_table = BuiltinMethodTable::load(&interface, &mut string_names);
-    out!("Loaded builtin method table.");
-
-    let utility_function_table = UtilityFunctionTable::load(&interface, &mut string_names);
-    out!("Loaded utility function table.");
+    // Entries are fetched lazily on first use, see load_builtin_method() and load_utility_function().
+    let builtin_method_table = BuiltinMethodTable::load();
+    let utility_function_table = UtilityFunctionTable::load();
 
     let runtime_metadata = GdextRuntimeMetadata {
         godot_version: version,
+        missing_functions,
     };
 
-    drop(string_names);
-
     BINDING = Some(GodotBinding {
         interface,
         global_method_table,
@@ -171,6 +178,8 @@ pub unsafe fn initialize(
             .to_str()
             .expect("unknown Godot version")
     );
+
+    Ok(())
 }
 
 /// # Safety
@@ -269,45 +278,32 @@ pub unsafe fn utility_function_table() -> &'static UtilityFunctionTable {
 pub unsafe fn load_class_method_table(api_level: ClassApiLevel) {
     let binding = unwrap_ref_unchecked_mut(&mut BINDING);
 
-    out!("Load class method table for level '{:?}'...", api_level);
-    let begin = std::time::Instant::now();
-
-    let mut string_names = StringCache::new(&binding.interface, &binding.global_method_table);
+    // Method binds are fetched lazily on first use (see load_class_method()). The table only becomes available at its level,
+    // since Godot registers the classes of a level only once that level is initialized.
     let (class_count, method_count);
     match api_level {
         ClassApiLevel::Server => {
-            binding.class_server_method_table = Some(ClassServersMethodTable::load(
-                &binding.interface,
-                &mut string_names,
-            ));
+            binding.class_server_method_table = Some(ClassServersMethodTable::load());
             class_count = ClassServersMethodTable::CLASS_COUNT;
             method_count = ClassServersMethodTable::METHOD_COUNT;
         }
         ClassApiLevel::Scene => {
-            binding.class_scene_method_table = Some(ClassSceneMethodTable::load(
-                &binding.interface,
-                &mut string_names,
-            ));
+            binding.class_scene_method_table = Some(ClassSceneMethodTable::load());
             class_count = ClassSceneMethodTable::CLASS_COUNT;
             method_count = ClassSceneMethodTable::METHOD_COUNT;
         }
         ClassApiLevel::Editor => {
-            binding.class_editor_method_table = Some(ClassEditorMethodTable::load(
-                &binding.interface,
-                &mut string_names,
-            ));
+            binding.class_editor_method_table = Some(ClassEditorMethodTable::load());
             class_count = ClassEditorMethodTable::CLASS_COUNT;
             method_count = ClassEditorMethodTable::METHOD_COUNT;
         }
     }
 
-    let _elapsed = std::time::Instant::now() - begin;
     out!(
-        "{:?} level: loaded {} classes and {} methods in {}s.",
+        "{:?} level: prepared lazy table for {} classes and {} methods.",
         api_level,
         class_count,
         method_count,
-        _elapsed.as_secs_f64()
     );
 }
 
@@ -319,6 +315,17 @@ pub(crate) unsafe fn runtime_metadata() -> &'static GdextRuntimeMetadata {
     &BINDING.as_ref().unwrap().runtime_metadata
 }
 
+/// Names of functions declared in the compiled-against GDExtension header, which the running Godot binary did not provide.
+///
+/// Calling one of them panics with its name.
+///
+/// # Safety
+///
+/// Must be accessed from the main thread, and the interface must have been initialized.
+pub unsafe fn missing_interface_functions() -> &'static [&'static str] {
+    &runtime_metadata().missing_functions
+}
+
 /// # Safety
 ///
 /// Must be accessed from the main thread, and the interface must have been initialized.
@@ -350,6 +357,21 @@ macro_rules! builtin_call {
 #[doc(hidden)]
 macro_rules! interface_fn {
     ($name:ident) => {{
-        unsafe { $crate::get_interface().$name.unwrap_unchecked() }
+        match unsafe { $crate::get_interface().$name } {
+            Some(func) => func,
+            None => $crate::interface_fn_missing(stringify!($name)),
+        }
     }};
 }
+
+/// Called when a GDExtension function is not provided by the running Godot binary.
+#[doc(hidden)]
+#[cold]
+pub fn interface_fn_missing(name: &str) -> ! {
+    panic!(
+        "GDExtension function `{name}` is not available in the running Godot binary ({runtime});\n\
+        gdext was compiled against {static_version}",
+        runtime = GdextBuild::godot_runtime_version_string(),
+        static_version = GdextBuild::godot_static_version_string(),
+    )
+}
@@ -7,6 +7,7 @@
 //! Functions and macros that are not very specific to gdext, but come in handy.
 
 use crate as sys;
+use crate::StringCache;
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 // Macros
@@ -138,6 +139,93 @@ pub fn hash_value<T: std::hash::Hash>(t: &T) -> u64 {
     hasher.finish()
 }
 
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Call thunks for generated code
+
+// Engine methods call these instead of emitting the ptrcall sequence inline. The thunks are generic only over the return type, so they
+// are instantiated once per distinct return type rather than once per method, which considerably shrinks the final binary.
+
+/// Calls an engine class method via ptrcall, writing the result (if any) to `return_ptr`.
+///
+/// # Safety
+/// `method_bind` must be a valid method bind for the class of `object_ptr` (or `object_ptr` null for static methods).
+/// `args` must point to arguments matching the method's signature; `return_ptr` must be null or point to storage of the return type.
+#[doc(hidden)]
+#[inline(never)]
+pub unsafe fn class_method_ptrcall(
+    method_bind: sys::GDExtensionMethodBindPtr,
+    object_ptr: sys::GDExtensionObjectPtr,
+    args: &[sys::GDExtensionConstTypePtr],
+    return_ptr: sys::GDExtensionTypePtr,
+) {
+    let call_fn = sys::interface_fn!(object_method_bind_ptrcall);
+    call_fn(method_bind, object_ptr, args.as_ptr(), return_ptr);
+}
+
+/// Calls an engine class method via ptrcall and converts its return value to `R`.
+///
+/// # Safety
+/// See [`class_method_ptrcall`]. Additionally, `R` must be the method's return type.
+#[doc(hidden)]
+#[inline(never)]
+pub unsafe fn class_method_ptrcall_ret<R: sys::GodotFuncMarshal>(
+    method_bind: sys::GDExtensionMethodBindPtr,
+    object_ptr: sys::GDExtensionObjectPtr,
+    args: &[sys::GDExtensionConstTypePtr],
+) -> R {
+    let via = <R::Via as sys::GodotFfi>::from_sys_init_default(|return_ptr| {
+        class_method_ptrcall(method_bind, object_ptr, args, return_ptr)
+    });
+
+    R::try_from_via(via).unwrap()
+}
+
+/// Contiguous list of argument pointers for vararg calls.
+///
+/// Up to [`Self::INLINE_CAPACITY`] pointers are stored inline, so that typical calls don't allocate; longer lists go to the heap.
+#[doc(hidden)]
+pub struct ArgPtrBuffer {
+    inline: [*const std::ffi::c_void; Self::INLINE_CAPACITY],
+    heap: Vec<*const std::ffi::c_void>,
+    len: usize,
+}
+
+impl ArgPtrBuffer {
+    pub const INLINE_CAPACITY: usize = 8;
+
+    /// Collects `len` pointers from `ptrs`, which must yield exactly that many.
+    pub fn new(len: usize, ptrs: impl Iterator<Item = *const std::ffi::c_void>) -> Self {
+        let mut inline = [std::ptr::null(); Self::INLINE_CAPACITY];
+        let mut heap = Vec::new();
+
+        if len <= Self::INLINE_CAPACITY {
+            for (slot, ptr) in inline.iter_mut().zip(ptrs) {
+                *slot = ptr;
+            }
+        } else {
+            heap.extend(ptrs);
+        }
+
+        Self { inline, heap, len }
+    }
+
+    pub fn as_ptr(&self) -> *const *const std::ffi::c_void {
+        if self.len <= Self::INLINE_CAPACITY {
+            self.inline.as_ptr()
+        } else {
+            self.heap.as_ptr()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 // Private helpers
 
@@ -257,4 +345,65 @@ pub(crate) fn validate_utility_function(
     })
 }
 
+/// Fetches a class method bind from Godot. Used by the lazy method tables on first access of an entry.
+///
+/// # Safety
+/// The binding must be initialized, and the class must be registered in Godot (i.e. its init level has been reached).
+pub(crate) unsafe fn load_class_method(
+    class_name: &'static str,
+    method_name: &'static str,
+    hash: i64,
+) -> ClassMethodBind {
+    let interface = sys::get_interface();
+    let mut string_names = StringCache::new(interface, sys::method_table());
+
+    let get_method_bind = interface
+        .classdb_get_method_bind
+        .expect("classdb_get_method_bind absent");
+    let method_bind = get_method_bind(
+        string_names.fetch(class_name),
+        string_names.fetch(method_name),
+        hash,
+    );
+
+    validate_class_method(method_bind, class_name, method_name, hash)
+}
+
+/// Fetches a builtin method from Godot. Used by the lazy method tables on first access of an entry.
+///
+/// # Safety
+/// The binding must be initialized.
+pub(crate) unsafe fn load_builtin_method(
+    variant_type: sys::GDExtensionVariantType,
+    variant_type_str: &'static str,
+    method_name: &'static str,
+    hash: i64,
+) -> BuiltinMethodBind {
+    let interface = sys::get_interface();
+    let mut string_names = StringCache::new(interface, sys::method_table());
+
+    let get_builtin_method = interface
+        .variant_get_ptr_builtin_method
+        .expect("variant_get_ptr_builtin_method absent");
+    let method = get_builtin_method(variant_type, string_names.fetch(method_name), hash);
+
+    validate_builtin_method(method, variant_type_str, method_name, hash)
+}
+
+/// Fetches a utility function from Godot. Used by the lazy method tables on first access of an entry.
+///
+/// # Safety
+/// The binding must be initialized.
+pub(crate) unsafe fn load_utility_function(name: &'static str, hash: i64) -> UtilityFunctionBind {
+    let interface = sys::get_interface();
+    let mut string_names = StringCache::new(interface, sys::method_table());
+
+    let get_utility_fn = interface
+        .variant_get_ptr_utility_function
+        .expect("variant_get_ptr_utility_function absent");
+    let utility_fn = get_utility_fn(string_names.fetch(name), hash);
+
+    validate_utility_function(utility_fn, name, hash)
+}
+
 const INFO: &str = "\nMake sure gdext and Godot are compatible: https://godot-rust.github.io/book/gdext/advanced/compatibility.html";
@@ -8,7 +8,7 @@ use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 
 use crate::class::{
-    make_existence_check, make_method_registration, Field, FieldHint, FuncDefinition,
+    make_existence_check, make_method_registration, Field, FieldHint, FuncContext, FuncDefinition,
 };
 use crate::util::KvParser;
 use crate::{util, ParseResult};
@@ -195,6 +195,8 @@ impl GetterSetterImpl {
             FuncDefinition {
                 func: signature,
                 rename: None,
+                context: FuncContext::Anywhere,
+                err_as_variant: false,
             },
         );
 
@@ -14,6 +14,18 @@ pub struct FuncDefinition {
     pub func: venial::Function,
     /// The name the function will be exposed as in Godot. If `None`, the Rust function name is used.
     pub rename: Option<String>,
+    /// Whether the function may only be called in the editor, or only outside of it.
+    pub context: FuncContext,
+    /// For functions returning `Result`: whether `Err` is returned as `Variant`, instead of failing the call.
+    pub err_as_variant: bool,
+}
+
+/// Where a `#[func]` may be called, set by `#[func(tool_only)]` or `#[func(runtime_only)]`.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum FuncContext {
+    Anywhere,
+    ToolOnly,
+    RuntimeOnly,
 }
 
 /// Returns a C function which acts as the callback when a virtual method of this instance is invoked.
@@ -27,11 +39,15 @@ pub fn make_virtual_method_callback(
     let signature_info = get_signature_info(method_signature);
     let method_name = &method_signature.name;
 
-    let wrapped_method = make_forwarding_closure(class_name, &signature_info);
+    let wrapped_method =
+        make_forwarding_closure(class_name, &signature_info, TokenStream::new(), |call| {
+            quote! { ::std::result::Result::Ok(#call) }
+        });
     let sig_tuple =
         util::make_signature_tuple_type(&signature_info.ret_type, &signature_info.param_types);
 
     let invocation = make_ptrcall_invocation(method_name, &sig_tuple, &wrapped_method, true);
+    let method_name_str = method_name.to_string();
 
     quote! {
         {
@@ -42,7 +58,13 @@ pub fn make_virtual_method_callback(
                 args_ptr: *const sys::GDExtensionConstTypePtr,
                 ret: sys::GDExtensionTypePtr,
             ) {
-                #invocation;
+                // No error channel for virtual calls; the return value keeps its default.
+                let _ = ::godot::private::handle_func_panic(
+                    #class_name::__config().panic_policy,
+                    <#class_name as ::godot::obj::GodotClass>::class_name,
+                    || #method_name_str,
+                    || #invocation
+                );
             }
             Some(function)
         }
@@ -54,7 +76,40 @@ pub fn make_method_registration(
     class_name: &Ident,
     func_definition: FuncDefinition,
 ) -> TokenStream {
-    let signature_info = get_signature_info(&func_definition.func);
+    let mut signature_info = get_signature_info(&func_definition.func);
+
+    // `Result<T, E>` is exposed as `T`, or as `Variant` with #[func(err_as_variant)].
+    // The conversion returns `CallResult`, whose `Err` fails the call.
+    let ret_conversion = match util::extract_result_type(&signature_info.ret_type) {
+        Some(_) if func_definition.err_as_variant => {
+            signature_info.ret_type = quote! { ::godot::builtin::Variant };
+            quote! { ::godot::private::func_result_to_variant }
+        }
+        Some((ok_type, _err_type)) => {
+            signature_info.ret_type = ok_type;
+            quote! { ::godot::private::func_result }
+        }
+        None => quote! { ::std::result::Result::Ok },
+    };
+
+    // `async fn` returns an object whose `completed` signal carries the (converted) output of the future.
+    let is_async = func_definition.func.qualifiers.tk_async.is_some();
+    if is_async {
+        signature_info.ret_type = quote! { ::godot::obj::Gd<::godot::engine::RefCounted> };
+    }
+    let async_name_str = signature_info.method_name.to_string();
+    let wrap_call = move |call: TokenStream| {
+        if is_async {
+            quote! {
+                ::std::result::Result::Ok(
+                    ::godot::private::spawn_async_func(#async_name_str, #call, #ret_conversion)
+                )
+            }
+        } else {
+            quote! { #ret_conversion(#call) }
+        }
+    };
+
     let sig_tuple =
         util::make_signature_tuple_type(&signature_info.ret_type, &signature_info.param_types);
 
@@ -63,11 +118,6 @@ pub fn make_method_registration(
 
     let method_flags = make_method_flags(signature_info.receiver_type);
 
-    let forwarding_closure = make_forwarding_closure(class_name, &signature_info);
-
-    let varcall_func = make_varcall_func(method_name, &sig_tuple, &forwarding_closure);
-    let ptrcall_func = make_ptrcall_func(method_name, &sig_tuple, &forwarding_closure);
-
     // String literals
     let class_name_str = class_name.to_string();
     let method_name_str = if let Some(rename) = func_definition.rename {
@@ -77,16 +127,23 @@ pub fn make_method_registration(
     };
     let param_ident_strs = param_idents.iter().map(|ident| ident.to_string());
 
+    let context_check = make_context_check(func_definition.context, &method_name_str);
+    let forwarding_closure =
+        make_forwarding_closure(class_name, &signature_info, context_check, wrap_call);
+
+    let varcall_func = make_varcall_func(class_name, method_name, &sig_tuple, &forwarding_closure);
+    let ptrcall_func = make_ptrcall_func(class_name, method_name, &sig_tuple, &forwarding_closure);
+
     quote! {
         {
             use ::godot::obj::GodotClass;
             use ::godot::builtin::meta::registration::method::MethodInfo;
-            use ::godot::builtin::{StringName, Variant};
+            use ::godot::builtin::Variant;
             use ::godot::sys;
 
             type Sig = #sig_tuple;
 
-            let method_name = StringName::from(#method_name_str);
+            let method_name = ::godot::builtin::meta::registration::cached_string_name(#method_name_str);
 
             let varcall_func = #varcall_func;
             let ptrcall_func = #ptrcall_func;
@@ -138,11 +195,36 @@ struct SignatureInfo {
     pub ret_type: TokenStream,
 }
 
+/// Returns a statement that panics if the function is called in a context excluded by `#[func(tool_only)]` or
+/// `#[func(runtime_only)]`. The panic is caught and reported like any other panic inside a `#[func]`.
+fn make_context_check(context: FuncContext, method_name_str: &str) -> TokenStream {
+    let tool_only = match context {
+        FuncContext::Anywhere => return TokenStream::new(),
+        FuncContext::ToolOnly => true,
+        FuncContext::RuntimeOnly => false,
+    };
+
+    quote! {
+        ::godot::private::ensure_func_context(#method_name_str, #tool_only);
+    }
+}
+
 /// Returns a closure expression that forwards the parameters to the Rust instance.
-fn make_forwarding_closure(class_name: &Ident, signature_info: &SignatureInfo) -> TokenStream {
+///
+/// `prelude` is inserted before the call, and may be empty. `wrap_call` maps the call expression to a `CallResult`, e.g. to
+/// convert its result.
+fn make_forwarding_closure(
+    class_name: &Ident,
+    signature_info: &SignatureInfo,
+    prelude: TokenStream,
+    wrap_call: impl Fn(TokenStream) -> TokenStream,
+) -> TokenStream {
     let method_name = &signature_info.method_name;
     let params = &signature_info.param_idents;
 
+    let instance_call = wrap_call(quote! { instance.#method_name(#(#params),*) });
+    let static_call = wrap_call(quote! { <#class_name>::#method_name(#(#params),*) });
+
     let instance_decl = match &signature_info.receiver_type {
         ReceiverType::Ref => quote! {
             let instance = storage.get();
@@ -158,12 +240,13 @@ fn make_forwarding_closure(class_name: &Ident, signature_info: &SignatureInfo) -
             quote! {
                 |instance_ptr, params| {
                     let ( #(#params,)* ) = params;
+                    #prelude
 
                     let storage =
                         unsafe { ::godot::private::as_storage::<#class_name>(instance_ptr) };
                     #instance_decl
 
-                    instance.#method_name(#(#params),*)
+                    #instance_call
                 }
             }
         }
@@ -171,7 +254,8 @@ fn make_forwarding_closure(class_name: &Ident, signature_info: &SignatureInfo) -
             quote! {
                 |_, params| {
                     let ( #(#params,)* ) = params;
-                    <#class_name>::#method_name(#(#params),*)
+                    #prelude
+                    #static_call
                 }
             }
         }
@@ -239,6 +323,7 @@ fn make_method_flags(method_type: ReceiverType) -> TokenStream {
 
 /// Generate code for a C FFI function that performs a varcall.
 fn make_varcall_func(
+    class_name: &Ident,
     method_name: &Ident,
     sig_tuple: &TokenStream,
     wrapped_method: &TokenStream,
@@ -252,19 +337,26 @@ fn make_varcall_func(
                 _method_data: *mut std::ffi::c_void,
                 instance_ptr: sys::GDExtensionClassInstancePtr,
                 args_ptr: *const sys::GDExtensionConstVariantPtr,
-                _arg_count: sys::GDExtensionInt,
+                arg_count: sys::GDExtensionInt,
                 ret: sys::GDExtensionVariantPtr,
                 err: *mut sys::GDExtensionCallError,
             ) {
-                let success = ::godot::private::handle_panic(
+                let result = ::godot::private::handle_func_panic(
+                    #class_name::__config().panic_policy,
+                    <#class_name as ::godot::obj::GodotClass>::class_name,
                     || #method_name_str,
                     || #invocation
                 );
 
-                if success.is_none() {
-                    // Signal error and set return type to Nil
-                    (*err).error = sys::GDEXTENSION_CALL_ERROR_INVALID_METHOD; // no better fitting enum?
+                if let Err(policy) = result {
+                    // Signal error, unless the policy continues as if the call succeeded
+                    (*err).error = if policy == ::godot::init::PanicPolicy::ReturnError {
+                        sys::GDEXTENSION_CALL_ERROR_INVALID_METHOD // no better fitting enum?
+                    } else {
+                        sys::GDEXTENSION_CALL_OK
+                    };
 
+                    // Set return type to Nil
                     // TODO(uninit)
                     sys::interface_fn!(variant_new_nil)(sys::AsUninit::as_uninit(ret));
                 }
@@ -277,6 +369,7 @@ fn make_varcall_func(
 
 /// Generate code for a C FFI function that performs a ptrcall.
 fn make_ptrcall_func(
+    class_name: &Ident,
     method_name: &Ident,
     sig_tuple: &TokenStream,
     wrapped_method: &TokenStream,
@@ -291,14 +384,13 @@ fn make_ptrcall_func(
                 args_ptr: *const sys::GDExtensionConstTypePtr,
                 ret: sys::GDExtensionTypePtr,
             ) {
-                let success = ::godot::private::handle_panic(
+                // No error channel for ptrcalls; the return value keeps its default.
+                let _ = ::godot::private::handle_func_panic(
+                    #class_name::__config().panic_policy,
+                    <#class_name as ::godot::obj::GodotClass>::class_name,
                     || stringify!(#method_name),
                     || #invocation
                 );
-
-                if success.is_none() {
-                    // TODO set return value to T::default()?
-                }
             }
 
             function
@@ -345,6 +437,7 @@ fn make_varcall_invocation(
         <#sig_tuple as ::godot::builtin::meta::VarcallSignatureTuple>::varcall(
             instance_ptr,
             args_ptr,
+            arg_count,
             ret,
             err,
             #wrapped_method,
@@ -180,14 +180,14 @@ pub fn make_property_impl(class_name: &Ident, fields: &Fields) -> TokenStream {
             let property_info = ::godot::builtin::meta::PropertyInfo {
                 variant_type: <<#field_type as ::godot::bind::property::Property>::Intermediate as ::godot::builtin::meta::VariantMetadata>::variant_type(),
                 class_name: #class_name_obj,
-                property_name: #field_name.into(),
+                property_name: ::godot::builtin::meta::registration::cached_string_name(#field_name),
                 hint,
                 hint_string,
                 usage,
             };
 
-            let getter_name = ::godot::builtin::StringName::from(#getter_name);
-            let setter_name = ::godot::builtin::StringName::from(#setter_name);
+            let getter_name = ::godot::builtin::meta::registration::cached_string_name(#getter_name);
+            let setter_name = ::godot::builtin::meta::registration::cached_string_name(#setter_name);
 
             let property_info_sys = property_info.property_sys();
 
@@ -9,6 +9,7 @@ use quote::{format_ident, quote};
 use venial::{Declaration, NamedField, Struct, StructFields};
 
 use crate::class::{make_property_impl, Field, FieldExport, FieldVar, Fields};
+use crate::docs;
 use crate::util::{bail, ident, KvParser};
 use crate::{util, ParseResult};
 
@@ -33,6 +34,13 @@ pub fn derive_godot_class(decl: Declaration) -> ParseResult<TokenStream> {
     let prv = quote! { ::godot::private };
     let godot_exports_impl = make_property_impl(class_name, &fields);
 
+    let base_field_impl = make_base_field_impl(class_name, &fields);
+    let thread_safe_impl = if struct_cfg.is_thread_safe {
+        make_thread_safe_impl(class_name, &fields)
+    } else {
+        TokenStream::new()
+    };
+
     let (godot_init_impl, create_fn);
     if struct_cfg.has_generated_init {
         godot_init_impl = make_godot_init_impl(class_name, fields);
@@ -42,13 +50,15 @@ pub fn derive_godot_class(decl: Declaration) -> ParseResult<TokenStream> {
         create_fn = quote! { None };
     };
 
-    let config_impl = make_config_impl(class_name, struct_cfg.is_tool);
+    let config_impl = make_config_impl(class_name, &struct_cfg);
+    let docs = docs::make_struct_docs(class);
 
     Ok(quote! {
         unsafe impl ::godot::obj::GodotClass for #class_name {
             type Base = #base_class;
             type Declarer = ::godot::obj::dom::UserDomain;
             type Mem = <Self::Base as ::godot::obj::GodotClass>::Mem;
+            const INIT_LEVEL: Option<::godot::init::InitLevel> = <#base_class as ::godot::obj::GodotClass>::INIT_LEVEL;
 
             fn class_name() -> ::godot::builtin::meta::ClassName {
                 ::godot::builtin::meta::ClassName::from_ascii_cstr(#class_name_cstr)
@@ -58,13 +68,17 @@ pub fn derive_godot_class(decl: Declaration) -> ParseResult<TokenStream> {
         #godot_init_impl
         #godot_exports_impl
         #config_impl
+        #base_field_impl
+        #thread_safe_impl
 
         ::godot::sys::plugin_add!(__GODOT_PLUGIN_REGISTRY in #prv; #prv::ClassPlugin {
             class_name: #class_name_obj,
             component: #prv::PluginComponent::ClassDef {
                 base_class_name: #base_class_name_obj,
+                init_level: <#class_name as ::godot::obj::GodotClass>::INIT_LEVEL,
                 generated_create_fn: #create_fn,
                 free_fn: #prv::callbacks::free::<#class_name>,
+                docs: #docs,
             },
         });
 
@@ -89,6 +103,8 @@ fn parse_struct_attributes(class: &Struct) -> ParseResult<ClassAttributes> {
     let mut base_ty = ident("RefCounted");
     let mut has_generated_init = false;
     let mut is_tool = false;
+    let mut is_thread_safe = false;
+    let mut panic_policy = None;
 
     // #[class] attribute on struct
     if let Some(mut parser) = KvParser::parse(&class.attributes, "class")? {
@@ -104,6 +120,22 @@ fn parse_struct_attributes(class: &Struct) -> ParseResult<ClassAttributes> {
             is_tool = true;
         }
 
+        if parser.handle_alone("thread_safe")? {
+            is_thread_safe = true;
+        }
+
+        if let Some(policy) = parser.handle_ident("on_panic")? {
+            const POLICIES: [&str; 4] = ["ReturnError", "LogAndContinue", "ReturnDefault", "Abort"];
+            if !POLICIES.contains(&policy.to_string().as_str()) {
+                bail!(
+                    &policy,
+                    "#[class(on_panic)] must be one of: {}",
+                    POLICIES.join(", ")
+                )?;
+            }
+            panic_policy = Some(policy);
+        }
+
         parser.finish()?;
     }
 
@@ -111,6 +143,8 @@ fn parse_struct_attributes(class: &Struct) -> ParseResult<ClassAttributes> {
         base_ty,
         has_generated_init,
         is_tool,
+        is_thread_safe,
+        panic_policy,
     })
 }
 
@@ -190,6 +224,8 @@ struct ClassAttributes {
     base_ty: Ident,
     has_generated_init: bool,
     is_tool: bool,
+    is_thread_safe: bool,
+    panic_policy: Option<Ident>,
 }
 
 fn make_godot_init_impl(class_name: &Ident, fields: Fields) -> TokenStream {
@@ -220,13 +256,54 @@ fn make_godot_init_impl(class_name: &Ident, fields: Fields) -> TokenStream {
     }
 }
 
-fn make_config_impl(class_name: &Ident, is_tool: bool) -> TokenStream {
+/// Implements `WithBaseField`, if the class has a `#[base]` field.
+fn make_base_field_impl(class_name: &Ident, fields: &Fields) -> TokenStream {
+    let Some(base_field) = &fields.base_field else {
+        return TokenStream::new();
+    };
+    let name = &base_field.name;
+
+    quote! {
+        impl ::godot::obj::cap::WithBaseField for #class_name {
+            fn __godot_base(&self) -> &::godot::obj::Gd<Self::Base> {
+                &*self.#name
+            }
+
+            fn __godot_base_mut(&mut self) -> &mut ::godot::obj::Gd<Self::Base> {
+                &mut *self.#name
+            }
+        }
+    }
+}
+
+/// Implements `ThreadSafe`, checking at compile time that all fields except `#[base]` are `Send` and `Sync`.
+fn make_thread_safe_impl(class_name: &Ident, fields: &Fields) -> TokenStream {
+    let field_types = fields.all_fields.iter().map(|field| &field.ty);
+
+    quote! {
+        const _: () = {
+            const fn assert_send_sync<T: ::std::marker::Send + ::std::marker::Sync>() {}
+            #( assert_send_sync::<#field_types>(); )*
+        };
+
+        unsafe impl ::godot::obj::cap::ThreadSafe for #class_name {}
+    }
+}
+
+fn make_config_impl(class_name: &Ident, struct_cfg: &ClassAttributes) -> TokenStream {
+    let is_tool = struct_cfg.is_tool;
+    let panic_policy = match &struct_cfg.panic_policy {
+        Some(policy) => quote! { Some(::godot::init::PanicPolicy::#policy) },
+        None => quote! { None },
+    };
+
     quote! {
         impl #class_name {
             #[doc(hidden)]
             pub fn __config() -> ::godot::private::ClassConfig {
                 ::godot::private::ClassConfig {
                     is_tool: #is_tool,
+                    panic_policy: #panic_policy,
                 }
             }
         }
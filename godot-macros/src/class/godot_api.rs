@@ -12,7 +12,10 @@ use venial::{
     TyExpr,
 };
 
-use crate::class::{make_method_registration, make_virtual_method_callback, FuncDefinition};
+use crate::class::{
+    make_method_registration, make_virtual_method_callback, FuncContext, FuncDefinition,
+};
+use crate::docs;
 use crate::util;
 use crate::util::{bail, KvParser};
 
@@ -47,7 +50,11 @@ pub fn attribute_godot_api(input_decl: Declaration) -> Result<TokenStream, Error
 
 /// Attribute for user-declared function
 enum BoundAttrType {
-    Func { rename: Option<String> },
+    Func {
+        rename: Option<String>,
+        context: FuncContext,
+        err_as_variant: bool,
+    },
     Signal(AttributeValue),
     Const(AttributeValue),
 }
@@ -68,6 +75,7 @@ impl BoundAttr {
 fn transform_inherent_impl(mut decl: Impl) -> Result<TokenStream, Error> {
     let class_name = util::validate_impl(&decl, None, "godot_api")?;
     let class_name_obj = util::class_name_obj(&class_name);
+    let docs = docs::make_inherent_impl_docs(&decl);
     let (funcs, signals) = process_godot_fns(&mut decl)?;
 
     let mut signal_name_strs: Vec<String> = Vec::new();
@@ -130,14 +138,13 @@ fn transform_inherent_impl(mut decl: Impl) -> Result<TokenStream, Error> {
         quote! {
             use ::godot::builtin::meta::registration::constant::*;
             use ::godot::builtin::meta::ClassName;
-            use ::godot::builtin::StringName;
 
             #(
                 ExportConstant::new(
                     #class_name_obj,
                     ConstantKind::Integer(
                         IntegerConstant::new(
-                            StringName::from(#integer_constant_names),
+                            ::godot::builtin::meta::registration::cached_string_name(#integer_constant_names),
                             #integer_constant_values
                         )
                     )
@@ -166,7 +173,7 @@ fn transform_inherent_impl(mut decl: Impl) -> Result<TokenStream, Error> {
                         let mut parameters_info_sys: [::godot::sys::GDExtensionPropertyInfo; #signal_parameters_count] =
                             std::array::from_fn(|i| parameters_info[i].property_sys());
 
-                        let signal_name = ::godot::builtin::StringName::from(#signal_name_strs);
+                        let signal_name = ::godot::builtin::meta::registration::cached_string_name(#signal_name_strs);
 
                         sys::interface_fn!(classdb_register_extension_class_signal)(
                             sys::get_library(),
@@ -192,6 +199,7 @@ fn transform_inherent_impl(mut decl: Impl) -> Result<TokenStream, Error> {
                 generated_register_fn: #prv::ErasedRegisterFn {
                     raw: #prv::callbacks::register_user_binds::<#class_name>,
                 },
+                docs: #docs,
             },
         });
     };
@@ -217,7 +225,6 @@ fn process_godot_fns(decl: &mut Impl) -> Result<(Vec<FuncDefinition>, Vec<Functi
 
             if method.qualifiers.tk_default.is_some()
                 || method.qualifiers.tk_const.is_some()
-                || method.qualifiers.tk_async.is_some()
                 || method.qualifiers.tk_unsafe.is_some()
                 || method.qualifiers.tk_extern.is_some()
                 || method.qualifiers.extern_abi.is_some()
@@ -229,11 +236,38 @@ fn process_godot_fns(decl: &mut Impl) -> Result<(Vec<FuncDefinition>, Vec<Functi
                 return attr.bail("generic fn parameters are not supported", method);
             }
 
+            if method.qualifiers.tk_async.is_some() {
+                if !matches!(attr.ty, BoundAttrType::Func { .. }) {
+                    return attr.bail("fn qualifiers are not allowed", method);
+                }
+
+                let has_receiver = method
+                    .params
+                    .inner
+                    .iter()
+                    .any(|(param, _)| matches!(param, FnParam::Receiver(_)));
+                if has_receiver {
+                    return attr.bail(
+                        "async fn cannot take `self`, as the future outlives the call; pass a `Gd<Self>` instead",
+                        method,
+                    );
+                }
+            }
+
             match attr.ty {
-                BoundAttrType::Func { rename } => {
+                BoundAttrType::Func {
+                    rename,
+                    context,
+                    err_as_variant,
+                } => {
                     // Signatures are the same thing without body
                     let sig = util::reduce_to_signature(method);
-                    func_definitions.push(FuncDefinition { func: sig, rename });
+                    func_definitions.push(FuncDefinition {
+                        func: sig,
+                        rename,
+                        context,
+                        err_as_variant,
+                    });
                 }
                 BoundAttrType::Signal(ref _attr_val) => {
                     if method.return_ty.is_some() {
@@ -317,10 +351,31 @@ where
 
                 let rename = parser.handle_expr("rename")?.map(|ts| ts.to_string());
 
+                let context = match (
+                    parser.handle_alone("tool_only")?,
+                    parser.handle_alone("runtime_only")?,
+                ) {
+                    (false, false) => FuncContext::Anywhere,
+                    (true, false) => FuncContext::ToolOnly,
+                    (false, true) => FuncContext::RuntimeOnly,
+                    (true, true) => {
+                        return bail!(
+                            parser.span(),
+                            "#[func]: `tool_only` and `runtime_only` are mutually exclusive"
+                        )
+                    }
+                };
+
+                let err_as_variant = parser.handle_alone("err_as_variant")?;
+
                 Some(BoundAttr {
                     attr_name: attr_name.clone(),
                     index,
-                    ty: BoundAttrType::Func { rename },
+                    ty: BoundAttrType::Func {
+                        rename,
+                        context,
+                        err_as_variant,
+                    },
                 })
             }
             name if name == "signal" => {
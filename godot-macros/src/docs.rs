@@ -0,0 +1,144 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Collection of `///` doc comments, turned into XML fragments for `godot::docs`.
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use venial::{Attribute, AttributeValue, Impl, ImplMember, Struct, StructFields};
+
+use crate::util::{path_is_single, unquote, KvParser};
+
+/// Returns `Some(StructDocs { .. })` expression for the struct and its `#[var]`/`#[export]` fields.
+pub fn make_struct_docs(class: &Struct) -> TokenStream {
+    let (brief, description) = match extract_doc(&class.attributes) {
+        Some(doc) => split_brief(&doc),
+        None => (String::new(), String::new()),
+    };
+
+    let mut members = String::new();
+    if let StructFields::Named(fields) = &class.fields {
+        for (field, _punct) in fields.fields.inner.iter() {
+            if !has_attr(&field.attributes, "var") && !has_attr(&field.attributes, "export") {
+                continue;
+            }
+
+            let doc = extract_doc(&field.attributes).unwrap_or_default();
+            members.push_str(&format!(
+                r#"<member name="{name}">{doc}</member>"#,
+                name = field.name,
+                doc = xml_escape(&doc),
+            ));
+        }
+    }
+
+    let brief = xml_escape(&brief);
+    let description = xml_escape(&description);
+
+    quote! {
+        Some(::godot::docs::StructDocs {
+            brief: #brief,
+            description: #description,
+            members: #members,
+        })
+    }
+}
+
+/// Returns `Some(InherentImplDocs { .. })` expression for the `#[func]`s and `#[signal]`s of the impl block.
+///
+/// Must be called before attributes are stripped from the impl.
+pub fn make_inherent_impl_docs(decl: &Impl) -> TokenStream {
+    let mut methods = String::new();
+    let mut signals = String::new();
+
+    for item in decl.body_items.iter() {
+        let ImplMember::Method(method) = item else {
+            continue;
+        };
+
+        let doc = extract_doc(&method.attributes).unwrap_or_default();
+        let doc = xml_escape(&doc);
+
+        if let Ok(Some(mut parser)) = KvParser::parse(&method.attributes, "func") {
+            let name = match parser.handle_expr("rename") {
+                Ok(Some(rename)) => rename.to_string(),
+                _ => method.name.to_string(),
+            };
+
+            methods.push_str(&format!(
+                r#"<method name="{name}"><description>{doc}</description></method>"#
+            ));
+        } else if has_attr(&method.attributes, "signal") {
+            signals.push_str(&format!(
+                r#"<signal name="{name}"><description>{doc}</description></signal>"#,
+                name = method.name,
+            ));
+        }
+    }
+
+    quote! {
+        Some(::godot::docs::InherentImplDocs {
+            methods: #methods,
+            signals: #signals,
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Implementation
+
+fn has_attr(attributes: &[Attribute], name: &str) -> bool {
+    attributes
+        .iter()
+        .any(|attr| path_is_single(&attr.path, name))
+}
+
+/// Joins all `#[doc = "..."]` attributes (which `///` comments desugar to) into one string.
+fn extract_doc(attributes: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attributes
+        .iter()
+        .filter(|attr| path_is_single(&attr.path, "doc"))
+        .filter_map(|attr| match &attr.value {
+            AttributeValue::Equals(_punct, tokens) => match tokens.as_slice() {
+                [TokenTree::Literal(lit)] => unquote(&lit.to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+        .collect();
+
+    let doc = lines.join("\n");
+    let doc = doc.trim();
+
+    if doc.is_empty() {
+        None
+    } else {
+        Some(doc.to_string())
+    }
+}
+
+/// Splits the first paragraph off, to be used as brief description.
+fn split_brief(doc: &str) -> (String, String) {
+    match doc.split_once("\n\n") {
+        Some((brief, rest)) => (brief.to_string(), rest.trim().to_string()),
+        None => (doc.to_string(), String::new()),
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,196 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use proc_macro2::{Literal, TokenStream, TokenTree};
+use quote::quote;
+use venial::Declaration;
+
+use crate::util::{bail, ident, path_is_single, unquote, KvParser};
+use crate::ParseResult;
+
+pub fn attribute_input_actions(input_decl: Declaration) -> ParseResult<TokenStream> {
+    let enum_ = match input_decl {
+        Declaration::Enum(e) => e,
+        _ => return bail!(&input_decl, "#[input_actions] can only be applied to enums"),
+    };
+
+    if !enum_.variants.is_empty() || enum_.generic_params.is_some() {
+        return bail!(
+            &enum_.name,
+            "#[input_actions] requires an empty, non-generic enum; variants are generated",
+        );
+    }
+
+    let mut parser = KvParser::parse_required(&enum_.attributes, "input_actions", &enum_.name)?;
+    let project_literal = parser
+        .handle_any("project")
+        .flatten()
+        .map(|value| value.single())
+        .transpose()?;
+    parser.finish()?;
+
+    let relative_path = match project_literal {
+        Some(TokenTree::Literal(lit)) => unquote(&lit.to_string()),
+        _ => None,
+    };
+    let Some(relative_path) = relative_path else {
+        return bail!(
+            &enum_.name,
+            "#[input_actions] requires `project = \"path/to/project.godot\"`",
+        );
+    };
+
+    // Relative to the crate being compiled, like include_str!() would be relative to the source file.
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = std::path::Path::new(&manifest_dir).join(&relative_path);
+    let project = match std::fs::read_to_string(&path) {
+        Ok(project) => project,
+        Err(err) => {
+            return bail!(
+                &enum_.name,
+                "#[input_actions]: cannot read `{}`: {err}",
+                path.display(),
+            )
+        }
+    };
+
+    let action_names = parse_input_actions(&project);
+
+    let mut variants = Vec::with_capacity(action_names.len());
+    for action in action_names.iter() {
+        let variant = to_variant_name(action);
+        if variants.contains(&variant) {
+            return bail!(
+                &enum_.name,
+                "#[input_actions]: several actions map to variant `{variant}`",
+            );
+        }
+        variants.push(variant);
+    }
+
+    let variant_idents: Vec<_> = variants.iter().map(|v| ident(v)).collect();
+    let variant_docs = action_names
+        .iter()
+        .map(|action| format!(" Input action `{action}`."));
+
+    let attributes = enum_
+        .attributes
+        .iter()
+        .filter(|attr| !path_is_single(&attr.path, "input_actions"));
+    let vis = &enum_.vis_marker;
+    let name = &enum_.name;
+
+    // Makes Cargo rebuild the crate when the project file changes.
+    let path_literal = Literal::string(&path.to_string_lossy());
+
+    Ok(quote! {
+        #( #attributes )*
+        #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+        #vis enum #name {
+            #(
+                #[doc = #variant_docs]
+                #variant_idents,
+            )*
+        }
+
+        impl #name {
+            /// All input actions of the project, in the order of `project.godot`.
+            pub const ALL: &'static [Self] = &[ #( Self::#variant_idents ),* ];
+
+            /// Name of the action, as used by `Input` and `InputMap`.
+            pub fn name(self) -> &'static str {
+                match self {
+                    #( Self::#variant_idents => #action_names, )*
+                }
+            }
+        }
+
+        impl From<#name> for ::godot::builtin::StringName {
+            fn from(action: #name) -> Self {
+                Self::from(action.name())
+            }
+        }
+
+        const _: &str = include_str!(#path_literal);
+    })
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Implementation
+
+/// Returns the action names declared in the `[input]` section of a `project.godot` file.
+fn parse_input_actions(project: &str) -> Vec<String> {
+    let mut actions = Vec::new();
+    let mut in_input_section = false;
+    let mut depth = 0;
+
+    for line in project.lines() {
+        if depth == 0 {
+            if line.starts_with('[') {
+                in_input_section = line.trim_end() == "[input]";
+                continue;
+            }
+
+            if in_input_section {
+                if let Some((key, _value)) = line.split_once('=') {
+                    let key = key.trim();
+                    let key = key
+                        .strip_prefix('"')
+                        .and_then(|k| k.strip_suffix('"'))
+                        .unwrap_or(key);
+
+                    if !key.is_empty() {
+                        actions.push(key.to_string());
+                    }
+                }
+            }
+        }
+
+        depth += brace_balance(line);
+    }
+
+    actions
+}
+
+/// Opened minus closed `{}` braces in a line, outside of string literals.
+fn brace_balance(line: &str) -> i32 {
+    let mut balance = 0;
+    let mut in_string = false;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if in_string => {
+                chars.next();
+            }
+            '"' => in_string = !in_string,
+            '{' if !in_string => balance += 1,
+            '}' if !in_string => balance -= 1,
+            _ => {}
+        }
+    }
+
+    balance
+}
+
+/// Converts an action name like `move_left` or `ui accept` to a variant name like `MoveLeft` or `UiAccept`.
+fn to_variant_name(action: &str) -> String {
+    let mut name: String = action
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            std::iter::once(first).chain(chars).collect::<String>()
+        })
+        .collect();
+
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name.insert_str(0, "Action");
+    }
+
+    name
+}
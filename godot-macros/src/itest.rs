@@ -29,6 +29,19 @@ pub fn attribute_itest(input_decl: Declaration) -> ParseResult<TokenStream> {
     let mut attr = KvParser::parse_required(&func.attributes, "itest", &func.name)?;
     let skipped = attr.handle_alone("skip")?;
     let focused = attr.handle_alone("focus")?;
+    let tags = if let Some(mut parser) = attr.handle_array("tags")? {
+        let mut tags = Vec::new();
+
+        while let Some(tag) = parser.next_ident()? {
+            tags.push(tag.to_string())
+        }
+
+        parser.finish()?;
+
+        tags
+    } else {
+        Vec::new()
+    };
     attr.finish()?;
 
     if skipped && focused {
@@ -68,6 +81,7 @@ pub fn attribute_itest(input_decl: Declaration) -> ParseResult<TokenStream> {
             name: #test_name_str,
             skipped: #skipped,
             focused: #focused,
+            tags: &[#(#tags),*],
             file: std::file!(),
             line: std::line!(),
             function: #test_name,
@@ -7,7 +7,9 @@
 mod bench;
 mod class;
 mod derive;
+mod docs;
 mod gdextension;
+mod input_actions;
 mod itest;
 mod util;
 
@@ -309,6 +311,93 @@ use crate::util::ident;
 /// #8](https://github.com/godot-rust/gdext/issues/8).
 ///
 ///
+/// # Documentation
+///
+/// `///` doc comments on the struct, on `#[var]`/`#[export]` fields, and on `#[func]`s and `#[signal]`s in the `#[godot_api]` impl
+/// are collected into Godot's XML class reference format. See [`godot::docs`](../docs/index.html).
+///
+///
+/// # Fallible functions
+///
+/// A `#[func]` may return `Result<T, E>`, where `E` implements `Display`. Godot sees the return type `T`. An `Ok` value is returned
+/// as-is; an `Err` prints its message as Godot error and fails the call, so that GDScript reports a script error at the calling line.
+/// Calls that cannot fail, e.g. ptrcalls from typed GDScript, instead receive the default value of `T`.
+///
+/// With `#[func(err_as_variant)]`, the function is instead exposed as returning `Variant`, which holds either the `Ok` or the `Err`
+/// value (`E` must then implement `ToVariant`). The caller can distinguish them by type, e.g. with an error code or a message.
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// # #[derive(GodotClass)]
+/// # #[class(init)]
+/// # struct Inventory {}
+/// #[godot_api]
+/// impl Inventory {
+///     #[func]
+///     fn load_items(&mut self, path: GodotString) -> Result<i64, String> {
+///         Err(format!("cannot open {path}"))
+///     }
+/// }
+/// ```
+///
+///
+/// # Async functions
+///
+/// A `#[func]` without `self` may be an `async fn`. Godot sees it as returning an object, whose `completed` signal carries the
+/// function's result once the future resolves. The future runs as a task of [`godot::task`](../task/index.html), which must be
+/// driven, e.g. with `task::drive_each_frame()`.
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// # #[derive(GodotClass)]
+/// # #[class(init)]
+/// # struct Pathfinder {}
+/// #[godot_api]
+/// impl Pathfinder {
+///     #[func]
+///     async fn find_path(from: Vector2, to: Vector2) -> PackedVector2Array {
+///         godot::task::next_frame().await;
+///         PackedVector2Array::from(&[from, to])
+///     }
+/// }
+/// ```
+///
+/// In GDScript: `var path = await Pathfinder.find_path(from, to).completed`.
+///
+///
+/// # Panics in exposed functions
+///
+/// A panic in a `#[func]` or virtual method override is caught before it reaches Godot. By default, it is printed as Godot
+/// error and the call fails. The key `on_panic` chooses another [`PanicPolicy`](../init/enum.PanicPolicy.html) for the class,
+/// e.g. to abort the process in release builds, instead of continuing in an inconsistent state:
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// #[derive(GodotClass)]
+/// #[class(init, base=Node, on_panic = Abort)]
+/// struct SaveGame {}
+/// ```
+///
+/// The policy for all classes of the extension is set by
+/// [`ExtensionLibrary::panic_policy()`](../init/trait.ExtensionLibrary.html#method.panic_policy).
+///
+///
+/// # Thread safety
+///
+/// Instances of Rust classes belong to the main thread by default. With the `threads` feature, `#[class(thread_safe)]` allows
+/// them to be accessed from other threads, through a [`ThreadHandle`](../obj/struct.ThreadHandle.html). All fields except
+/// `#[base]` must then be `Send` and `Sync`.
+///
+/// ```ignore
+/// # use godot::prelude::*;
+/// #[derive(GodotClass)]
+/// #[class(init, thread_safe)]
+/// struct WorldModel {
+///     heights: Vec<f32>,
+/// }
+/// ```
+///
+///
 /// # Running code in the editor
 ///
 /// If you annotate a class with `#[class(tool)]`, its lifecycle methods (`ready()`, `process()` etc.) will be invoked in the editor. This
@@ -318,6 +407,29 @@ use crate::util::ident;
 /// for more information and further customization.
 ///
 /// This is very similar to [GDScript's `@tool` feature](https://docs.godotengine.org/en/stable/tutorials/plugins/running_code_in_the_editor.html).
+///
+/// Individual functions can be restricted to one side with `#[func(tool_only)]` or `#[func(runtime_only)]`. Calling them in the
+/// other context prints an error and leaves the object untouched. Inside function bodies,
+/// [`godot::engine::is_editor_hint()`](../engine/fn.is_editor_hint.html) distinguishes the two.
+///
+/// ```no_run
+/// # use godot::prelude::*;
+/// #[derive(GodotClass)]
+/// #[class(init, tool, base=Node)]
+/// struct Level {
+///     #[base]
+///     base: Base<Node>,
+/// }
+///
+/// #[godot_api]
+/// impl Level {
+///     #[func(tool_only)]
+///     fn bake_navigation(&mut self) { /* ... */ }
+///
+///     #[func(runtime_only)]
+///     fn spawn_enemies(&mut self) { /* ... */ }
+/// }
+/// ```
 #[proc_macro_derive(GodotClass, attributes(class, base, var, export, init, signal))]
 pub fn derive_godot_class(input: TokenStream) -> TokenStream {
     translate(input, class::derive_godot_class)
@@ -522,9 +634,48 @@ pub fn derive_export(input: TokenStream) -> TokenStream {
     translate(input, derive::derive_export)
 }
 
+/// Attribute macro generating an enum of the input actions declared in a Godot project.
+///
+/// Applied to an empty enum, this reads the `[input]` section of the given `project.godot` file at compile time and adds one
+/// variant per action, named in PascalCase (`move_left` becomes `MoveLeft`). The path is relative to the crate's `Cargo.toml`.
+/// The crate is rebuilt whenever the project file changes, so renamed or removed actions turn into compile errors.
+///
+/// The enum derives `Copy`, `Clone`, `Debug`, `Eq`, `PartialEq` and `Hash`, and provides:
+/// * `ALL`, a slice of all actions
+/// * `name()`, the action's name in Godot
+/// * `From<Action> for StringName`, so that it can be passed to `Input` methods
+///
+/// ```ignore
+/// use godot::bind::input_actions;
+/// use godot::engine::Input;
+///
+/// #[input_actions(project = "../godot/project.godot")]
+/// pub enum Action {}
+///
+/// fn is_jumping() -> bool {
+///     Input::singleton().is_action_pressed(Action::Jump.into())
+/// }
+/// ```
+///
+/// Built-in actions like `ui_accept` are only included if the project overrides them.
+#[proc_macro_attribute]
+pub fn input_actions(meta: TokenStream, input: TokenStream) -> TokenStream {
+    translate_meta(
+        "input_actions",
+        meta,
+        input,
+        input_actions::attribute_input_actions,
+    )
+}
+
 /// Similar to `#[test]`, but runs an integration test with Godot.
 ///
 /// Transforms the `fn` into one returning `bool` (success of the test), which must be called explicitly.
+///
+/// Keys:
+/// * `skip`: the test is reported as skipped, without running it.
+/// * `focus`: if any test is focused, only focused tests are run.
+/// * `tags = [net, slow]`: labels that can be selected or excluded with the `--tags` and `--skip-tags` runner arguments.
 #[proc_macro_attribute]
 pub fn itest(meta: TokenStream, input: TokenStream) -> TokenStream {
     translate_meta("itest", meta, input, itest::attribute_itest)
@@ -101,6 +101,100 @@ pub fn make_signature_tuple_type(
     }
 }
 
+/// If `ty` is a `Result<T, E>` (optionally with a path such as `std::result::`), returns `T` and `E`.
+///
+/// Aliases with a single type argument, like `io::Result<T>`, are not recognized.
+pub fn extract_result_type(ty: &TokenStream) -> Option<(TokenStream, TokenStream)> {
+    let tokens: Vec<TokenTree> = ty.clone().into_iter().collect();
+
+    // Leading path: idents and `::`, ending in `Result`.
+    let open = tokens.iter().position(|tt| is_punct(tt, '<'))?;
+    let (path, rest) = tokens.split_at(open);
+    let path_ok = path
+        .iter()
+        .all(|tt| matches!(tt, TokenTree::Ident(_)) || is_punct(tt, ':'));
+    if !path_ok || path.last()?.to_string() != "Result" || !is_punct(rest.last()?, '>') {
+        return None;
+    }
+
+    // Split generic arguments at top-level commas.
+    let mut args = vec![TokenStream::new()];
+    let mut depth = 0;
+    let mut prev: Option<&TokenTree> = None;
+    for tt in &rest[1..rest.len() - 1] {
+        let is_arrow = prev.map_or(false, |p| is_punct(p, '-'));
+        if is_punct(tt, '<') {
+            depth += 1;
+        } else if is_punct(tt, '>') && !is_arrow {
+            depth -= 1;
+        } else if is_punct(tt, ',') && depth == 0 {
+            args.push(TokenStream::new());
+            prev = Some(tt);
+            continue;
+        }
+
+        args.last_mut().unwrap().append(tt.clone());
+        prev = Some(tt);
+    }
+
+    // Allow trailing comma.
+    if args.last().map_or(false, |arg| arg.is_empty()) {
+        args.pop();
+    }
+
+    match <[TokenStream; 2]>::try_from(args) {
+        Ok([ok, err]) if depth == 0 => Some((ok, err)),
+        _ => None,
+    }
+}
+
+/// Converts the source representation of a string literal back to its value.
+pub fn unquote(literal: &str) -> Option<String> {
+    // Raw strings: r"..." or r#"..."#
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let content = &raw[hashes..raw.len() - hashes];
+        return content
+            .strip_prefix('"')
+            .and_then(|c| c.strip_suffix('"'))
+            .map(str::to_string);
+    }
+
+    let content = literal.strip_prefix('"')?.strip_suffix('"')?;
+    let mut result = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next()? {
+            'n' => result.push('\n'),
+            'r' => result.push('\r'),
+            't' => result.push('\t'),
+            '0' => result.push('\0'),
+            '\\' => result.push('\\'),
+            '\'' => result.push('\''),
+            '"' => result.push('"'),
+            'u' => {
+                // \u{XXXX}
+                let code: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                let code = code.strip_prefix('{')?;
+                result.push(char::from_u32(u32::from_str_radix(code, 16).ok()?)?);
+            }
+            '\n' => {
+                // Line continuation: skip leading whitespace of the next line.
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            }
+            _ => return None,
+        }
+    }
+
+    Some(result)
+}
+
 fn is_punct(tt: &TokenTree, c: char) -> bool {
     match tt {
         TokenTree::Punct(punct) => punct.as_char() == c,
@@ -125,6 +125,11 @@
 //!   ```
 //!   <br>
 //!
+//! * **`rayon`**
+//!
+//!   Parallel processing of packed arrays on the [rayon](https://docs.rs/rayon) thread pool, through methods such as
+//!   `PackedFloat32Array::par_chunks_mut()`. Excludes `PackedStringArray`.<br><br>
+//!
 //! * **`serde`**
 //!
 //!   Implement the [serde](https://docs.rs/serde) traits `Serialize` and `Deserialize` traits for certain built-in types.
@@ -135,7 +140,36 @@
 //!
 //!   Experimental threading support. This enables `Send`/`Sync` traits for `Gd<T>` and makes the guard types `Gd`/`GdMut` aware of
 //!   multi-threaded references. The safety aspects of this are not ironed out yet; use at your own risk. The API may also change
-//!   at any time.
+//!   at any time.<br><br>
+//!
+//! * **`profiling`**
+//!
+//!   Times every call from Godot into `#[func]` and virtual methods. The collected timings are available in the
+//!   `profiling` module and can be sent each frame to Godot's debugger, for an `EditorDebuggerPlugin` to display; the
+//!   editor's built-in profiler does not show them. Adds a small overhead to each call.<br><br>
+//!
+//! * **`log`**
+//!
+//!   Provides `log::GodotLogger`, a backend for the [log](https://docs.rs/log) crate that forwards records to Godot's
+//!   output panel. Errors and warnings show up in the editor's debugger, like those of `godot_error!` and `godot_warn!`.<br><br>
+//!
+//! * **`debug-log`**
+//!
+//!   Reports internal diagnostics through the `log` crate, with target `godot::diagnostics`: class registration (including
+//!   why a class was skipped or rejected), failed argument conversions, panics caught at the FFI boundary, reference-count
+//!   anomalies and virtual-method dispatch. Implies `log`; install a logger such as `log::GodotLogger` to see the output.<br><br>
+//!
+//! * **`debug-liveness`**
+//!
+//!   Checks on every dereference of a manually-managed `Gd<T>` that the object is still alive. Using a freed object then
+//!   panics with the backtrace of the [`Gd::free()`][crate::obj::Gd::free] call that destroyed it, instead of crashing the
+//!   engine. Adds an engine call to each dereference and keeps one backtrace per freed object, so enable it for debugging only.<br><br>
+//!
+//! * **`debug-threads`**
+//!
+//!   Audits thread access, panicking with the offending thread instead of silently racing. Nodes inside the scene tree may then
+//!   only be used from the main thread. Unless `threads` is enabled, instances of Rust classes may additionally only be bound
+//!   (`bind()`, `bind_mut()`, `#[func]` calls) and reference-counted on the thread that created them.
 //!
 //! # Public API
 //!
@@ -154,7 +188,11 @@
 //! This allows us to decide whether it fits the scope of the library and to design proper APIs for it.
 
 #[doc(inline)]
-pub use godot_core::{builtin, engine, log, obj};
+pub use godot_core::{allocator, audio, batch, builtin, docs, engine, error, http, log, obj, task};
+
+#[cfg(feature = "profiling")]
+#[doc(inline)]
+pub use godot_core::profiling;
 
 #[doc(hidden)]
 pub use godot_core::sys;
@@ -169,7 +207,9 @@ pub mod init {
 /// Export user-defined classes and methods to be called by the engine.
 pub mod bind {
     pub use godot_core::property;
-    pub use godot_macros::{godot_api, Export, FromVariant, GodotClass, Property, ToVariant};
+    pub use godot_macros::{
+        godot_api, input_actions, Export, FromVariant, GodotClass, Property, ToVariant,
+    };
 }
 
 /// Testing facilities (unstable).
@@ -202,5 +242,7 @@ pub mod prelude {
 
     // Make trait methods available
     pub use super::engine::NodeExt as _;
+    pub use super::engine::SceneTreeExt as _;
+    pub use super::obj::cap::WithBaseField as _;
     pub use super::obj::EngineEnum as _;
 }
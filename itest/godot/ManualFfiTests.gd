@@ -304,3 +304,11 @@ func test_func_rename():
 	assert_eq(func_rename.has_method("renamed_static"), false)
 	assert_eq(func_rename.has_method("spell_static"), true)
 	assert_eq(func_rename.spell_static(), "static")
+
+func test_func_context_gated():
+	var gated := FuncContextGated.new()
+
+	# Both functions are registered; only the runtime one can be called outside the editor.
+	assert_eq(gated.has_method("editor_value"), true)
+	assert_eq(gated.has_method("runtime_value"), true)
+	assert_eq(gated.runtime_value(), 2)
@@ -47,3 +47,16 @@ func test_collision_object_2d_input_event():
 
 	window.queue_free()
 
+
+# Test that closures passed to `Gd::run_deferred()` and `task::defer()` run at idle time in order, not immediately.
+func test_run_deferred():
+	var obj := DeferredTest.new()
+
+	obj.schedule_increment()
+	obj.schedule_multiply()
+	obj.schedule_increment()
+	assert_eq(obj.get_counter(), 0)
+
+	await Engine.get_main_loop().process_frame
+
+	assert_eq(obj.get_counter(), 11)
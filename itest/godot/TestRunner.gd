@@ -9,19 +9,51 @@ func _ready():
 	# Ensure physics is initialized, for tests that require it.
 	await get_tree().physics_frame
 
-	var allow_focus := true
-	var filters: Array = []
+	# Options passed after `--`, see RunConfig in the Rust framework:
+	#   [name1,name2]         only run tests whose names contain any of the given strings
+	#   --tags=net,slow       only run tests with any of the given #[itest(tags)]; excludes GDScript tests
+	#   --skip-tags=net,slow  do not run tests with any of the given tags
+	#   --fail-fast           stop after the first failed test
+	#   --repeat=N            run each test N times; a test fails if any repetition fails
+	#   --junit=path          write a JUnit XML report (paths may use res:// or user://)
+	#   --json=path           write a JSON report
+	#   --disallow-focus      fail if #[itest(focus)] is encountered
+	var config := {
+		"allow_focus": true,
+		"filters": [],
+		"tags": [],
+		"skip_tags": [],
+		"fail_fast": false,
+		"repeat": 1,
+		"junit": "",
+		"json": "",
+	}
 	var unrecognized_args: Array = []
 	for arg in OS.get_cmdline_user_args():
-		match arg:
-			"--disallow-focus":
-				allow_focus = false
-			_:
-				if not arg.begins_with("[") or not arg.ends_with("]"):
-					unrecognized_args.push_back(arg)
-
-				var args = arg.lstrip("[").rstrip("]").split(",")
-				filters.append_array(args)
+		if arg == "--disallow-focus":
+			config.allow_focus = false
+		elif arg == "--fail-fast":
+			config.fail_fast = true
+		elif arg.begins_with("--tags="):
+			config.tags.append_array(arg.trim_prefix("--tags=").split(",", false))
+		elif arg.begins_with("--skip-tags="):
+			config.skip_tags.append_array(arg.trim_prefix("--skip-tags=").split(",", false))
+		elif arg.begins_with("--repeat="):
+			var count: String = arg.trim_prefix("--repeat=")
+			if count.is_valid_int() and count.to_int() > 0:
+				config.repeat = count.to_int()
+			else:
+				unrecognized_args.push_back(arg)
+		elif arg.begins_with("--junit="):
+			config.junit = arg.trim_prefix("--junit=")
+		elif arg.begins_with("--json="):
+			config.json = arg.trim_prefix("--json=")
+		else:
+			if not arg.begins_with("[") or not arg.ends_with("]"):
+				unrecognized_args.push_back(arg)
+
+			var args = arg.lstrip("[").rstrip("]").split(",")
+			config.filters.append_array(args)
 
 	if unrecognized_args:
 		push_error("Unrecognized arguments: ", unrecognized_args)
@@ -56,9 +88,8 @@ func _ready():
 	var success: bool = rust_runner.run_all_tests(
 		gdscript_tests,
 		gdscript_suites.size(),
-		allow_focus,
 		self,
-		filters
+		config
 	)
 
 	if success:
@@ -0,0 +1,115 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use godot::builtin::{PackedFloat32Array, PackedInt32Array, PackedVector2Array, Vector2};
+use rayon::prelude::*;
+
+use crate::framework::{expect_panic, itest};
+
+#[itest]
+fn packed_array_par_chunks() {
+    let array = PackedInt32Array::from_iter(0..10);
+
+    let mut chunks: Vec<(usize, Vec<i32>)> = array
+        .par_chunks(4)
+        .map(|(offset, chunk)| (offset, chunk.to_vec()))
+        .collect();
+    chunks.sort_by_key(|(offset, _)| *offset);
+
+    assert_eq!(
+        chunks,
+        vec![
+            (0, vec![0, 1, 2, 3]),
+            (4, vec![4, 5, 6, 7]),
+            (8, vec![8, 9]),
+        ]
+    );
+}
+
+#[itest]
+fn packed_array_par_chunks_mut() {
+    let mut array = PackedInt32Array::new();
+    array.resize(10);
+
+    array.par_chunks_mut(3).for_each(|(offset, chunk)| {
+        for (i, element) in chunk.iter_mut().enumerate() {
+            *element = (offset + i) as i32 * 2;
+        }
+    });
+
+    let expected: Vec<i32> = (0..10).map(|i| i * 2).collect();
+    assert_eq!(array.to_vec(), expected);
+}
+
+#[itest]
+fn packed_array_par_chunks_mut_copies_shared_buffer() {
+    let original = PackedFloat32Array::from(&[1.0, 2.0, 3.0]);
+    let mut copy = original.clone();
+
+    copy.par_chunks_mut(2).for_each(|(_, chunk)| {
+        for element in chunk {
+            *element *= 10.0;
+        }
+    });
+
+    // Copy-on-write happens before the chunks are handed out, so the original is unaffected.
+    assert_eq!(copy.to_vec(), vec![10.0, 20.0, 30.0]);
+    assert_eq!(original.to_vec(), vec![1.0, 2.0, 3.0]);
+}
+
+#[itest]
+fn packed_array_par_chunks_zero_size() {
+    let mut array = PackedInt32Array::from(&[1, 2, 3]);
+
+    expect_panic("chunk size 0", move || {
+        array.par_chunks_mut(0).for_each(|_| {});
+    });
+}
+
+#[itest]
+fn packed_array_par_update() {
+    let mut array = PackedVector2Array::new();
+    array.resize(100);
+
+    array.par_update(|i, element| *element = Vector2::new(i as f32, -(i as f32)));
+
+    assert_eq!(array.len(), 100);
+    for i in 0..100 {
+        assert_eq!(array.get(i), Vector2::new(i as f32, -(i as f32)));
+    }
+}
+
+#[itest]
+fn packed_array_par_map_chunks() {
+    let array = PackedInt32Array::from_iter(1..=10);
+
+    // Results are in chunk order, regardless of which thread finished first.
+    let sums = array.par_map_chunks(3, |offset, chunk| (offset, chunk.iter().sum::<i32>()));
+    assert_eq!(sums, vec![(0, 6), (3, 15), (6, 24), (9, 10)]);
+
+    let total: i32 = sums.iter().map(|(_, sum)| sum).sum();
+    assert_eq!(total, 55);
+}
+
+#[itest]
+fn packed_array_par_map_chunks_empty() {
+    let array = PackedInt32Array::new();
+
+    let results = array.par_map_chunks(4, |_, chunk| chunk.len());
+    assert!(results.is_empty());
+}
+
+#[itest]
+fn packed_array_par_from_fn() {
+    let array = PackedFloat32Array::par_from_fn(1000, |i| i as f32 * 0.5);
+
+    assert_eq!(array.len(), 1000);
+    let expected: Vec<f32> = (0..1000).map(|i| i as f32 * 0.5).collect();
+    assert_eq!(array.to_vec(), expected);
+
+    let empty = PackedFloat32Array::par_from_fn(0, |_| unreachable!());
+    assert!(empty.is_empty());
+}
@@ -5,7 +5,9 @@
  */
 
 use crate::framework::{expect_panic, itest};
-use godot::builtin::{PackedByteArray, PackedFloat32Array, PackedStringArray};
+use godot::builtin::{
+    FromVariant, PackedByteArray, PackedFloat32Array, PackedStringArray, ToVariant,
+};
 
 #[itest]
 fn packed_array_default() {
@@ -54,6 +56,40 @@ fn packed_array_into_iterator() {
 }
 */
 
+#[itest]
+fn packed_array_shares_buffer() {
+    let array = PackedByteArray::from(&[1, 2, 3]);
+    let buffer = array.as_slice().as_ptr();
+
+    // Copy-on-write: neither cloning nor passing through Variant (as done for engine calls) copies the buffer.
+    let cloned = array.clone();
+    assert_eq!(cloned.as_slice().as_ptr(), buffer);
+
+    let variant = array.to_variant();
+    let from_variant = PackedByteArray::from_variant(&variant);
+    assert_eq!(from_variant.as_slice().as_ptr(), buffer);
+
+    // Writing detaches the copy.
+    let mut modified = cloned;
+    modified.set(0, 42);
+    assert_ne!(modified.as_slice().as_ptr(), buffer);
+    assert_eq!(array.to_vec(), vec![1, 2, 3]);
+}
+
+#[itest]
+fn packed_array_make_unique() {
+    let array = PackedByteArray::from(&[1, 2, 3]);
+    let mut copy = array.clone();
+    assert!(copy.shares_buffer_with(&array));
+
+    copy.make_unique();
+    assert!(!copy.shares_buffer_with(&array));
+    assert_eq!(copy, array);
+
+    let empty = PackedByteArray::new();
+    assert!(!empty.shares_buffer_with(&empty.clone()));
+}
+
 #[itest]
 fn packed_array_eq() {
     assert_eq!(
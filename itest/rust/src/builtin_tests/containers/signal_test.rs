@@ -4,14 +4,17 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 
 use godot::bind::{godot_api, GodotClass};
-use godot::builtin::{GodotString, Variant};
+use godot::builtin::{Callable, GodotString, ToVariant, Variant};
 
-use godot::engine::Object;
+use godot::engine::{Object, ObjectExt};
+use godot::error::{ConnectErrorKind, SignalError};
 use godot::obj::{Base, Gd, Share};
 use godot::sys;
+use godot::task;
 
 use crate::framework::itest;
 
@@ -87,3 +90,90 @@ fn signals() {
     receiver.free();
     emitter.free();
 }
+
+#[itest]
+fn signal_try_connect() {
+    let mut emitter = Gd::<Emitter>::new_default();
+    let receiver = Gd::<Receiver>::new_default();
+    let callable = receiver.callable("receive_0_arg");
+
+    assert!(!emitter.is_signal_connected("signal_0_arg", &callable));
+    emitter.try_connect("signal_0_arg", &callable).unwrap();
+    assert!(emitter.is_signal_connected("signal_0_arg", &callable));
+
+    let err = emitter.try_connect("signal_0_arg", &callable).unwrap_err();
+    assert_eq!(err.kind(), ConnectErrorKind::AlreadyConnected);
+    assert_eq!(err.signal(), "signal_0_arg");
+
+    emitter.emit_signal("signal_0_arg".into(), &[]);
+    assert!(receiver.bind().used[0].get());
+
+    emitter.try_disconnect("signal_0_arg", &callable).unwrap();
+    assert!(!emitter.is_signal_connected("signal_0_arg", &callable));
+
+    let err = emitter
+        .try_disconnect("signal_0_arg", &callable)
+        .unwrap_err();
+    assert_eq!(err.kind(), ConnectErrorKind::NotConnected);
+
+    receiver.free();
+    emitter.free();
+}
+
+#[itest]
+fn signal_try_connect_invalid() {
+    let mut emitter = Gd::<Emitter>::new_default();
+    let receiver = Gd::<Receiver>::new_default();
+
+    let err = emitter
+        .try_connect("no_such_signal", &receiver.callable("receive_0_arg"))
+        .unwrap_err();
+    assert_eq!(err.kind(), ConnectErrorKind::UnknownSignal);
+
+    let err = emitter
+        .try_connect("signal_0_arg", &Callable::invalid())
+        .unwrap_err();
+    assert_eq!(err.kind(), ConnectErrorKind::InvalidCallable);
+
+    receiver.free();
+    emitter.free();
+}
+
+#[itest]
+fn signal_future() {
+    task::drive_each_frame();
+    let mut emitter = Gd::<Emitter>::new_default();
+
+    let result = Rc::new(RefCell::new(None));
+    let await_signal = |emitter: &Gd<Emitter>, signal: &str| {
+        let result = result.clone();
+        let future = task::signal_future(emitter, signal);
+        task::spawn(async move {
+            *result.borrow_mut() = Some(future.await);
+        })
+    };
+
+    let handle = await_signal(&emitter, "signal_1_arg");
+    assert!(!handle.is_finished());
+
+    emitter.emit_signal("signal_1_arg".into(), &[987.to_variant()]);
+    task::poll();
+    assert!(handle.is_finished());
+    assert_eq!(result.borrow_mut().take(), Some(Ok(())));
+
+    // Fails immediately.
+    await_signal(&emitter, "no_such_signal");
+    let Some(Err(SignalError::Connect(err))) = result.borrow_mut().take() else {
+        panic!("unknown signal must fail to connect");
+    };
+    assert_eq!(err.kind(), ConnectErrorKind::UnknownSignal);
+
+    // Cancelling the task disconnects the signal.
+    let handle = await_signal(&emitter, "signal_0_arg");
+    handle.cancel();
+    assert!(emitter
+        .get_signal_connection_list("signal_0_arg".into())
+        .is_empty());
+
+    emitter.free();
+}
@@ -19,6 +19,8 @@ mod containers {
     mod array_test;
     mod callable_test;
     mod dictionary_test;
+    #[cfg(feature = "rayon")]
+    mod packed_array_par_test;
     mod rid_test;
     mod signal_test;
     mod variant_test;
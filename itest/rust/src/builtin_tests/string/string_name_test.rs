@@ -5,9 +5,12 @@
  */
 
 use std::collections::HashSet;
+use std::thread;
 
 use crate::framework::itest;
 use godot::builtin::{GodotString, NodePath, StringName};
+use godot::engine::{Node, Node3D, Object, RefCounted};
+use godot::obj::GodotClass;
 
 #[itest]
 fn string_name_default() {
@@ -109,3 +112,31 @@ fn string_name_is_empty() {
     let empty = StringName::default();
     assert!(empty.is_empty());
 }
+
+#[itest]
+fn class_name_to_string_name_multi_thread() {
+    let threads = (0..4)
+        .map(|_| {
+            thread::spawn(|| {
+                for _ in 0..100 {
+                    let _ = Node::class_name().to_string_name();
+                }
+
+                [
+                    Object::class_name().to_string_name(),
+                    RefCounted::class_name().to_string_name(),
+                    Node::class_name().to_string_name(),
+                    Node3D::class_name().to_string_name(),
+                ]
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for thread in threads.into_iter() {
+        let names = thread.join().unwrap();
+        assert_eq!(names[0], StringName::from("Object"));
+        assert_eq!(names[1], StringName::from("RefCounted"));
+        assert_eq!(names[2], StringName::from("Node"));
+        assert_eq!(names[3], StringName::from("Node3D"));
+    }
+}
@@ -0,0 +1,87 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use godot::batch::{CommandBuffer, MultimeshBuffer};
+use godot::builtin::{Transform3D, Vector3};
+use godot::engine::rendering_server::MultimeshTransformFormat;
+use godot::engine::{PhysicsServer3D, RenderingServer};
+
+use crate::framework::itest;
+
+#[itest]
+fn command_buffer_submit() {
+    let mut physics = PhysicsServer3D::singleton();
+    let body = physics.body_create();
+    physics.body_set_collision_layer(body, 1);
+
+    let mut commands = CommandBuffer::<PhysicsServer3D>::new();
+    commands.push(move |server| server.body_set_collision_layer(body, 3));
+    commands.push(move |server| server.body_set_collision_layer(body, 5));
+    assert_eq!(commands.len(), 2);
+
+    // Nothing runs before submitting.
+    assert_eq!(physics.body_get_collision_layer(body), 1);
+
+    // Commands run in recording order.
+    commands.submit();
+    assert_eq!(physics.body_get_collision_layer(body), 5);
+
+    physics.free_rid(body);
+}
+
+#[itest]
+fn command_buffer_append() {
+    let mut physics = PhysicsServer3D::singleton();
+    let body = physics.body_create();
+
+    let mut first = CommandBuffer::<PhysicsServer3D>::new();
+    first.push(move |server| server.body_set_collision_mask(body, 2));
+
+    let mut second = CommandBuffer::<PhysicsServer3D>::new();
+    second.push(move |server| server.body_set_collision_mask(body, 4));
+
+    first.append(&mut second);
+    assert!(second.is_empty());
+    assert_eq!(first.len(), 2);
+
+    first.submit();
+    assert_eq!(physics.body_get_collision_mask(body), 4);
+
+    physics.free_rid(body);
+}
+
+#[itest]
+fn command_buffer_multimesh_set_buffer() {
+    let mut rendering = RenderingServer::singleton();
+    let multimesh = rendering.multimesh_create();
+    rendering.multimesh_allocate_data(
+        multimesh,
+        2,
+        MultimeshTransformFormat::MULTIMESH_TRANSFORM_3D,
+    );
+
+    let transform = Transform3D::IDENTITY.translated(Vector3::new(1.0, 2.0, 3.0));
+    let mut buffer = MultimeshBuffer::new(2, false, false);
+    buffer.set_transform(1, transform);
+    let expected = buffer.as_slice().to_vec();
+
+    let mut commands = CommandBuffer::<RenderingServer>::new();
+    commands.multimesh_set_buffer(multimesh, buffer);
+    assert_eq!(commands.len(), 1);
+    commands.submit();
+
+    // The headless dummy renderer discards multimesh data; only check the contents where a renderer keeps them.
+    let stored = rendering.multimesh_get_buffer(multimesh).to_vec();
+    if !stored.is_empty() {
+        assert_eq!(stored, expected);
+        assert_eq!(
+            rendering.multimesh_instance_get_transform(multimesh, 1),
+            transform
+        );
+    }
+
+    rendering.free_rid(multimesh);
+}
@@ -61,6 +61,7 @@ fn cfg_test() {
     // Makes sure that since_api and before_api are mutually exclusive
     assert_ne!(cfg!(since_api = "4.1"), cfg!(before_api = "4.1"));
     assert_ne!(cfg!(since_api = "4.2"), cfg!(before_api = "4.2"));
+    assert_ne!(cfg!(since_api = "4.3"), cfg!(before_api = "4.3"));
 }
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
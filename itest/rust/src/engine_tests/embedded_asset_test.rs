@@ -0,0 +1,102 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use godot::builtin::{Dictionary, FromVariant, ToVariant};
+use godot::engine::image::Format;
+use godot::engine::{
+    audio_stream_from_wav_bytes, audio_stream_wav, font_from_bytes, image_from_bytes,
+    json_from_str, Image,
+};
+
+use crate::framework::itest;
+
+#[itest]
+fn embedded_image_png() {
+    let original = Image::create(4, 2, false, Format::FORMAT_RGBA8).unwrap();
+    let png = original.save_png_to_buffer();
+
+    let image = image_from_bytes(png.as_slice()).expect("PNG decoded");
+    assert_eq!(image.get_width(), 4);
+    assert_eq!(image.get_height(), 2);
+}
+
+#[itest]
+fn embedded_image_invalid() {
+    assert!(image_from_bytes(&[1, 2, 3]).is_none());
+}
+
+#[itest]
+fn embedded_audio_wav() {
+    let samples = [0u8, 0, 0xFF, 0x7F, 0, 0x80, 0, 0];
+    let wav = make_wav(2, 22050, 16, &samples);
+
+    let stream = audio_stream_from_wav_bytes(&wav).expect("WAV parsed");
+    assert_eq!(
+        stream.get_format(),
+        audio_stream_wav::Format::FORMAT_16_BITS
+    );
+    assert_eq!(stream.get_mix_rate(), 22050);
+    assert!(stream.is_stereo());
+    assert_eq!(stream.get_data().to_vec(), samples);
+}
+
+#[itest]
+fn embedded_audio_wav_8bit() {
+    let wav = make_wav(1, 8000, 8, &[0x80, 0xFF, 0x00]);
+
+    let stream = audio_stream_from_wav_bytes(&wav).expect("WAV parsed");
+    assert_eq!(stream.get_format(), audio_stream_wav::Format::FORMAT_8_BITS);
+    assert!(!stream.is_stereo());
+    assert_eq!(stream.get_data().to_vec(), [0x00, 0x7F, 0x80]);
+}
+
+#[itest]
+fn embedded_audio_invalid() {
+    assert!(audio_stream_from_wav_bytes(b"RIFF").is_none());
+    assert!(audio_stream_from_wav_bytes(&make_wav(1, 44100, 24, &[0; 6])).is_none());
+}
+
+#[itest]
+fn embedded_json() {
+    let json = json_from_str(r#"{"level": 3}"#).expect("valid JSON");
+    let data = Dictionary::from_variant(&json.get_data());
+    assert_eq!(data.get("level"), Some(3.0.to_variant()));
+
+    assert!(json_from_str("{ nope").is_none());
+}
+
+#[itest]
+fn embedded_font() {
+    let bytes = [0u8, 1, 0, 0];
+    let font = font_from_bytes(&bytes);
+    assert_eq!(font.get_data().to_vec(), bytes);
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+fn make_wav(channels: u16, sample_rate: u32, bits_per_sample: u16, samples: &[u8]) -> Vec<u8> {
+    let block_align = channels * bits_per_sample / 8;
+    let byte_rate = sample_rate * block_align as u32;
+
+    let mut wav = Vec::new();
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + samples.len() as u32).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&channels.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&byte_rate.to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&bits_per_sample.to_le_bytes());
+
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&(samples.len() as u32).to_le_bytes());
+    wav.extend_from_slice(samples);
+    wav
+}
@@ -0,0 +1,129 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::error::Error as _;
+
+use godot::bind::{godot_api, GodotClass};
+use godot::builtin::{NodePath, ToVariant, Variant};
+use godot::engine::{global, Node, NodeError, NodeExt, ObjectExt, RefCounted};
+use godot::error::{capture, CapturedMessage, ConvertError, Error, IoError, Severity};
+use godot::log::{godot_error, godot_warn};
+use godot::obj::Gd;
+
+use crate::framework::itest;
+
+fn convert(variant: Variant) -> Result<i64, Error> {
+    let value = variant.try_to::<i64>()?;
+    Ok(value)
+}
+
+fn find_child(node: &Gd<Node>) -> Result<Gd<Node>, Error> {
+    let child = node.try_get_node_as::<Node>("absent")?;
+    Ok(child)
+}
+
+#[itest]
+fn error_from_convert() {
+    assert_eq!(convert(42.to_variant()), Ok(42));
+
+    let err = convert("string".to_variant()).unwrap_err();
+    assert!(matches!(err, Error::Convert(_)));
+    assert!(err.source().is_some());
+}
+
+#[itest]
+fn error_from_node() {
+    let node = Node::new_alloc();
+
+    let err = find_child(&node).unwrap_err();
+    assert_eq!(
+        err,
+        Error::Node(NodeError::NotFound {
+            path: NodePath::from("absent")
+        })
+    );
+    assert_eq!(err.to_string(), "no node found at path `absent`");
+
+    node.free();
+}
+
+#[itest]
+fn error_io_check() {
+    assert_eq!(IoError::check(global::Error::OK), Ok(()));
+
+    let err = IoError::check(global::Error::ERR_FILE_NOT_FOUND).unwrap_err();
+    assert_eq!(err.code(), global::Error::ERR_FILE_NOT_FOUND);
+
+    let err = Error::from(err);
+    assert!(matches!(err, Error::Io(_)));
+}
+
+#[itest]
+fn error_convert_alias() {
+    let err: ConvertError = Variant::nil().try_to::<i64>().unwrap_err();
+    assert_eq!(Error::from(err), Error::Convert(err));
+}
+
+#[itest]
+fn error_capture_messages() {
+    let (value, messages) = capture(|| {
+        godot_warn!("first {}", 1);
+        godot_error!("second");
+        42
+    });
+
+    assert_eq!(value, 42);
+    assert_eq!(
+        messages,
+        vec![
+            CapturedMessage {
+                severity: Severity::Warning,
+                message: "first 1".to_string(),
+            },
+            CapturedMessage {
+                severity: Severity::Error,
+                message: "second".to_string(),
+            },
+        ]
+    );
+}
+
+#[itest]
+fn error_capture_nested() {
+    let ((), outer) = capture(|| {
+        let ((), inner) = capture(|| godot_warn!("inner"));
+        assert_eq!(inner.len(), 1);
+
+        godot_warn!("outer");
+    });
+
+    assert_eq!(outer.len(), 1);
+    assert_eq!(outer[0].message, "outer");
+}
+
+#[itest]
+fn error_capture_engine_call() {
+    let mut obj = Gd::<ErrorReporter>::new_default();
+
+    let (result, messages) = capture(|| obj.try_call("fail", &[]));
+
+    assert!(result.is_err());
+    assert!(messages
+        .iter()
+        .any(|msg| msg.severity == Severity::Error && msg.message.contains("reporter failed")));
+}
+
+#[derive(GodotClass)]
+#[class(init, base=RefCounted)]
+struct ErrorReporter;
+
+#[godot_api]
+impl ErrorReporter {
+    #[func]
+    fn fail(&self) -> Result<(), String> {
+        Err("reporter failed".to_string())
+    }
+}
@@ -0,0 +1,97 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use godot::builtin::GodotString;
+use godot::engine::resource_loader::{CacheMode, ThreadLoadStatus};
+use godot::engine::{
+    load, load_threaded, load_threaded_ex, try_load, PackedScene, Resource, ResourceLoader, Script,
+};
+use godot::error::LoadErrorKind;
+
+use crate::framework::{expect_panic, itest};
+
+#[itest]
+fn load_typed_resource() {
+    let scene = load::<PackedScene>("res://TestRunner.tscn");
+    assert!(scene.can_instantiate());
+
+    let resource = try_load::<Resource>("res://TestRunner.tscn");
+    assert!(resource.is_ok());
+}
+
+#[itest]
+fn load_wrong_type() {
+    let err = try_load::<Script>("res://TestRunner.tscn").expect_err("wrong class");
+    assert_eq!(err.path(), "res://TestRunner.tscn");
+    assert_eq!(
+        err.kind(),
+        &LoadErrorKind::WrongClass {
+            actual: "PackedScene".to_string()
+        }
+    );
+
+    expect_panic("load() with wrong type", || {
+        load::<Script>("res://TestRunner.tscn");
+    });
+}
+
+#[itest]
+fn load_nonexistent() {
+    let err = try_load::<PackedScene>("res://ThisFileDoesNotExist.tscn").expect_err("missing file");
+    assert_eq!(err.kind(), &LoadErrorKind::NotFound);
+
+    expect_panic("load() of missing file", || {
+        load::<PackedScene>("res://ThisFileDoesNotExist.tscn");
+    });
+}
+
+#[itest]
+fn load_threaded_typed_resource() {
+    let load = load_threaded::<PackedScene>("res://TestRunner.tscn").expect("request failed");
+    assert_eq!(load.path(), &GodotString::from("res://TestRunner.tscn"));
+
+    // Blocks until loaded.
+    let scene = load.get().expect("loading failed");
+    assert!(scene.can_instantiate());
+}
+
+#[itest]
+fn load_threaded_nonexistent() {
+    // Depending on the Godot version, the request itself or the load fails.
+    let result =
+        load_threaded::<PackedScene>("res://ThisFileDoesNotExist.tscn").and_then(|load| load.get());
+
+    assert!(result.is_err());
+}
+
+#[itest]
+fn load_threaded_ex_options() {
+    let load = load_threaded_ex::<PackedScene>(
+        "res://TestRunner.tscn",
+        true,
+        CacheMode::CACHE_MODE_IGNORE,
+    )
+    .expect("request failed");
+
+    let scene = load.get().expect("loading failed");
+    assert!(scene.can_instantiate());
+}
+
+#[itest]
+fn load_threaded_engine_methods() {
+    // The wrapped engine methods remain available.
+    let path = GodotString::from("res://TestRunner.tscn");
+    let mut loader = ResourceLoader::singleton();
+
+    let code = loader.load_threaded_request(path.clone());
+    assert_eq!(code, godot::engine::global::Error::OK);
+
+    let status = loader.load_threaded_get_status(path.clone());
+    assert_ne!(status, ThreadLoadStatus::THREAD_LOAD_INVALID_RESOURCE);
+
+    let resource = loader.load_threaded_get(path).expect("loading failed");
+    assert!(resource.try_cast::<PackedScene>().is_some());
+}
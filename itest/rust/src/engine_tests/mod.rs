@@ -4,6 +4,21 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+mod batch_test;
+mod embedded_asset_test;
+mod error_test;
+mod load_test;
 mod native_structures_test;
+#[cfg(since_api = "4.1")]
+mod navigation_test;
 mod node_test;
+mod os_test;
+mod packed_scene_test;
+mod performance_test;
+mod project_settings_test;
+mod task_test;
+mod time_test;
+mod undo_redo_test;
 mod utilities_test;
+#[cfg(since_api = "4.1")]
+mod worker_pool_test;
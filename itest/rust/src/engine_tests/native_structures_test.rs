@@ -5,10 +5,16 @@
  */
 
 use crate::framework::itest;
-use godot::engine::native::{AudioFrame, CaretInfo, Glyph};
+use godot::engine::native::{
+    AudioFrame, CaretInfo, Glyph, ObjectId, PhysicsServer2DExtensionRayResult,
+    PhysicsServer3DExtensionMotionCollision, PhysicsServer3DExtensionMotionResult,
+};
 use godot::engine::text_server::Direction;
-use godot::engine::{TextServer, TextServerExtension, TextServerExtensionVirtual};
-use godot::prelude::{godot_api, Base, Gd, GodotClass, Rect2, Rid, Share, Variant};
+use godot::engine::{Node, TextServer, TextServerExtension, TextServerExtensionVirtual};
+use godot::obj::EngineEnum;
+use godot::prelude::{
+    godot_api, Base, Gd, GodotClass, Rect2, Rid, Share, Variant, Vector2, Vector3,
+};
 
 use std::cell::Cell;
 
@@ -52,9 +58,10 @@ impl TextServerExtensionVirtual for TestTextServer {
         *caret = CaretInfo {
             leading_caret: Rect2::from_components(0.0, 0.0, 0.0, 0.0),
             trailing_caret: Rect2::from_components(1.0, 1.0, 1.0, 1.0),
-            leading_direction: Direction::DIRECTION_AUTO,
-            trailing_direction: Direction::DIRECTION_LTR,
+            leading_direction: Direction::DIRECTION_AUTO.ord(),
+            trailing_direction: 0,
         };
+        (*caret).set_trailing_direction_as_enum(Direction::DIRECTION_LTR);
     }
 
     fn shaped_text_get_glyph_count(&self, _shaped: Rid) -> i64 {
@@ -136,3 +143,75 @@ fn test_native_structure_pointer_to_array_parameter() {
     assert_eq!(result.get(0).get("start"), Some(Variant::from(99)));
     assert_eq!(result.get(1).get("start"), Some(Variant::from(700)));
 }
+
+#[itest]
+fn test_native_structure_object_accessors() {
+    let node = Node::new_alloc();
+    let mut result = PhysicsServer2DExtensionRayResult {
+        position: Vector2::ZERO,
+        normal: Vector2::ZERO,
+        rid: Rid::new(0),
+        collider_id: ObjectId { id: 0 },
+        collider: std::ptr::null_mut(),
+        shape: 0,
+    };
+
+    assert_eq!(result.collider_id_as_instance_id(), None);
+    assert!(unsafe { result.collider_as_object() }.is_none());
+
+    result.set_collider_id_as_instance_id(Some(node.instance_id()));
+    result.set_collider_as_object(&node);
+
+    assert_eq!(
+        result.collider_id_as_instance_id(),
+        Some(node.instance_id())
+    );
+    let collider = unsafe { result.collider_as_object() }.expect("collider set");
+    assert_eq!(collider.instance_id(), node.instance_id());
+
+    node.free();
+}
+
+#[itest]
+fn test_native_structure_enum_accessors() {
+    let mut caret = CaretInfo {
+        leading_caret: Rect2::from_components(0.0, 0.0, 0.0, 0.0),
+        trailing_caret: Rect2::from_components(0.0, 0.0, 0.0, 0.0),
+        leading_direction: 0,
+        trailing_direction: 0,
+    };
+
+    caret.set_leading_direction_as_enum(Direction::DIRECTION_RTL);
+    assert_eq!(caret.leading_direction, Direction::DIRECTION_RTL.ord());
+    assert_eq!(caret.leading_direction_as_enum(), Direction::DIRECTION_RTL);
+
+    // Fields may hold ordinals that the bindings do not know, e.g. when written by a newer engine.
+    caret.trailing_direction = 1234;
+    assert_eq!(caret.trailing_direction_as_enum(), Direction::Unknown(1234));
+}
+
+#[itest]
+fn test_native_structure_array_field() {
+    let result = PhysicsServer3DExtensionMotionResult {
+        travel: Vector3::ZERO,
+        remainder: Vector3::ZERO,
+        collision_depth: 0.0,
+        collision_safe_fraction: 0.0,
+        collision_unsafe_fraction: 0.0,
+        collisions: std::array::from_fn(|i| PhysicsServer3DExtensionMotionCollision {
+            position: Vector3::ZERO,
+            normal: Vector3::UP,
+            collider_velocity: Vector3::ZERO,
+            collider_angular_velocity: Vector3::ZERO,
+            depth: 0.0,
+            local_shape: i as i32,
+            collider_id: ObjectId { id: 0 },
+            collider: Rid::new(0),
+            collider_shape: 0,
+        }),
+        collision_count: 0,
+    };
+
+    assert_eq!(result.collisions.len(), 32);
+    assert_eq!(result.collisions[31].local_shape, 31);
+}
@@ -0,0 +1,158 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use godot::engine::{
+    NavigationMesh, NavigationServer2D, NavigationServer2DExt, NavigationServer3D,
+    NavigationServer3DExt,
+};
+use godot::prelude::*;
+
+use crate::framework::itest;
+
+#[derive(GodotClass)]
+#[class(init, base=RefCounted)]
+struct AvoidanceReceiver {
+    safe_velocity: Option<Vector3>,
+}
+
+#[godot_api]
+impl AvoidanceReceiver {
+    #[func]
+    fn on_safe_velocity(&mut self, safe_velocity: Vector3) {
+        self.safe_velocity = Some(safe_velocity);
+    }
+}
+
+/// Runs one step of the navigation server, which also serves 2D maps: synchronizes maps, then runs avoidance callbacks.
+fn step_navigation() {
+    NavigationServer3D::singleton().process(0.1);
+}
+
+#[itest]
+fn navigation_avoidance_fn_2d() {
+    let mut server = NavigationServer2D::singleton();
+    let map = server.map_create();
+    server.map_set_active(map, true);
+
+    let agent = server.agent_create();
+    server.agent_set_map(agent, map);
+    server.agent_set_avoidance_enabled(agent, true);
+    server.agent_set_max_speed(agent, 10.0);
+    server.agent_set_velocity(agent, Vector2::new(2.0, 0.0));
+
+    let safe_velocity = Rc::new(Cell::new(None));
+    let sender = safe_velocity.clone();
+    server.agent_set_avoidance_fn(agent, move |velocity| sender.set(Some(velocity)));
+
+    step_navigation();
+
+    // Without other agents, the desired velocity is safe.
+    let safe_velocity = safe_velocity
+        .get()
+        .expect("avoidance callback was not invoked");
+    assert!((safe_velocity - Vector2::new(2.0, 0.0)).length() < 0.01);
+
+    server.agent_clear_avoidance_fn(agent);
+    server.free_rid(agent);
+    server.free_rid(map);
+}
+
+#[itest]
+fn navigation_avoidance_fn_3d() {
+    let mut server = NavigationServer3D::singleton();
+    let (map, agent) = create_agent_3d(&mut server);
+
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    server.agent_set_avoidance_fn(agent, move |_velocity| counter.set(counter.get() + 1));
+    step_navigation();
+    assert_eq!(calls.get(), 1);
+
+    // Cleared callbacks are no longer invoked.
+    server.agent_clear_avoidance_fn(agent);
+    step_navigation();
+    assert_eq!(calls.get(), 1);
+
+    server.free_rid(agent);
+    server.free_rid(map);
+}
+
+#[itest]
+fn navigation_avoidance_receiver() {
+    let mut server = NavigationServer3D::singleton();
+    let (map, agent) = create_agent_3d(&mut server);
+    let receiver = Gd::<AvoidanceReceiver>::new_default();
+
+    server.agent_set_avoidance_receiver(agent, receiver.share(), "on_safe_velocity");
+    step_navigation();
+
+    let safe_velocity = receiver.bind().safe_velocity;
+    let safe_velocity = safe_velocity.expect("avoidance receiver was not invoked");
+    assert!((safe_velocity - Vector3::new(2.0, 0.0, 0.0)).length() < 0.01);
+
+    server.agent_clear_avoidance_receiver(agent);
+    server.free_rid(agent);
+    server.free_rid(map);
+}
+
+#[itest]
+fn navigation_map_queries() {
+    let mut server = NavigationServer3D::singleton();
+    let map = server.map_create();
+    server.map_set_active(map, true);
+
+    // Square floor of 10x10 units.
+    let mut mesh = NavigationMesh::new();
+    mesh.set_vertices(PackedVector3Array::from(&[
+        Vector3::new(0.0, 0.0, 0.0),
+        Vector3::new(10.0, 0.0, 0.0),
+        Vector3::new(10.0, 0.0, 10.0),
+        Vector3::new(0.0, 0.0, 10.0),
+    ]));
+    mesh.add_polygon(PackedInt32Array::from(&[0, 1, 2, 3]));
+
+    let region = server.region_create();
+    server.region_set_map(region, map);
+    server.region_set_navigation_mesh(region, mesh);
+
+    // Maps are synchronized with their regions at the next step.
+    let from = Vector3::new(1.0, 0.0, 1.0);
+    let to = Vector3::new(8.0, 0.0, 8.0);
+    assert!(server.map_find_path(map, from, to).is_empty());
+    step_navigation();
+
+    let path = server.map_find_path(map, from, to);
+    assert!(path.len() >= 2, "path: {path:?}");
+    assert!((path[0] - from).length() < 0.01, "path: {path:?}");
+    assert!(
+        (path[path.len() - 1] - to).length() < 0.01,
+        "path: {path:?}"
+    );
+
+    assert_eq!(
+        server.map_find_owner(map, Vector3::new(5.0, 0.0, 5.0)),
+        Some(region)
+    );
+
+    server.free_rid(region);
+    server.free_rid(map);
+}
+
+fn create_agent_3d(server: &mut Gd<NavigationServer3D>) -> (Rid, Rid) {
+    let map = server.map_create();
+    server.map_set_active(map, true);
+
+    let agent = server.agent_create();
+    server.agent_set_map(agent, map);
+    server.agent_set_avoidance_enabled(agent, true);
+    server.agent_set_max_speed(agent, 10.0);
+    server.agent_set_velocity(agent, Vector3::new(2.0, 0.0, 0.0));
+
+    (map, agent)
+}
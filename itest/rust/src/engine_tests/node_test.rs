@@ -7,10 +7,13 @@
 use std::str::FromStr;
 
 use godot::builtin::{NodePath, Variant};
-use godot::engine::{global, Node, Node3D, NodeExt, PackedScene, SceneTree};
-use godot::obj::Share;
+use godot::engine::{
+    autoload, global, try_autoload, CastPolicy, Node, Node2D, Node3D, NodeError, NodeExt,
+    PackedScene, SceneTree, SceneTreeExt,
+};
+use godot::obj::{Gd, GodotClass, Share};
 
-use crate::framework::{itest, TestContext};
+use crate::framework::{expect_panic, itest, TestContext};
 
 #[itest]
 fn node_get_node() {
@@ -20,11 +23,11 @@ fn node_get_node() {
 
     let mut parent = Node3D::new_alloc();
     parent.set_name("parent".into());
-    parent.add_child(child.share().upcast());
+    parent.add_child(&child);
 
     let mut grandparent = Node::new_alloc();
     grandparent.set_name("grandparent".into());
-    grandparent.add_child(parent.share().upcast());
+    grandparent.add_child(&parent);
 
     // Directly on Gd<T>
     let found = grandparent.get_node_as::<Node3D>(NodePath::from("parent/child"));
@@ -32,7 +35,7 @@ fn node_get_node() {
 
     // Deref via &T
     let found = grandparent.try_get_node_as::<Node3D>(NodePath::from("parent/child"));
-    let found = found.expect("try_get_node_as() returned Some(..)");
+    let found = found.expect("try_get_node_as() returned Ok(..)");
     assert_eq!(found.instance_id(), child_id);
 
     grandparent.free();
@@ -44,11 +47,37 @@ fn node_get_node_fail() {
     child.set_name("child".into());
 
     let found = child.try_get_node_as::<Node3D>(NodePath::from("non-existent"));
-    assert!(found.is_none());
+    assert_eq!(
+        found,
+        Err(NodeError::NotFound {
+            path: NodePath::from("non-existent")
+        })
+    );
 
     child.free();
 }
 
+#[itest]
+fn node_get_node_wrong_type() {
+    let mut child = Node3D::new_alloc();
+    child.set_name("child".into());
+
+    let mut parent = Node::new_alloc();
+    parent.add_child(child.share());
+
+    let found = parent.try_get_node_as::<Node2D>(NodePath::from("child"));
+    assert_eq!(
+        found,
+        Err(NodeError::WrongType {
+            path: NodePath::from("child"),
+            expected: Node2D::class_name(),
+            actual: "Node3D".into(),
+        })
+    );
+
+    parent.free();
+}
+
 #[itest]
 fn node_path_from_str(ctx: &TestContext) {
     let child = ctx.scene_tree.share();
@@ -90,3 +119,150 @@ fn node_call_group(ctx: &TestContext) {
     node.add_to_group("group".into());
     tree.call_group("group".into(), "set_name".into(), &[Variant::from("name")]);
 }
+
+#[itest]
+fn node_add_child_ex() {
+    let mut child = Node::new_alloc();
+    child.set_name("child".into());
+
+    let mut parent = Node::new_alloc();
+    parent.set_name("parent".into());
+
+    // Default-param builder: only override the parameter that matters.
+    parent
+        .add_child_ex(child.share())
+        .force_readable_name(true)
+        .done();
+    assert_eq!(parent.get_child_count(), 1);
+
+    // Simple overload uses all defaults.
+    let mut other = Node::new_alloc();
+    other.set_name("other".into());
+    parent.add_child(other.share());
+    assert_eq!(parent.get_child_count(), 2);
+
+    parent.free();
+}
+
+#[itest]
+fn node_add_child_subclass() {
+    let mut parent = Node::new_alloc();
+    let child = Node3D::new_alloc();
+    let other = Node3D::new_alloc();
+
+    // Gd<Node3D> is accepted where Gd<Node> is expected, both by reference and by value.
+    parent.add_child(&child);
+    parent.add_child(other);
+    assert_eq!(parent.get_child_count(), 2);
+
+    let first = parent.get_child(0).expect("child exists");
+    assert_eq!(first.instance_id(), child.instance_id());
+
+    parent.remove_child(&child);
+    assert_eq!(parent.get_child_count(), 1);
+
+    child.free();
+    parent.free();
+}
+
+#[itest]
+fn node_autoload(ctx: &TestContext) {
+    let mut root = ctx.scene_tree.get_tree().unwrap().get_root().unwrap();
+
+    let mut autoload_node = Node3D::new_alloc();
+    autoload_node.set_name("AutoloadTest".into());
+    root.add_child(autoload_node.share());
+
+    let found = autoload::<Node3D>("AutoloadTest");
+    assert_eq!(found, autoload_node);
+
+    // Served from cache.
+    let found = try_autoload::<Node>("AutoloadTest");
+    assert_eq!(
+        found.map(|n| n.instance_id()),
+        Ok(autoload_node.instance_id())
+    );
+
+    let found = try_autoload::<Node2D>("AutoloadTest");
+    assert!(matches!(found, Err(NodeError::WrongType { .. })));
+
+    // Stale cache entry must not be used.
+    autoload_node.free();
+    let found = try_autoload::<Node3D>("AutoloadTest");
+    assert_eq!(
+        found,
+        Err(NodeError::NotFound {
+            path: NodePath::from("/root/AutoloadTest")
+        })
+    );
+}
+
+#[itest]
+fn scene_tree_nodes_in_group_as(ctx: &TestContext) {
+    let mut parent = ctx.scene_tree.share();
+    let tree = parent.get_tree().unwrap();
+
+    let mut node_3d = Node3D::new_alloc();
+    node_3d.add_to_group("typed_group".into());
+    parent.add_child(node_3d.share());
+
+    let mut node_2d = Node2D::new_alloc();
+    node_2d.add_to_group("typed_group".into());
+    parent.add_child(node_2d.share());
+
+    let found: Vec<_> = tree
+        .nodes_in_group_as::<Node3D>("typed_group", CastPolicy::Skip)
+        .collect();
+    assert_eq!(found, vec![node_3d.share()]);
+
+    let found = tree.nodes_in_group_as::<Node>("typed_group", CastPolicy::Panic);
+    assert_eq!(found.count(), 2);
+
+    let tree_copy = tree.share();
+    expect_panic("nodes_in_group_as() with CastPolicy::Panic", move || {
+        tree_copy
+            .nodes_in_group_as::<Node3D>("typed_group", CastPolicy::Panic)
+            .for_each(drop);
+    });
+
+    node_3d.free();
+    node_2d.free();
+}
+
+#[itest]
+fn node_descendants_ancestors() {
+    // root
+    // ├── a (Node3D)
+    // │   └── a1
+    // └── b (Node2D)
+    let mut root = Node::new_alloc();
+    root.set_name("root".into());
+
+    let mut a = Node3D::new_alloc();
+    a.set_name("a".into());
+    root.add_child(a.share());
+
+    let mut a1 = Node::new_alloc();
+    a1.set_name("a1".into());
+    a.add_child(a1.share());
+
+    let mut b = Node2D::new_alloc();
+    b.set_name("b".into());
+    root.add_child(b.share());
+
+    let names = |nodes: Vec<Gd<Node>>| -> Vec<String> {
+        nodes.iter().map(|n| n.get_name().to_string()).collect()
+    };
+
+    assert_eq!(names(root.descendants().collect()), ["a", "a1", "b"]);
+    assert_eq!(names(a1.ancestors().collect()), ["a", "root"]);
+    assert_eq!(root.ancestors().count(), 0);
+
+    let found = root.find_children_by::<Node3D>(|_| true);
+    assert_eq!(found, vec![a.share()]);
+
+    let found = root.find_children_by::<Node>(|n| n.get_name().to_string().starts_with('a'));
+    assert_eq!(names(found), ["a", "a1"]);
+
+    root.free();
+}
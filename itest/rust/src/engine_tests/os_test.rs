@@ -0,0 +1,57 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use godot::engine::{FromUserArgs, Os, OsExt};
+
+use crate::framework::itest;
+
+struct ArgCount(usize);
+
+impl FromUserArgs for ArgCount {
+    type Err = ();
+
+    fn from_user_args(args: Vec<String>) -> Result<Self, Self::Err> {
+        Ok(Self(args.len()))
+    }
+}
+
+#[itest]
+fn os_cmdline_args() {
+    let os = Os::singleton();
+
+    let expected: Vec<String> = os
+        .get_cmdline_args()
+        .as_slice()
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+    assert_eq!(os.cmdline_args(), expected);
+
+    let expected_user = os.get_cmdline_user_args().len();
+    assert_eq!(os.user_args().len(), expected_user);
+
+    let parsed: ArgCount = os.parse_user_args().unwrap();
+    assert_eq!(parsed.0, expected_user);
+}
+
+#[itest]
+fn os_user_arg_absent() {
+    let os = Os::singleton();
+
+    assert_eq!(os.user_arg("gdext-itest-absent"), None);
+    assert!(!os.has_user_flag("gdext-itest-absent"));
+}
+
+#[itest]
+fn os_env_var() {
+    let os = Os::singleton();
+
+    std::env::set_var("GDEXT_ITEST_ENV", "value");
+    assert_eq!(os.env_var("GDEXT_ITEST_ENV"), Some("value".to_string()));
+
+    std::env::remove_var("GDEXT_ITEST_ENV");
+    assert_eq!(os.env_var("GDEXT_ITEST_ENV"), None);
+}
@@ -0,0 +1,71 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use godot::builtin::StringName;
+use godot::engine::{global, Node, Node2D, Node3D, NodeExt, PackedScene, PackedSceneExt};
+use godot::obj::{Gd, Share};
+
+use crate::framework::{expect_panic, itest};
+
+#[itest]
+fn packed_scene_instantiate_as() {
+    let scene = make_scene();
+
+    let root = scene.instantiate_as::<Node3D>();
+    assert_eq!(root.get_name(), StringName::from("root"));
+    assert_eq!(root.get_child_count(), 1);
+    root.free();
+
+    // Upcasts are fine, too.
+    let root = scene
+        .try_instantiate_as::<Node>()
+        .expect("instantiated as Node");
+    root.free();
+}
+
+#[itest]
+fn packed_scene_instantiate_as_wrong_type() {
+    let scene = make_scene();
+
+    assert!(scene.try_instantiate_as::<Node2D>().is_none());
+
+    expect_panic("instantiate_as() with wrong type", move || {
+        scene.instantiate_as::<Node2D>();
+    });
+}
+
+#[itest]
+fn node_spawn_child_as() {
+    let scene = make_scene();
+    let mut parent = Node::new_alloc();
+
+    let child = parent.spawn_child_as::<Node3D>(&scene);
+    assert_eq!(parent.get_child_count(), 1);
+    assert_eq!(
+        child.get_parent().map(|p| p.instance_id()),
+        Some(parent.instance_id())
+    );
+
+    parent.free();
+}
+
+fn make_scene() -> Gd<PackedScene> {
+    let mut root = Node3D::new_alloc();
+    root.set_name("root".into());
+
+    let mut child = Node::new_alloc();
+    child.set_name("child".into());
+    root.add_child(child.share());
+    child.set_owner(root.share());
+
+    let mut scene = PackedScene::new();
+    let err = scene.pack(root.share());
+    assert_eq!(err, global::Error::OK);
+
+    // Packing copies the nodes, the originals remain owned by us.
+    root.free();
+    scene
+}
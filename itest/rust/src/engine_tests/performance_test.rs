@@ -0,0 +1,52 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use godot::builtin::{FromVariant, StringName};
+use godot::engine::{Performance, PerformanceExt};
+
+use crate::framework::{expect_panic, itest};
+
+#[itest]
+fn performance_monitor_polls_closure() {
+    let mut performance = Performance::singleton();
+    let id = StringName::from("itest/counter");
+
+    let counter = Rc::new(Cell::new(0));
+    let polled = counter.clone();
+    performance.add_monitor(id.clone(), move || {
+        polled.set(polled.get() + 1);
+        polled.get() as f64
+    });
+    assert!(performance.has_custom_monitor(id.clone()));
+
+    let value = performance.get_custom_monitor(id.clone());
+    assert_eq!(f64::from_variant(&value), 1.0);
+    let value = performance.get_custom_monitor(id.clone());
+    assert_eq!(f64::from_variant(&value), 2.0);
+    assert_eq!(counter.get(), 2);
+
+    assert!(performance.remove_monitor(id.clone()));
+    assert!(!performance.has_custom_monitor(id.clone()));
+    assert!(!performance.remove_monitor(id));
+}
+
+#[itest]
+fn performance_monitor_duplicate_id() {
+    let mut performance = Performance::singleton();
+    let id = StringName::from("itest/duplicate");
+
+    performance.add_monitor(id.clone(), || 0.0);
+
+    let duplicate = id.clone();
+    expect_panic("monitor ID already in use", move || {
+        Performance::singleton().add_monitor(duplicate, || 1.0);
+    });
+
+    assert!(performance.remove_monitor(id));
+}
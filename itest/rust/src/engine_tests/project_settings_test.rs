@@ -0,0 +1,39 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use godot::builtin::{GodotString, ToVariant};
+use godot::engine::global::PropertyHint;
+use godot::engine::{ProjectSetting, ProjectSettings};
+
+use crate::framework::itest;
+
+#[itest]
+fn project_setting_register_get_set() {
+    let setting = ProjectSetting::new("gdext/itest/max_speed", 12.5)
+        .with_hint(PropertyHint::PROPERTY_HINT_RANGE, "0,100,0.5")
+        .basic();
+
+    setting.register();
+    assert_eq!(setting.get(), 12.5);
+
+    setting.set(40.0);
+    assert_eq!(setting.get(), 40.0);
+
+    ProjectSettings::singleton().clear(setting.name().clone());
+    assert_eq!(setting.try_get(), Some(12.5));
+}
+
+#[itest]
+fn project_setting_wrong_type() {
+    let name = GodotString::from("gdext/itest/wrong_type");
+    let mut settings = ProjectSettings::singleton();
+    settings.set_setting(name.clone(), "not a number".to_variant());
+
+    let setting = ProjectSetting::new(name.clone(), 0_i64);
+    assert_eq!(setting.try_get(), None);
+
+    settings.clear(name);
+}
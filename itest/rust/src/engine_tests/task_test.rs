@@ -0,0 +1,306 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use godot::bind::{godot_api, GodotClass};
+use godot::builtin::{Callable, ToVariant};
+use godot::engine::{
+    ClassDb, Engine, Object, ObjectExt, RefCounted, SceneTree, SceneTreeExt, Script, Timer,
+    TimerExt,
+};
+use godot::error::HttpErrorKind;
+use godot::obj::{Gd, Share};
+use godot::{http, task};
+
+use crate::framework::itest;
+
+/// Future that completes once `set()` has been called.
+#[derive(Clone, Default)]
+struct Flag {
+    is_set: Rc<Cell<bool>>,
+    waker: Rc<RefCell<Option<Waker>>>,
+}
+
+impl Flag {
+    fn set(&self) {
+        self.is_set.set(true);
+        if let Some(waker) = self.waker.borrow_mut().take() {
+            waker.wake();
+        }
+    }
+}
+
+impl Future for Flag {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.is_set.get() {
+            Poll::Ready(())
+        } else {
+            *self.waker.borrow_mut() = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, base=Object)]
+struct AsyncFuncs {
+    received: Option<i64>,
+}
+
+#[godot_api]
+impl AsyncFuncs {
+    #[func]
+    async fn double(value: i64) -> i64 {
+        value * 2
+    }
+
+    #[func]
+    fn receive(&mut self, result: i64) {
+        self.received = Some(result);
+    }
+}
+
+#[itest]
+fn task_runs_until_first_await() {
+    let steps = Rc::new(Cell::new(0));
+    let flag = Flag::default();
+
+    let handle = {
+        let steps = steps.clone();
+        let flag = flag.clone();
+        task::spawn(async move {
+            steps.set(1);
+            flag.await;
+            steps.set(2);
+        })
+    };
+
+    assert_eq!(steps.get(), 1);
+    assert!(!handle.is_finished());
+
+    // Not woken yet.
+    task::poll();
+    assert_eq!(steps.get(), 1);
+
+    flag.set();
+    task::poll();
+    assert_eq!(steps.get(), 2);
+    assert!(handle.is_finished());
+}
+
+#[itest]
+fn task_cancel() {
+    let finished = Rc::new(Cell::new(false));
+    let flag = Flag::default();
+
+    let handle = {
+        let finished = finished.clone();
+        let flag = flag.clone();
+        task::spawn(async move {
+            flag.await;
+            finished.set(true);
+        })
+    };
+
+    handle.cancel();
+    assert!(handle.is_finished());
+
+    flag.set();
+    task::poll();
+    assert!(!finished.get());
+}
+
+#[itest]
+fn task_async_func() {
+    let mut obj = Gd::<AsyncFuncs>::new_default();
+
+    let mut awaitable = obj
+        .share()
+        .upcast::<Object>()
+        .call("double".into(), &[21.to_variant()])
+        .to::<Gd<RefCounted>>();
+
+    // Emitted in the next poll, even though the future is ready immediately.
+    let callable = Callable::from_object_method(obj.share(), "receive");
+    awaitable
+        .try_connect(task::COMPLETED_SIGNAL, &callable)
+        .expect("awaitable has a `completed` signal");
+    assert_eq!(obj.bind().received, None);
+
+    task::poll();
+    assert_eq!(obj.bind().received, Some(42));
+
+    obj.free();
+}
+
+#[itest]
+fn task_call_on_main() {
+    let main_thread = std::thread::current().id();
+    let ran_on = Arc::new(Mutex::new(None));
+
+    let worker = {
+        let ran_on = ran_on.clone();
+        std::thread::spawn(move || {
+            task::call_on_main(move || {
+                *ran_on.lock().unwrap() = Some(std::thread::current().id());
+            });
+        })
+    };
+    worker.join().unwrap();
+
+    // Queued until the next poll.
+    assert_eq!(*ran_on.lock().unwrap(), None);
+
+    task::poll();
+    assert_eq!(*ran_on.lock().unwrap(), Some(main_thread));
+}
+
+#[itest]
+fn task_channel() {
+    let (sender, receiver) = task::channel::<i32>();
+
+    let worker = std::thread::spawn(move || {
+        for i in 0..3 {
+            sender.send(i).unwrap();
+        }
+    });
+    worker.join().unwrap();
+
+    assert_eq!(receiver.try_iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+}
+
+#[itest]
+fn task_channel_dispatch() {
+    let (sender, receiver) = task::channel::<i32>();
+    let received = Rc::new(RefCell::new(Vec::new()));
+
+    let handle = {
+        let received = received.clone();
+        receiver.dispatch(move |value| received.borrow_mut().push(value))
+    };
+
+    sender.send(1).unwrap();
+    sender.send(2).unwrap();
+    assert!(received.borrow().is_empty());
+
+    task::poll();
+    assert_eq!(*received.borrow(), vec![1, 2]);
+
+    // Dropping the last sender ends the task.
+    drop(sender);
+    task::poll();
+    assert!(handle.is_finished());
+}
+
+#[itest]
+fn task_timer_timeout() {
+    task::drive_each_frame();
+    let mut timer = Timer::new_alloc();
+
+    let fired = Rc::new(Cell::new(false));
+    let handle = {
+        let fired = fired.clone();
+        let timeout = timer.timeout();
+        task::spawn(async move {
+            fired.set(timeout.await.is_ok());
+        })
+    };
+    assert!(!handle.is_finished());
+
+    timer.emit_signal("timeout".into(), &[]);
+    task::poll();
+    assert!(handle.is_finished());
+    assert!(fired.get());
+
+    timer.free();
+}
+
+#[itest]
+fn task_sleep() {
+    task::drive_each_frame();
+    let mut tree = Engine::singleton()
+        .get_main_loop()
+        .unwrap()
+        .cast::<SceneTree>();
+
+    let sleep = tree.sleep(Duration::from_secs(60));
+    let handle = task::spawn(async move {
+        sleep.await.ok();
+    });
+
+    // Cancelling drops the future, which disconnects from the timer.
+    assert!(!handle.is_finished());
+    handle.cancel();
+}
+
+#[itest]
+fn task_coroutine_future() {
+    task::drive_each_frame();
+
+    let mut script = ClassDb::singleton()
+        .instantiate("GDScript".into())
+        .to::<Gd<Script>>();
+    script.set_source_code(
+        "extends RefCounted\nsignal go\nfunc double(x):\n\tawait go\n\treturn x * 2\nfunc triple(x):\n\treturn x * 3\n"
+            .into(),
+    );
+    script.reload();
+
+    let mut object = RefCounted::new();
+    object.set_script(script.to_variant());
+
+    let result = Rc::new(RefCell::new(None));
+    let handle = {
+        let result = result.clone();
+        let future = task::coroutine_future(object.call("double".into(), &[21.to_variant()]));
+        task::spawn(async move {
+            *result.borrow_mut() = Some(future.await);
+        })
+    };
+    assert!(!handle.is_finished());
+
+    object.emit_signal("go".into(), &[]);
+    task::poll();
+    assert!(handle.is_finished());
+    assert_eq!(result.borrow_mut().take(), Some(Ok(42.to_variant())));
+
+    // Functions that do not await complete right away.
+    let handle = {
+        let result = result.clone();
+        let future = task::coroutine_future(object.call("triple".into(), &[5.to_variant()]));
+        task::spawn(async move {
+            *result.borrow_mut() = Some(future.await);
+        })
+    };
+    assert!(handle.is_finished());
+    assert_eq!(result.borrow_mut().take(), Some(Ok(15.to_variant())));
+}
+
+#[itest(tags = [http])]
+fn task_http_invalid_url() {
+    let result = Rc::new(RefCell::new(None));
+    let handle = {
+        let result = result.clone();
+        task::spawn(async move {
+            *result.borrow_mut() = Some(http::get("ftp://example.com/file").await);
+        })
+    };
+
+    // Fails without any network access.
+    assert!(handle.is_finished());
+    let err = result.borrow_mut().take().unwrap().unwrap_err();
+    assert_eq!(err.kind(), HttpErrorKind::InvalidUrl);
+    assert_eq!(err.url(), "ftp://example.com/file");
+}
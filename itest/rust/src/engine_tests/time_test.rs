@@ -0,0 +1,48 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::time::{Duration, SystemTime};
+
+use godot::engine::{Engine, EngineExt, Time, TimeExt};
+
+use crate::framework::itest;
+
+#[itest]
+fn time_ticks() {
+    let time = Time::singleton();
+
+    let first = time.ticks();
+    let second = time.ticks();
+    assert!(second >= first);
+    assert!(first > Duration::ZERO);
+}
+
+#[itest]
+fn time_system_time() {
+    let godot_time = Time::singleton()
+        .system_time()
+        .expect("system time after 1970");
+    let rust_time = SystemTime::now();
+
+    let difference = match rust_time.duration_since(godot_time) {
+        Ok(d) => d,
+        Err(e) => e.duration(),
+    };
+    assert!(difference < Duration::from_secs(5), "{difference:?}");
+}
+
+#[itest]
+fn engine_frame_counters() {
+    let engine = Engine::singleton();
+
+    assert_eq!(engine.process_frame_count(), engine.get_process_frames());
+    assert_eq!(engine.drawn_frame_count(), engine.get_frames_drawn() as u64);
+
+    let ticks_per_second = engine.get_physics_ticks_per_second();
+    let step = engine.physics_step().expect("positive physics ticks");
+    let product = step.as_secs_f64() * ticks_per_second as f64;
+    assert!((product - 1.0).abs() < 1e-6, "step {step:?}");
+}
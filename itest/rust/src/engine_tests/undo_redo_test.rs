@@ -0,0 +1,111 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use godot::engine::{UndoRedo, UndoRedoExt};
+use godot::prelude::*;
+
+use crate::framework::itest;
+
+#[itest]
+fn undo_redo_closures() {
+    let mut undo_redo = UndoRedo::new_alloc();
+    let count = Rc::new(Cell::new(0));
+
+    let (inc, dec) = (count.clone(), count.clone());
+    undo_redo
+        .action("Increment")
+        .do_fn(move || inc.set(inc.get() + 1))
+        .undo_fn(move || dec.set(dec.get() - 1))
+        .commit();
+
+    // Committing executes the "do" operations.
+    assert_eq!(count.get(), 1);
+    assert!(undo_redo.has_undo());
+
+    assert!(undo_redo.undo());
+    assert_eq!(count.get(), 0);
+
+    assert!(undo_redo.redo());
+    assert_eq!(count.get(), 1);
+
+    undo_redo.free();
+}
+
+#[itest]
+fn undo_redo_methods_and_properties() {
+    let mut undo_redo = UndoRedo::new_alloc();
+    let mut node = Node::new_alloc();
+    node.set_name("Before".into());
+
+    undo_redo
+        .action("Rename and tag")
+        .do_property(&node, "name", "After")
+        .undo_property(&node, "name", node.get_name())
+        .do_method(&node, "add_to_group", &["tagged".to_variant()])
+        .undo_method(&node, "remove_from_group", &["tagged".to_variant()])
+        .commit();
+
+    assert_eq!(node.get_name(), "After".into());
+    assert!(node.is_in_group("tagged".into()));
+
+    undo_redo.undo();
+    assert_eq!(node.get_name(), "Before".into());
+    assert!(!node.is_in_group("tagged".into()));
+
+    undo_redo.redo();
+    assert_eq!(node.get_name(), "After".into());
+    assert!(node.is_in_group("tagged".into()));
+
+    undo_redo.free();
+    node.free();
+}
+
+#[itest]
+fn undo_redo_dropped_action_is_discarded() {
+    let mut undo_redo = UndoRedo::new_alloc();
+    let called = Rc::new(Cell::new(false));
+
+    let flag = called.clone();
+    let action = undo_redo.action("Discarded").do_fn(move || flag.set(true));
+    drop(action);
+
+    assert!(!called.get());
+    assert!(!undo_redo.has_undo());
+
+    undo_redo.free();
+}
+
+#[itest]
+fn undo_redo_closures_released_with_history() {
+    let mut undo_redo = UndoRedo::new_alloc();
+    let count = Rc::new(Cell::new(0));
+
+    let (inc, dec) = (count.clone(), count.clone());
+    undo_redo
+        .action("Increment")
+        .do_fn(move || inc.set(inc.get() + 1))
+        .undo_fn(move || dec.set(dec.get() - 1))
+        .commit();
+    assert_eq!(Rc::strong_count(&count), 3);
+
+    // Replacing an undone action discards it.
+    undo_redo.undo();
+    let inc = count.clone();
+    undo_redo
+        .action("Increment again")
+        .do_fn(move || inc.set(inc.get() + 1))
+        .commit();
+    assert_eq!(Rc::strong_count(&count), 2);
+
+    undo_redo.clear_history();
+    assert_eq!(Rc::strong_count(&count), 1);
+    assert_eq!(count.get(), 1);
+
+    undo_redo.free();
+}
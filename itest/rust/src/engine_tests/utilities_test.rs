@@ -54,3 +54,10 @@ fn utilities_max() {
     );
     assert_eq!(output, Variant::from(-1.0));
 }
+
+#[itest]
+fn utilities_print_macros() {
+    // Output is not captured; this verifies that the utility functions are found.
+    godot::log::godot_print_rich!("[i]godot_print_rich![/i] from {}", "itest");
+    godot::log::godot_printerr!("godot_printerr! from {} (expected)", "itest");
+}
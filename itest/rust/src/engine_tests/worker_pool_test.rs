@@ -0,0 +1,38 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+use godot::engine::{WorkerThreadPool, WorkerThreadPoolExt};
+
+use crate::framework::itest;
+
+#[itest]
+fn worker_pool_task() {
+    let mut pool = WorkerThreadPool::singleton();
+
+    let task = pool.add_rust_task(|| 6 * 7);
+    assert_eq!(task.join().ok(), Some(42));
+
+    let task = pool.add_rust_task(|| panic!("expected panic in pool task"));
+    assert!(task.join().is_err());
+}
+
+#[itest]
+fn worker_pool_group_task() {
+    let sum = Arc::new(AtomicU32::new(0));
+
+    let task = {
+        let sum = sum.clone();
+        WorkerThreadPool::singleton().add_rust_group_task(100, move |index| {
+            sum.fetch_add(index, Ordering::Relaxed);
+        })
+    };
+
+    assert!(task.join().is_ok());
+    assert_eq!(sum.load(Ordering::Relaxed), (0..100).sum());
+}
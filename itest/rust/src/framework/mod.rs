@@ -4,15 +4,19 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use godot::builtin::{Dictionary, VariantArray};
 use godot::engine::{Engine, Node};
 use godot::obj::Gd;
 use godot::sys;
+use std::cell::RefCell;
 use std::collections::HashSet;
 
 mod bencher;
+mod report;
 mod runner;
 
 pub use bencher::*;
+pub use report::*;
 pub use runner::*;
 
 /// Allow re-import as `crate::framework::itest`.
@@ -26,7 +30,7 @@ sys::plugin_registry!(pub(crate) __GODOT_ITEST: RustTestCase);
 sys::plugin_registry!(pub(crate) __GODOT_BENCH: RustBenchmark);
 
 /// Finds all `#[itest]` tests.
-fn collect_rust_tests(filters: &[String]) -> (Vec<RustTestCase>, usize, bool) {
+fn collect_rust_tests(config: &RunConfig) -> (Vec<RustTestCase>, usize, bool) {
     let mut all_files = HashSet::new();
     let mut tests: Vec<RustTestCase> = vec![];
     let mut is_focus_run = false;
@@ -40,7 +44,7 @@ fn collect_rust_tests(filters: &[String]) -> (Vec<RustTestCase>, usize, bool) {
         }
 
         // Only collect tests if normal mode, or focus mode and test is focused.
-        if (!is_focus_run || test.focused) && passes_filter(filters, test.name) {
+        if (!is_focus_run || test.focused) && config.selects(test.name, test.tags) {
             all_files.insert(test.file);
             tests.push(*test);
         }
@@ -82,6 +86,8 @@ pub struct RustTestCase {
     pub skipped: bool,
     /// If one or more tests are focused, only they will be executed. Helpful for debugging and working on specific features.
     pub focused: bool,
+    /// Labels from `#[itest(tags = [...])]`, to select tests with `--tags` and `--skip-tags`.
+    pub tags: &'static [&'static str],
     #[allow(dead_code)]
     pub line: u32,
     pub function: fn(&TestContext),
@@ -97,10 +103,94 @@ pub struct RustBenchmark {
     pub repetitions: usize,
 }
 
+/// Options of a test run, parsed from the command line by `TestRunner.gd`.
+#[derive(Debug)]
+pub struct RunConfig {
+    /// If false, focused tests make the run fail (used in CI).
+    pub allow_focus: bool,
+    /// Substrings of test names; tests matching any of them are run.
+    pub filters: Vec<String>,
+    /// Only tests with at least one of these tags are run.
+    pub tags: Vec<String>,
+    /// Tests with any of these tags are not run.
+    pub skip_tags: Vec<String>,
+    /// Stops after the first failed test.
+    pub fail_fast: bool,
+    /// How often each test is run; a test fails if any repetition fails.
+    pub repeat: usize,
+    /// Path of a JUnit XML report to write.
+    pub junit_path: Option<String>,
+    /// Path of a JSON report to write.
+    pub json_path: Option<String>,
+}
+
+impl RunConfig {
+    pub fn from_dictionary(config: &Dictionary) -> Self {
+        let string = |key: &str| {
+            let value = config.get_or_nil(key).to::<String>();
+            (!value.is_empty()).then_some(value)
+        };
+        let strings = |key: &str| {
+            config
+                .get_or_nil(key)
+                .to::<VariantArray>()
+                .iter_shared()
+                .map(|v| v.to::<String>())
+                .collect::<Vec<_>>()
+        };
+
+        Self {
+            allow_focus: config.get_or_nil("allow_focus").to::<bool>(),
+            filters: strings("filters"),
+            tags: strings("tags"),
+            skip_tags: strings("skip_tags"),
+            fail_fast: config.get_or_nil("fail_fast").to::<bool>(),
+            repeat: config.get_or_nil("repeat").to::<i64>().max(1) as usize,
+            junit_path: string("junit"),
+            json_path: string("json"),
+        }
+    }
+
+    /// Whether a test with the given name and tags passes the name and tag filters.
+    ///
+    /// GDScript tests have no tags, so they are excluded as soon as `--tags` is given.
+    pub fn selects(&self, test_name: &str, test_tags: &[&str]) -> bool {
+        let has_tag = |tags: &[String]| tags.iter().any(|tag| test_tags.contains(&tag.as_str()));
+
+        passes_filter(&self.filters, test_name)
+            && (self.tags.is_empty() || has_tag(&self.tags))
+            && !has_tag(&self.skip_tags)
+    }
+}
+
 pub fn passes_filter(filters: &[String], test_name: &str) -> bool {
     filters.is_empty() || filters.iter().any(|x| test_name.contains(x))
 }
 
+thread_local! {
+    static SKIP_REASON: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Marks the running test as skipped, for preconditions that can only be checked at runtime.
+///
+/// The test should return right after this call, so that it does not pass without checking anything:
+/// ```ignore
+/// let Some(editor) = running_editor() else {
+///     return skip("needs a running editor");
+/// };
+/// ```
+pub fn skip(reason: &str) {
+    SKIP_REASON.with(|skip| *skip.borrow_mut() = Some(reason.to_string()));
+}
+
+fn skip_requested() -> bool {
+    SKIP_REASON.with(|skip| skip.borrow().is_some())
+}
+
+fn take_skip_reason() -> Option<String> {
+    SKIP_REASON.with(|skip| skip.borrow_mut().take())
+}
+
 pub fn expect_panic(context: &str, code: impl FnOnce() + std::panic::UnwindSafe) {
     use std::panic;
 
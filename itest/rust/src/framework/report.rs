@@ -0,0 +1,198 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Machine-readable test reports, for CI systems and other tooling.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::framework::TestOutcome;
+
+/// Result of a single test, as written to reports.
+#[derive(Debug)]
+pub struct TestRecord {
+    pub name: String,
+    /// File that contains the test.
+    pub suite: String,
+    /// `"rust"` or `"gdscript"`.
+    pub language: &'static str,
+    pub tags: Vec<String>,
+    pub outcome: TestOutcome,
+    /// Total time of all repetitions.
+    pub time: Duration,
+    /// Details about a failure, in addition to the output printed during the test, or why a test was skipped.
+    pub message: Option<String>,
+}
+
+/// Creates a JUnit XML report, with one `<testsuite>` per file.
+pub fn junit_report(records: &[TestRecord], time: Duration) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        r#"<testsuites name="itest" tests="{}" failures="{}" skipped="{}" time="{:.3}">"#,
+        records.len(),
+        count(records, TestOutcome::Failed),
+        count(records, TestOutcome::Skipped),
+        time.as_secs_f64(),
+    );
+
+    for (suite, records) in group_by_suite(records) {
+        let suite_time: Duration = records.iter().map(|record| record.time).sum();
+        let _ = writeln!(
+            xml,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" skipped="{}" time="{:.3}">"#,
+            escape_xml(suite),
+            records.len(),
+            count(records.iter().copied(), TestOutcome::Failed),
+            count(records.iter().copied(), TestOutcome::Skipped),
+            suite_time.as_secs_f64(),
+        );
+
+        for record in records {
+            let _ = write!(
+                xml,
+                r#"    <testcase name="{}" classname="{}" time="{:.3}""#,
+                escape_xml(&record.name),
+                escape_xml(suite),
+                record.time.as_secs_f64(),
+            );
+
+            match record.outcome {
+                TestOutcome::Passed => xml.push_str("/>\n"),
+                TestOutcome::Skipped => match &record.message {
+                    Some(reason) => {
+                        let _ = write!(
+                            xml,
+                            ">\n      <skipped message=\"{}\"/>\n    </testcase>\n",
+                            escape_xml(reason)
+                        );
+                    }
+                    None => xml.push_str(">\n      <skipped/>\n    </testcase>\n"),
+                },
+                TestOutcome::Failed => {
+                    let message = record.message.as_deref().unwrap_or("test failed");
+                    let _ = write!(
+                        xml,
+                        ">\n      <failure message=\"{}\"/>\n    </testcase>\n",
+                        escape_xml(message)
+                    );
+                }
+            }
+        }
+
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Creates a JSON report with summary counts and one entry per test.
+pub fn json_report(records: &[TestRecord], time: Duration) -> String {
+    let mut json = String::new();
+    let _ = writeln!(
+        json,
+        r#"{{"passed": {}, "failed": {}, "skipped": {}, "time": {:.3}, "tests": ["#,
+        count(records, TestOutcome::Passed),
+        count(records, TestOutcome::Failed),
+        count(records, TestOutcome::Skipped),
+        time.as_secs_f64(),
+    );
+
+    for (i, record) in records.iter().enumerate() {
+        let tags = record
+            .tags
+            .iter()
+            .map(|tag| format!("\"{}\"", escape_json(tag)))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let message = match &record.message {
+            Some(message) => format!("\"{}\"", escape_json(message)),
+            None => "null".to_string(),
+        };
+
+        let separator = if i + 1 < records.len() { "," } else { "" };
+        let _ = writeln!(
+            json,
+            r#"  {{"name": "{}", "suite": "{}", "language": "{}", "tags": [{tags}], "outcome": "{}", "time": {:.3}, "message": {message}}}{separator}"#,
+            escape_json(&record.name),
+            escape_json(&record.suite),
+            record.language,
+            outcome_name(record.outcome),
+            record.time.as_secs_f64(),
+        );
+    }
+
+    json.push_str("]}\n");
+    json
+}
+
+fn outcome_name(outcome: TestOutcome) -> &'static str {
+    match outcome {
+        TestOutcome::Passed => "passed",
+        TestOutcome::Failed => "failed",
+        TestOutcome::Skipped => "skipped",
+    }
+}
+
+fn count<'a>(records: impl IntoIterator<Item = &'a TestRecord>, outcome: TestOutcome) -> usize {
+    records
+        .into_iter()
+        .filter(|record| record.outcome == outcome)
+        .count()
+}
+
+/// Groups records by suite, in order of first appearance.
+fn group_by_suite(records: &[TestRecord]) -> Vec<(&str, Vec<&TestRecord>)> {
+    let mut groups: Vec<(&str, Vec<&TestRecord>)> = Vec::new();
+    for record in records {
+        match groups.iter_mut().find(|(suite, _)| *suite == record.suite) {
+            Some((_, group)) => group.push(record),
+            None => groups.push((&record.suite, vec![record])),
+        }
+    }
+
+    groups
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            // Other control characters are not allowed in XML 1.0.
+            c if c.is_control() && c != '\t' && c != '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
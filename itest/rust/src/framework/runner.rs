@@ -7,13 +7,14 @@
 use std::time::{Duration, Instant};
 
 use godot::bind::{godot_api, GodotClass};
-use godot::builtin::{Array, GodotString, ToVariant, Variant, VariantArray};
-use godot::engine::{Engine, Node, Os};
+use godot::builtin::{Array, Dictionary, GodotString, ToVariant, Variant, VariantArray};
+use godot::engine::{Engine, Node, Os, ProjectSettings};
 use godot::log::godot_error;
 use godot::obj::Gd;
 
 use crate::framework::{
-    bencher, passes_filter, BenchResult, RustBenchmark, RustTestCase, TestContext,
+    bencher, json_report, junit_report, BenchResult, RunConfig, RustBenchmark, RustTestCase,
+    TestContext, TestRecord,
 };
 
 #[derive(GodotClass, Debug)]
@@ -23,6 +24,9 @@ pub struct IntegrationTests {
     passed: i64,
     skipped: i64,
     focus_run: bool,
+    /// Set once a test fails with `--fail-fast`; remaining tests are not run.
+    stopped: bool,
+    records: Vec<TestRecord>,
 }
 
 #[godot_api]
@@ -33,21 +37,19 @@ impl IntegrationTests {
         &mut self,
         gdscript_tests: VariantArray,
         gdscript_file_count: i64,
-        allow_focus: bool,
         scene_tree: Gd<Node>,
-        filters: VariantArray,
+        config: Dictionary,
     ) -> bool {
         println!("{}Run{} Godot integration tests...", FMT_CYAN_BOLD, FMT_END);
-        let filters: Vec<String> = filters.iter_shared().map(|v| v.to::<String>()).collect();
+        let config = RunConfig::from_dictionary(&config);
         let gdscript_tests = gdscript_tests
             .iter_shared()
             .filter(|test| {
                 let test_name = get_property(test, "method_name");
-                passes_filter(filters.as_slice(), &test_name)
+                config.selects(&test_name, &[])
             })
             .collect::<Array<_>>();
-        let (rust_tests, rust_file_count, focus_run) =
-            super::collect_rust_tests(filters.as_slice());
+        let (rust_tests, rust_file_count, focus_run) = super::collect_rust_tests(&config);
 
         // Print based on focus/not focus.
         self.focus_run = focus_run;
@@ -66,18 +68,25 @@ impl IntegrationTests {
                 gdscript_file_count
             );
         }
+        if config.repeat > 1 {
+            println!("  Each test is repeated {} times.", config.repeat);
+        }
 
         let clock = Instant::now();
-        self.run_rust_tests(rust_tests, scene_tree);
+        self.run_rust_tests(rust_tests, scene_tree, &config);
         let rust_time = clock.elapsed();
         let gdscript_time = if !focus_run {
-            let extra_duration = self.run_gdscript_tests(gdscript_tests);
+            let extra_duration = self.run_gdscript_tests(gdscript_tests, &config);
             Some((clock.elapsed() - rust_time) + extra_duration)
         } else {
             None
         };
 
-        self.conclude_tests(rust_time, gdscript_time, allow_focus)
+        let all_passed = self.conclude_tests(rust_time, gdscript_time, config.allow_focus);
+        let total_time = rust_time + gdscript_time.unwrap_or_default();
+        let reports_written = self.write_reports(&config, total_time);
+
+        all_passed && reports_written
     }
 
     #[func]
@@ -113,45 +122,86 @@ impl IntegrationTests {
         }
     }
 
-    fn run_rust_tests(&mut self, tests: Vec<RustTestCase>, scene_tree: Gd<Node>) {
+    fn run_rust_tests(
+        &mut self,
+        tests: Vec<RustTestCase>,
+        scene_tree: Gd<Node>,
+        config: &RunConfig,
+    ) {
         let ctx = TestContext { scene_tree };
 
         let mut last_file = None;
         for test in tests {
-            print_test_pre(test.name, test.file.to_string(), &mut last_file, false);
-            let outcome = run_rust_test(&test, &ctx);
+            if self.stopped {
+                break;
+            }
 
-            self.update_stats(&outcome);
-            print_test_post(test.name, outcome);
+            print_test_pre(test.name, test.file.to_string(), &mut last_file, false);
+            let clock = Instant::now();
+            let (outcome, message) = repeat_test(config.repeat, || run_rust_test(&test, &ctx));
+            let message = match outcome {
+                TestOutcome::Skipped => super::take_skip_reason(),
+                _ => message,
+            };
+
+            self.update_stats(&outcome, config);
+            self.records.push(TestRecord {
+                name: test.name.to_string(),
+                suite: test.file.to_string(),
+                language: "rust",
+                tags: test.tags.iter().map(|tag| tag.to_string()).collect(),
+                outcome,
+                time: clock.elapsed(),
+                message: message.clone(),
+            });
+            print_test_post(test.name, outcome, message.as_deref());
         }
     }
 
-    fn run_gdscript_tests(&mut self, tests: VariantArray) -> Duration {
+    fn run_gdscript_tests(&mut self, tests: VariantArray, config: &RunConfig) -> Duration {
         let mut last_file = None;
         let mut extra_duration = Duration::new(0, 0);
 
         for test in tests.iter_shared() {
+            if self.stopped {
+                break;
+            }
+
             let test_file = get_property(&test, "suite_name");
             let test_case = get_property(&test, "method_name");
 
-            print_test_pre(&test_case, test_file, &mut last_file, true);
-            let result = test.call("run", &[]);
-            // In case a test needs to disable error messages to ensure it runs properly.
-            Engine::singleton().set_print_error_messages(true);
-
-            if let Some(duration) = get_execution_time(&test) {
-                extra_duration += duration;
-            }
-            let success = result.try_to::<bool>().unwrap_or_else(|_| {
-                panic!("GDScript test case {test} returned non-bool: {result}")
+            print_test_pre(&test_case, test_file.clone(), &mut last_file, true);
+            let clock = Instant::now();
+            let mut test_duration = Duration::new(0, 0);
+            let (outcome, message) = repeat_test(config.repeat, || {
+                let result = test.call("run", &[]);
+                // In case a test needs to disable error messages to ensure it runs properly.
+                Engine::singleton().set_print_error_messages(true);
+
+                if let Some(duration) = get_execution_time(&test) {
+                    test_duration += duration;
+                }
+                let success = result.try_to::<bool>().unwrap_or_else(|_| {
+                    panic!("GDScript test case {test} returned non-bool: {result}")
+                });
+                for error in get_errors(&test).iter_shared() {
+                    godot_error!("{error}");
+                }
+                TestOutcome::from_bool(success)
             });
-            for error in get_errors(&test).iter_shared() {
-                godot_error!("{error}");
-            }
-            let outcome = TestOutcome::from_bool(success);
-
-            self.update_stats(&outcome);
-            print_test_post(&test_case, outcome);
+            extra_duration += test_duration;
+
+            self.update_stats(&outcome, config);
+            self.records.push(TestRecord {
+                name: test_case.clone(),
+                suite: test_file,
+                language: "gdscript",
+                tags: Vec::new(),
+                outcome,
+                time: clock.elapsed() + test_duration,
+                message,
+            });
+            print_test_post(&test_case, outcome, None);
         }
         extra_duration
     }
@@ -166,6 +216,7 @@ impl IntegrationTests {
             total,
             passed,
             skipped,
+            stopped,
             ..
         } = *self;
 
@@ -188,6 +239,9 @@ impl IntegrationTests {
         };
 
         println!("\nTest result: {outcome}. {passed} passed; {failed} failed{extra}.");
+        if stopped {
+            println!("  {FMT_YELLOW}Stopped after first failure (--fail-fast).{FMT_END}");
+        }
         if let Some(gdscript_time) = gdscript_time {
             let total_time = rust_time + gdscript_time;
             println!(
@@ -224,14 +278,53 @@ impl IntegrationTests {
 
     fn conclude_benchmarks(&self) {}
 
-    fn update_stats(&mut self, outcome: &TestOutcome) {
+    fn update_stats(&mut self, outcome: &TestOutcome, config: &RunConfig) {
         self.total += 1;
         match outcome {
             TestOutcome::Passed => self.passed += 1,
-            TestOutcome::Failed => {}
+            TestOutcome::Failed => self.stopped = config.fail_fast,
             TestOutcome::Skipped => self.skipped += 1,
         }
     }
+
+    /// Writes the reports requested with `--junit` and `--json`. Returns false if any could not be written.
+    fn write_reports(&self, config: &RunConfig, time: Duration) -> bool {
+        let reports = [
+            (
+                &config.junit_path,
+                junit_report as fn(&[TestRecord], Duration) -> String,
+            ),
+            (&config.json_path, json_report),
+        ];
+
+        let mut success = true;
+        for (path, create_report) in reports {
+            let Some(path) = path else {
+                continue;
+            };
+
+            // Resolve res:// and user:// paths; other paths are relative to the working directory.
+            let path = ProjectSettings::singleton()
+                .globalize_path(path.into())
+                .to_string();
+            let report = create_report(&self.records, time);
+
+            let result = std::path::Path::new(&path)
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|()| std::fs::write(&path, report));
+
+            match result {
+                Ok(()) => println!("  Report written to {path}."),
+                Err(err) => {
+                    godot_error!("Failed to write test report to {path}: {err}");
+                    success = false;
+                }
+            }
+        }
+
+        success
+    }
 }
 
 // For more colors, see https://stackoverflow.com/a/54062826
@@ -251,11 +344,38 @@ fn run_rust_test(test: &RustTestCase, ctx: &TestContext) -> TestOutcome {
         return TestOutcome::Skipped;
     }
 
+    // Discard a reason left over from a previous repetition that failed after calling skip().
+    super::take_skip_reason();
+
     // Explicit type to prevent tests from returning a value
     let err_context = || format!("itest `{}` failed", test.name);
     let success: Option<()> = godot::private::handle_panic(err_context, || (test.function)(ctx));
 
-    TestOutcome::from_bool(success.is_some())
+    match success {
+        Some(()) if super::skip_requested() => TestOutcome::Skipped,
+        _ => TestOutcome::from_bool(success.is_some()),
+    }
+}
+
+/// Runs a test up to `repeat` times, stopping at the first repetition that does not pass.
+///
+/// Returns the outcome, and for failed repeated tests, the failing repetition.
+fn repeat_test(
+    repeat: usize,
+    mut run: impl FnMut() -> TestOutcome,
+) -> (TestOutcome, Option<String>) {
+    for i in 1..=repeat {
+        match run() {
+            TestOutcome::Passed => {}
+            TestOutcome::Failed if repeat > 1 => {
+                let message = format!("failed in repetition {i} of {repeat}");
+                return (TestOutcome::Failed, Some(message));
+            }
+            outcome => return (outcome, None),
+        }
+    }
+
+    (TestOutcome::Passed, None)
 }
 
 fn print_test_pre(test_case: &str, test_file: String, last_file: &mut Option<String>, flush: bool) {
@@ -293,12 +413,12 @@ fn print_file_header(file: String, last_file: &mut Option<String>) {
 ///
 /// Note that this is run after a test run, so stdout/stderr output during the test will be printed before.
 /// It would be possible to print the test name before and the outcome after, but that would split or duplicate the line.
-fn print_test_post(test_case: &str, outcome: TestOutcome) {
+fn print_test_post(test_case: &str, outcome: TestOutcome, skip_reason: Option<&str>) {
     // If test failed, something was printed (e.g. assertion), so we can print the entire line again; otherwise just outcome on same line.
-    if matches!(outcome, TestOutcome::Failed) {
-        println!("   -- {test_case} ... {outcome}");
-    } else {
-        println!("{outcome}");
+    match (outcome, skip_reason) {
+        (TestOutcome::Failed, _) => println!("   -- {test_case} ... {outcome}"),
+        (TestOutcome::Skipped, Some(reason)) => println!("{outcome} ({reason})"),
+        _ => println!("{outcome}"),
     }
 }
 
@@ -341,7 +461,8 @@ fn get_errors(test: &Variant) -> Array<GodotString> {
 }
 
 #[must_use]
-enum TestOutcome {
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TestOutcome {
     Passed,
     Failed,
     Skipped,
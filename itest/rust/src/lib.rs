@@ -4,7 +4,7 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use godot::init::{gdextension, ExtensionLibrary};
+use godot::init::{gdextension, ExtensionLibrary, InitLevel};
 
 mod benchmarks;
 mod builtin_tests;
@@ -18,4 +18,22 @@ mod register_tests;
 // Entry point
 
 #[gdextension(entry_point=itest_init)]
-unsafe impl ExtensionLibrary for framework::IntegrationTests {}
+unsafe impl ExtensionLibrary for framework::IntegrationTests {
+    // Loaded before Godot creates its servers, to test custom server classes.
+    fn min_level() -> InitLevel {
+        InitLevel::Servers
+    }
+
+    fn on_level_init(level: InitLevel) {
+        if level == InitLevel::Scene {
+            object_tests::register_movie_writer();
+            object_tests::select_main_loop();
+        }
+    }
+
+    fn on_level_deinit(level: InitLevel) {
+        if level == InitLevel::Scene {
+            object_tests::unregister_movie_writer();
+        }
+    }
+}
@@ -5,6 +5,7 @@
  */
 
 use crate::framework::itest;
+use godot::engine::notify::NodeNotification;
 use godot::prelude::*;
 
 #[itest(skip)]
@@ -93,6 +94,15 @@ fn base_with_init() {
     obj.free();
 }
 
+#[itest]
+fn base_mut_allows_reentrant_callback() {
+    let mut obj = Gd::<ReentrantParent>::new_default();
+    obj.bind_mut().add_child_through_base();
+    assert!(obj.bind().child_order_changed);
+
+    obj.free();
+}
+
 #[derive(GodotClass)]
 #[class(init, base=Node2D)]
 struct Based {
@@ -107,3 +117,28 @@ struct Based {
 struct Baseless {
     // No need for fields, we just test if we can access this as Gd<Node2D>.
 }
+
+#[derive(GodotClass)]
+#[class(init, base=Node)]
+struct ReentrantParent {
+    #[base]
+    base: Base<Node>,
+
+    child_order_changed: bool,
+}
+
+impl ReentrantParent {
+    fn add_child_through_base(&mut self) {
+        // Godot notifies this node synchronously, while it is still bound.
+        self.base_mut().add_child(Node::new_alloc());
+    }
+}
+
+#[godot_api]
+impl NodeVirtual for ReentrantParent {
+    fn on_notification(&mut self, what: NodeNotification) {
+        if what == NodeNotification::ChildOrderChanged {
+            self.child_order_changed = true;
+        }
+    }
+}
@@ -0,0 +1,79 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use godot::bind::{godot_api, GodotClass};
+use godot::engine::{ClassDb, RefCounted};
+use godot::obj::{Base, Gd, Share};
+use godot::task;
+
+use crate::framework::itest;
+
+#[derive(GodotClass)]
+#[class(init, base=RefCounted)]
+pub struct DeferredTest {
+    #[base]
+    base: Base<RefCounted>,
+    counter: i64,
+}
+
+#[godot_api]
+impl DeferredTest {
+    /// Used by SpecialTests.gd, which can wait for the next frame.
+    #[func]
+    fn schedule_increment(&mut self) {
+        let this = self.base.share().cast::<Self>();
+        this.run_deferred(|this: &mut Self| this.counter += 1);
+    }
+
+    /// Used by SpecialTests.gd; like `schedule_increment()`, but through `task::defer()`.
+    #[func]
+    fn schedule_multiply(&mut self) {
+        let mut this = self.base.share().cast::<Self>();
+        task::defer(move || this.bind_mut().counter *= 10);
+    }
+
+    #[func]
+    fn get_counter(&self) -> i64 {
+        self.counter
+    }
+}
+
+#[itest]
+fn run_deferred_runs_later() {
+    let obj = Gd::<DeferredTest>::new_default();
+
+    obj.run_deferred(|this| this.counter += 1);
+    assert_eq!(obj.bind().counter, 0);
+
+    let ran = Rc::new(Cell::new(false));
+    let deferred = ran.clone();
+    task::defer(move || deferred.set(true));
+    assert!(!ran.get());
+
+    // The object is freed before idle time; its closure is then skipped.
+}
+
+#[itest]
+fn run_deferred_adds_no_methods() {
+    // Closures are dispatched through an internal class, not through hidden methods of user classes.
+    let class_db = ClassDb::singleton();
+    let methods = class_db.class_get_method_list("DeferredTest".into());
+
+    let names: Vec<String> = methods
+        .iter_shared()
+        .filter_map(|method| method.get("name"))
+        .map(|name| name.to_string())
+        .collect();
+
+    assert!(names.contains(&"schedule_increment".to_string()));
+    assert!(
+        names.iter().all(|name| !name.starts_with("__godot")),
+        "unexpected methods: {names:?}"
+    );
+}
@@ -6,6 +6,7 @@
 
 use crate::framework::itest;
 use godot::builtin::varray;
+use godot::engine::global::{MouseButtonMask, PropertyUsageFlags};
 use godot::engine::input::CursorShape;
 use godot::engine::mesh::PrimitiveType;
 use godot::engine::{time, ArrayMesh};
@@ -43,6 +44,47 @@ fn enum_equality() {
     // );
 }
 
+#[itest]
+fn enum_try_from() {
+    assert_eq!(CursorShape::try_from(3), Ok(CursorShape::CURSOR_CROSS));
+    assert_eq!(CursorShape::try_from(16), Ok(CursorShape::CURSOR_HELP));
+    assert!(CursorShape::try_from(-1).is_err());
+    assert!(CursorShape::try_from(9999).is_err());
+    assert!(CursorShape::try_from(i64::MAX).is_err());
+}
+
+#[itest]
+fn enum_exhaustive_match() {
+    fn quarter(month: time::Month) -> u8 {
+        use time::Month::*;
+
+        // No wildcard arm: adding an enumerator would fail to compile.
+        match month {
+            MONTH_JANUARY | MONTH_FEBRUARY | MONTH_MARCH => 1,
+            MONTH_APRIL | MONTH_MAY | MONTH_JUNE => 2,
+            MONTH_JULY | MONTH_AUGUST | MONTH_SEPTEMBER => 3,
+            MONTH_OCTOBER | MONTH_NOVEMBER | MONTH_DECEMBER => 4,
+            Unknown(_) => 0,
+        }
+    }
+
+    assert_eq!(quarter(time::Month::MONTH_FEBRUARY), 1);
+    assert_eq!(quarter(time::Month::MONTH_NOVEMBER), 4);
+}
+
+#[itest]
+fn enum_unknown_ord() {
+    use godot::obj::EngineEnum;
+
+    // Known ordinals are never mapped to `Unknown`.
+    assert_eq!(CursorShape::from_ord(3), CursorShape::CURSOR_CROSS);
+    assert_eq!(CursorShape::try_from_ord(9999), None);
+
+    let unknown = CursorShape::from_ord(9999);
+    assert_eq!(unknown, CursorShape::Unknown(9999));
+    assert_eq!(unknown.ord(), 9999);
+}
+
 #[itest]
 fn enum_hash() {
     let mut months = HashSet::new();
@@ -62,6 +104,43 @@ fn enum_hash() {
     assert_eq!(months.len(), 12);
 }
 
+#[itest]
+fn bitfield_ops() {
+    let mut mask =
+        MouseButtonMask::MOUSE_BUTTON_MASK_LEFT | MouseButtonMask::MOUSE_BUTTON_MASK_MIDDLE;
+    assert!(mask.contains(MouseButtonMask::MOUSE_BUTTON_MASK_LEFT));
+    assert!(!mask.contains(MouseButtonMask::MOUSE_BUTTON_MASK_RIGHT));
+    assert!(!mask.contains(
+        MouseButtonMask::MOUSE_BUTTON_MASK_LEFT | MouseButtonMask::MOUSE_BUTTON_MASK_RIGHT
+    ));
+
+    mask &= MouseButtonMask::MOUSE_BUTTON_MASK_MIDDLE;
+    assert_eq!(mask, MouseButtonMask::MOUSE_BUTTON_MASK_MIDDLE);
+
+    mask |= MouseButtonMask::MOUSE_BUTTON_MASK_RIGHT;
+    assert!(mask.contains(MouseButtonMask::MOUSE_BUTTON_MASK_RIGHT));
+    assert!(!mask.is_empty());
+    assert!(MouseButtonMask::default().is_empty());
+}
+
+#[itest]
+fn bitfield_debug() {
+    let flags =
+        PropertyUsageFlags::PROPERTY_USAGE_STORAGE | PropertyUsageFlags::PROPERTY_USAGE_EDITOR;
+
+    // Exact match with predefined combination.
+    assert_eq!(
+        format!("{flags:?}"),
+        "PropertyUsageFlags { PROPERTY_USAGE_DEFAULT }"
+    );
+
+    let mask = MouseButtonMask::MOUSE_BUTTON_MASK_LEFT | MouseButtonMask::MOUSE_BUTTON_MASK_MIDDLE;
+    assert_eq!(
+        format!("{mask:?}"),
+        "MouseButtonMask { MOUSE_BUTTON_MASK_LEFT | MOUSE_BUTTON_MASK_MIDDLE }"
+    );
+}
+
 // Testing https://github.com/godot-rust/gdext/issues/335
 // This fails upon calling the function, we dont actually need to make a good call.
 #[itest]
@@ -5,7 +5,14 @@
  */
 
 mod base_test;
+mod deferred_test;
 mod object_test;
 mod property_test;
+mod script_instance_test;
 mod singleton_test;
+mod thread_handle_test;
 mod virtual_methods_test;
+
+pub(crate) use virtual_methods_test::{
+    register_movie_writer, select_main_loop, unregister_movie_writer,
+};
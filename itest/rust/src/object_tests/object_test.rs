@@ -9,11 +9,14 @@ use std::rc::Rc;
 
 use godot::bind::{godot_api, GodotClass};
 use godot::builtin::{
-    FromVariant, GodotString, StringName, ToVariant, Variant, VariantConversionError, Vector3,
+    FromVariant, GodotString, StringName, ToVariant, Variant, VariantConversionError, VariantType,
+    Vector3,
 };
 use godot::engine::{
-    file_access, Area2D, Camera3D, FileAccess, Node, Node3D, Object, RefCounted, RefCountedVirtual,
+    file_access, Area2D, Camera3D, FileAccess, Node, Node3D, Object, ObjectExt, RefCounted,
+    RefCountedVirtual,
 };
+use godot::error::CallErrorKind;
 use godot::obj::{Base, Gd, InstanceId};
 use godot::obj::{Inherits, Share};
 use godot::sys::{self, GodotFfi};
@@ -36,6 +39,21 @@ fn object_construct_value() {
     assert_eq!(obj.bind().value, 222);
 }
 
+#[itest]
+fn object_construct_after_destroy() {
+    // Storage of destroyed instances is recycled; new instances must not observe previous state.
+    for i in 0..300 {
+        let obj = Gd::new(ObjPayload { value: i });
+        assert_eq!(obj.bind().value, i);
+    }
+
+    let objs: Vec<_> = (0..10).map(|i| Gd::new(ObjPayload { value: i })).collect();
+    drop(objs);
+
+    let obj = Gd::<ObjPayload>::new_default();
+    assert_eq!(obj.bind().value, 111);
+}
+
 // TODO(#23): DerefMut on Gd pointer may be used to break subtyping relations
 #[itest(skip)]
 fn object_subtype_swap() {
@@ -100,6 +118,21 @@ fn object_user_roundtrip_write() {
     assert_eq!(obj2.bind().value, value);
 } // drop
 
+#[itest]
+fn object_user_bind_unchecked() {
+    let mut obj: Gd<ObjPayload> = Gd::new(ObjPayload { value: 11 });
+
+    // SAFETY: no other references to the instance exist meanwhile.
+    unsafe {
+        obj.bind_mut_unchecked().value = 22;
+        assert_eq!(obj.bind_unchecked().value, 22);
+    }
+
+    // Checked binds still work afterwards.
+    obj.bind_mut().value = 33;
+    assert_eq!(obj.bind().value, 33);
+}
+
 #[itest]
 fn object_engine_roundtrip() {
     let pos = Vector3::new(1.0, 2.0, 3.0);
@@ -651,7 +684,7 @@ fn object_get_scene_tree(ctx: &TestContext) {
     let node = Node3D::new_alloc();
 
     let mut tree = ctx.scene_tree.share();
-    tree.add_child(node.upcast());
+    tree.add_child(node);
 
     let count = tree.get_child_count();
     assert_eq!(count, 1);
@@ -840,3 +873,106 @@ fn double_use_reference() {
     double_use.free();
     emitter.free();
 }
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[itest]
+fn object_try_call() {
+    let mut node = Node3D::new_alloc();
+    let position = Vector3::new(1.0, 2.0, 3.0);
+
+    let result = node.try_call("set_position", &[position.to_variant()]);
+    assert_eq!(result, Ok(Variant::nil()));
+
+    let result = node.try_call("get_position", &[]);
+    assert_eq!(result, Ok(position.to_variant()));
+
+    let err = node.try_call("gut_position", &[]).unwrap_err();
+    assert_eq!(err.method_name(), "gut_position");
+    assert_eq!(err.kind(), CallErrorKind::InvalidMethod);
+
+    let err = node.try_call("set_position", &[]).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        CallErrorKind::TooFewArguments {
+            expected: 1,
+            actual: 0
+        }
+    );
+
+    let err = node
+        .try_call("set_position", &[77.to_variant()])
+        .unwrap_err();
+    assert_eq!(
+        err.kind(),
+        CallErrorKind::InvalidArgument {
+            index: 0,
+            expected: VariantType::Vector3,
+            actual: VariantType::Int,
+        }
+    );
+
+    node.free();
+}
+
+#[itest]
+fn object_try_call_user() {
+    let mut obj: Gd<DoubleUse> = Gd::new_default();
+
+    assert_eq!(obj.try_call("use_1", &[]), Ok(Variant::nil()));
+    assert!(obj.bind().used.get());
+
+    let err = obj.try_call("use_2", &[]).unwrap_err();
+    assert_eq!(err.kind(), CallErrorKind::InvalidMethod);
+
+    obj.free();
+}
+
+#[itest]
+fn object_try_call_deferred() {
+    let mut node = Node3D::new_alloc();
+
+    assert_eq!(node.try_call_deferred("get_position", &[]), Ok(()));
+
+    let err = node.try_call_deferred("gut_position", &[]).unwrap_err();
+    assert_eq!(err.kind(), CallErrorKind::InvalidMethod);
+
+    // Pending deferred call is dropped by Godot once the object is freed.
+    node.free();
+}
+
+#[itest]
+fn object_try_set() {
+    let mut node = Node3D::new_alloc();
+    let position = Vector3::new(1.0, 2.0, 3.0);
+
+    assert_eq!(node.try_set("position", position.to_variant()), Ok(()));
+    assert_eq!(node.get_position(), position);
+
+    let err = node.try_set("posiiton", position.to_variant()).unwrap_err();
+    assert_eq!(err.method_name(), "posiiton");
+    assert_eq!(err.kind(), CallErrorKind::InvalidProperty);
+
+    node.free();
+}
+
+#[itest]
+fn object_checked_access() {
+    let mut node = Node3D::new_alloc();
+    let mut other = node.share();
+    let id = node.instance_id();
+
+    let position = Vector3::new(1.0, 2.0, 3.0);
+    other.checked_mut().unwrap().set_position(position);
+    assert_eq!(other.checked().map(|n| n.get_position()), Ok(position));
+
+    node.free();
+
+    let err = other.checked().unwrap_err();
+    assert_eq!(err.instance_id(), Some(id));
+    assert_eq!(
+        err.class_name(),
+        <Node3D as godot::obj::GodotClass>::class_name()
+    );
+    assert!(other.checked_mut().is_err());
+}
@@ -368,3 +368,92 @@ fn derive_export() {
         "A:0,B:1,C:2".to_variant()
     );
 }
+
+#[derive(GodotClass)]
+#[class(init)]
+struct ScalarProperties {
+    #[var]
+    int_val: i64,
+
+    #[var]
+    small_int_val: i32,
+
+    #[var]
+    float_val: f64,
+
+    #[var]
+    small_float_val: f32,
+
+    #[var]
+    bool_val: bool,
+}
+
+#[godot_api]
+impl ScalarProperties {}
+
+#[itest]
+fn scalar_property_access() {
+    let mut obj = Gd::<ScalarProperties>::new_default();
+
+    // Setters and getters are called by Godot, with variants created on the Rust side.
+    obj.set("int_val".into(), i64::MIN.to_variant());
+    obj.set("small_int_val".into(), (-7).to_variant());
+    obj.set("float_val".into(), f64::INFINITY.to_variant());
+    obj.set("small_float_val".into(), 0.5.to_variant());
+    obj.set("bool_val".into(), true.to_variant());
+
+    {
+        let bound = obj.bind();
+        assert_eq!(bound.int_val, i64::MIN);
+        assert_eq!(bound.small_int_val, -7);
+        assert_eq!(bound.float_val, f64::INFINITY);
+        assert_eq!(bound.small_float_val, 0.5);
+        assert!(bound.bool_val);
+    }
+
+    assert_eq!(obj.get("int_val".into()).to::<i64>(), i64::MIN);
+    assert_eq!(obj.get("small_int_val".into()).to::<i32>(), -7);
+    assert_eq!(obj.get("float_val".into()).to::<f64>(), f64::INFINITY);
+    assert_eq!(obj.get("small_float_val".into()).to::<f32>(), 0.5);
+    assert!(obj.get("bool_val".into()).to::<bool>());
+
+    // Same through the generated accessor methods.
+    assert_eq!(obj.call("get_int_val".into(), &[]), i64::MIN.to_variant());
+    obj.call("set_bool_val".into(), &[false.to_variant()]);
+    assert_eq!(obj.call("get_bool_val".into(), &[]), false.to_variant());
+}
+
+#[itest]
+fn scalar_variant_layout_matches_godot() {
+    // Variants written directly by Rust must be understood by Godot...
+    let int = 42.to_variant();
+    assert_eq!(int.get_type(), VariantType::Int);
+    assert_eq!(int.stringify(), "42".into());
+
+    let float = (-2.5).to_variant();
+    assert_eq!(float.get_type(), VariantType::Float);
+    assert_eq!(float.stringify(), "-2.5".into());
+
+    let boolean = true.to_variant();
+    assert_eq!(boolean.get_type(), VariantType::Bool);
+    assert_eq!(boolean.stringify(), "true".into());
+
+    // ...and variants created by Godot must be read correctly.
+    let sum = int.evaluate(&8.to_variant(), VariantOperator::Add).unwrap();
+    assert_eq!(sum.to::<i64>(), 50);
+
+    let product = float
+        .evaluate(&4.0.to_variant(), VariantOperator::Multiply)
+        .unwrap();
+    assert_eq!(product.to::<f64>(), -10.0);
+
+    let negated = boolean.evaluate(&true.to_variant(), VariantOperator::Xor);
+    assert_eq!(negated.map(|v| v.to::<bool>()), Some(false));
+
+    // Other types are rejected without conversion.
+    assert_eq!(float.try_to::<i64>(), Err(VariantConversionError::BadType));
+    assert_eq!(
+        GodotString::from("1").to_variant().try_to::<bool>(),
+        Err(VariantConversionError::BadType)
+    );
+}
@@ -0,0 +1,211 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::Cell;
+use std::ffi::c_void;
+use std::rc::Rc;
+
+use godot::bind::{godot_api, GodotClass};
+use godot::builtin::meta::PropertyInfo;
+use godot::builtin::{GodotString, StringName, ToVariant, Variant, VariantType};
+use godot::engine::{
+    Engine, Object, Script, ScriptExtension, ScriptExtensionVirtual, ScriptLanguage,
+};
+use godot::obj::{create_script_instance, Base, Gd, ScriptInstance, ScriptMethodInfo, Share};
+use godot::sys;
+
+use crate::framework::{expect_panic, itest};
+
+#[derive(GodotClass)]
+#[class(init, base=ScriptExtension)]
+struct TestScript {
+    dropped: Option<Rc<Cell<bool>>>,
+
+    #[base]
+    base: Base<ScriptExtension>,
+}
+
+#[godot_api]
+impl ScriptExtensionVirtual for TestScript {
+    fn can_instantiate(&self) -> bool {
+        true
+    }
+
+    unsafe fn instance_create(&self, for_object: Gd<Object>) -> *mut c_void {
+        create_script_instance(TestInstance {
+            script: self.base.share().upcast(),
+            owner: for_object,
+            health: 100,
+            dropped: self.dropped.clone(),
+        })
+    }
+}
+
+struct TestInstance {
+    script: Gd<Script>,
+    owner: Gd<Object>,
+    health: i64,
+    dropped: Option<Rc<Cell<bool>>>,
+}
+
+impl ScriptInstance for TestInstance {
+    fn set(&mut self, name: StringName, value: &Variant) -> bool {
+        if name.to_string() != "health" {
+            return false;
+        }
+
+        self.health = value.to();
+        true
+    }
+
+    fn get(&self, name: StringName) -> Option<Variant> {
+        (name.to_string() == "health").then(|| self.health.to_variant())
+    }
+
+    fn get_property_list(&self) -> Vec<PropertyInfo> {
+        Vec::new()
+    }
+
+    fn get_method_list(&self) -> Vec<ScriptMethodInfo> {
+        Vec::new()
+    }
+
+    fn call(
+        &mut self,
+        method: StringName,
+        args: &[&Variant],
+    ) -> Result<Variant, sys::GDExtensionCallErrorType> {
+        match method.to_string().as_str() {
+            "double" => Ok((args[0].to::<i64>() * 2).to_variant()),
+            "panic" => panic!("script method panicked"),
+
+            // Accesses the instance while `call()` still borrows it.
+            "reenter" => Ok(self.owner.get("health".into())),
+
+            _ => Err(sys::GDEXTENSION_CALL_ERROR_INVALID_METHOD),
+        }
+    }
+
+    fn is_placeholder(&self) -> bool {
+        false
+    }
+
+    fn has_method(&self, method: StringName) -> bool {
+        matches!(method.to_string().as_str(), "double" | "panic" | "reenter")
+    }
+
+    fn get_script(&self) -> &Gd<Script> {
+        &self.script
+    }
+
+    fn get_property_type(&self, name: StringName) -> Option<VariantType> {
+        (name.to_string() == "health").then_some(VariantType::Int)
+    }
+
+    fn to_string(&self) -> Option<GodotString> {
+        Some(format!("TestInstance(health={})", self.health).into())
+    }
+
+    fn get_language(&self) -> Gd<ScriptLanguage> {
+        Engine::singleton()
+            .get_script_language(0)
+            .expect("at least one script language")
+    }
+}
+
+impl Drop for TestInstance {
+    fn drop(&mut self) {
+        if let Some(dropped) = &self.dropped {
+            dropped.set(true);
+        }
+    }
+}
+
+fn scripted_object(dropped: Option<Rc<Cell<bool>>>) -> Gd<Object> {
+    let mut script = Gd::<TestScript>::new_default();
+    script.bind_mut().dropped = dropped;
+
+    let mut object = Object::new_alloc();
+    object.set_script(script.to_variant());
+    object
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[itest]
+fn script_instance_get_set() {
+    let mut object = scripted_object(None);
+    assert_eq!(object.get("health".into()), 100.to_variant());
+
+    object.set("health".into(), 42.to_variant());
+    assert_eq!(object.get("health".into()), 42.to_variant());
+
+    // Unknown properties are not handled by the script.
+    assert_eq!(object.get("mana".into()), Variant::nil());
+
+    object.free();
+}
+
+#[itest]
+fn script_instance_call() {
+    let mut object = scripted_object(None);
+    assert!(object.has_method("double".into()));
+
+    let result = object.call("double".into(), &[21.to_variant()]);
+    assert_eq!(result, 42.to_variant());
+
+    // Methods not handled by the script fall back to the object's class.
+    let class = object.call("get_class".into(), &[]);
+    assert_eq!(class, "Object".to_variant());
+
+    object.free();
+}
+
+#[itest]
+fn script_instance_to_string() {
+    let mut object = scripted_object(None);
+    object.set("health".into(), 7.to_variant());
+
+    assert_eq!(object.to_string(), "TestInstance(health=7)");
+
+    object.free();
+}
+
+#[itest]
+fn script_instance_dropped_with_object() {
+    let dropped = Rc::new(Cell::new(false));
+    let object = scripted_object(Some(dropped.clone()));
+    assert!(!dropped.get());
+
+    object.free();
+    assert!(dropped.get());
+}
+
+#[itest]
+fn script_instance_panic_fails_call() {
+    let object = scripted_object(None);
+
+    let mut panicking = object.share();
+    expect_panic("script method panics", move || {
+        panicking.call("panic".into(), &[]);
+    });
+
+    // The instance is still usable afterwards.
+    let mut object = object;
+    assert_eq!(object.get("health".into()), 100.to_variant());
+    object.free();
+}
+
+#[itest]
+fn script_instance_reentrant_access_fails() {
+    let mut object = scripted_object(None);
+
+    // The nested `get` cannot borrow the instance; it fails instead of aborting, and Godot falls back to a nil value.
+    let result = object.call("reenter".into(), &[]);
+    assert_eq!(result, Variant::nil());
+
+    object.free();
+}
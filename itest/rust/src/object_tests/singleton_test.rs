@@ -5,9 +5,12 @@
  */
 
 use crate::framework::itest;
-use godot::builtin::GodotString;
-use godot::engine::{Input, Os};
-use godot::obj::Gd;
+use godot::bind::GodotClass;
+use godot::builtin::{GodotString, StringName};
+use godot::engine::{
+    editor_interface, register_singleton, unregister_singleton, Engine, Input, Object, Os,
+};
+use godot::obj::{Base, Gd};
 
 #[itest]
 fn singleton_is_unique() {
@@ -42,3 +45,37 @@ fn singleton_is_operational() {
     let read_value = os.get_environment(key);
     assert_eq!(read_value, value);
 }
+
+#[derive(GodotClass)]
+#[class(init, base=Object)]
+struct RustSingleton {
+    #[base]
+    base: Base<Object>,
+}
+
+#[itest]
+fn singleton_register_rust_class() {
+    let name = StringName::from("GdextRustSingleton");
+    let instance = Gd::<RustSingleton>::new_default();
+    let id = instance.instance_id();
+
+    register_singleton(name.clone(), instance);
+
+    let engine = Engine::singleton();
+    assert!(engine.has_singleton(name.clone()));
+    let fetched = engine
+        .get_singleton(name.clone())
+        .expect("registered singleton");
+    assert_eq!(fetched.instance_id(), id);
+
+    assert!(unregister_singleton(name.clone()));
+    assert!(!engine.has_singleton(name.clone()));
+    assert!(Gd::<Object>::try_from_instance_id(id).is_none());
+
+    assert!(!unregister_singleton(name));
+}
+
+#[itest]
+fn singleton_editor_interface_outside_editor() {
+    assert!(editor_interface().is_none());
+}
@@ -0,0 +1,40 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::thread;
+
+use godot::engine::Node;
+use godot::obj::{Gd, Share, ThreadHandle};
+
+use crate::framework::itest;
+
+#[itest]
+fn thread_handle_get() {
+    let node = Node::new_alloc();
+    let handle = ThreadHandle::new(&node);
+    assert_eq!(handle.instance_id(), node.instance_id());
+
+    // Handles are Send, and survive a round trip through another thread.
+    let handle = thread::spawn(move || handle).join().unwrap();
+    assert_eq!(handle.get(), Some(node.share()));
+
+    node.free();
+    assert_eq!(handle.get(), None);
+}
+
+#[itest]
+fn thread_handle_get_off_main_thread() {
+    let node = Node::new_alloc();
+    let handle = ThreadHandle::new(&node);
+
+    let result = thread::spawn(move || {
+        let _: Option<Gd<Node>> = handle.get();
+    })
+    .join();
+    assert!(result.is_err(), "get() must panic off the main thread");
+
+    node.free();
+}
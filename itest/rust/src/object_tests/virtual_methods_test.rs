@@ -6,22 +6,51 @@
 
 #![allow(dead_code)]
 
-use crate::framework::{itest, TestContext};
+use std::cell::Cell;
+use std::sync::{Arc, Mutex};
+
+use crate::framework::{itest, skip, TestContext};
 
 use godot::bind::{godot_api, GodotClass};
 use godot::builtin::{
-    real, varray, Color, GodotString, PackedByteArray, PackedColorArray, PackedFloat32Array,
-    PackedInt32Array, PackedStringArray, PackedVector2Array, PackedVector3Array, RealConv,
-    StringName, ToVariant, Variant, VariantArray, Vector2, Vector3,
+    dict, real, varray, Array, Color, Dictionary, GodotString, PackedByteArray, PackedColorArray,
+    PackedFloat32Array, PackedInt32Array, PackedStringArray, PackedVector2Array,
+    PackedVector3Array, RealConv, StringName, ToVariant, Variant, VariantArray, Vector2, Vector2i,
+    Vector3,
 };
+use godot::engine::audio_server::SpeakerMode;
+use godot::engine::editor_plugin::DockSlot;
+use godot::engine::global;
+use godot::engine::native::AudioFrame;
 use godot::engine::notify::NodeNotification;
 use godot::engine::resource_loader::CacheMode;
 use godot::engine::{
-    BoxMesh, InputEvent, InputEventAction, Node, Node2D, Node2DVirtual, NodeVirtual, PrimitiveMesh,
-    PrimitiveMeshVirtual, RefCounted, RefCountedVirtual, ResourceFormatLoader,
-    ResourceFormatLoaderVirtual, ResourceLoader, RigidBody2DVirtual, Viewport, Window,
+    audio_frames_mut, AudioStreamPlayback, AudioStreamPlaybackVirtual, BoxMesh, ClassDb, Control,
+    EditorDebuggerPlugin, EditorDebuggerPluginVirtual, EditorExportPlugin,
+    EditorExportPluginVirtual, EditorImportPlugin, EditorImportPluginVirtual,
+    EditorInspectorPlugin, EditorInspectorPluginVirtual, EditorNode3DGizmo,
+    EditorNode3DGizmoPlugin, EditorNode3DGizmoPluginVirtual, EditorNode3DGizmoVirtual,
+    EditorPlugin, EditorPluginExt, EditorPluginVirtual, EditorProperty, EditorPropertyVirtual,
+    EditorResourcePreviewGenerator, EditorResourcePreviewGeneratorVirtual,
+    EditorScenePostImportPlugin, EditorScenePostImportPluginVirtual, EditorSyntaxHighlighter,
+    EditorSyntaxHighlighterVirtual, EditorTranslationParserPlugin,
+    EditorTranslationParserPluginVirtual, EditorUndoRedoManagerExt, Engine, EngineDebugger,
+    InputEvent, InputEventAction, MainLoop, MainLoopVirtual, MovieWriter, MovieWriterVirtual,
+    MultiplayerPeer, MultiplayerPeerExtension, MultiplayerPeerExtensionVirtual, Node, Node2D,
+    Node2DVirtual, Node3D, NodeVirtual, Object, Os, OsExt, PackedScene, PhysicsServer3DExtension,
+    PrimitiveMesh, PrimitiveMeshVirtual, ProjectSettings, RefCounted, RefCountedVirtual, Resource,
+    ResourceFormatLoader, ResourceFormatLoaderVirtual, ResourceFormatSaver,
+    ResourceFormatSaverVirtual, ResourceLoader, ResourceSaver, RigidBody2DVirtual, SceneTree,
+    SceneTreeVirtual, StandardMaterial3D, StreamPeer, StreamPeerExtension,
+    StreamPeerExtensionVirtual, SyntaxHighlighter, TextServer, TextServerExtension,
+    TextServerExtensionVirtual, Texture2D, Translation, TranslationVirtual, VideoStream,
+    VideoStreamPlayback, VideoStreamPlaybackVirtual, VideoStreamPlayer, VideoStreamVirtual,
+    Viewport, WebRtcDataChannel, WebRtcDataChannelExtension, WebRtcDataChannelExtensionVirtual,
+    Window,
 };
-use godot::obj::{Base, Gd, Share};
+use godot::init::InitLevel;
+use godot::log::godot_print;
+use godot::obj::{Base, Gd, GodotClass, InstanceId, Share};
 use godot::private::class_macros::assert_eq_approx;
 
 /// Simple class, that deliberately has no constructor accessible from GDScript
@@ -34,6 +63,419 @@ struct WithoutInit {
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 
+// Editor classes are only registered if Godot loads its editor classes (see `editor_classes_loaded()`). The plugin is never
+// added to an editor in tests, so this mostly checks that the trait is generated.
+#[derive(GodotClass)]
+#[class(init, tool, base=EditorPlugin)]
+struct EditorPluginVirtualTest {
+    dock: Option<Gd<Control>>,
+
+    #[base]
+    base: Base<EditorPlugin>,
+}
+
+#[godot_api]
+impl EditorPluginVirtualTest {
+    #[func]
+    fn on_tool_menu(&mut self) {}
+
+    #[func]
+    fn rename_with_undo(&mut self, node: Gd<Node>, new_name: GodotString) {
+        let mut undo_redo = self.base.get_undo_redo().expect("undo/redo manager");
+        undo_redo
+            .action("Rename node")
+            .do_property(&node, "name", new_name)
+            .undo_property(&node, "name", node.get_name())
+            .commit();
+    }
+}
+
+#[godot_api]
+impl EditorPluginVirtual for EditorPluginVirtualTest {
+    fn enter_tree(&mut self) {
+        self.base
+            .add_tool_menu_method("Virtual test", "on_tool_menu");
+        self.base.add_command_palette_method(
+            "Virtual test",
+            "virtual_test/on_tool_menu",
+            "on_tool_menu",
+        );
+
+        let mut clicks = 0;
+        self.base
+            .add_tool_menu_fn("Virtual test (closure)", move || {
+                clicks += 1;
+                godot_print!("Clicked {clicks} times");
+            });
+        self.base
+            .add_command_palette_fn("Virtual test (closure)", "virtual_test/closure", || {
+                godot_print!("Command invoked")
+            });
+
+        let dock = Control::new_alloc();
+        self.base
+            .add_dock(DockSlot::DOCK_SLOT_RIGHT_UL, "Virtual test", dock.share());
+        self.dock = Some(dock);
+    }
+
+    fn exit_tree(&mut self) {
+        self.base.remove_tool_menu_item("Virtual test".into());
+        self.base
+            .remove_command_palette_method("virtual_test/on_tool_menu");
+
+        self.base.remove_tool_menu_fn("Virtual test (closure)");
+        self.base
+            .remove_command_palette_method("virtual_test/closure");
+
+        if let Some(dock) = self.dock.take() {
+            self.base.remove_dock(dock);
+        }
+    }
+
+    fn get_plugin_name(&self) -> GodotString {
+        "EditorPluginVirtualTest".into()
+    }
+
+    fn has_main_screen(&self) -> bool {
+        false
+    }
+}
+
+// Exports only run in an editor process, so test_editor_export_plugin() exports a project in a separate Godot process. There, the
+// plugin writes the hooks it saw to `log_path`.
+#[derive(GodotClass)]
+#[class(init, tool, base=EditorExportPlugin)]
+struct EditorExportPluginVirtualTest {
+    #[base]
+    base: Base<EditorExportPlugin>,
+    #[var]
+    log_path: GodotString,
+    log: Vec<String>,
+}
+
+#[godot_api]
+impl EditorExportPluginVirtualTest {}
+
+#[godot_api]
+impl EditorExportPluginVirtual for EditorExportPluginVirtualTest {
+    fn export_begin(
+        &mut self,
+        _features: PackedStringArray,
+        _is_debug: bool,
+        _path: GodotString,
+        _flags: u32,
+    ) {
+        self.log.push("begin".to_string());
+    }
+
+    fn export_file(&mut self, path: GodotString, _type: GodotString, _features: PackedStringArray) {
+        self.log.push(format!("file {path}"));
+    }
+
+    fn export_end(&mut self) {
+        self.log.push("end".to_string());
+
+        let log_path = self.log_path.to_string();
+        if !log_path.is_empty() {
+            std::fs::write(global_path(&log_path), self.log.join("\n")).expect("write export log");
+        }
+        self.log.clear();
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, tool, base=EditorInspectorPlugin)]
+struct EditorInspectorPluginVirtualTest {
+    #[base]
+    base: Base<EditorInspectorPlugin>,
+}
+
+#[godot_api]
+impl EditorInspectorPluginVirtual for EditorInspectorPluginVirtualTest {
+    fn can_handle(&self, object: Gd<Object>) -> bool {
+        object.is_class("Node".into())
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, tool, base=EditorProperty)]
+struct EditorPropertyVirtualTest {
+    #[base]
+    base: Base<EditorProperty>,
+    updates: u32,
+}
+
+#[godot_api]
+impl EditorPropertyVirtual for EditorPropertyVirtualTest {
+    fn update_property(&mut self) {
+        self.updates += 1;
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, tool, base=EditorNode3DGizmoPlugin)]
+struct EditorNode3DGizmoPluginVirtualTest {
+    #[base]
+    base: Base<EditorNode3DGizmoPlugin>,
+    name_queries: Cell<u32>,
+}
+
+#[godot_api]
+impl EditorNode3DGizmoPluginVirtual for EditorNode3DGizmoPluginVirtualTest {
+    fn get_gizmo_name(&self) -> GodotString {
+        self.name_queries.set(self.name_queries.get() + 1);
+        "VirtualTestGizmo".into()
+    }
+
+    fn has_gizmo(&self, for_node_3d: Gd<Node3D>) -> bool {
+        for_node_3d.is_inside_tree()
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, tool, base=EditorNode3DGizmo)]
+struct EditorNode3DGizmoVirtualTest {
+    #[base]
+    base: Base<EditorNode3DGizmo>,
+    material: Option<Gd<StandardMaterial3D>>,
+    redraws: u32,
+}
+
+#[godot_api]
+impl EditorNode3DGizmoVirtual for EditorNode3DGizmoVirtualTest {
+    fn redraw(&mut self) {
+        self.redraws += 1;
+
+        let material = self.material.as_ref().expect("material set before redraw");
+        let lines = PackedVector3Array::from(&[Vector3::ZERO, Vector3::UP]);
+        self.base.clear();
+        self.base.add_lines(lines, material);
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, tool, base=EditorSyntaxHighlighter)]
+struct EditorSyntaxHighlighterVirtualTest {
+    #[base]
+    base: Base<EditorSyntaxHighlighter>,
+    cache_clears: u32,
+}
+
+#[godot_api]
+impl EditorSyntaxHighlighterVirtual for EditorSyntaxHighlighterVirtualTest {
+    fn get_name(&self) -> GodotString {
+        "VirtualTestDsl".into()
+    }
+
+    fn get_supported_languages(&self) -> PackedStringArray {
+        PackedStringArray::from(&["VirtualTestDsl".into()])
+    }
+
+    fn get_line_syntax_highlighting(&self, _line: i32) -> Dictionary {
+        // Column 0 starts in red.
+        dict! { 0: dict! { "color": Color::from_rgb(1.0, 0.0, 0.0) } }
+    }
+
+    fn clear_highlighting_cache(&mut self) {
+        self.cache_clears += 1;
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, tool, base=EditorDebuggerPlugin)]
+struct EditorDebuggerPluginVirtualTest {
+    #[base]
+    base: Base<EditorDebuggerPlugin>,
+    messages: Vec<GodotString>,
+    sessions: Vec<i32>,
+}
+
+#[godot_api]
+impl EditorDebuggerPluginVirtual for EditorDebuggerPluginVirtualTest {
+    fn setup_session(&mut self, session_id: i32) {
+        self.sessions.push(session_id);
+    }
+
+    fn has_capture(&self, capture: GodotString) -> bool {
+        capture == GodotString::from("virtual_test")
+    }
+
+    fn capture(&mut self, message: GodotString, _data: VariantArray, _session_id: i32) -> bool {
+        self.messages.push(message);
+        true
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, tool, base=EditorTranslationParserPlugin)]
+struct EditorTranslationParserPluginVirtualTest {
+    #[base]
+    base: Base<EditorTranslationParserPlugin>,
+}
+
+#[godot_api]
+impl EditorTranslationParserPluginVirtual for EditorTranslationParserPluginVirtualTest {
+    fn get_recognized_extensions(&self) -> PackedStringArray {
+        PackedStringArray::from(&["gdextdialog".into()])
+    }
+
+    fn parse_file(
+        &mut self,
+        _path: GodotString,
+        mut msgids: VariantArray,
+        _msgids_context_plural: VariantArray,
+    ) {
+        msgids.push("Hello".to_variant());
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, tool, base=EditorScenePostImportPlugin)]
+struct EditorScenePostImportPluginVirtualTest {
+    #[base]
+    base: Base<EditorScenePostImportPlugin>,
+}
+
+#[godot_api]
+impl EditorScenePostImportPluginVirtual for EditorScenePostImportPluginVirtualTest {
+    fn post_process(&mut self, mut scene: Gd<Node>) {
+        scene.set_name("Imported".into());
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, base=Resource)]
+struct PreviewResourceTest {
+    #[base]
+    base: Base<Resource>,
+}
+
+#[derive(GodotClass)]
+#[class(init, tool, base=EditorResourcePreviewGenerator)]
+struct EditorResourcePreviewGeneratorVirtualTest {
+    #[base]
+    base: Base<EditorResourcePreviewGenerator>,
+    /// Set by the previewer thread, so it is shared outside the object.
+    generated_size: Arc<Mutex<Option<Vector2i>>>,
+}
+
+#[godot_api]
+impl EditorResourcePreviewGeneratorVirtualTest {
+    #[func]
+    fn on_preview_ready(
+        &mut self,
+        _path: GodotString,
+        _preview: Variant,
+        _thumbnail: Variant,
+        _userdata: Variant,
+    ) {
+    }
+}
+
+#[godot_api]
+impl EditorResourcePreviewGeneratorVirtual for EditorResourcePreviewGeneratorVirtualTest {
+    fn handles(&self, type_: GodotString) -> bool {
+        type_ == GodotString::from("PreviewResourceTest")
+    }
+
+    fn generate(
+        &self,
+        _resource: Gd<Resource>,
+        size: Vector2i,
+        _metadata: Dictionary,
+    ) -> Option<Gd<Texture2D>> {
+        *self.generated_size.lock().unwrap() = Some(size);
+        None
+    }
+
+    fn generate_small_preview_automatically(&self) -> bool {
+        true
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, tool, base=EditorImportPlugin)]
+struct EditorImportPluginVirtualTest {
+    #[base]
+    base: Base<EditorImportPlugin>,
+    /// Options of each `_import()` call.
+    imports: Vec<Dictionary>,
+}
+
+#[godot_api]
+impl EditorImportPluginVirtual for EditorImportPluginVirtualTest {
+    fn get_importer_name(&self) -> GodotString {
+        "gdext.test_importer".into()
+    }
+
+    fn get_visible_name(&self) -> GodotString {
+        "gdext test importer".into()
+    }
+
+    fn get_recognized_extensions(&self) -> PackedStringArray {
+        PackedStringArray::from(&["gdexttest".into()])
+    }
+
+    fn get_save_extension(&self) -> GodotString {
+        "res".into()
+    }
+
+    fn get_resource_type(&self) -> GodotString {
+        "Resource".into()
+    }
+
+    fn get_priority(&self) -> f64 {
+        1.0
+    }
+
+    fn get_import_order(&self) -> i32 {
+        0
+    }
+
+    fn get_preset_count(&self) -> i32 {
+        1
+    }
+
+    fn get_preset_name(&self, _preset_index: i32) -> GodotString {
+        "Default".into()
+    }
+
+    fn get_import_options(&self, _path: GodotString, _preset_index: i32) -> Array<Dictionary> {
+        let mut options = Array::new();
+        options.push(dict! { "name": "greeting", "default_value": "hello" });
+        options
+    }
+
+    fn get_option_visibility(
+        &self,
+        _path: GodotString,
+        _option_name: StringName,
+        _options: Dictionary,
+    ) -> bool {
+        true
+    }
+
+    fn import(
+        &mut self,
+        _source_file: GodotString,
+        save_path: GodotString,
+        options: Dictionary,
+        _platform_variants: Array<GodotString>,
+        _gen_files: Array<GodotString>,
+    ) -> global::Error {
+        let mut resource = Resource::new();
+        resource.set_name(options.get_or_nil("greeting").to::<GodotString>());
+        self.imports.push(options);
+
+        ResourceSaver::singleton()
+            .save_ex(resource)
+            .path(format!("{save_path}.res").into())
+            .done()
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
 #[derive(GodotClass, Debug)]
 #[class(init, base=RefCounted)]
 struct VirtualMethodTest {
@@ -198,6 +640,287 @@ impl ResourceFormatLoaderVirtual for FormatLoaderTest {
     }
 }
 
+#[derive(GodotClass, Debug)]
+#[class(init, base=ResourceFormatSaver)]
+struct FormatSaverTest {
+    #[base]
+    base: Base<ResourceFormatSaver>,
+}
+
+#[godot_api]
+impl ResourceFormatSaverVirtual for FormatSaverTest {
+    fn get_recognized_extensions(&self, _resource: Gd<Resource>) -> PackedStringArray {
+        [GodotString::from("saver_extension")].into_iter().collect()
+    }
+
+    fn recognize(&self, resource: Gd<Resource>) -> bool {
+        resource.try_cast::<BoxMesh>().is_some()
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, base=MultiplayerPeerExtension)]
+struct MultiplayerPeerVirtualTest {
+    #[base]
+    base: Base<MultiplayerPeerExtension>,
+    queued_packets: Vec<PackedByteArray>,
+}
+
+#[godot_api]
+impl MultiplayerPeerExtensionVirtual for MultiplayerPeerVirtualTest {
+    fn get_available_packet_count(&self) -> i32 {
+        self.queued_packets.len() as i32
+    }
+
+    fn get_unique_id(&self) -> i32 {
+        42
+    }
+}
+
+// Custom servers are registered at `Servers` level, since itest loads the library at that level (see `min_level()` in lib.rs).
+#[derive(GodotClass)]
+#[class(init, base=PhysicsServer3DExtension)]
+struct PhysicsServerVirtualTest {
+    #[base]
+    base: Base<PhysicsServer3DExtension>,
+}
+
+#[derive(GodotClass)]
+#[class(init, base=TextServerExtension)]
+struct TextServerVirtualTest {
+    #[base]
+    base: Base<TextServerExtension>,
+}
+
+#[godot_api]
+impl TextServerExtensionVirtual for TextServerVirtualTest {
+    fn get_name(&self) -> GodotString {
+        "gdext test text server".into()
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, base=AudioStreamPlayback)]
+struct AudioStreamPlaybackVirtualTest {
+    #[base]
+    base: Base<AudioStreamPlayback>,
+    amplitude: f32,
+}
+
+#[godot_api]
+impl AudioStreamPlaybackVirtual for AudioStreamPlaybackVirtualTest {
+    unsafe fn mix(&mut self, buffer: *mut AudioFrame, _rate_scale: f32, frames: i32) -> i32 {
+        for frame in audio_frames_mut(buffer, frames) {
+            frame.left = self.amplitude;
+            frame.right = -self.amplitude;
+        }
+
+        frames
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, base=VideoStreamPlayback)]
+struct VideoStreamPlaybackVirtualTest {
+    #[base]
+    base: Base<VideoStreamPlayback>,
+}
+
+#[godot_api]
+impl VideoStreamPlaybackVirtual for VideoStreamPlaybackVirtualTest {
+    fn get_length(&self) -> f64 {
+        12.5
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, base=VideoStream)]
+struct VideoStreamVirtualTest {
+    #[base]
+    base: Base<VideoStream>,
+}
+
+#[godot_api]
+impl VideoStreamVirtual for VideoStreamVirtualTest {
+    fn instantiate_playback(&mut self) -> Option<Gd<VideoStreamPlayback>> {
+        Some(Gd::<VideoStreamPlaybackVirtualTest>::new_default().upcast())
+    }
+}
+
+// Registered through MovieWriter::add_writer() at the Scene level (see register_movie_writer()), so `--write-movie` can pick it.
+#[derive(GodotClass)]
+#[class(init, base=MovieWriter)]
+struct MovieWriterVirtualTest {
+    log_path: Option<String>,
+    #[base]
+    base: Base<MovieWriter>,
+}
+
+impl MovieWriterVirtualTest {
+    fn log(&self, line: &str) {
+        use std::io::Write as _;
+
+        let path = self.log_path.as_ref().expect("write_begin() called first");
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .expect("open movie log");
+        writeln!(file, "{line}").expect("write movie log");
+    }
+}
+
+#[godot_api]
+impl MovieWriterVirtual for MovieWriterVirtualTest {
+    fn get_audio_mix_rate(&self) -> u32 {
+        48000
+    }
+
+    fn get_audio_speaker_mode(&self) -> SpeakerMode {
+        SpeakerMode::SPEAKER_MODE_STEREO
+    }
+
+    fn handles_file(&self, path: GodotString) -> bool {
+        path.to_string().ends_with(".gdextmovie")
+    }
+
+    fn write_begin(
+        &mut self,
+        _movie_size: Vector2i,
+        fps: u32,
+        base_path: GodotString,
+    ) -> global::Error {
+        self.log_path = Some(format!("{base_path}.log"));
+        self.log(&format!(
+            "begin fps={fps} mix_rate={}",
+            self.get_audio_mix_rate()
+        ));
+        global::Error::OK
+    }
+
+    // _write_frame() is not overridden: the headless renderer has no frame image to pass.
+
+    fn write_end(&mut self) {
+        self.log("end");
+    }
+}
+
+static MOVIE_WRITER: Mutex<Option<InstanceId>> = Mutex::new(None);
+
+/// Adds [`MovieWriterVirtualTest`] to Godot's movie writers. Godot keeps a raw pointer, so the writer lives until
+/// [`unregister_movie_writer()`].
+pub(crate) fn register_movie_writer() {
+    let writer = Gd::<MovieWriterVirtualTest>::new_default();
+    *MOVIE_WRITER.lock().unwrap() = Some(writer.instance_id());
+
+    MovieWriter::add_writer(writer);
+}
+
+/// Frees the writer added by [`register_movie_writer()`]. Godot has already ended any movie at this point.
+pub(crate) fn unregister_movie_writer() {
+    if let Some(id) = MOVIE_WRITER.lock().unwrap().take() {
+        Gd::<MovieWriterVirtualTest>::from_instance_id(id).free();
+    }
+}
+
+/// Main loop of child processes started with the user argument `--main-loop-log=<path>` (see select_main_loop()).
+#[derive(GodotClass)]
+#[class(init, base=MainLoop)]
+struct MainLoopVirtualTest {
+    calls: Vec<String>,
+    #[base]
+    base: Base<MainLoop>,
+}
+
+#[godot_api]
+impl MainLoopVirtual for MainLoopVirtualTest {
+    fn initialize(&mut self) {
+        self.calls.push("initialize".to_string());
+    }
+
+    fn process(&mut self, _delta: f64) -> bool {
+        self.calls.push("process".to_string());
+
+        // Returning true ends the main loop.
+        self.calls.len() > 3
+    }
+
+    fn finalize(&mut self) {
+        self.calls.push("finalize".to_string());
+
+        let path = Os::singleton()
+            .user_arg(MAIN_LOOP_LOG_ARG)
+            .expect("main loop selected without log path");
+        std::fs::write(path, self.calls.join("\n")).expect("write main loop log");
+    }
+}
+
+const MAIN_LOOP_LOG_ARG: &str = "main-loop-log";
+
+/// Runs [`MainLoopVirtualTest`] instead of the scene tree, if this process is a child started by test_main_loop_extension().
+pub(crate) fn select_main_loop() {
+    if Os::singleton().user_arg(MAIN_LOOP_LOG_ARG).is_some() {
+        godot::engine::set_main_loop::<MainLoopVirtualTest>();
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, base=SceneTree)]
+struct SceneTreeVirtualTest {
+    #[base]
+    base: Base<SceneTree>,
+}
+
+#[godot_api]
+impl SceneTreeVirtual for SceneTreeVirtualTest {}
+
+#[derive(GodotClass)]
+#[class(init, base=Translation)]
+struct TranslationVirtualTest {
+    #[base]
+    base: Base<Translation>,
+}
+
+#[godot_api]
+impl TranslationVirtual for TranslationVirtualTest {
+    fn get_message(&self, src_message: StringName, _context: StringName) -> StringName {
+        src_message.to_string().to_uppercase().into()
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, base=WebRtcDataChannelExtension)]
+struct WebRtcDataChannelVirtualTest {
+    #[base]
+    base: Base<WebRtcDataChannelExtension>,
+}
+
+#[godot_api]
+impl WebRtcDataChannelExtensionVirtual for WebRtcDataChannelVirtualTest {
+    fn get_label(&self) -> GodotString {
+        "gdext-channel".into()
+    }
+
+    fn get_id(&self) -> i32 {
+        7
+    }
+}
+
+#[derive(GodotClass)]
+#[class(init, base=StreamPeerExtension)]
+struct StreamPeerVirtualTest {
+    #[base]
+    base: Base<StreamPeerExtension>,
+    buffer: Vec<u8>,
+}
+
+#[godot_api]
+impl StreamPeerExtensionVirtual for StreamPeerVirtualTest {
+    fn get_available_bytes(&self) -> i32 {
+        self.buffer.len() as i32
+    }
+}
+
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 
 #[derive(Eq, PartialEq, Debug)]
@@ -240,7 +963,7 @@ fn test_ready(test_context: &TestContext) {
 
     // Add to scene tree
     let mut test_node = test_context.scene_tree.share();
-    test_node.add_child(obj.share().upcast());
+    test_node.add_child(&obj);
 
     // _ready runs, increments implementation_value once.
     assert_eq!(obj.bind().implementation_value, 1);
@@ -254,14 +977,14 @@ fn test_ready_multiple_fires(test_context: &TestContext) {
     let mut test_node = test_context.scene_tree.share();
 
     // Add to scene tree
-    test_node.add_child(obj.share().upcast());
+    test_node.add_child(&obj);
 
     // _ready runs, increments implementation_value once.
     assert_eq!(obj.bind().implementation_value, 1);
 
     // Remove and re-add to scene tree
-    test_node.remove_child(obj.share().upcast());
-    test_node.add_child(obj.share().upcast());
+    test_node.remove_child(&obj);
+    test_node.add_child(&obj);
 
     // _ready does NOT run again, implementation_value should still be 1.
     assert_eq!(obj.bind().implementation_value, 1);
@@ -275,14 +998,14 @@ fn test_ready_request_ready(test_context: &TestContext) {
     let mut test_node = test_context.scene_tree.share();
 
     // Add to scene tree
-    test_node.add_child(obj.share().upcast());
+    test_node.add_child(&obj);
 
     // _ready runs, increments implementation_value once.
     assert_eq!(obj.bind().implementation_value, 1);
 
     // Remove and re-add to scene tree
-    test_node.remove_child(obj.share().upcast());
-    test_node.add_child(obj.share().upcast());
+    test_node.remove_child(&obj);
+    test_node.add_child(&obj);
 
     // _ready does NOT run again, implementation_value should still be 1.
     assert_eq!(obj.bind().implementation_value, 1);
@@ -291,8 +1014,8 @@ fn test_ready_request_ready(test_context: &TestContext) {
     obj.share().upcast::<Node>().request_ready();
 
     // Remove and re-add to scene tree
-    test_node.remove_child(obj.share().upcast());
-    test_node.add_child(obj.share().upcast());
+    test_node.remove_child(&obj);
+    test_node.add_child(&obj);
 
     // _ready runs again since we asked it to; implementation_value should be 2.
     assert_eq!(obj.bind().implementation_value, 2);
@@ -306,15 +1029,15 @@ fn test_tree_enters_exits(test_context: &TestContext) {
     let mut test_node = test_context.scene_tree.share();
 
     // Add to scene tree
-    test_node.add_child(obj.share().upcast());
+    test_node.add_child(&obj);
     assert_eq!(obj.bind().tree_enters, 1);
     assert_eq!(obj.bind().tree_exits, 0);
 
     // Remove and re-add to scene tree
-    test_node.remove_child(obj.share().upcast());
+    test_node.remove_child(&obj);
     assert_eq!(obj.bind().tree_enters, 1);
     assert_eq!(obj.bind().tree_exits, 1);
-    test_node.add_child(obj.share().upcast());
+    test_node.add_child(&obj);
     assert_eq!(obj.bind().tree_enters, 2);
     assert_eq!(obj.bind().tree_exits, 1);
 }
@@ -352,12 +1075,545 @@ fn test_virtual_method_with_return() {
     );
 }
 
+#[itest]
+fn test_virtual_init_level() {
+    assert_eq!(VirtualMethodTest::INIT_LEVEL, Some(InitLevel::Scene));
+    assert_eq!(ReadyVirtualTest::INIT_LEVEL, Some(InitLevel::Scene));
+    assert_eq!(EditorPluginVirtualTest::INIT_LEVEL, Some(InitLevel::Editor));
+    assert_eq!(
+        EditorExportPluginVirtualTest::INIT_LEVEL,
+        Some(InitLevel::Editor)
+    );
+    assert_eq!(
+        EditorImportPluginVirtualTest::INIT_LEVEL,
+        Some(InitLevel::Editor)
+    );
+    assert_eq!(
+        PhysicsServerVirtualTest::INIT_LEVEL,
+        Some(InitLevel::Servers)
+    );
+    assert_eq!(TextServerVirtualTest::INIT_LEVEL, Some(InitLevel::Servers));
+}
+
+#[itest]
+fn test_physics_server_registration() {
+    // Registered at `Servers` level, before Godot creates its servers, so it can be selected as physics engine. Other classes
+    // follow at the level of their base class.
+    use godot::private::registered_level;
+    assert_eq!(
+        registered_level(PhysicsServerVirtualTest::class_name()),
+        Some(InitLevel::Servers)
+    );
+    assert_eq!(
+        registered_level(VirtualMethodTest::class_name()),
+        Some(InitLevel::Scene)
+    );
+
+    let class_db = ClassDb::singleton();
+    let class_name = StringName::from("PhysicsServerVirtualTest");
+    assert_eq!(
+        class_db.get_parent_class(class_name.clone()),
+        StringName::from("PhysicsServer3DExtension")
+    );
+    assert!(class_db.can_instantiate(class_name));
+}
+
+/// Whether Godot has loaded its editor classes, and thus the extension's classes at `InitLevel::Editor`.
+///
+/// This is the case with editor builds of Godot, even when running a project outside the editor.
+fn editor_classes_loaded() -> bool {
+    ClassDb::singleton().class_exists("EditorPlugin".into())
+}
+
+/// Absolute file system path for a `res://` or `user://` path.
+fn global_path(path: &str) -> String {
+    ProjectSettings::singleton()
+        .globalize_path(path.into())
+        .to_string()
+}
+
+const NO_EDITOR_CLASSES: &str = "Godot runs without editor classes";
+const NO_RUNNING_EDITOR: &str = "needs a running editor";
+
+/// Checks that Godot knows the editor class `T` with its base, and creates Rust instances of it through ClassDB.
+fn assert_editor_class_registered<T>()
+where
+    T: GodotClass,
+{
+    assert_eq!(T::INIT_LEVEL, Some(InitLevel::Editor));
+
+    let class_db = ClassDb::singleton();
+    let class_name = T::class_name().to_string_name();
+    let base_name = T::Base::class_name().to_string_name();
+    assert_eq!(class_db.get_parent_class(class_name.clone()), base_name);
+    assert!(class_db.can_instantiate(class_name.clone()));
+
+    let instance = class_db.instantiate(class_name.clone()).to::<Gd<Object>>();
+    assert_eq!(
+        instance.get_class(),
+        GodotString::from(class_name.to_string())
+    );
+
+    // Nodes are manually managed; reference-counted instances are released with `instance`.
+    if let Some(node) = instance.try_cast::<Node>() {
+        node.free();
+    }
+}
+
+#[itest(tags = [slow])]
+fn test_editor_export_plugin() {
+    if !editor_classes_loaded() {
+        return skip(NO_EDITOR_CLASSES);
+    }
+
+    // Export a small project with `--export-pack`, in an editor process that adds the plugin (see export_project_files()).
+    let project_dir = write_child_project("virtual_test_export", export_project_files());
+
+    let pack_path = format!("{project_dir}/virtual_test.pck");
+    let (exit_code, output) = run_godot(&[
+        "--headless",
+        "--path",
+        &project_dir,
+        "--export-pack",
+        "VirtualTest",
+        &pack_path,
+    ]);
+    assert_eq!(exit_code, 0, "export failed: {output:?}");
+
+    // Godot calls _export_begin(), then _export_file() for each exported resource, then _export_end().
+    let log = std::fs::read_to_string(format!("{project_dir}/export_hooks.log"))
+        .unwrap_or_else(|err| panic!("export hooks not called ({err}): {output:?}"));
+    let log = log.lines().collect::<Vec<_>>();
+    assert_eq!(log.first(), Some(&"begin"), "hooks: {log:?}");
+    assert!(log.contains(&"file res://data.tres"), "hooks: {log:?}");
+    assert_eq!(log.last(), Some(&"end"));
+
+    let _ = std::fs::remove_dir_all(&project_dir);
+}
+
+/// Files of the project exported by test_editor_export_plugin(), relative to the project directory.
+fn export_project_files() -> Vec<(&'static str, String)> {
+    let project = r#"config_version=5
+
+[application]
+
+config/name="VirtualTestExport"
+
+[editor_plugins]
+
+enabled=PackedStringArray("res://addons/virtual_test/plugin.cfg")
+"#;
+
+    // "Linux/X11" is also accepted by Godot versions that renamed the platform to "Linux".
+    let export_presets = r#"[preset.0]
+
+name="VirtualTest"
+platform="Linux/X11"
+runnable=false
+custom_features=""
+export_filter="all_resources"
+include_filter=""
+exclude_filter=""
+export_path=""
+encryption_include_filters=""
+encryption_exclude_filters=""
+encrypt_pck=false
+encrypt_directory=false
+"#;
+
+    let plugin_cfg = r#"[plugin]
+
+name="Virtual test"
+description="Adds EditorExportPluginVirtualTest."
+author="gdext"
+version="1.0"
+script="plugin.gd"
+"#;
+
+    let plugin_gd = r#"@tool
+extends EditorPlugin
+
+var export_plugin: EditorExportPlugin
+
+func _enter_tree():
+	export_plugin = ClassDB.instantiate("EditorExportPluginVirtualTest")
+	export_plugin.log_path = "res://export_hooks.log"
+	add_export_plugin(export_plugin)
+
+func _exit_tree():
+	remove_export_plugin(export_plugin)
+"#;
+
+    let data = "[gd_resource type=\"Resource\" format=3]\n\n[resource]\n";
+
+    vec![
+        ("project.godot", project.to_string()),
+        ("export_presets.cfg", export_presets.to_string()),
+        ("addons/virtual_test/plugin.cfg", plugin_cfg.to_string()),
+        ("addons/virtual_test/plugin.gd", plugin_gd.to_string()),
+        ("data.tres", data.to_string()),
+    ]
+}
+
+/// Writes a project that loads this library to `user://<name>`, replacing previous contents. Returns the project's absolute path.
+fn write_child_project(name: &str, files: Vec<(&str, String)>) -> String {
+    // The child process loads this library, with paths relative to the integration test project.
+    let library_dir = global_path("res://");
+    let gdextension = std::fs::read_to_string(global_path("res://itest.gdextension"))
+        .expect("read itest.gdextension")
+        .replace(
+            "\"res://",
+            &format!("\"{}/", library_dir.trim_end_matches('/')),
+        );
+
+    let extension_files = [
+        ("itest.gdextension", gdextension),
+        (
+            ".godot/extension_list.cfg",
+            "res://itest.gdextension\n".to_string(),
+        ),
+    ];
+
+    let project_dir = global_path(&format!("user://{name}"));
+    let _ = std::fs::remove_dir_all(&project_dir);
+    for (file, content) in files.into_iter().chain(extension_files) {
+        let path = std::path::Path::new(&project_dir).join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).expect("create project directory");
+        std::fs::write(path, content).expect("write project file");
+    }
+
+    project_dir
+}
+
+/// Runs the Godot executable of this test run in a child process. Returns its exit code and output (stdout and stderr).
+fn run_godot(args: &[&str]) -> (i64, VariantArray) {
+    let output = VariantArray::new();
+    let exit_code = Os::singleton()
+        .execute_ex(
+            Os::singleton().get_executable_path(),
+            args.iter().copied().map(GodotString::from).collect(),
+        )
+        .output(output.share())
+        .read_stderr(true)
+        .done();
+
+    (exit_code, output)
+}
+
+#[itest]
+fn test_editor_import_plugin() {
+    if !editor_classes_loaded() {
+        return skip(NO_EDITOR_CLASSES);
+    }
+    // Files are imported by the editor's file system.
+    let Some(editor) = godot::engine::editor_interface() else {
+        return skip(NO_RUNNING_EDITOR);
+    };
+
+    let source_path = "res://virtual_test_import.gdexttest";
+    std::fs::write(global_path(source_path), "").expect("write import source");
+
+    // Godot picks the importer by _get_recognized_extensions() and fills in the defaults of _get_import_options().
+    let importer = Gd::<EditorImportPluginVirtualTest>::new_default();
+    let mut editor_plugin = EditorPlugin::new_alloc();
+    editor_plugin.add_import_plugin(&importer);
+
+    let mut file_system = editor
+        .get_resource_filesystem()
+        .expect("editor file system");
+    file_system.update_file(source_path.into());
+    file_system.reimport_files(PackedStringArray::from(&[source_path.into()]));
+
+    {
+        let importer = importer.bind();
+        assert_eq!(importer.imports.len(), 1);
+        let options = &importer.imports[0];
+        assert_eq!(options.get_or_nil("greeting"), "hello".to_variant());
+    }
+
+    // The resource saved by the _import() override is loaded in place of the source file.
+    let import_config = std::fs::read_to_string(global_path(&format!("{source_path}.import")))
+        .expect("import file written");
+    assert!(import_config.contains("importer=\"gdext.test_importer\""));
+    let resource = ResourceLoader::singleton()
+        .load_ex(source_path.into())
+        .cache_mode(CacheMode::CACHE_MODE_IGNORE)
+        .done()
+        .expect("imported resource");
+    assert_eq!(resource.get_name(), GodotString::from("hello"));
+
+    editor_plugin.remove_import_plugin(&importer);
+    editor_plugin.free();
+    let _ = std::fs::remove_file(global_path(source_path));
+    let _ = std::fs::remove_file(global_path(&format!("{source_path}.import")));
+    file_system.update_file(source_path.into());
+}
+
+#[itest]
+fn test_editor_inspector_plugin() {
+    if !editor_classes_loaded() {
+        return skip(NO_EDITOR_CLASSES);
+    }
+
+    assert_editor_class_registered::<EditorInspectorPluginVirtualTest>();
+    assert_editor_class_registered::<EditorPropertyVirtualTest>();
+
+    // Godot forwards update_property() to the _update_property() override.
+    let mut property = Gd::<EditorPropertyVirtualTest>::new_alloc();
+    let mut engine_property = property.share().upcast::<EditorProperty>();
+    engine_property.update_property();
+    engine_property.update_property();
+    assert_eq!(property.bind().updates, 2);
+
+    property.free();
+}
+
+#[itest]
+fn test_editor_gizmo_plugin() {
+    if !editor_classes_loaded() {
+        return skip(NO_EDITOR_CLASSES);
+    }
+    // Gizmos call into the 3D editor (Node3DEditor) when they are created and redrawn.
+    let Some(editor) = godot::engine::editor_interface() else {
+        return skip(NO_RUNNING_EDITOR);
+    };
+
+    let gizmo_plugin = Gd::<EditorNode3DGizmoPluginVirtualTest>::new_default();
+    let mut editor_plugin = EditorPlugin::new_alloc();
+
+    // The 3D editor sorts its gizmo plugins by name, through the _get_gizmo_name() override.
+    editor_plugin.add_node_3d_gizmo_plugin(&gizmo_plugin);
+    assert!(gizmo_plugin.bind().name_queries.get() > 0);
+
+    let mut engine_plugin = gizmo_plugin.share().upcast::<EditorNode3DGizmoPlugin>();
+    engine_plugin.create_material("lines".into(), Color::from_rgb(1.0, 0.0, 0.0));
+
+    let mut node = Node3D::new_alloc();
+    editor
+        .get_base_control()
+        .expect("editor base control")
+        .add_child(&node);
+
+    let mut gizmo = Gd::<EditorNode3DGizmoVirtualTest>::new_default();
+    gizmo.set_node_3d(&node);
+    let material = engine_plugin
+        .get_material("lines".into(), &gizmo)
+        .expect("material created by the plugin");
+    gizmo.bind_mut().material = Some(material);
+
+    // Node3D::add_gizmo() creates the gizmo and draws it through the _redraw() override.
+    node.add_gizmo(&gizmo);
+    assert_eq!(gizmo.bind().redraws, 1);
+    assert_eq!(node.get_gizmos().len(), 1);
+
+    node.clear_gizmos();
+    node.free();
+    editor_plugin.remove_node_3d_gizmo_plugin(&gizmo_plugin);
+    editor_plugin.free();
+}
+
+#[itest]
+fn test_editor_syntax_highlighter() {
+    if !editor_classes_loaded() {
+        return skip(NO_EDITOR_CLASSES);
+    }
+
+    assert_editor_class_registered::<EditorSyntaxHighlighterVirtualTest>();
+
+    // Godot forwards clear_highlighting_cache() to the _clear_highlighting_cache() override.
+    let highlighter = Gd::<EditorSyntaxHighlighterVirtualTest>::new_default();
+    highlighter
+        .share()
+        .upcast::<SyntaxHighlighter>()
+        .clear_highlighting_cache();
+    assert_eq!(highlighter.bind().cache_clears, 1);
+}
+
+#[itest]
+fn test_editor_debugger_plugin() {
+    if !editor_classes_loaded() {
+        return skip(NO_EDITOR_CLASSES);
+    }
+    // Sessions are created by the debugger dock of the editor.
+    if godot::engine::editor_interface().is_none() {
+        return skip(NO_RUNNING_EDITOR);
+    }
+
+    let debugger_plugin = Gd::<EditorDebuggerPluginVirtualTest>::new_default();
+    let mut editor_plugin = EditorPlugin::new_alloc();
+
+    // The debugger dock sets up a session for each of its debuggers, through the _setup_session() override.
+    editor_plugin.add_debugger_plugin(&debugger_plugin);
+    let session_ids = debugger_plugin.bind().sessions.clone();
+    assert!(!session_ids.is_empty());
+
+    let engine_plugin = debugger_plugin.share().upcast::<EditorDebuggerPlugin>();
+    assert_eq!(engine_plugin.get_sessions().len(), session_ids.len());
+    for id in session_ids {
+        let mut session = engine_plugin.get_session(id).expect("session set up");
+
+        // No game runs, so messages are dropped and never reach _capture().
+        assert!(!session.is_active());
+        session.send_message("virtual_test:ping".into());
+    }
+    assert!(debugger_plugin.bind().messages.is_empty());
+
+    editor_plugin.remove_debugger_plugin(&debugger_plugin);
+    editor_plugin.free();
+}
+
+#[itest]
+fn test_editor_translation_parser_plugin() {
+    if !editor_classes_loaded() {
+        return skip(NO_EDITOR_CLASSES);
+    }
+    // POT files are generated from the localization tab of the editor's project settings.
+    let Some(editor) = godot::engine::editor_interface() else {
+        return skip(NO_RUNNING_EDITOR);
+    };
+
+    // The tab's save dialog starts the generation once a file is selected.
+    let base_control = editor.get_base_control().expect("editor base control");
+    let mut pot_dialog = base_control
+        .find_children_ex("*".into())
+        .type_("EditorFileDialog".into())
+        .owned(false)
+        .done()
+        .iter_shared()
+        .find(|dialog| {
+            let in_localization_tab = dialog
+                .get_parent()
+                .map_or(false, |parent| parent.is_class("LocalizationEditor".into()));
+            let saves_file = dialog.get("file_mode".into()).to::<i64>() == 4; // FILE_MODE_SAVE_FILE
+
+            in_localization_tab && saves_file
+        })
+        .expect("POT generation dialog");
+
+    let source_path = "user://virtual_test.gdextdialog";
+    let pot_path = "user://virtual_test.pot";
+    std::fs::write(global_path(source_path), "").expect("write dialog file");
+
+    let parser = Gd::<EditorTranslationParserPluginVirtualTest>::new_default();
+    let mut editor_plugin = EditorPlugin::new_alloc();
+    editor_plugin.add_translation_parser_plugin(&parser);
+
+    let mut settings = ProjectSettings::singleton();
+    let setting = GodotString::from("internationalization/locale/translations_pot_files");
+    let previous_sources = settings.get_setting(setting.clone());
+    let sources = PackedStringArray::from(&[source_path.into()]);
+    settings.set_setting(setting.clone(), sources.to_variant());
+
+    // Godot picks the parser by _get_recognized_extensions() and collects the strings of _parse_file().
+    pot_dialog.emit_signal("file_selected".into(), &[pot_path.to_variant()]);
+    let pot = std::fs::read_to_string(global_path(pot_path)).expect("POT file generated");
+    assert!(pot.contains("msgid \"Hello\""), "POT file:\n{pot}");
+
+    settings.set_setting(setting, previous_sources);
+    editor_plugin.remove_translation_parser_plugin(&parser);
+    editor_plugin.free();
+    let _ = std::fs::remove_file(global_path(source_path));
+    let _ = std::fs::remove_file(global_path(pot_path));
+}
+
+#[itest]
+fn test_editor_scene_post_import_plugin() {
+    if !editor_classes_loaded() {
+        return skip(NO_EDITOR_CLASSES);
+    }
+    // Scenes are imported by the editor's file system.
+    let Some(editor) = godot::engine::editor_interface() else {
+        return skip(NO_RUNNING_EDITOR);
+    };
+
+    // Smallest glTF document with a scene: a single node without mesh.
+    let scene_path = "res://virtual_test_import.gltf";
+    let gltf = r#"{"asset": {"version": "2.0"}, "scene": 0, "scenes": [{"nodes": [0]}], "nodes": [{"name": "Child"}]}"#;
+    std::fs::write(global_path(scene_path), gltf).expect("write glTF file");
+
+    let post_import = Gd::<EditorScenePostImportPluginVirtualTest>::new_default();
+    let mut editor_plugin = EditorPlugin::new_alloc();
+    editor_plugin.add_scene_post_import_plugin(&post_import);
+
+    // The scene importer hands the imported scene to the _post_process() override, before saving it.
+    let mut file_system = editor
+        .get_resource_filesystem()
+        .expect("editor file system");
+    file_system.update_file(scene_path.into());
+    file_system.reimport_files(PackedStringArray::from(&[scene_path.into()]));
+
+    let scene = ResourceLoader::singleton()
+        .load_ex(scene_path.into())
+        .cache_mode(CacheMode::CACHE_MODE_IGNORE)
+        .done()
+        .and_then(|resource| resource.try_cast::<PackedScene>())
+        .expect("imported scene");
+    let root = scene.instantiate().expect("instantiated scene");
+    assert_eq!(root.get_name(), StringName::from("Imported"));
+    root.free();
+
+    editor_plugin.remove_scene_post_import_plugin(&post_import);
+    editor_plugin.free();
+    let _ = std::fs::remove_file(global_path(scene_path));
+    let _ = std::fs::remove_file(global_path(&format!("{scene_path}.import")));
+    file_system.update_file(scene_path.into());
+}
+
+#[itest]
+fn test_editor_resource_preview_generator() {
+    if !editor_classes_loaded() {
+        return skip(NO_EDITOR_CLASSES);
+    }
+    // Previews are generated on the thread of the editor's previewer, which does not run headless.
+    let Some(editor) = godot::engine::editor_interface() else {
+        return skip(NO_RUNNING_EDITOR);
+    };
+    let display_server = Engine::singleton()
+        .get_singleton("DisplayServer".into())
+        .expect("DisplayServer singleton")
+        .call("get_name".into(), &[]);
+    if display_server.to::<GodotString>() == GodotString::from("headless") {
+        return skip("the editor's resource previewer does not run headless");
+    }
+
+    let generator = Gd::<EditorResourcePreviewGeneratorVirtualTest>::new_default();
+    let generated_size = generator.bind().generated_size.clone();
+    let mut previewer = editor
+        .get_resource_previewer()
+        .expect("editor resource previewer");
+    previewer.add_preview_generator(&generator);
+
+    // The previewer asks the _handles() override for the resource type, then calls _generate().
+    let resource = Gd::<PreviewResourceTest>::new_default();
+    previewer.queue_edited_resource_preview(
+        &resource,
+        &generator,
+        "on_preview_ready".into(),
+        Variant::nil(),
+    );
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    let size = loop {
+        if let Some(size) = *generated_size.lock().unwrap() {
+            break Some(size);
+        }
+        if std::time::Instant::now() > deadline {
+            break None;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    };
+
+    previewer.remove_preview_generator(&generator);
+    let size = size.expect("preview generated within 5 seconds");
+    assert!(size.x > 0 && size.y > 0);
+}
+
 #[itest]
 fn test_format_loader(_test_context: &TestContext) {
     let format_loader = Gd::<FormatLoaderTest>::new_default();
     let mut loader = ResourceLoader::singleton();
     loader
-        .add_resource_format_loader_ex(format_loader.share().upcast())
+        .add_resource_format_loader_ex(&format_loader)
         .at_front(true)
         .done();
 
@@ -372,7 +1628,201 @@ fn test_format_loader(_test_context: &TestContext) {
         .unwrap();
     assert!(resource.try_cast::<BoxMesh>().is_some());
 
-    loader.remove_resource_format_loader(format_loader.upcast());
+    loader.remove_resource_format_loader(format_loader);
+}
+
+#[itest]
+fn test_format_saver() {
+    let format_saver = Gd::<FormatSaverTest>::new_default();
+    let mut saver = ResourceSaver::singleton();
+    saver
+        .add_resource_format_saver_ex(&format_saver)
+        .at_front(true)
+        .done();
+
+    let extensions = saver.get_recognized_extensions(BoxMesh::new());
+    assert!(extensions.contains("saver_extension".into()));
+
+    saver.remove_resource_format_saver(format_saver);
+}
+
+#[itest]
+fn test_multiplayer_peer_extension() {
+    let mut peer = Gd::<MultiplayerPeerVirtualTest>::new_default();
+    peer.bind_mut().queued_packets.push(PackedByteArray::new());
+
+    let peer = peer.upcast::<MultiplayerPeer>();
+    assert_eq!(peer.get_unique_id(), 42);
+    assert_eq!(peer.get_available_packet_count(), 1);
+}
+
+#[itest]
+fn test_text_server_extension() {
+    let server = Gd::<TextServerVirtualTest>::new_default().upcast::<TextServer>();
+    assert_eq!(
+        server.get_name(),
+        GodotString::from("gdext test text server")
+    );
+}
+
+#[itest]
+fn test_audio_stream_playback_mix() {
+    let mut playback = Gd::<AudioStreamPlaybackVirtualTest>::new_default();
+    playback.bind_mut().amplitude = 0.5;
+
+    let silence = AudioFrame {
+        left: 0.0,
+        right: 0.0,
+    };
+    let mut buffer = vec![silence; 4];
+
+    // SAFETY: buffer holds exactly 4 frames.
+    let mixed = unsafe {
+        playback
+            .bind_mut()
+            .mix(buffer.as_mut_ptr(), 1.0, buffer.len() as i32)
+    };
+
+    assert_eq!(mixed, 4);
+    for frame in buffer.iter() {
+        assert_eq!(frame.left, 0.5);
+        assert_eq!(frame.right, -0.5);
+    }
+}
+
+#[itest]
+fn test_video_stream_extension() {
+    let stream = Gd::<VideoStreamVirtualTest>::new_default();
+    let mut player = VideoStreamPlayer::new_alloc();
+
+    player.set_stream(stream);
+    assert_eq!(player.get_stream_length(), 12.5);
+
+    player.free();
+}
+
+#[itest(tags = [slow])]
+fn test_movie_writer_extension() {
+    // Godot picks the writer for `--write-movie` by _handles_file(), then calls _write_begin() and _write_end() around the run.
+    let project_dir = write_child_project("virtual_test_movie", movie_project_files());
+
+    let movie_path = format!("{project_dir}/out.gdextmovie");
+    let (exit_code, output) = run_godot(&[
+        "--headless",
+        "--path",
+        &project_dir,
+        "--write-movie",
+        &movie_path,
+        "--fixed-fps",
+        "10",
+        "--quit-after",
+        "3",
+    ]);
+    assert_eq!(exit_code, 0, "movie run failed: {output:?}");
+
+    let log = std::fs::read_to_string(format!("{movie_path}.log"))
+        .unwrap_or_else(|err| panic!("movie writer not called ({err}): {output:?}"));
+    let log = log.lines().collect::<Vec<_>>();
+    assert_eq!(log, vec!["begin fps=10 mix_rate=48000", "end"]);
+
+    let _ = std::fs::remove_dir_all(&project_dir);
+}
+
+/// Files of the project run by test_movie_writer_extension(), relative to the project directory.
+fn movie_project_files() -> Vec<(&'static str, String)> {
+    let project = r#"config_version=5
+
+[application]
+
+config/name="VirtualTestMovie"
+run/main_scene="res://main.tscn"
+"#;
+
+    let main_scene = "[gd_scene format=3]\n\n[node name=\"Main\" type=\"Node\"]\n";
+
+    vec![
+        ("project.godot", project.to_string()),
+        ("main.tscn", main_scene.to_string()),
+    ]
+}
+
+#[itest(tags = [slow])]
+fn test_main_loop_extension() {
+    // Godot instantiates the selected main loop through ClassDB, so the classes must be known there.
+    let class_db = ClassDb::singleton();
+    let main_loop = StringName::from("MainLoopVirtualTest");
+    assert!(class_db.can_instantiate(main_loop.clone()));
+    assert!(class_db.is_parent_class(main_loop, "MainLoop".into()));
+    assert!(class_db.is_parent_class("SceneTreeVirtualTest".into(), "SceneTree".into()));
+
+    // The child process selects MainLoopVirtualTest in on_level_init(), see select_main_loop().
+    let project_dir = write_child_project("virtual_test_main_loop", main_loop_project_files());
+
+    let log_path = format!("{project_dir}/main_loop.log");
+    let (exit_code, output) = run_godot(&[
+        "--headless",
+        "--path",
+        &project_dir,
+        "--",
+        &format!("--{MAIN_LOOP_LOG_ARG}={log_path}"),
+    ]);
+    assert_eq!(exit_code, 0, "main loop run failed: {output:?}");
+
+    // Godot calls _initialize(), then _process() each frame until it returns true, then _finalize().
+    let log = std::fs::read_to_string(&log_path)
+        .unwrap_or_else(|err| panic!("main loop not run ({err}): {output:?}"));
+    let log = log.lines().collect::<Vec<_>>();
+    assert_eq!(
+        log,
+        vec!["initialize", "process", "process", "process", "finalize"]
+    );
+
+    let _ = std::fs::remove_dir_all(&project_dir);
+}
+
+/// Files of the project run by test_main_loop_extension(), relative to the project directory.
+fn main_loop_project_files() -> Vec<(&'static str, String)> {
+    let project = r#"config_version=5
+
+[application]
+
+config/name="VirtualTestMainLoop"
+"#;
+
+    vec![("project.godot", project.to_string())]
+}
+
+#[itest]
+fn test_engine_debugger_inactive() {
+    // The game side of a debugger plugin talks to the editor via EngineDebugger, which is inactive when running tests.
+    let debugger = EngineDebugger::singleton();
+    assert!(!debugger.is_active());
+    assert!(!debugger.has_capture("virtual_test".into()));
+}
+
+#[itest]
+fn test_translation_extension() {
+    let translation = Gd::<TranslationVirtualTest>::new_default().upcast::<Translation>();
+    assert_eq!(
+        translation.get_message("hello".into()),
+        StringName::from("HELLO")
+    );
+}
+
+#[itest]
+fn test_webrtc_data_channel_extension() {
+    let channel = Gd::<WebRtcDataChannelVirtualTest>::new_default().upcast::<WebRtcDataChannel>();
+    assert_eq!(channel.get_label(), GodotString::from("gdext-channel"));
+    assert_eq!(channel.get_id(), 7);
+}
+
+#[itest]
+fn test_stream_peer_extension() {
+    let mut peer = Gd::<StreamPeerVirtualTest>::new_default();
+    peer.bind_mut().buffer.extend_from_slice(&[1, 2, 3]);
+
+    let peer = peer.upcast::<StreamPeer>();
+    assert_eq!(peer.get_available_bytes(), 3);
 }
 
 #[itest]
@@ -381,19 +1831,16 @@ fn test_input_event(test_context: &TestContext) {
     assert_eq!(obj.bind().event, None);
     let mut test_viewport = Window::new_alloc();
 
-    test_context
-        .scene_tree
-        .share()
-        .add_child(test_viewport.share().upcast());
+    test_context.scene_tree.share().add_child(&test_viewport);
 
-    test_viewport.share().add_child(obj.share().upcast());
+    test_viewport.share().add_child(&obj);
 
     let mut event = InputEventAction::new();
     event.set_action("debug".into());
     event.set_pressed(true);
 
     // We're running in headless mode, so Input.parse_input_event does not work
-    test_viewport.share().push_input(event.share().upcast());
+    test_viewport.share().push_input(&event);
 
     assert_eq!(obj.bind().event, Some(event.upcast::<InputEvent>()));
 
@@ -412,13 +1859,10 @@ fn test_input_event_multiple(test_context: &TestContext) {
     }
     let mut test_viewport = Window::new_alloc();
 
-    test_context
-        .scene_tree
-        .share()
-        .add_child(test_viewport.share().upcast());
+    test_context.scene_tree.share().add_child(&test_viewport);
 
     for obj in objs.iter() {
-        test_viewport.share().add_child(obj.share().upcast())
+        test_viewport.share().add_child(obj)
     }
 
     let mut event = InputEventAction::new();
@@ -426,7 +1870,7 @@ fn test_input_event_multiple(test_context: &TestContext) {
     event.set_pressed(true);
 
     // We're running in headless mode, so Input.parse_input_event does not work
-    test_viewport.share().push_input(event.share().upcast());
+    test_viewport.share().push_input(&event);
 
     for obj in objs.iter() {
         assert_eq!(obj.bind().event, Some(event.share().upcast::<InputEvent>()));
@@ -0,0 +1,56 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use godot::docs::gather_xml_docs;
+use godot::prelude::*;
+
+use crate::framework::itest;
+
+/// A class with <docs>.
+///
+/// Second paragraph, "quoted".
+#[derive(GodotClass)]
+#[class(init, base=RefCounted)]
+struct DocumentedClass {
+    /// Current speed.
+    #[var]
+    speed: f32,
+
+    /// Not visible in docs.
+    _hidden: i32,
+}
+
+#[godot_api]
+impl DocumentedClass {
+    /// Sets speed to zero.
+    #[func]
+    fn halt(&mut self) {
+        self.speed = 0.0;
+    }
+
+    /// Emitted when the speed changes.
+    #[signal]
+    fn speed_changed();
+}
+
+#[itest]
+fn docs_gathered_from_comments() {
+    let xml = gather_xml_docs()
+        .find(|xml| xml.contains(r#"<class name="DocumentedClass""#))
+        .expect("docs for DocumentedClass");
+
+    assert!(xml.contains(r#"inherits="RefCounted""#));
+    assert!(xml.contains("<brief_description>A class with &lt;docs&gt;.</brief_description>"));
+    assert!(xml.contains("<description>Second paragraph, &quot;quoted&quot;.</description>"));
+    assert!(xml.contains(r#"<member name="speed">Current speed.</member>"#));
+    assert!(!xml.contains("Not visible"));
+    assert!(xml.contains(
+        r#"<method name="halt"><description>Sets speed to zero.</description></method>"#
+    ));
+    assert!(xml.contains(
+        r#"<signal name="speed_changed"><description>Emitted when the speed changes.</description></signal>"#
+    ));
+}
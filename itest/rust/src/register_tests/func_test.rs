@@ -4,8 +4,12 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use godot::engine::ObjectExt;
+use godot::error::{capture, CallErrorKind, Severity};
 use godot::prelude::*;
 
+use crate::framework::{expect_panic, itest};
+
 #[derive(GodotClass)]
 #[class(base=RefCounted)]
 struct FuncRename;
@@ -46,3 +50,175 @@ impl RefCountedVirtual for FuncRename {
         Self
     }
 }
+
+#[derive(GodotClass)]
+#[class(init, base=RefCounted)]
+struct FuncContextGated;
+
+#[godot_api]
+impl FuncContextGated {
+    #[func(tool_only)]
+    fn editor_value(&self) -> i32 {
+        1
+    }
+
+    #[func(runtime_only)]
+    fn runtime_value(&self) -> i32 {
+        2
+    }
+}
+
+#[itest]
+fn func_context_is_editor_hint() {
+    // Tests run as a game, not in the editor.
+    assert!(!godot::engine::is_editor_hint());
+}
+
+#[derive(GodotClass)]
+#[class(init, base=RefCounted, on_panic = ReturnDefault)]
+struct FuncPanicDefault;
+
+#[godot_api]
+impl FuncPanicDefault {
+    #[func]
+    fn fail(&self) -> i32 {
+        panic!("panic expected by test")
+    }
+}
+
+#[itest]
+fn func_panic_returns_default() {
+    let mut obj = Gd::<FuncPanicDefault>::new_default().upcast::<Object>();
+
+    // Without ReturnDefault, the failed call would panic in Object::call().
+    let result = obj.call("fail".into(), &[]);
+    assert!(result.is_nil());
+}
+
+#[itest]
+fn func_panic_hook() {
+    use std::sync::{Arc, Mutex};
+
+    let previous_hook = godot::init::take_panic_hook();
+
+    let seen = Arc::new(Mutex::new(None));
+    let seen_in_hook = seen.clone();
+    godot::init::set_panic_hook(move |ctx| {
+        *seen_in_hook.lock().unwrap() = Some((
+            ctx.class_name.to_string(),
+            ctx.method_name.to_string(),
+            ctx.message.map(str::to_string),
+            ctx.policy,
+        ));
+    });
+
+    let mut obj = Gd::<FuncPanicDefault>::new_default().upcast::<Object>();
+    obj.call("fail".into(), &[]);
+
+    match previous_hook {
+        Some(hook) => godot::init::set_panic_hook(hook),
+        None => godot::init::clear_panic_hook(),
+    }
+
+    let (class_name, method_name, message, policy) = seen.lock().unwrap().take().unwrap();
+    assert_eq!(class_name, "FuncPanicDefault");
+    assert_eq!(method_name, "fail");
+    assert_eq!(message.as_deref(), Some("panic expected by test"));
+    assert_eq!(policy, godot::init::PanicPolicy::ReturnDefault);
+}
+
+#[derive(GodotClass)]
+#[class(init, base=RefCounted)]
+struct FuncResult;
+
+#[godot_api]
+impl FuncResult {
+    #[func]
+    fn parse_positive(&self, value: i64) -> Result<i64, String> {
+        if value > 0 {
+            Ok(value)
+        } else {
+            Err(format!("{value} is not positive"))
+        }
+    }
+
+    #[func]
+    fn lookup(&self, key: GodotString) -> Result<i64, String> {
+        Err(format!("no entry „{key}“"))
+    }
+
+    #[func(err_as_variant)]
+    fn checked_div(&self, a: i64, b: i64) -> Result<i64, GodotString> {
+        a.checked_div(b)
+            .ok_or_else(|| GodotString::from("division by zero"))
+    }
+}
+
+#[itest]
+fn func_result_ok_and_err() {
+    let mut obj = Gd::<FuncResult>::new_default().upcast::<Object>();
+
+    let ok = obj.call("parse_positive".into(), &[5.to_variant()]);
+    assert_eq!(ok, 5.to_variant());
+
+    // Err fails the call, which Object::call() reports as panic.
+    expect_panic("Err fails the call", move || {
+        obj.call("parse_positive".into(), &[(-5).to_variant()]);
+    });
+}
+
+#[itest]
+fn func_result_err_message() {
+    let mut obj = Gd::<FuncResult>::new_default();
+
+    let (result, messages) = capture(|| obj.try_call("parse_positive", &[(-5).to_variant()]));
+    assert!(result.is_err());
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].severity, Severity::Error);
+    assert_eq!(messages[0].message, "parse_positive: -5 is not positive");
+
+    // Non-ASCII messages are escaped when printed, instead of being rejected.
+    let result = obj.try_call("lookup", &["Käse".to_variant()]);
+    assert!(result.is_err());
+}
+
+#[itest]
+fn func_result_err_as_variant() {
+    let mut obj = Gd::<FuncResult>::new_default().upcast::<Object>();
+
+    let ok = obj.call("checked_div".into(), &[7.to_variant(), 2.to_variant()]);
+    assert_eq!(ok, 3.to_variant());
+
+    let err = obj.call("checked_div".into(), &[7.to_variant(), 0.to_variant()]);
+    assert_eq!(err, "division by zero".to_variant());
+}
+
+#[itest]
+fn func_call_validation() {
+    let mut obj = Gd::<FuncResult>::new_default();
+
+    let (result, messages) = capture(|| obj.try_call("parse_positive", &[]));
+    let err = result.unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        CallErrorKind::TooFewArguments { expected: 1, .. }
+    ));
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].severity, Severity::ScriptError);
+    assert!(messages[0]
+        .message
+        .contains("fn parse_positive(i64) -> i64"));
+
+    let (result, messages) = capture(|| obj.try_call("parse_positive", &["five".to_variant()]));
+    let err = result.unwrap_err();
+    assert_eq!(
+        err.kind(),
+        CallErrorKind::InvalidArgument {
+            index: 0,
+            expected: VariantType::Int,
+            actual: VariantType::String,
+        }
+    );
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].message.contains("argument [0] of type String"));
+}
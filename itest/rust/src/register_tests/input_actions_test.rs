@@ -0,0 +1,37 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use godot::bind::input_actions;
+use godot::builtin::StringName;
+use godot::engine::Input;
+
+use crate::framework::itest;
+
+#[input_actions(project = "../godot/project.godot")]
+enum ItestAction {}
+
+#[itest]
+fn input_actions_generated() {
+    assert_eq!(
+        ItestAction::ALL,
+        &[ItestAction::ItestJump, ItestAction::ItestMoveLeft]
+    );
+    assert_eq!(ItestAction::ItestJump.name(), "itest_jump");
+    assert_eq!(
+        StringName::from(ItestAction::ItestMoveLeft),
+        StringName::from("itest_move_left")
+    );
+}
+
+#[itest]
+fn input_actions_known_to_godot() {
+    let input = Input::singleton();
+
+    for &action in ItestAction::ALL {
+        // Godot prints an error for unknown actions; these are registered through project.godot.
+        assert!(!input.is_action_pressed(action.into()));
+    }
+}
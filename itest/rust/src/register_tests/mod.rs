@@ -6,7 +6,9 @@
 
 mod constant_test;
 mod derive_variant_test;
+mod docs_test;
 mod func_test;
 mod gdscript_ffi_test;
+mod input_actions_test;
 mod option_ffi_test;
 mod var_test;